@@ -23,6 +23,18 @@ pub enum UmpireSupervisorMenuChoice {
     Back,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum StatisticsMenuChoice {
+    AllGames,
+    ByGame,
+    ByTeam,
+    ByLeague,
+    BySeason,
+    BoxScore,
+    SprayChart,
+    Back,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum LeagueMenuChoice {
     CreateLeague,
@@ -52,6 +64,8 @@ pub enum DBMenuChoice {
     VacuumDB,
     ClearData,
     ExportGame,
+    ImportGame,
+    ExportSeason,
     Back,
 }
 
@@ -258,6 +272,45 @@ impl Menu {
         }
     }
 
+    /// Display statistics menu: which games the stats are read from
+    pub fn show_statistics_menu() -> StatisticsMenuChoice {
+        loop {
+            utils::term::clear_screen();
+            println!("╔════════════════════════════════════════════╗");
+            println!("║              📊  STATISTICS                ║");
+            println!("╚════════════════════════════════════════════╝");
+            println!();
+            println!("  1. 🌐 All Games");
+            println!("  2. 🎮 Single Game");
+            println!("  3. ⚾ By Team");
+            println!("  4. 🏆 By League");
+            println!("  5. 📅 By Season");
+            println!("  6. 🧾 Box Score");
+            println!("  7. 🎯 Spray Chart");
+            println!();
+            println!("  0. 🔙 Back to Main Menu");
+            println!();
+            print!("Select an option (1-7 or 0): ");
+            io::stdout().flush().unwrap();
+
+            let choice = utils::term::read_choice();
+            match choice {
+                1 => return StatisticsMenuChoice::AllGames,
+                2 => return StatisticsMenuChoice::ByGame,
+                3 => return StatisticsMenuChoice::ByTeam,
+                4 => return StatisticsMenuChoice::ByLeague,
+                5 => return StatisticsMenuChoice::BySeason,
+                6 => return StatisticsMenuChoice::BoxScore,
+                7 => return StatisticsMenuChoice::SprayChart,
+                0 => return StatisticsMenuChoice::Back,
+                _ => {
+                    println!("\n❌ Invalid choice. Press ENTER to continue...");
+                    utils::term::wait_for_enter();
+                }
+            }
+        }
+    }
+
     /// Display umpire supervisor menu
     pub fn show_umpire_supervisor_menu() -> UmpireSupervisorMenuChoice {
         loop {
@@ -308,10 +361,12 @@ impl Menu {
             println!("  6. 🧹 Vacuum Database");
             println!("  7. 🗑️  Clear All Data");
             println!("  8. 📤 Export Game");
+            println!("  9. 📥 Import Game (JSON / Retrosheet)");
+            println!(" 10. 📊 Export Season (CSV)");
             println!();
             println!("  0. 🔙 Back to Main Menu");
             println!();
-            print!("Select an option (1-8 or 0): ");
+            print!("Select an option (1-10 or 0): ");
             io::stdout().flush().unwrap();
 
             let choice = utils::term::read_choice();
@@ -324,6 +379,8 @@ impl Menu {
                 6 => return DBMenuChoice::VacuumDB,
                 7 => return DBMenuChoice::ClearData,
                 8 => return DBMenuChoice::ExportGame,
+                9 => return DBMenuChoice::ImportGame,
+                10 => return DBMenuChoice::ExportSeason,
                 0 => return DBMenuChoice::Back,
                 _ => {
                     println!("\n❌ Invalid choice. Press ENTER to continue...");
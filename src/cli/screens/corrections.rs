@@ -0,0 +1,137 @@
+use crate::Database;
+use crate::db::plate_appearances::list_plate_appearances;
+use crate::engine::correction::{apply_correction, plan_correction};
+use crate::engine::play_ball::plate_appearance_summary;
+use crate::engine::undo::count_before_play;
+use crate::utils::term;
+
+/// Change the ruling on a recorded plate appearance of any game, finished
+/// ones included, and rebuild the rest of the game on top of it.
+pub fn correct_plate_appearances(db: &mut Database) {
+    term::show_header("CORRECT PLATE APPEARANCES");
+
+    let conn = db.get_connection_mut();
+
+    let games: Vec<(i64, String, String, String, String)> = {
+        let mut stmt = match conn.prepare(
+            "SELECT g.id, g.game_id, g.game_date, t1.name, t2.name
+             FROM games g
+             JOIN teams t1 ON g.away_team_id = t1.id
+             JOIN teams t2 ON g.home_team_id = t2.id
+             WHERE EXISTS (SELECT 1 FROM plate_appearances pa WHERE pa.game_id = g.id)
+             ORDER BY g.game_date DESC, g.id DESC",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                term::show_error(&format!("Error querying games: {e}"));
+                return;
+            }
+        };
+
+        match stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        }) {
+            Ok(rows) => rows.filter_map(Result::ok).collect(),
+            Err(e) => {
+                term::show_error(&format!("Error querying games: {e}"));
+                return;
+            }
+        }
+    };
+
+    if games.is_empty() {
+        println!("📭 No scored games found.");
+        term::wait_for_enter();
+        return;
+    }
+
+    println!("\n📋 Scored Games:\n");
+    for (i, (_id, game_id, date, away, home)) in games.iter().enumerate() {
+        println!(
+            "  {}. {} - {} @ {} | ID: {}",
+            i + 1,
+            date,
+            away,
+            home,
+            game_id
+        );
+    }
+    println!();
+
+    let game_pk = match term::read_i64("Select game (number, 0 to cancel): ") {
+        Some(0) | None => return,
+        Some(c) if c > 0 && (c as usize) <= games.len() => games[c as usize - 1].0,
+        _ => {
+            term::show_error("Invalid selection");
+            return;
+        }
+    };
+
+    let pas = match list_plate_appearances(conn, game_pk) {
+        Ok(v) => v,
+        Err(e) => {
+            term::show_error(&format!("Error loading plate appearances: {e}"));
+            return;
+        }
+    };
+
+    println!();
+    for pa in &pas {
+        println!("  #{:<4} {}", pa.seq, plate_appearance_summary(pa));
+    }
+    println!();
+
+    let seq = match term::read_i64("Plate appearance to correct (#, 0 to cancel): ") {
+        Some(0) | None => return,
+        Some(seq) => seq,
+    };
+    let Some(row) = pas.iter().find(|pa| pa.seq == seq) else {
+        term::show_error(&format!("No plate appearance #{seq} in this game."));
+        return;
+    };
+
+    let count = count_before_play(row);
+    println!("\n  #{seq} {}", plate_appearance_summary(row));
+    println!(
+        "  Re-enter the play as during the game, from a {}-{} count.",
+        count.balls, count.strikes
+    );
+    let Some(line) = term::read_optional_string("New play (ENTER to cancel): ") else {
+        return;
+    };
+
+    let plan = match plan_correction(conn, game_pk, seq, &line) {
+        Ok(plan) => plan,
+        Err(msg) => {
+            term::show_error(&msg);
+            return;
+        }
+    };
+
+    println!("\n  before: {}", plate_appearance_summary(&plan.original));
+    println!(
+        "  after:  {}",
+        plate_appearance_summary(&plan.corrected_row())
+    );
+    for (later_seq, outs) in &plan.outs_changes {
+        println!("  #{later_seq}: now {outs} out(s) after the play");
+    }
+    println!();
+
+    if !term::confirm("Apply this correction?") {
+        println!("\n❌ Correction cancelled.");
+        term::wait_for_enter();
+        return;
+    }
+
+    match apply_correction(conn, game_pk, &plan) {
+        Ok(()) => term::show_success(&format!("Plate appearance #{seq} corrected.")),
+        Err(e) => term::show_error(&format!("Failed to save the correction: {e}")),
+    }
+}
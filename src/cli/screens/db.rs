@@ -1,12 +1,20 @@
 use crate::cli::menu::DBMenuChoice;
+use crate::db::game_archive::{GameArchive, export_game_archive, import_game_archive};
+use crate::db::game_csv::GameCsv;
+use crate::db::league::League;
 use crate::db::migrations;
+use crate::retrosheet::export::export_event_game;
+use crate::retrosheet::import::import_event_game;
+use crate::retrosheet::parse_event_file;
+use crate::stats::box_score::BoxScore;
+use crate::stats::scorecard::{Scorecard, layout, pdf, svg};
 use crate::utils::term;
 use crate::{Database, Menu, get_db_path, get_db_path_display};
 use chrono::Local;
 use std::fs;
 use std::path::Path;
 
-pub fn handle_db_menu(db: &Database) {
+pub fn handle_db_menu(db: &mut Database) {
     loop {
         match Menu::show_db_menu() {
             DBMenuChoice::ViewInfo => view_db_info(db),
@@ -17,6 +25,8 @@ pub fn handle_db_menu(db: &Database) {
             DBMenuChoice::VacuumDB => vacuum_database(db),
             DBMenuChoice::ClearData => clear_all_data(db),
             DBMenuChoice::ExportGame => export_game(db),
+            DBMenuChoice::ImportGame => import_game(db),
+            DBMenuChoice::ExportSeason => export_season_csv(db),
             DBMenuChoice::Back => break,
         }
     }
@@ -620,8 +630,11 @@ fn export_game(db: &Database) {
         let (game_db_id, game_id, _, _, _, _, _) = &games[(choice - 1) as usize];
 
         println!("\nExport format:");
-        println!("  1. JSON (detailed)");
-        println!("  2. CSV (simplified)");
+        println!("  1. JSON (complete, can be imported)");
+        println!("  2. CSV (plate appearances + runner movements)");
+        println!("  3. Box score (text)");
+        println!("  4. Retrosheet event file (.EVX)");
+        println!("  5. Scorecards (SVG + PDF)");
         println!();
         println!("  0. Cancel");
         println!();
@@ -630,6 +643,9 @@ fn export_game(db: &Database) {
         match format_choice {
             1 => export_game_json(db, *game_db_id, game_id),
             2 => export_game_csv(db, *game_db_id, game_id),
+            3 => export_box_score(db, *game_db_id, game_id),
+            4 => export_event_file(db, *game_db_id, game_id),
+            5 => export_scorecards(db, *game_db_id, game_id),
             0 => {
                 println!("\n❌ Export cancelled");
                 term::wait_for_enter();
@@ -642,20 +658,20 @@ fn export_game(db: &Database) {
 }
 
 fn export_game_json(db: &Database, game_id: i64, game_id_str: &str) {
-    let _conn = db.get_connection();
-
-    // Fetch game data (simplified - you'll need to build complete Game struct)
-    let game_data = format!(
-        r#"{{
-  "game_id": "{}",
-  "database_id": {},
-  "export_date": "{}",
-  "note": "Full game export with all plate appearances"
-}}"#,
-        game_id_str,
-        game_id,
-        Local::now().format("%Y-%m-%d %H:%M:%S")
-    );
+    let archive = match export_game_archive(db.get_connection(), game_id) {
+        Ok(archive) => archive,
+        Err(e) => {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+    };
+    let game_data = match serde_json::to_string_pretty(&archive) {
+        Ok(json) => json,
+        Err(e) => {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+    };
 
     // Save to file
     let filename = format!("{}_export.json", game_id_str);
@@ -674,43 +690,130 @@ fn export_game_json(db: &Database, game_id: i64, game_id_str: &str) {
     }
 }
 
-fn export_game_csv(db: &Database, game_id: i64, game_id_str: &str) {
-    let conn = db.get_connection();
+fn export_event_file(db: &Database, game_id: i64, game_id_str: &str) {
+    let game = match export_event_game(db.get_connection(), game_id) {
+        Ok(game) => game,
+        Err(e) => {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+    };
+
+    let filename = format!("{}.EVX", game_id_str);
+    let file_path = std::env::current_dir().unwrap_or_default().join(&filename);
 
-    // Build CSV with plate appearances
-    let mut csv_data = String::from("Inning,Half,Batter,Result,RBIs,Runs\n");
-
-    // Fetch plate appearances (simplified)
-    if let Ok(mut stmt) = conn.prepare(
-        "SELECT inning, half_inning, \
-         (SELECT name FROM players WHERE id = batter_id) as batter, \
-         result_type, rbis, runs_scored \
-         FROM plate_appearances WHERE game_id = ? ORDER BY id",
-    ) && let Ok(rows) = stmt.query_map([game_id], |row| {
-        Ok(format!(
-            "{},{},{},{},{},{}\n",
-            row.get::<_, i64>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)
-                .unwrap_or_else(|_| "Unknown".to_string()),
-            row.get::<_, String>(3)?,
-            row.get::<_, i64>(4).unwrap_or(0),
-            row.get::<_, i64>(5).unwrap_or(0)
-        ))
-    }) {
-        for row in rows.flatten() {
-            csv_data.push_str(&row);
+    match fs::write(&file_path, game.to_string()) {
+        Ok(_) => {
+            term::show_success(&format!(
+                "Game exported as a Retrosheet event file!\n   File: {}",
+                file_path.display()
+            ));
+        }
+        Err(e) => {
+            term::show_error(&format!("Failed to export: {}", e));
         }
     }
+}
 
-    // Save to file
-    let filename = format!("{}_export.csv", game_id_str);
+fn import_game(db: &mut Database) {
+    term::show_header("IMPORT GAME");
+
+    println!("JSON exports: the teams must exist with the same abbreviation, and");
+    println!("their players with the same jersey numbers. Missing umpires are created.");
+    println!("Retrosheet event files (.EVA/.EVN/.EVX): the teams must exist with the");
+    println!("same abbreviation, and their players with the same names.\n");
+
+    let filepath = term::read_string("JSON or event file path: ");
+    if filepath.is_empty() {
+        term::show_error("File path is required!");
+        return;
+    }
+
+    let content = match fs::read_to_string(&filepath) {
+        Ok(c) => c,
+        Err(e) => {
+            term::show_error(&format!("Failed to read file: {}", e));
+            return;
+        }
+    };
+
+    let is_event_file = Path::new(&filepath)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| ["EVA", "EVN", "EVX"].contains(&e.to_ascii_uppercase().as_str()))
+        || content.trim_start().starts_with("id,");
+    if is_event_file {
+        import_event_file(db, &content);
+        return;
+    }
+
+    let archive: GameArchive = match serde_json::from_str(&content) {
+        Ok(archive) => archive,
+        Err(e) => {
+            term::show_error(&format!("Invalid game export: {}", e));
+            return;
+        }
+    };
+
+    match import_game_archive(db.get_connection_mut(), &archive) {
+        Ok(_) => term::show_success(&format!(
+            "Game {} imported: {} plate appearances, {} runner movements, {} events.",
+            archive.game.game_id,
+            archive.plate_appearances.len(),
+            archive.runner_movements.len(),
+            archive.game_events.len()
+        )),
+        Err(e) => term::show_error(&format!("Failed to import: {}", e)),
+    }
+}
+
+/// Import every game of an event file; one that fails does not stop the
+/// others.
+fn import_event_file(db: &mut Database, content: &str) {
+    let games = match parse_event_file(content) {
+        Ok(games) => games,
+        Err(e) => {
+            term::show_error(&format!("Invalid event file: {}", e));
+            return;
+        }
+    };
+
+    let mut imported = 0;
+    let mut failures = Vec::new();
+    for game in &games {
+        match import_event_game(db.get_connection_mut(), game) {
+            Ok(_) => imported += 1,
+            Err(e) => failures.push(e.to_string()),
+        }
+    }
+    if failures.is_empty() {
+        term::show_success(&format!("{} game(s) imported.", imported));
+    } else {
+        term::show_error(&format!(
+            "{} of {} game(s) imported.\n   {}",
+            imported,
+            games.len(),
+            failures.join("\n   ")
+        ));
+    }
+}
+
+fn export_box_score(db: &Database, game_id: i64, game_id_str: &str) {
+    let box_score = match BoxScore::load(db.get_connection(), game_id) {
+        Ok(box_score) => box_score,
+        Err(e) => {
+            term::show_error(&format!("Failed to build the box score: {}", e));
+            return;
+        }
+    };
+
+    let filename = format!("{}_box_score.txt", game_id_str);
     let file_path = std::env::current_dir().unwrap_or_default().join(&filename);
 
-    match fs::write(&file_path, csv_data) {
+    match fs::write(&file_path, box_score.to_string()) {
         Ok(_) => {
             term::show_success(&format!(
-                "Game exported to CSV!\n   File: {}",
+                "Box score exported!\n   File: {}",
                 file_path.display()
             ));
         }
@@ -719,3 +822,142 @@ fn export_game_csv(db: &Database, game_id: i64, game_id_str: &str) {
         }
     }
 }
+
+/// One SVG per team and a PDF with both teams.
+fn export_scorecards(db: &Database, game_id: i64, game_id_str: &str) {
+    let cards = match Scorecard::load(db.get_connection(), game_id) {
+        Ok(cards) => cards,
+        Err(e) => {
+            term::show_error(&format!("Failed to build the scorecards: {}", e));
+            return;
+        }
+    };
+    let pages = cards.each_ref().map(layout::page);
+
+    let dir = std::env::current_dir().unwrap_or_default();
+    let mut files = Vec::new();
+    for (side, page) in ["away", "home"].iter().zip(&pages) {
+        files.push((
+            dir.join(format!("{}_scorecard_{}.svg", game_id_str, side)),
+            svg::render(page).into_bytes(),
+        ));
+    }
+    files.push((
+        dir.join(format!("{}_scorecard.pdf", game_id_str)),
+        pdf::render(&pages),
+    ));
+
+    for (path, content) in &files {
+        if let Err(e) = fs::write(path, content) {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+    }
+    let paths: Vec<String> = files
+        .iter()
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    term::show_success(&format!(
+        "Scorecards exported!\n   Files: {}",
+        paths.join("\n          ")
+    ));
+}
+
+fn export_game_csv(db: &Database, game_id: i64, game_id_str: &str) {
+    match GameCsv::load(db.get_connection(), &[game_id]) {
+        Ok(csv) => write_csv_files(&csv, game_id_str, "Game exported to CSV!"),
+        Err(e) => term::show_error(&format!("Failed to export: {}", e)),
+    }
+}
+
+/// Every game of a league season in one pair of CSV files.
+fn export_season_csv(db: &Database) {
+    term::show_header("EXPORT SEASON (CSV)");
+
+    let conn = db.get_connection();
+    let leagues = match League::get_all(conn) {
+        Ok(leagues) if !leagues.is_empty() => leagues,
+        Ok(_) => {
+            term::show_error("No leagues found");
+            return;
+        }
+        Err(e) => {
+            term::show_error(&format!("Error loading leagues: {}", e));
+            return;
+        }
+    };
+
+    println!("📋 Leagues:\n");
+    for (i, league) in leagues.iter().enumerate() {
+        let season = league.season.as_deref().unwrap_or("-");
+        term::show_list_item(i + 1, &format!("{} ({})", league.name, season));
+    }
+    println!();
+
+    let league = match term::read_i64("Select league (0 to cancel): ") {
+        Some(0) | None => {
+            println!("\n❌ Export cancelled");
+            term::wait_for_enter();
+            return;
+        }
+        Some(c) if c > 0 && (c as usize) <= leagues.len() => &leagues[c as usize - 1],
+        _ => {
+            term::show_error("Invalid selection");
+            return;
+        }
+    };
+    let Some(league_id) = league.id else {
+        term::show_error("Invalid selection");
+        return;
+    };
+
+    let season = league.season.as_deref().filter(|s| !s.is_empty());
+    let csv = match GameCsv::load_season(conn, league_id, season) {
+        Ok(csv) => csv,
+        Err(e) => {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+    };
+    if csv.plate_appearances.is_empty() {
+        term::show_error("No plate appearances recorded in this league");
+        return;
+    }
+
+    let prefix: String = format!("{}_{}", league.name, season.unwrap_or("all"))
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    write_csv_files(&csv, &prefix, "Season exported to CSV!");
+}
+
+/// Write `<prefix>_plate_appearances.csv` and
+/// `<prefix>_runner_movements.csv` to the current directory.
+fn write_csv_files(csv: &GameCsv, prefix: &str, message: &str) {
+    let dir = std::env::current_dir().unwrap_or_default();
+    let files = [
+        ("plate_appearances", csv.plate_appearances_csv()),
+        ("runner_movements", csv.runner_movements_csv()),
+    ];
+    let mut paths = Vec::new();
+    for (name, content) in files {
+        let path = dir.join(format!("{}_{}.csv", prefix, name));
+        let written = content.and_then(|text| Ok(fs::write(&path, text)?));
+        if let Err(e) = written {
+            term::show_error(&format!("Failed to export: {}", e));
+            return;
+        }
+        paths.push(path.display().to_string());
+    }
+    term::show_success(&format!(
+        "{}\n   Files: {}",
+        message,
+        paths.join("\n          ")
+    ));
+}
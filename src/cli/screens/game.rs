@@ -1,6 +1,9 @@
 use crate::cli::menu::GameMenuChoice;
+use crate::cli::screens::corrections::correct_plate_appearances;
 use crate::cli::screens::play_ball::play_ball;
 use crate::db::game_events::refactor_batter_order;
+use crate::db::game_queries::shared_league_rules;
+use crate::models::rules::RuleSet;
 use crate::utils::term;
 use crate::{Database, Menu, Team};
 use anyhow::{Result, anyhow};
@@ -16,6 +19,7 @@ pub enum EditGameMenuChoice {
     EditLineups,
     ImportLineup,
     EditInningsScore,
+    CorrectPlateAppearances,
     Back,
 }
 
@@ -52,6 +56,7 @@ pub fn handle_edit_game_menu(db: &mut Database) {
             EditGameMenuChoice::EditLineups => edit_lineups(db),
             EditGameMenuChoice::ImportLineup => import_lineup(db),
             EditGameMenuChoice::EditInningsScore => edit_innings_score(db),
+            EditGameMenuChoice::CorrectPlateAppearances => correct_plate_appearances(db),
             EditGameMenuChoice::Back => break,
         }
     }
@@ -126,10 +131,11 @@ pub fn show_edit_game_menu() -> EditGameMenuChoice {
         println!("  2. 📋 Edit Lineups");
         println!("  3. 📥 Import Lineup (JSON/CSV)");
         println!("  4. ✏️ Edit Innings/Score");
+        println!("  5. 📝 Correct Plate Appearances");
         println!();
         println!("  0. 🔙 Back to Main Menu");
         println!();
-        print!("Select an option (1-3 or 0): ");
+        print!("Select an option (1-5 or 0): ");
         io::stdout().flush().unwrap();
 
         let choice = term::read_choice();
@@ -138,6 +144,7 @@ pub fn show_edit_game_menu() -> EditGameMenuChoice {
             2 => return EditGameMenuChoice::EditLineups,
             3 => return EditGameMenuChoice::ImportLineup,
             4 => return EditGameMenuChoice::EditInningsScore,
+            5 => return EditGameMenuChoice::CorrectPlateAppearances,
             0 => return EditGameMenuChoice::Back,
             _ => {
                 println!("\n❌ Invalid choice. Press ENTER to continue...");
@@ -301,12 +308,34 @@ fn create_new_game(db: &Database) {
         return;
     }
 
+    // Rules: the league's profile when both teams play in it
+    let (league_id, mut rules) = match shared_league_rules(conn, away_team_id, home_team_id) {
+        Ok(Some((id, rules))) => (Some(id), rules),
+        Ok(None) => (None, RuleSet::default()),
+        Err(e) => {
+            term::show_error(&format!("Error loading league rules: {}", e));
+            return;
+        }
+    };
+    println!("\nRules: {}", rules.summary());
+
+    match term::read_optional_string(&format!("Scheduled innings [{}]: ", rules.innings)) {
+        None => {}
+        Some(s) => match s.parse::<u32>() {
+            Ok(n) if n > 0 => rules.innings = n,
+            _ => {
+                term::show_error("Scheduled innings must be a positive number!");
+                return;
+            }
+        },
+    }
+
     // STEP 5: Insert lineup for AWAY team
     println!("\n═══════════════════════════════════════");
     println!("    AWAY TEAM LINEUP: {}", away_team.name);
     println!("═══════════════════════════════════════\n");
 
-    let at_uses_dh = ask_team_dh("AWAY", &away_team.name);
+    let at_uses_dh = rules.dh_allowed && ask_team_dh("AWAY", &away_team.name);
     let away_required = if at_uses_dh { 10 } else { 9 };
 
     let away_lineup =
@@ -324,7 +353,7 @@ fn create_new_game(db: &Database) {
     println!("    HOME TEAM LINEUP: {}", home_team.name);
     println!("═══════════════════════════════════════\n");
 
-    let ht_uses_dh = ask_team_dh("HOME", &home_team.name);
+    let ht_uses_dh = rules.dh_allowed && ask_team_dh("HOME", &home_team.name);
     let home_required = if ht_uses_dh { 10 } else { 9 };
 
     let home_lineup =
@@ -340,8 +369,10 @@ fn create_new_game(db: &Database) {
     // STEP 7: Save game to database
     match conn.execute(
         "INSERT INTO games (game_id, home_team_id, away_team_id, venue, game_date, game_time,
-                            at_uses_dh, ht_uses_dh, status)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1)",
+                            at_uses_dh, ht_uses_dh, status, league_id, game_kind,
+                            scheduled_innings, dh_allowed, mercy_rules, max_runs_per_half,
+                            tie_break_inning)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         rusqlite::params![
             game_id,
             home_team_id,
@@ -350,7 +381,14 @@ fn create_new_game(db: &Database) {
             game_date,
             game_time,
             at_uses_dh,
-            ht_uses_dh
+            ht_uses_dh,
+            league_id,
+            rules.kind.as_str(),
+            rules.innings,
+            rules.dh_allowed,
+            rules.mercy_rules_json(),
+            rules.max_runs_per_half,
+            rules.tie_break_inning
         ],
     ) {
         Ok(_) => {
@@ -372,7 +410,8 @@ fn create_new_game(db: &Database) {
                  Date: {} at {}\n\
                  Away: {} {}\n\
                  Home: {} {}\n\
-                 Venue: {}\n\n\
+                 Venue: {}\n\
+                 Rules: {}\n\n\
                  Use 'Play Ball!' to start scoring.",
                 game_id,
                 game_date,
@@ -381,7 +420,8 @@ fn create_new_game(db: &Database) {
                 if at_uses_dh { "(DH)" } else { "" },
                 home_team.name,
                 if ht_uses_dh { "(DH)" } else { "" },
-                venue
+                venue,
+                rules.summary()
             ));
         }
         Err(e) => {
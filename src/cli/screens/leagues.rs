@@ -1,3 +1,4 @@
+use crate::models::rules::{GameKind, MercyRule, RuleSet};
 use crate::utils::term;
 use crate::{Database, League, LeagueMenuChoice, Menu};
 
@@ -26,6 +27,10 @@ fn create_league(db: &Database) {
     let description = term::read_optional_string("Description [optional]: ");
 
     let mut league = League::new(name, season, description);
+    let Some(rules) = read_rules(&league.rules) else {
+        return;
+    };
+    league.rules = rules;
 
     match league.create(db.get_connection()) {
         Ok(id) => {
@@ -54,6 +59,7 @@ fn view_leagues(db: &Database) {
                     if let Some(desc) = league.description {
                         println!("     {}", desc);
                     }
+                    println!("     Rules: {}", league.rules.summary());
                     term::show_separator(35);
                 }
             }
@@ -90,6 +96,10 @@ fn edit_league(db: &Database) {
                 league.name = term::read_string(&format!("Name [{}]: ", league.name));
                 league.season = term::read_optional_string("Stagione: ");
                 league.description = term::read_optional_string("Descrizione: ");
+                let Some(rules) = read_rules(&league.rules) else {
+                    return;
+                };
+                league.rules = rules;
 
                 match league.update(db.get_connection()) {
                     Ok(_) => term::show_success("League updated!"),
@@ -138,3 +148,111 @@ fn delete_league(db: &Database) {
         Err(e) => term::show_error(&format!("Error: {}", e)),
     }
 }
+
+/// Ask for a rule profile; ENTER keeps the value shown in brackets. Returns
+/// `None` after an invalid answer.
+fn read_rules(current: &RuleSet) -> Option<RuleSet> {
+    println!("\nGame rules: {}\n", current.summary());
+    for (i, kind) in GameKind::ALL.iter().enumerate() {
+        term::show_list_item(i + 1, &kind.to_string());
+    }
+    let current_kind = GameKind::ALL
+        .iter()
+        .position(|k| *k == current.kind)
+        .unwrap_or(0);
+    let kind = match term::read_optional_string(&format!("Game [{}]: ", current_kind + 1)) {
+        None => current.kind,
+        Some(s) => match s.parse::<usize>() {
+            Ok(n) if (1..=GameKind::ALL.len()).contains(&n) => GameKind::ALL[n - 1],
+            _ => {
+                term::show_error("Invalid selection");
+                return None;
+            }
+        },
+    };
+
+    // Switching game kind starts from that kind's standard length.
+    let mut rules = current.clone();
+    if kind != current.kind {
+        rules.kind = kind;
+        rules.innings = kind.default_innings();
+    }
+
+    if let Some(s) = term::read_optional_string(&format!("Innings [{}]: ", rules.innings)) {
+        match s.parse::<u32>() {
+            Ok(n) if n > 0 => rules.innings = n,
+            _ => {
+                term::show_error("Innings must be a positive number!");
+                return None;
+            }
+        }
+    }
+
+    let dh = if rules.dh_allowed { "y" } else { "n" };
+    if let Some(s) = term::read_optional_string(&format!("DH allowed (y/n) [{}]: ", dh)) {
+        rules.dh_allowed = s.eq_ignore_ascii_case("y");
+    }
+
+    let mercy: Vec<_> = rules.mercy_rules.iter().map(ToString::to_string).collect();
+    let prompt = format!(
+        "Run rule, <runs>@<inning> e.g. 15@5,10@7 ('-' for none) [{}]: ",
+        if mercy.is_empty() {
+            "none".to_string()
+        } else {
+            mercy.join(",")
+        }
+    );
+    match term::read_optional_string(&prompt).as_deref() {
+        None => {}
+        Some("-") => rules.mercy_rules.clear(),
+        Some(s) => match MercyRule::parse_list(s) {
+            Ok(list) => rules.mercy_rules = list,
+            Err(e) => {
+                term::show_error(&e);
+                return None;
+            }
+        },
+    }
+
+    rules.max_runs_per_half = read_optional_limit(
+        "Max runs per half-inning ('-' for none)",
+        rules.max_runs_per_half,
+    )?;
+    rules.tie_break_inning = read_optional_limit(
+        "Tie-break runner from inning ('-' for none)",
+        rules.tie_break_inning,
+    )?;
+    if let Some(inning) = rules.tie_break_inning
+        && inning <= rules.innings
+    {
+        term::show_error(&format!(
+            "The tie-break starts in an extra inning, after inning {}.",
+            rules.innings
+        ));
+        return None;
+    }
+
+    Some(rules)
+}
+
+/// Ask for an optional positive number: ENTER keeps `current`, `-` clears
+/// it. Returns `None` after an invalid answer.
+fn read_optional_limit<T>(label: &str, current: Option<T>) -> Option<Option<T>>
+where
+    T: std::str::FromStr + std::fmt::Display + Default + PartialOrd,
+{
+    let shown = current
+        .as_ref()
+        .map_or("none".to_string(), ToString::to_string);
+    match term::read_optional_string(&format!("{} [{}]: ", label, shown)).as_deref() {
+        None => Some(current),
+        Some("-") => Some(None),
+        Some(s) => match s.parse::<T>() {
+            Ok(n) if n > T::default() => Some(Some(n)),
+            _ => {
+                term::show_error(&format!("'{}' is not a positive number", s));
+                None
+            }
+        },
+    }
+}
@@ -1,3 +1,4 @@
+pub mod corrections;
 pub mod db;
 pub mod export;
 pub mod game;
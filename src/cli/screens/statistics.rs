@@ -1,13 +1,424 @@
-use crate::Database;
+use crate::cli::menu::{Menu, StatisticsMenuChoice};
+use crate::models::player_traits::BatSide;
+use crate::stats::box_score::BoxScore;
+use crate::stats::scorecard::svg;
+use crate::stats::spray::{SprayChart, SpraySubject};
+use crate::stats::{StatsFilter, StatsReport, format_rate, load_stats, player_names};
 use crate::utils::term;
+use crate::{Database, League, Player, Team};
+use rusqlite::Connection;
+use std::collections::HashMap;
 
-pub fn handle_statistics(_db: &Database) {
-    term::show_header("STATISTICS");
-    println!("🚧 Statistics module under development...\n");
-    println!("  Here you will be able to view:");
-    println!("  - Player statistics");
-    println!("  - Batting average, ERA, OPS");
-    println!("  - League standings");
-    println!("  - Game history\n");
+/// Batting, pitching and fielding lines over the games the scorer picks,
+/// the box score of one game, or a spray chart.
+pub fn handle_statistics(db: &Database) {
+    loop {
+        let conn = db.get_connection();
+        let (title, filter) = match Menu::show_statistics_menu() {
+            StatisticsMenuChoice::AllGames => {
+                ("ALL GAMES".to_string(), Some(StatsFilter::default()))
+            }
+            StatisticsMenuChoice::ByGame => ("GAME".to_string(), select_game(conn)),
+            StatisticsMenuChoice::ByTeam => ("TEAM".to_string(), select_team(conn)),
+            StatisticsMenuChoice::ByLeague => ("LEAGUE".to_string(), select_league(conn)),
+            StatisticsMenuChoice::BySeason => {
+                let season = term::read_string("Season (e.g. 2026): ");
+                let season = season.trim().to_string();
+                let filter = (!season.is_empty()).then(|| StatsFilter {
+                    season: Some(season.clone()),
+                    ..Default::default()
+                });
+                (format!("SEASON {season}"), filter)
+            }
+            StatisticsMenuChoice::BoxScore => {
+                if let Some(game_pk) = select_game(conn).and_then(|f| f.game_pk) {
+                    show_box_score(conn, game_pk);
+                }
+                continue;
+            }
+            StatisticsMenuChoice::SprayChart => {
+                show_spray_chart(conn);
+                continue;
+            }
+            StatisticsMenuChoice::Back => break,
+        };
+        let Some(filter) = filter else {
+            continue;
+        };
+
+        match load_stats(conn, &filter) {
+            Ok(report) => show_report(conn, &format!("STATISTICS - {title}"), &report),
+            Err(e) => term::show_error(&format!("Error computing statistics: {e}")),
+        }
+    }
+}
+
+fn select_game(conn: &Connection) -> Option<StatsFilter> {
+    let games: Vec<(i64, String, String, String, String)> = {
+        let mut stmt = match conn.prepare(
+            "SELECT g.id, g.game_id, g.game_date, t1.name, t2.name
+             FROM games g
+             JOIN teams t1 ON g.away_team_id = t1.id
+             JOIN teams t2 ON g.home_team_id = t2.id
+             WHERE EXISTS (SELECT 1 FROM plate_appearances pa WHERE pa.game_id = g.id)
+             ORDER BY g.game_date DESC, g.id DESC",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                term::show_error(&format!("Error querying games: {e}"));
+                return None;
+            }
+        };
+
+        match stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        }) {
+            Ok(rows) => rows.filter_map(Result::ok).collect(),
+            Err(e) => {
+                term::show_error(&format!("Error querying games: {e}"));
+                return None;
+            }
+        }
+    };
+
+    if games.is_empty() {
+        println!("📭 No scored games found.");
+        term::wait_for_enter();
+        return None;
+    }
+
+    println!("\n📋 Scored Games:\n");
+    for (i, (_id, game_id, date, away, home)) in games.iter().enumerate() {
+        println!(
+            "  {}. {} - {} @ {} | ID: {}",
+            i + 1,
+            date,
+            away,
+            home,
+            game_id
+        );
+    }
+    println!();
+
+    let game_pk = pick(games.len(), "Select game (number, 0 to cancel): ").map(|i| games[i].0)?;
+    Some(StatsFilter {
+        game_pk: Some(game_pk),
+        ..Default::default()
+    })
+}
+
+fn select_team(conn: &Connection) -> Option<StatsFilter> {
+    let teams = match Team::get_all(conn) {
+        Ok(teams) if !teams.is_empty() => teams,
+        Ok(_) => {
+            println!("📭 No teams found.");
+            term::wait_for_enter();
+            return None;
+        }
+        Err(e) => {
+            term::show_error(&format!("Error loading teams: {e}"));
+            return None;
+        }
+    };
+
+    println!("\n📋 Teams:\n");
+    for (i, team) in teams.iter().enumerate() {
+        term::show_list_item(i + 1, &team.name);
+    }
+    println!();
+
+    let team_id =
+        pick(teams.len(), "Select team (number, 0 to cancel): ").and_then(|i| teams[i].id)?;
+    Some(StatsFilter {
+        team_id: Some(team_id),
+        ..Default::default()
+    })
+}
+
+fn select_league(conn: &Connection) -> Option<StatsFilter> {
+    let leagues = match League::get_all(conn) {
+        Ok(leagues) if !leagues.is_empty() => leagues,
+        Ok(_) => {
+            println!("📭 No leagues found.");
+            term::wait_for_enter();
+            return None;
+        }
+        Err(e) => {
+            term::show_error(&format!("Error loading leagues: {e}"));
+            return None;
+        }
+    };
+
+    println!("\n📋 Leagues:\n");
+    for (i, league) in leagues.iter().enumerate() {
+        let season = league.season.as_deref().unwrap_or("-");
+        term::show_list_item(i + 1, &format!("{} ({season})", league.name));
+    }
+    println!();
+
+    let league_id =
+        pick(leagues.len(), "Select league (number, 0 to cancel): ").and_then(|i| leagues[i].id)?;
+    Some(StatsFilter {
+        league_id: Some(league_id),
+        ..Default::default()
+    })
+}
+
+/// Index of the entry picked from a numbered list of `len` entries.
+fn pick(len: usize, prompt: &str) -> Option<usize> {
+    match term::read_i64(prompt) {
+        Some(0) | None => None,
+        Some(c) if c > 0 && (c as usize) <= len => Some(c as usize - 1),
+        _ => {
+            term::show_error("Invalid selection");
+            None
+        }
+    }
+}
+
+fn show_report(conn: &Connection, title: &str, report: &StatsReport) {
+    term::clear_screen();
+    term::show_header(title);
+
+    if report.games == 0 {
+        println!("📭 No games match the selection.");
+        term::wait_for_enter();
+        return;
+    }
+
+    let players = player_names(conn).unwrap_or_default();
+    let teams = team_labels(conn);
+    let name = |id: i64| {
+        players
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| format!("#{id}"))
+    };
+    let team = |id: i64| teams.get(&id).cloned().unwrap_or_default();
+
+    println!("  Games: {}\n", report.games);
+
+    println!("  🏏 BATTING");
+    println!(
+        "  {:<22} {:<5} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>5} {:>5} {:>5} {:>5}",
+        "Player",
+        "Team",
+        "PA",
+        "AB",
+        "R",
+        "H",
+        "2B",
+        "3B",
+        "HR",
+        "RBI",
+        "BB",
+        "K",
+        "AVG",
+        "OBP",
+        "SLG",
+        "OPS"
+    );
+    term::show_separator(98);
+    for l in &report.batting {
+        println!(
+            "  {:<22} {:<5} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>5} {:>5} {:>5} {:>5}",
+            name(l.player_id),
+            team(l.team_id),
+            l.plate_appearances,
+            l.at_bats,
+            l.runs,
+            l.hits,
+            l.doubles,
+            l.triples,
+            l.home_runs,
+            l.rbi,
+            l.walks,
+            l.strikeouts,
+            format_rate(l.avg()),
+            format_rate(l.obp()),
+            format_rate(l.slg()),
+            format_rate(l.ops()),
+        );
+    }
+
+    println!("\n  🎯 PITCHING (ERA over {} innings)", report.era_innings);
+    println!(
+        "  {:<22} {:<5} {:>5} {:>4} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>6} {:>5}",
+        "Player", "Team", "IP", "PC", "BF", "H", "R", "ER", "BB", "K", "ERA", "WHIP"
+    );
+    term::show_separator(82);
+    for l in &report.pitching {
+        let two_decimals = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.2}"));
+        println!(
+            "  {:<22} {:<5} {:>5} {:>4} {:>3} {:>3} {:>3} {:>3} {:>3} {:>3} {:>6} {:>5}",
+            name(l.player_id),
+            team(l.team_id),
+            l.innings_pitched(),
+            l.pitches,
+            l.batters_faced,
+            l.hits,
+            l.runs,
+            l.earned_runs,
+            l.walks,
+            l.strikeouts,
+            two_decimals(l.era(report.era_innings)),
+            two_decimals(l.whip()),
+        );
+    }
+
+    println!("\n  🧤 FIELDING");
+    println!(
+        "  {:<22} {:<5} {:>3} {:>3} {:>3} {:>3} {:>5}",
+        "Player", "Team", "PO", "A", "E", "DP", "FPCT"
+    );
+    term::show_separator(50);
+    for l in &report.fielding {
+        println!(
+            "  {:<22} {:<5} {:>3} {:>3} {:>3} {:>3} {:>5}",
+            name(l.player_id),
+            team(l.team_id),
+            l.putouts,
+            l.assists,
+            l.errors,
+            l.double_plays,
+            format_rate(l.fielding_pct()),
+        );
+    }
+    println!();
     term::wait_for_enter();
 }
+
+fn show_box_score(conn: &Connection, game_pk: i64) {
+    match BoxScore::load(conn, game_pk) {
+        Ok(box_score) => {
+            term::clear_screen();
+            term::show_header("BOX SCORE");
+            println!("{box_score}");
+            term::wait_for_enter();
+        }
+        Err(e) => term::show_error(&format!("Error building the box score: {e}")),
+    }
+}
+
+/// Spray chart of a batter, a team or a pitcher, optionally for one
+/// season and one batting side, with an SVG export. Printed as text like
+/// the box score: the ratatui screen only runs during Play Ball.
+fn show_spray_chart(conn: &Connection) {
+    println!("\n  1. Batter\n  2. Team\n  3. Pitcher (batters faced)\n");
+    let Some(kind) = pick(3, "Chart (number, 0 to cancel): ") else {
+        return;
+    };
+    let Some(mut filter) = select_team(conn) else {
+        return;
+    };
+    let Some(team_id) = filter.team_id else {
+        return;
+    };
+    let team = team_labels(conn).remove(&team_id).unwrap_or_default();
+
+    let (subject, label) = if kind == 1 {
+        (SpraySubject::Team(team_id), team)
+    } else {
+        let players = match Player::get_by_team(conn, team_id) {
+            Ok(players) if !players.is_empty() => players,
+            Ok(_) => {
+                println!("📭 No players found.");
+                term::wait_for_enter();
+                return;
+            }
+            Err(e) => {
+                term::show_error(&format!("Error loading players: {e}"));
+                return;
+            }
+        };
+        println!("\n📋 Players:\n");
+        for (i, p) in players.iter().enumerate() {
+            term::show_list_item(
+                i + 1,
+                &format!("#{} {} {}", p.number, p.first_name, p.last_name),
+            );
+        }
+        println!();
+        let Some(player) =
+            pick(players.len(), "Select player (number, 0 to cancel): ").map(|i| &players[i])
+        else {
+            return;
+        };
+        let Some(id) = player.id else {
+            return;
+        };
+        let label = format!("{} {}", player.first_name, player.last_name);
+        if kind == 0 {
+            (SpraySubject::Batter(id), label)
+        } else {
+            (
+                SpraySubject::Pitcher(id),
+                format!("{label} (batters faced)"),
+            )
+        }
+    };
+
+    let season = term::read_string("Season (e.g. 2026, empty for all): ");
+    let season = season.trim();
+    filter.season = (!season.is_empty()).then(|| season.to_string());
+    let side = term::choose_enum_optional::<BatSide>();
+
+    let chart = match SprayChart::load(conn, &filter, subject, side) {
+        Ok(chart) => chart,
+        Err(e) => {
+            term::show_error(&format!("Error building the spray chart: {e}"));
+            return;
+        }
+    };
+    let subtitle = format!(
+        "{} - {} batters",
+        filter.season.as_deref().unwrap_or("All seasons"),
+        side.map_or("all", |s| s.as_str())
+    );
+
+    term::clear_screen();
+    term::show_header(&format!("SPRAY CHART - {label}"));
+    println!("  {subtitle}\n");
+    println!("{}", chart.diagram());
+
+    if term::confirm("Export as SVG?") {
+        let slug: String = label
+            .chars()
+            .take_while(|c| *c != '(')
+            .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_")
+            .to_lowercase();
+        let path = std::env::current_dir().unwrap_or_default().join(format!(
+            "spray_{}_{slug}.svg",
+            ["batter", "team", "pitcher"][kind]
+        ));
+        match std::fs::write(&path, svg::render(&chart.page(&label, &subtitle))) {
+            Ok(()) => term::show_success(&format!(
+                "Spray chart exported!\n   File: {}",
+                path.display()
+            )),
+            Err(e) => term::show_error(&format!("Failed to export: {e}")),
+        }
+    }
+}
+
+/// Team abbreviation, or name when it has none, by id.
+fn team_labels(conn: &Connection) -> HashMap<i64, String> {
+    Team::get_all(conn)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|t| {
+            let label = t.abbreviation.clone().unwrap_or_else(|| t.name.clone());
+            t.id.map(|id| (id, label))
+        })
+        .collect()
+}
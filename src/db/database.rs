@@ -109,4 +109,90 @@ mod tests {
         let version = crate::db::migrations::get_schema_version(db.get_connection()).unwrap();
         assert_eq!(version, crate::db::migrations::CURRENT_SCHEMA_VERSION);
     }
+
+    #[test]
+    fn player_foreign_keys_left_on_players_old_are_repaired() {
+        // A database last opened at v23, before the repair migration.
+        let db = Database::new(":memory:").unwrap();
+        let conn = db.get_connection();
+        migrations::init_meta_table(conn).unwrap();
+        for migration in migrations::get_migrations()
+            .into_iter()
+            .filter(|m| m.version < 24)
+        {
+            (migration.up)(conn).unwrap();
+        }
+        migrations::set_meta_value(conn, "schema_version", "23").unwrap();
+
+        let stale = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE sql LIKE '%players_old%'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            stale(conn),
+            3,
+            "plate_appearances, at_bat_draft and at_bats"
+        );
+
+        conn.execute_batch(
+            "INSERT INTO teams (id, name) VALUES (1, 'Lions');
+             INSERT INTO players (id, team_id, number, first_name, last_name, position)
+             VALUES (1, 1, 7, 'Ada', 'Park', 'P');
+             INSERT INTO games (id, game_id, home_team_id, away_team_id, venue, game_date)
+             VALUES (1, 'G1', 1, 1, 'Park', '2026-04-01');",
+        )
+        .unwrap();
+        let draft = "INSERT INTO at_bat_draft (game_id, inning, half_inning, batter_id, pitcher_id, pitch_count_json)
+                     VALUES (1, 1, 'Top', 1, 1, '{}')";
+        assert!(conn.execute(draft, []).is_err());
+
+        // A row written while foreign keys were off must survive the rebuild.
+        conn.pragma_update(None, "foreign_keys", "OFF").unwrap();
+        conn.execute(
+            "INSERT INTO plate_appearances (game_id, seq, inning, half_inning, batter_id, pitcher_id,
+                                            outcome_type, outs)
+             VALUES (1, 1, 1, 'Top', 1, 1, 'single', 0)",
+            [],
+        )
+        .unwrap();
+        conn.pragma_update(None, "foreign_keys", "ON").unwrap();
+
+        db.init_schema().unwrap();
+        assert_eq!(stale(conn), 0);
+        conn.execute(draft, []).unwrap();
+
+        let kept: i64 = conn
+            .query_row("SELECT COUNT(*) FROM plate_appearances", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(kept, 1);
+        let violations: i64 = conn
+            .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(violations, 0);
+        let indexes: Vec<String> = conn
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'index' AND tbl_name IN ('plate_appearances', 'at_bat_draft')
+                   AND sql IS NOT NULL ORDER BY name",
+            )
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            indexes,
+            [
+                "idx_at_bat_draft_game",
+                "idx_pa_batter",
+                "idx_pa_compact_game_seq"
+            ]
+        );
+    }
 }
@@ -0,0 +1,241 @@
+//! Teams, rosters and games shared by the tests that need a database,
+//! and the stored rows shared by the tests that build games in memory.
+
+use crate::Position;
+use crate::db::lineups::StartingSpot;
+use crate::db::plate_appearances::PlateAppearanceRow;
+use crate::db::runner_movements::RunnerMovementRow;
+use crate::models::game_state::BatterOrder;
+use crate::stats::GameRecord;
+use rusqlite::{Connection, params};
+
+pub(crate) fn team(conn: &Connection, name: &str, abbreviation: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO teams (name, abbreviation) VALUES (?1, ?2)",
+        params![name, abbreviation],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+pub(crate) fn player(
+    conn: &Connection,
+    team_id: i64,
+    number: i32,
+    away_number: Option<i32>,
+    first_name: &str,
+    last_name: &str,
+    position: &str,
+) -> i64 {
+    conn.execute(
+        "INSERT INTO players (team_id, number, away_number, first_name, last_name, position)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            team_id,
+            number,
+            away_number,
+            first_name,
+            last_name,
+            position
+        ],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+pub(crate) fn game(conn: &Connection, game_id: &str, away_team_id: i64, home_team_id: i64) -> i64 {
+    conn.execute(
+        "INSERT INTO games (game_id, home_team_id, away_team_id, venue, game_date)
+         VALUES (?1, ?2, ?3, 'Park', '2026-04-01')",
+        params![game_id, home_team_id, away_team_id],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+/// A starter in `batting_order` at `position` (a `game_lineups` value).
+pub(crate) fn starter(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+    player_id: i64,
+    batting_order: u8,
+    position: &str,
+) {
+    conn.execute(
+        "INSERT INTO game_lineups (game_id, team_id, player_id, batting_order, defensive_position)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![game_id, team_id, player_id, batting_order, position],
+    )
+    .unwrap();
+}
+
+/// A game between two full lineups.
+pub(crate) struct NineVsNine {
+    pub game_pk: i64,
+    pub away_team_id: i64,
+    pub home_team_id: i64,
+}
+
+/// `game_id` between Away (`AWY`) and Home (`HOM`): on both teams the
+/// player batting n-th wears n and plays position n, so the pitcher bats
+/// first.
+pub(crate) fn nine_vs_nine(conn: &Connection, game_id: &str) -> NineVsNine {
+    let away_team_id = team(conn, "Away", "AWY");
+    let home_team_id = team(conn, "Home", "HOM");
+    let game_pk = game(conn, game_id, away_team_id, home_team_id);
+    for (team_id, last_name) in [(away_team_id, "Visitor"), (home_team_id, "Local")] {
+        for n in 1..=9u8 {
+            let position = Position::from_number(n).unwrap().to_db_value();
+            let id = player(
+                conn,
+                team_id,
+                i32::from(n),
+                None,
+                "Pat",
+                last_name,
+                &position,
+            );
+            starter(conn, game_id, team_id, id, n, &position);
+        }
+    }
+    NineVsNine {
+        game_pk,
+        away_team_id,
+        home_team_id,
+    }
+}
+
+/// A game with a home bullpen and a few away batters.
+pub(crate) struct BullpenGame {
+    pub game_pk: i64,
+    pub home_team_id: i64,
+    /// In the order given, the starter first.
+    pub pitchers: Vec<i64>,
+    /// Wearing 1, 2, ...
+    pub batters: Vec<i64>,
+}
+
+/// `game_id` between Away (`AWY`) and Home (`HOM`). The home team has
+/// `pitchers` (jersey, road jersey, first and last name), the first one
+/// starting and batting ninth; the away team has `batters` batters and
+/// no lineup.
+pub(crate) fn bullpen_game(
+    conn: &Connection,
+    game_id: &str,
+    pitchers: &[(i32, Option<i32>, &str, &str)],
+    batters: i32,
+) -> BullpenGame {
+    let home_team_id = team(conn, "Home", "HOM");
+    let away_team_id = team(conn, "Away", "AWY");
+    let pitchers: Vec<i64> = pitchers
+        .iter()
+        .map(|&(number, away_number, first, last)| {
+            player(conn, home_team_id, number, away_number, first, last, "P")
+        })
+        .collect();
+    let batters = (1..=batters)
+        .map(|number| player(conn, away_team_id, number, None, "Bo", "Batter", "CF"))
+        .collect();
+    let game_pk = game(conn, game_id, away_team_id, home_team_id);
+    starter(conn, game_id, home_team_id, pitchers[0], 9, "1");
+    BullpenGame {
+        game_pk,
+        home_team_id,
+        pitchers,
+        batters,
+    }
+}
+
+pub(crate) const AWAY: i64 = 1;
+pub(crate) const HOME: i64 = 2;
+
+pub(crate) fn pa(
+    seq: i64,
+    half: &str,
+    order: BatterOrder,
+    pitcher_id: i64,
+    outcome: &str,
+    data: Option<&str>,
+    outs: i64,
+) -> PlateAppearanceRow {
+    PlateAppearanceRow {
+        id: seq,
+        game_id: 1,
+        seq,
+        inning: 1,
+        half_inning: half.to_string(),
+        batter_id: i64::from(order) + if half == "Top" { 10 } else { 20 },
+        batter_order: order,
+        pitcher_id,
+        pitches: 3,
+        pitches_sequence: "[]".to_string(),
+        outcome_type: outcome.to_string(),
+        outcome_data: data.map(str::to_string),
+        outs,
+        runner_overrides_json: "[]".to_string(),
+        double_play_json: None,
+        rbi: None,
+    }
+}
+
+pub(crate) fn rm(
+    id: i64,
+    pa_seq: Option<i64>,
+    half: &str,
+    order: BatterOrder,
+    from: &str,
+    to: &str,
+    kind: &str,
+) -> RunnerMovementRow {
+    RunnerMovementRow {
+        id,
+        game_id: 1,
+        pa_seq,
+        game_event_id: None,
+        inning: 1,
+        half_inning: half.to_string(),
+        runner_id: Some(i64::from(order) + if half == "Top" { 10 } else { 20 }),
+        batter_order: order,
+        start_base: from.to_string(),
+        end_base: to.to_string(),
+        advancement_type: kind.to_string(),
+        is_out: to == "OUT",
+        scored: to == "HOME",
+        is_earned: true,
+        fielders: None,
+    }
+}
+
+/// A game between `AWAY` (players 11-19) and `HOME` (players 21-29),
+/// both fielding slot n at position n.
+pub(crate) fn game_record(
+    plate_appearances: Vec<PlateAppearanceRow>,
+    movements: Vec<RunnerMovementRow>,
+) -> GameRecord {
+    let starters = [(AWAY, 10), (HOME, 20)]
+        .into_iter()
+        .flat_map(|(team_id, base)| {
+            (1..=9u8).map(move |n| {
+                (
+                    team_id,
+                    StartingSpot {
+                        player_id: base + i64::from(n),
+                        batting_order: n,
+                        position: Position::from_number(n).unwrap(),
+                    },
+                )
+            })
+        })
+        .collect();
+    GameRecord {
+        game_pk: 1,
+        away_team_id: AWAY,
+        home_team_id: HOME,
+        innings: 9,
+        plate_appearances,
+        movements,
+        events: Vec::new(),
+        starters,
+    }
+}
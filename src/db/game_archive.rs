@@ -0,0 +1,1306 @@
+//! Complete JSON archive of a game, to move it to another database.
+//!
+//! The archive holds every row the game is rebuilt from: the `games`
+//! header, all the `game_lineups` rows (substituted players included),
+//! `plate_appearances`, `runner_movements`, `game_events`, the umpire
+//! assignments and evaluations, and the in-progress at-bat draft.
+//!
+//! Row ids of the source database are kept in the archive only to link
+//! the rows together. On import, teams are found by abbreviation (by name
+//! when the team has none), players by team and the jersey they wore in
+//! the game (`away_number` for the away team when they have one) and the
+//! league by name; umpires are found by name and created when missing. Every id is
+//! rewritten, including the ones inside the `game_events` payloads, so
+//! the imported game replays to the same state.
+
+use crate::db::at_bat_draft::load_at_bat_draft;
+use crate::db::game_events::list_game_events;
+use crate::db::plate_appearances::list_plate_appearances;
+use crate::db::runner_movements::list_runner_movements;
+use crate::db::umpire::{Umpire, UmpireEvaluation, list_game_umpires};
+use crate::models::events::DomainEvent;
+use anyhow::{Context, Result, anyhow, bail};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// `format` of every archive.
+pub const GAME_ARCHIVE_FORMAT: &str = "bs_scoring.game";
+
+/// Version written by this build; older versions are still read.
+pub const GAME_ARCHIVE_VERSION: u32 = 1;
+
+/// A game and everything recorded about it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameArchive {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub game: ArchiveGame,
+    pub league: Option<String>,
+    pub away_team: ArchiveTeam,
+    pub home_team: ArchiveTeam,
+    /// Every player the other sections refer to.
+    pub players: Vec<ArchivePlayer>,
+    /// `game_lineups` rows in the order they were written.
+    pub lineups: Vec<ArchiveLineupRow>,
+    pub plate_appearances: Vec<ArchivePlateAppearance>,
+    pub runner_movements: Vec<ArchiveRunnerMovement>,
+    pub game_events: Vec<ArchiveGameEvent>,
+    pub umpires: Vec<ArchiveUmpire>,
+    pub umpire_assignments: Vec<ArchiveUmpireAssignment>,
+    pub umpire_evaluations: Vec<ArchiveUmpireEvaluation>,
+    /// The at-bat in progress when the game was exported.
+    #[serde(default)]
+    pub at_bat_draft: Option<ArchiveAtBatDraft>,
+}
+
+/// The `games` row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveGame {
+    pub game_id: String,
+    pub venue: String,
+    pub game_date: String,
+    pub game_time: Option<String>,
+    pub at_uses_dh: bool,
+    pub ht_uses_dh: bool,
+    pub away_score: i64,
+    pub home_score: i64,
+    pub status: i64,
+    pub scheduled_innings: i64,
+    pub game_kind: String,
+    pub dh_allowed: bool,
+    pub mercy_rules: String,
+    pub max_runs_per_half: Option<i64>,
+    pub tie_break_inning: Option<i64>,
+    pub end_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveTeam {
+    pub id: i64,
+    pub name: String,
+    pub abbreviation: Option<String>,
+}
+
+/// Which of the two teams a player belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveSide {
+    Away,
+    Home,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePlayer {
+    pub id: i64,
+    pub team: ArchiveSide,
+    pub number: i32,
+    #[serde(default)]
+    pub away_number: Option<i32>,
+    pub first_name: String,
+    pub last_name: Option<String>,
+}
+
+impl ArchivePlayer {
+    /// The jersey worn in the game: the away number on the away team.
+    fn jersey(&self) -> i32 {
+        match self.team {
+            ArchiveSide::Away => self.away_number.unwrap_or(self.number),
+            ArchiveSide::Home => self.number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveLineupRow {
+    pub team: ArchiveSide,
+    pub player_id: i64,
+    pub batting_order: u8,
+    pub defensive_position: String,
+    pub is_starting: bool,
+    pub substituted_at_inning: Option<i64>,
+    pub substituted_at_half: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePlateAppearance {
+    pub seq: i64,
+    pub inning: i64,
+    pub half_inning: String,
+    pub batter_id: i64,
+    pub batter_order: u8,
+    pub pitcher_id: i64,
+    pub pitches: i64,
+    pub pitches_sequence: String,
+    pub outcome_type: String,
+    pub outcome_data: Option<String>,
+    pub outs: i64,
+    pub runner_overrides_json: String,
+    pub double_play_json: Option<String>,
+    pub rbi: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRunnerMovement {
+    pub pa_seq: Option<i64>,
+    pub game_event_id: Option<i64>,
+    pub inning: i64,
+    pub half_inning: String,
+    pub runner_id: Option<i64>,
+    pub batter_order: u8,
+    pub start_base: String,
+    pub end_base: String,
+    pub advancement_type: String,
+    pub is_out: bool,
+    pub scored: bool,
+    pub is_earned: bool,
+    pub fielders: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveGameEvent {
+    pub id: i64,
+    pub inning: i64,
+    pub half_inning: String,
+    pub event_type: String,
+    pub event_data: Option<String>,
+    pub description: Option<String>,
+    pub created_at: Option<String>,
+    pub pa_seq: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveUmpire {
+    pub id: i64,
+    pub first_name: String,
+    pub last_name: String,
+    pub license_number: Option<String>,
+    pub level: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveUmpireAssignment {
+    pub umpire_id: i64,
+    pub position: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveUmpireEvaluation {
+    pub umpire_id: i64,
+    pub evaluator_name: Option<String>,
+    pub position_evaluated: String,
+    pub strike_zone_accuracy: Option<i32>,
+    pub safe_out_accuracy: Option<i32>,
+    pub positioning: Option<i32>,
+    pub timing: Option<i32>,
+    pub game_management: Option<i32>,
+    pub professionalism: Option<i32>,
+    pub communication: Option<i32>,
+    pub hustle: Option<i32>,
+    pub overall_score: Option<i32>,
+    pub strengths: Option<String>,
+    pub areas_to_improve: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveAtBatDraft {
+    pub inning: i64,
+    pub half_inning: String,
+    pub batter_id: Option<i64>,
+    pub pitcher_id: Option<i64>,
+    pub pitch_count_json: String,
+}
+
+/// Call `player` on every player id and `team` on every team id of `ev`.
+fn visit_event_ids(
+    ev: &mut DomainEvent,
+    player: &mut dyn FnMut(&mut i64),
+    team: &mut dyn FnMut(&mut i64),
+) {
+    match ev {
+        DomainEvent::AtBatStarted {
+            batting_team_id,
+            batter_id,
+            pitcher_id,
+            ..
+        } => {
+            team(batting_team_id);
+            player(batter_id);
+            player(pitcher_id);
+        }
+        DomainEvent::PitchRecorded {
+            pitcher_id,
+            batter_id,
+            ..
+        } => {
+            player(pitcher_id);
+            player(batter_id);
+        }
+        DomainEvent::PitcherChanged { pitcher_id, .. }
+        | DomainEvent::AtBatPitchesCount { pitcher_id, .. } => player(pitcher_id),
+        DomainEvent::WalkIssued { batter_id } | DomainEvent::Strikeout { batter_id, .. } => {
+            player(batter_id)
+        }
+        DomainEvent::RunnerToFirst { runner_id, .. } => player(runner_id),
+        DomainEvent::PlayerSubstituted(d) => {
+            team(&mut d.team_id);
+            if let Some(out) = d.player_out_id.as_mut() {
+                player(out);
+            }
+            player(&mut d.player_in_id);
+        }
+        DomainEvent::SideChange(_)
+        | DomainEvent::StatusChanged(_)
+        | DomainEvent::GameStarted
+        | DomainEvent::CountReset
+        | DomainEvent::OutRecorded(_) => {}
+    }
+}
+
+fn parse_event(data: Option<&str>) -> Option<DomainEvent> {
+    serde_json::from_str(data?).ok()
+}
+
+/// Archive game `game_pk` (the `games` row id).
+pub fn export_game_archive(conn: &Connection, game_pk: i64) -> Result<GameArchive> {
+    let (game, away_team_id, home_team_id, league): (ArchiveGame, i64, i64, Option<String>) = conn
+        .query_row(
+            r#"
+            SELECT g.game_id, g.venue, g.game_date, g.game_time,
+                   g.at_uses_dh, g.ht_uses_dh, g.away_score, g.home_score, g.status,
+                   g.scheduled_innings, g.game_kind, g.dh_allowed, g.mercy_rules,
+                   g.max_runs_per_half, g.tie_break_inning, g.end_reason,
+                   g.away_team_id, g.home_team_id, l.name
+            FROM games g
+            LEFT JOIN leagues l ON l.id = g.league_id
+            WHERE g.id = ?1
+            "#,
+            params![game_pk],
+            |r| {
+                Ok((
+                    ArchiveGame {
+                        game_id: r.get(0)?,
+                        venue: r.get(1)?,
+                        game_date: r.get(2)?,
+                        game_time: r.get(3)?,
+                        at_uses_dh: r.get(4)?,
+                        ht_uses_dh: r.get(5)?,
+                        away_score: r.get(6)?,
+                        home_score: r.get(7)?,
+                        status: r.get(8)?,
+                        scheduled_innings: r.get(9)?,
+                        game_kind: r.get(10)?,
+                        dh_allowed: r.get(11)?,
+                        mercy_rules: r.get(12)?,
+                        max_runs_per_half: r.get(13)?,
+                        tie_break_inning: r.get(14)?,
+                        end_reason: r.get(15)?,
+                    },
+                    r.get(16)?,
+                    r.get(17)?,
+                    r.get(18)?,
+                ))
+            },
+        )
+        .optional()?
+        .ok_or_else(|| anyhow!("Game {game_pk} not found"))?;
+
+    let team = |id: i64| -> Result<ArchiveTeam> {
+        Ok(conn.query_row(
+            "SELECT id, name, abbreviation FROM teams WHERE id = ?1",
+            params![id],
+            |r| {
+                Ok(ArchiveTeam {
+                    id: r.get(0)?,
+                    name: r.get(1)?,
+                    abbreviation: r.get(2)?,
+                })
+            },
+        )?)
+    };
+    let side = |team_id: i64| {
+        if team_id == home_team_id {
+            ArchiveSide::Home
+        } else {
+            ArchiveSide::Away
+        }
+    };
+
+    let lineups = {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT team_id, player_id, batting_order, defensive_position, is_starting,
+                   substituted_at_inning, substituted_at_half
+            FROM game_lineups
+            WHERE game_id = ?1
+            ORDER BY id
+            "#,
+        )?;
+        stmt.query_map(params![game.game_id], |r| {
+            Ok(ArchiveLineupRow {
+                team: side(r.get(0)?),
+                player_id: r.get(1)?,
+                batting_order: r.get(2)?,
+                defensive_position: r.get(3)?,
+                is_starting: r.get::<_, Option<bool>>(4)?.unwrap_or(true),
+                substituted_at_inning: r.get(5)?,
+                substituted_at_half: r.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let plate_appearances: Vec<ArchivePlateAppearance> = list_plate_appearances(conn, game_pk)?
+        .into_iter()
+        .map(|r| ArchivePlateAppearance {
+            seq: r.seq,
+            inning: r.inning,
+            half_inning: r.half_inning,
+            batter_id: r.batter_id,
+            batter_order: r.batter_order,
+            pitcher_id: r.pitcher_id,
+            pitches: r.pitches,
+            pitches_sequence: r.pitches_sequence,
+            outcome_type: r.outcome_type,
+            outcome_data: r.outcome_data,
+            outs: r.outs,
+            runner_overrides_json: r.runner_overrides_json,
+            double_play_json: r.double_play_json,
+            rbi: r.rbi,
+        })
+        .collect();
+
+    // Insertion order, which the replay relies on among equal sort keys.
+    let mut movements = list_runner_movements(conn, game_pk)?;
+    movements.sort_by_key(|m| m.id);
+    let runner_movements: Vec<ArchiveRunnerMovement> = movements
+        .into_iter()
+        .map(|m| ArchiveRunnerMovement {
+            pa_seq: m.pa_seq,
+            game_event_id: m.game_event_id,
+            inning: m.inning,
+            half_inning: m.half_inning,
+            runner_id: m.runner_id.filter(|id| *id > 0),
+            batter_order: m.batter_order,
+            start_base: m.start_base,
+            end_base: m.end_base,
+            advancement_type: m.advancement_type,
+            is_out: m.is_out,
+            scored: m.scored,
+            is_earned: m.is_earned,
+            fielders: m.fielders,
+        })
+        .collect();
+
+    let game_events: Vec<ArchiveGameEvent> = list_game_events(conn, game_pk)?
+        .into_iter()
+        .map(|e| ArchiveGameEvent {
+            id: e.id,
+            inning: e.inning,
+            half_inning: e.half_inning,
+            event_type: e.event_type,
+            event_data: e.event_data,
+            description: e.description,
+            created_at: e.created_at,
+            pa_seq: e.pa_seq,
+        })
+        .collect();
+
+    let assignments = list_game_umpires(conn, game_pk)?;
+    let evaluations = UmpireEvaluation::list_by_game(conn, game_pk)?;
+    let umpire_ids: BTreeSet<i64> = assignments
+        .iter()
+        .map(|a| a.umpire_id)
+        .chain(evaluations.iter().map(|e| e.umpire_id))
+        .collect();
+    let umpires = umpire_ids
+        .into_iter()
+        .map(|id| {
+            let u = Umpire::get_by_id(conn, id)?;
+            Ok(ArchiveUmpire {
+                id,
+                first_name: u.first_name,
+                last_name: u.last_name,
+                license_number: u.license_number,
+                level: u.level,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let at_bat_draft = load_at_bat_draft(conn, game_pk)?.map(|d| ArchiveAtBatDraft {
+        inning: d.inning,
+        half_inning: d.half_inning,
+        batter_id: d.batter_id,
+        pitcher_id: d.pitcher_id,
+        pitch_count_json: d.pitch_count_json,
+    });
+
+    // Every player referred to anywhere, event payloads included.
+    let mut player_ids = BTreeSet::new();
+    player_ids.extend(lineups.iter().map(|l| l.player_id));
+    for pa in &plate_appearances {
+        player_ids.extend([pa.batter_id, pa.pitcher_id]);
+    }
+    player_ids.extend(runner_movements.iter().filter_map(|m| m.runner_id));
+    for e in &game_events {
+        if let Some(mut ev) = parse_event(e.event_data.as_deref()) {
+            visit_event_ids(
+                &mut ev,
+                &mut |id| {
+                    player_ids.insert(*id);
+                },
+                &mut |_| {},
+            );
+        }
+    }
+    if let Some(d) = &at_bat_draft {
+        player_ids.extend(d.batter_id.into_iter().chain(d.pitcher_id));
+    }
+    let players = player_ids
+        .into_iter()
+        .map(|id| {
+            conn.query_row(
+                "SELECT team_id, number, away_number, first_name, last_name
+                 FROM players WHERE id = ?1",
+                params![id],
+                |r| {
+                    Ok(ArchivePlayer {
+                        id,
+                        team: side(r.get(0)?),
+                        number: r.get(1)?,
+                        away_number: r.get(2)?,
+                        first_name: r.get(3)?,
+                        last_name: r.get(4)?,
+                    })
+                },
+            )
+            .with_context(|| format!("Player {id} of the game not found"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(GameArchive {
+        format: GAME_ARCHIVE_FORMAT.to_string(),
+        version: GAME_ARCHIVE_VERSION,
+        exported_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        game,
+        league,
+        away_team: team(away_team_id)?,
+        home_team: team(home_team_id)?,
+        players,
+        lineups,
+        plate_appearances,
+        runner_movements,
+        game_events,
+        umpires,
+        umpire_assignments: assignments
+            .into_iter()
+            .map(|a| ArchiveUmpireAssignment {
+                umpire_id: a.umpire_id,
+                position: a.position,
+            })
+            .collect(),
+        umpire_evaluations: evaluations
+            .into_iter()
+            .map(|e| ArchiveUmpireEvaluation {
+                umpire_id: e.umpire_id,
+                evaluator_name: e.evaluator_name,
+                position_evaluated: e.position_evaluated,
+                strike_zone_accuracy: e.strike_zone_accuracy,
+                safe_out_accuracy: e.safe_out_accuracy,
+                positioning: e.positioning,
+                timing: e.timing,
+                game_management: e.game_management,
+                professionalism: e.professionalism,
+                communication: e.communication,
+                hustle: e.hustle,
+                overall_score: e.overall_score,
+                strengths: e.strengths,
+                areas_to_improve: e.areas_to_improve,
+                notes: e.notes,
+            })
+            .collect(),
+        at_bat_draft,
+    })
+}
+
+/// The team of this database matching `team`: by abbreviation, or by
+/// name when the archive has none.
+fn find_team(conn: &Connection, team: &ArchiveTeam) -> Result<i64> {
+    let found = match team.abbreviation.as_deref().map(str::trim) {
+        Some(abbreviation) if !abbreviation.is_empty() => conn
+            .query_row(
+                "SELECT id FROM teams WHERE UPPER(TRIM(abbreviation)) = UPPER(?1) ORDER BY id",
+                params![abbreviation],
+                |r| r.get(0),
+            )
+            .optional()?,
+        _ => conn
+            .query_row(
+                "SELECT id FROM teams WHERE name = ?1 ORDER BY id",
+                params![team.name],
+                |r| r.get(0),
+            )
+            .optional()?,
+    };
+    found.ok_or_else(|| {
+        anyhow!(
+            "Team {} ({}) not found",
+            team.name,
+            team.abbreviation.as_deref().unwrap_or("-")
+        )
+    })
+}
+
+/// The umpire of this database with `umpire`'s name, created when
+/// missing.
+fn find_or_create_umpire(conn: &Connection, umpire: &ArchiveUmpire) -> Result<i64> {
+    let found = conn
+        .query_row(
+            "SELECT id FROM umpires WHERE first_name = ?1 AND last_name = ?2 ORDER BY id",
+            params![umpire.first_name, umpire.last_name],
+            |r| r.get(0),
+        )
+        .optional()?;
+    if let Some(id) = found {
+        return Ok(id);
+    }
+    let mut new = Umpire::new(umpire.first_name.clone(), umpire.last_name.clone());
+    new.license_number = umpire.license_number.clone();
+    new.level = umpire.level.clone();
+    Ok(new.create(conn)?)
+}
+
+/// Recreate the game of `archive` in this database. Returns the new
+/// `games` row id. Nothing is written when a team, a player or the league
+/// cannot be found, or when a game with the same `game_id` already exists.
+pub fn import_game_archive(conn: &mut Connection, archive: &GameArchive) -> Result<i64> {
+    if archive.format != GAME_ARCHIVE_FORMAT {
+        bail!("Not a game archive (format {:?})", archive.format);
+    }
+    if archive.version > GAME_ARCHIVE_VERSION {
+        bail!(
+            "Game archive version {} is newer than this version of the program ({})",
+            archive.version,
+            GAME_ARCHIVE_VERSION
+        );
+    }
+
+    let tx = conn.transaction()?;
+
+    let exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM games WHERE game_id = ?1)",
+        params![archive.game.game_id],
+        |r| r.get(0),
+    )?;
+    if exists {
+        bail!("Game {} already exists", archive.game.game_id);
+    }
+
+    let away_team_id = find_team(&tx, &archive.away_team)?;
+    let home_team_id = find_team(&tx, &archive.home_team)?;
+    let team_of = |side: ArchiveSide| match side {
+        ArchiveSide::Away => away_team_id,
+        ArchiveSide::Home => home_team_id,
+    };
+    let teams = HashMap::from([
+        (archive.away_team.id, away_team_id),
+        (archive.home_team.id, home_team_id),
+    ]);
+
+    let mut players = HashMap::new();
+    let mut missing = Vec::new();
+    for p in &archive.players {
+        let jersey = p.jersey();
+        let found: Option<i64> = match p.team {
+            ArchiveSide::Away => tx
+                .query_row(
+                    "SELECT id FROM players
+                     WHERE team_id = ?1 AND COALESCE(away_number, number) = ?2",
+                    params![away_team_id, jersey],
+                    |r| r.get(0),
+                )
+                .optional()?,
+            ArchiveSide::Home => tx
+                .query_row(
+                    "SELECT id FROM players WHERE team_id = ?1 AND number = ?2",
+                    params![home_team_id, jersey],
+                    |r| r.get(0),
+                )
+                .optional()?,
+        };
+        match found {
+            Some(id) => {
+                players.insert(p.id, id);
+            }
+            None => {
+                let team = match p.team {
+                    ArchiveSide::Away => &archive.away_team,
+                    ArchiveSide::Home => &archive.home_team,
+                };
+                missing.push(format!(
+                    "{} #{} {} {}",
+                    team.abbreviation.as_deref().unwrap_or(&team.name),
+                    jersey,
+                    p.first_name,
+                    p.last_name.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+    }
+    if !missing.is_empty() {
+        bail!("Players not found: {}", missing.join(", "));
+    }
+    let player = |id: i64| -> Result<i64> {
+        players
+            .get(&id)
+            .copied()
+            .ok_or_else(|| anyhow!("Player {id} is not listed in the archive"))
+    };
+
+    let league_id: Option<i64> = match &archive.league {
+        Some(name) => Some(
+            tx.query_row(
+                "SELECT id FROM leagues WHERE name = ?1",
+                params![name],
+                |r| r.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("League {name} not found"))?,
+        ),
+        None => None,
+    };
+
+    let g = &archive.game;
+    tx.execute(
+        r#"
+        INSERT INTO games (
+            game_id, home_team_id, away_team_id, venue, game_date, game_time,
+            at_uses_dh, ht_uses_dh, home_score, away_score, status,
+            scheduled_innings, league_id, game_kind, dh_allowed, mercy_rules,
+            max_runs_per_half, tie_break_inning, end_reason
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)
+        "#,
+        params![
+            g.game_id,
+            home_team_id,
+            away_team_id,
+            g.venue,
+            g.game_date,
+            g.game_time,
+            g.at_uses_dh,
+            g.ht_uses_dh,
+            g.home_score,
+            g.away_score,
+            g.status,
+            g.scheduled_innings,
+            league_id,
+            g.game_kind,
+            g.dh_allowed,
+            g.mercy_rules,
+            g.max_runs_per_half,
+            g.tie_break_inning,
+            g.end_reason,
+        ],
+    )?;
+    let game_pk = tx.last_insert_rowid();
+
+    for l in &archive.lineups {
+        tx.execute(
+            r#"
+            INSERT INTO game_lineups (
+                game_id, team_id, player_id, batting_order, defensive_position,
+                is_starting, substituted_at_inning, substituted_at_half
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+            params![
+                g.game_id,
+                team_of(l.team),
+                player(l.player_id)?,
+                l.batting_order,
+                l.defensive_position,
+                l.is_starting,
+                l.substituted_at_inning,
+                l.substituted_at_half,
+            ],
+        )?;
+    }
+
+    for pa in &archive.plate_appearances {
+        tx.execute(
+            r#"
+            INSERT INTO plate_appearances (
+                game_id, seq, inning, half_inning,
+                batter_id, batter_order,
+                pitcher_id, pitches, pitches_sequence,
+                outcome_type, outcome_data,
+                outs, runner_overrides_json, double_play, rbi
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            "#,
+            params![
+                game_pk,
+                pa.seq,
+                pa.inning,
+                pa.half_inning,
+                player(pa.batter_id)?,
+                pa.batter_order,
+                player(pa.pitcher_id)?,
+                pa.pitches,
+                pa.pitches_sequence,
+                pa.outcome_type,
+                pa.outcome_data,
+                pa.outs,
+                pa.runner_overrides_json,
+                pa.double_play_json,
+                pa.rbi,
+            ],
+        )?;
+    }
+
+    let mut events = HashMap::new();
+    for e in &archive.game_events {
+        let data = match parse_event(e.event_data.as_deref()) {
+            Some(mut ev) => {
+                let mut unknown = None;
+                visit_event_ids(
+                    &mut ev,
+                    &mut |id| match players.get(id) {
+                        Some(new) => *id = *new,
+                        None => unknown = Some(*id),
+                    },
+                    &mut |id| {
+                        if let Some(new) = teams.get(id) {
+                            *id = *new;
+                        }
+                    },
+                );
+                if let Some(id) = unknown {
+                    bail!("Player {id} is not listed in the archive");
+                }
+                Some(serde_json::to_string(&ev)?)
+            }
+            // Not a domain event: kept as written.
+            None => e.event_data.clone(),
+        };
+        tx.execute(
+            r#"
+            INSERT INTO game_events (
+                game_id, at_bat_id, inning, half_inning, event_type, event_data,
+                description, created_at, pa_seq
+            ) VALUES (?1, NULL, ?2, ?3, ?4, ?5, ?6, COALESCE(?7, CURRENT_TIMESTAMP), ?8)
+            "#,
+            params![
+                game_pk,
+                e.inning,
+                e.half_inning,
+                e.event_type,
+                data,
+                e.description,
+                e.created_at,
+                e.pa_seq,
+            ],
+        )?;
+        events.insert(e.id, tx.last_insert_rowid());
+    }
+
+    for m in &archive.runner_movements {
+        tx.execute(
+            r#"
+            INSERT INTO runner_movements (
+                game_id, pa_seq, game_event_id,
+                inning, half_inning,
+                runner_id, batter_order,
+                start_base, end_base, advancement_type,
+                is_out, scored, is_earned, fielders
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            "#,
+            params![
+                game_pk,
+                m.pa_seq,
+                m.game_event_id.and_then(|id| events.get(&id)),
+                m.inning,
+                m.half_inning,
+                m.runner_id.map(player).transpose()?,
+                m.batter_order,
+                m.start_base,
+                m.end_base,
+                m.advancement_type,
+                m.is_out,
+                m.scored,
+                m.is_earned,
+                m.fielders,
+            ],
+        )?;
+    }
+
+    let mut umpires = HashMap::new();
+    for u in &archive.umpires {
+        umpires.insert(u.id, find_or_create_umpire(&tx, u)?);
+    }
+    let umpire = |id: i64| -> Result<i64> {
+        umpires
+            .get(&id)
+            .copied()
+            .ok_or_else(|| anyhow!("Umpire {id} is not listed in the archive"))
+    };
+    for a in &archive.umpire_assignments {
+        tx.execute(
+            "INSERT INTO game_umpires (game_id, umpire_id, position) VALUES (?1, ?2, ?3)",
+            params![game_pk, umpire(a.umpire_id)?, a.position],
+        )?;
+    }
+    for e in &archive.umpire_evaluations {
+        let mut evaluation = UmpireEvaluation {
+            id: None,
+            game_id: game_pk,
+            umpire_id: umpire(e.umpire_id)?,
+            evaluator_name: e.evaluator_name.clone(),
+            position_evaluated: e.position_evaluated.clone(),
+            strike_zone_accuracy: e.strike_zone_accuracy,
+            safe_out_accuracy: e.safe_out_accuracy,
+            positioning: e.positioning,
+            timing: e.timing,
+            game_management: e.game_management,
+            professionalism: e.professionalism,
+            communication: e.communication,
+            hustle: e.hustle,
+            overall_score: e.overall_score,
+            strengths: e.strengths.clone(),
+            areas_to_improve: e.areas_to_improve.clone(),
+            notes: e.notes.clone(),
+        };
+        evaluation.save(&tx)?;
+    }
+
+    if let Some(d) = &archive.at_bat_draft {
+        tx.execute(
+            r#"
+            INSERT INTO at_bat_draft (game_id, inning, half_inning, batter_id, pitcher_id, pitch_count_json)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                game_pk,
+                d.inning,
+                d.half_inning,
+                d.batter_id.map(player).transpose()?,
+                d.pitcher_id.map(player).transpose()?,
+                d.pitch_count_json,
+            ],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(game_pk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::at_bat_draft::upsert_at_bat_draft;
+    use crate::db::database::Database;
+    use crate::db::game_events::append_game_event;
+    use crate::db::lineups::substitute_player;
+    use crate::db::plate_appearances::append_plate_appearance;
+    use crate::db::runner_movements::{
+        RunnerMovementInsert, append_runner_movement, resolve_runner_ids,
+    };
+    use crate::db::umpire::{UmpirePosition, assign_umpire};
+    use crate::engine::play_ball::replayed_state;
+    use crate::models::events::{StrikeoutKind, SubstitutionData, SubstitutionKind};
+    use crate::models::plate_appearance::PlateAppearanceOutcome;
+    use crate::models::plate_appearance::tests::pa;
+    use crate::models::types::{HalfInning, Pitch, PitchCount, Position};
+
+    const GAME_ID: &str = "G-ARCHIVE";
+
+    /// Teams AWY (jerseys 1-9) and HOM (jerseys 11-20, 20 a reliever).
+    /// With `shifted` another team and its players are created first, so
+    /// no id matches the ones of the first database.
+    fn seed(conn: &Connection, shifted: bool) -> (i64, i64) {
+        if shifted {
+            conn.execute(
+                "INSERT INTO teams (name, abbreviation) VALUES ('Other', 'OTH')",
+                [],
+            )
+            .unwrap();
+            let other = conn.last_insert_rowid();
+            for number in 1..=30 {
+                conn.execute(
+                    "INSERT INTO players (team_id, number, first_name, last_name, position)
+                     VALUES (?1, ?2, 'Oth', 'Er', 'CF')",
+                    params![other, number],
+                )
+                .unwrap();
+            }
+        }
+        let mut team_ids = Vec::new();
+        for (name, abbreviation, numbers) in [("Away", "AWY", 1..=9), ("Home", "HOM", 11..=20)] {
+            conn.execute(
+                "INSERT INTO teams (name, abbreviation) VALUES (?1, ?2)",
+                params![name, abbreviation],
+            )
+            .unwrap();
+            let team_id = conn.last_insert_rowid();
+            for number in numbers {
+                conn.execute(
+                    "INSERT INTO players (team_id, number, first_name, last_name, position)
+                     VALUES (?1, ?2, 'P', ?3, 'CF')",
+                    params![team_id, number, format!("N{number}")],
+                )
+                .unwrap();
+            }
+            team_ids.push(team_id);
+        }
+        (team_ids[0], team_ids[1])
+    }
+
+    fn player(conn: &Connection, team_id: i64, number: i32) -> i64 {
+        conn.query_row(
+            "SELECT id FROM players WHERE team_id = ?1 AND number = ?2",
+            params![team_id, number],
+            |r| r.get(0),
+        )
+        .unwrap()
+    }
+
+    fn movement(
+        game_pk: i64,
+        pa_seq: i64,
+        order: u8,
+        (start_base, end_base): (&'static str, &'static str),
+        kind: &'static str,
+    ) -> RunnerMovementInsert {
+        RunnerMovementInsert {
+            game_id: game_pk,
+            pa_seq: Some(pa_seq),
+            game_event_id: None,
+            inning: 1,
+            half_inning: "Top".to_string(),
+            runner_id: None,
+            batter_order: order,
+            start_base,
+            end_base,
+            advancement_type: kind,
+            is_out: false,
+            scored: end_base == "HOME",
+            is_earned: true,
+            fielders: None,
+        }
+    }
+
+    /// Top of the first: single, steal, pitching change, double (one run),
+    /// strikeout; then a 1-0 count on the next batter.
+    fn record_game(conn: &Connection) -> i64 {
+        let (away, home) = seed(conn, false);
+        conn.execute(
+            "INSERT INTO games (game_id, home_team_id, away_team_id, venue, game_date)
+             VALUES (?1, ?2, ?3, 'Park', '2026-05-01')",
+            params![GAME_ID, home, away],
+        )
+        .unwrap();
+        let game_pk = conn.last_insert_rowid();
+        for (team_id, first) in [(away, 1), (home, 11)] {
+            for order in 1..=9u8 {
+                conn.execute(
+                    "INSERT INTO game_lineups (game_id, team_id, player_id, batting_order, defensive_position)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        GAME_ID,
+                        team_id,
+                        player(conn, team_id, first + i32::from(order) - 1),
+                        order,
+                        Position::from_number(order).unwrap().to_db_value()
+                    ],
+                )
+                .unwrap();
+            }
+        }
+        let starter = player(conn, home, 11);
+        let reliever = player(conn, home, 20);
+        let batter = |n| player(conn, away, n);
+
+        append_plate_appearance(
+            conn,
+            game_pk,
+            &pa(
+                batter(1),
+                1,
+                starter,
+                PlateAppearanceOutcome::Single {
+                    zone: None,
+                    modifier: None,
+                },
+                &[Pitch::Ball, Pitch::Foul],
+                0,
+            ),
+        )
+        .unwrap();
+        let mut rows = vec![
+            movement(game_pk, 1, 1, ("BAT", "1B"), "hit_auto"),
+            movement(game_pk, 1, 1, ("1B", "2B"), "steal"),
+        ];
+        resolve_runner_ids(conn, game_pk, &mut rows).unwrap();
+        for m in &rows {
+            append_runner_movement(conn, m).unwrap();
+        }
+
+        substitute_player(
+            conn,
+            GAME_ID,
+            home,
+            1,
+            reliever,
+            Position::Pitcher,
+            1,
+            HalfInning::Top,
+        )
+        .unwrap();
+        let change = DomainEvent::PlayerSubstituted(SubstitutionData {
+            team_id: home,
+            kind: SubstitutionKind::PitchingChange,
+            batting_order: 1,
+            player_out_id: Some(starter),
+            player_in_id: reliever,
+            player_in_jersey_no: 20,
+            player_in_first_name: "P".to_string(),
+            player_in_last_name: "N20".to_string(),
+            position: Position::Pitcher,
+            previous_position: Some(Position::Pitcher),
+        });
+        append_game_event(
+            conn,
+            game_pk,
+            1,
+            HalfInning::Top,
+            &change,
+            "pitching change",
+        )
+        .unwrap();
+
+        append_plate_appearance(
+            conn,
+            game_pk,
+            &pa(
+                batter(2),
+                2,
+                reliever,
+                PlateAppearanceOutcome::Double {
+                    zone: None,
+                    modifier: None,
+                },
+                &[Pitch::Ball, Pitch::Foul],
+                0,
+            ),
+        )
+        .unwrap();
+        let mut rows = vec![
+            movement(game_pk, 2, 1, ("2B", "HOME"), "hit_auto"),
+            movement(game_pk, 2, 2, ("BAT", "2B"), "hit_auto"),
+        ];
+        resolve_runner_ids(conn, game_pk, &mut rows).unwrap();
+        for m in &rows {
+            append_runner_movement(conn, m).unwrap();
+        }
+        conn.execute(
+            "UPDATE plate_appearances SET rbi = 1 WHERE game_id = ?1 AND seq = 2",
+            params![game_pk],
+        )
+        .unwrap();
+        append_plate_appearance(
+            conn,
+            game_pk,
+            &pa(
+                batter(3),
+                3,
+                reliever,
+                PlateAppearanceOutcome::Strikeout(StrikeoutKind::Swinging),
+                &[Pitch::Ball, Pitch::Foul],
+                1,
+            ),
+        )
+        .unwrap();
+
+        let count = PitchCount {
+            balls: 1,
+            strikes: 0,
+            sequence: vec![Pitch::Ball],
+        };
+        upsert_at_bat_draft(
+            conn,
+            game_pk,
+            1,
+            HalfInning::Top,
+            Some(batter(4)),
+            Some(reliever),
+            &count,
+        )
+        .unwrap();
+
+        let mut umpire = Umpire::new("Ada".to_string(), "Plate".to_string());
+        let umpire_id = umpire.create(conn).unwrap();
+        assign_umpire(conn, game_pk, umpire_id, UmpirePosition::HomePlate).unwrap();
+        let mut evaluation = UmpireEvaluation::new(game_pk, umpire_id, UmpirePosition::HomePlate);
+        evaluation.overall_score = Some(8);
+        evaluation.save(conn).unwrap();
+
+        game_pk
+    }
+
+    #[test]
+    fn imported_game_replays_to_the_same_state() {
+        let mut source = Database::new(":memory:").unwrap();
+        source.init_schema().unwrap();
+        let game_pk = record_game(source.get_connection());
+        let archive = export_game_archive(source.get_connection(), game_pk).unwrap();
+
+        // Through the file format.
+        let json = serde_json::to_string_pretty(&archive).unwrap();
+        let archive: GameArchive = serde_json::from_str(&json).unwrap();
+        assert_eq!(archive.version, GAME_ARCHIVE_VERSION);
+        assert_eq!(archive.lineups.len(), 19);
+        assert_eq!(archive.plate_appearances.len(), 3);
+        assert_eq!(archive.runner_movements.len(), 4);
+        assert_eq!(archive.game_events.len(), 1);
+        assert_eq!(archive.umpire_evaluations.len(), 1);
+
+        let mut target = Database::new(":memory:").unwrap();
+        target.init_schema().unwrap();
+        let (_, home) = seed(target.get_connection(), true);
+        let imported = import_game_archive(target.get_connection_mut(), &archive).unwrap();
+
+        let before = replayed_state(source.get_connection_mut(), game_pk).unwrap();
+        let after = replayed_state(target.get_connection_mut(), imported).unwrap();
+        let summary = |s: &crate::models::game_state::GameState| {
+            (
+                s.inning,
+                s.half,
+                s.outs,
+                (s.score.away, s.score.home),
+                (s.on_1b, s.on_2b, s.on_3b),
+                (s.away_next_batting_order, s.home_next_batting_order),
+                s.current_batter_jersey_no,
+                s.current_pitcher_jersey_no,
+                (s.pitch_count.balls, s.pitch_count.strikes),
+            )
+        };
+        assert_eq!(summary(&after), summary(&before));
+        assert_eq!(after.current_pitcher_jersey_no, Some(20));
+        assert_eq!(after.score.away, 1);
+
+        // Ids were rewritten for the target database, payloads included.
+        let conn = target.get_connection();
+        let reliever = player(conn, home, 20);
+        assert_eq!(after.current_pitcher_id, Some(reliever));
+        let events = list_game_events(conn, imported).unwrap();
+        match parse_event(events[0].event_data.as_deref()) {
+            Some(DomainEvent::PlayerSubstituted(d)) => {
+                assert_eq!((d.team_id, d.player_in_id), (home, reliever));
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+        let umpires = list_game_umpires(conn, imported).unwrap();
+        assert_eq!(umpires[0].umpire_name.as_deref(), Some("Ada Plate"));
+        let pas = list_plate_appearances(conn, imported).unwrap();
+        assert_eq!(pas[1].rbi, Some(1));
+    }
+
+    #[test]
+    fn missing_players_or_existing_game_write_nothing() {
+        let mut source = Database::new(":memory:").unwrap();
+        source.init_schema().unwrap();
+        let game_pk = record_game(source.get_connection());
+        let archive = export_game_archive(source.get_connection(), game_pk).unwrap();
+
+        // The game is already there.
+        let err = import_game_archive(source.get_connection_mut(), &archive).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let mut target = Database::new(":memory:").unwrap();
+        target.init_schema().unwrap();
+        let (_, home) = seed(target.get_connection(), false);
+        target
+            .get_connection()
+            .execute(
+                "DELETE FROM players WHERE team_id = ?1 AND number = 20",
+                params![home],
+            )
+            .unwrap();
+        let err = import_game_archive(target.get_connection_mut(), &archive).unwrap_err();
+        assert!(err.to_string().contains("HOM #20"));
+        let games: i64 = target
+            .get_connection()
+            .query_row("SELECT COUNT(*) FROM games", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(games, 0);
+    }
+
+    #[test]
+    fn away_players_are_found_by_the_jersey_they_wore() {
+        let source = Database::new(":memory:").unwrap();
+        source.init_schema().unwrap();
+        let game_pk = record_game(source.get_connection());
+        source
+            .get_connection()
+            .execute(
+                "UPDATE players SET away_number = 41
+                 WHERE number = 1 AND team_id = (SELECT id FROM teams WHERE abbreviation = 'AWY')",
+                [],
+            )
+            .unwrap();
+        let archive = export_game_archive(source.get_connection(), game_pk).unwrap();
+
+        // The leadoff batter wore 41 on the road; here their home jersey
+        // changed and 1 went to somebody else.
+        let mut target = Database::new(":memory:").unwrap();
+        target.init_schema().unwrap();
+        let conn = target.get_connection();
+        let (away, _) = seed(conn, false);
+        conn.execute(
+            "UPDATE players SET number = 51, away_number = 41 WHERE team_id = ?1 AND number = 1",
+            params![away],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO players (team_id, number, first_name, last_name, position)
+             VALUES (?1, 1, 'New', 'Comer', 'CF')",
+            params![away],
+        )
+        .unwrap();
+        let leadoff = player(conn, away, 51);
+
+        let imported = import_game_archive(target.get_connection_mut(), &archive).unwrap();
+        let conn = target.get_connection();
+        let batter: i64 = conn
+            .query_row(
+                "SELECT player_id FROM game_lineups
+                 WHERE game_id = ?1 AND team_id = ?2 AND batting_order = 1",
+                params![GAME_ID, away],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(batter, leadoff);
+        let pas = list_plate_appearances(conn, imported).unwrap();
+        assert_eq!(pas[0].batter_id, leadoff);
+    }
+
+    #[test]
+    fn unknown_league_writes_nothing() {
+        let source = Database::new(":memory:").unwrap();
+        source.init_schema().unwrap();
+        let game_pk = record_game(source.get_connection());
+        let mut archive = export_game_archive(source.get_connection(), game_pk).unwrap();
+        archive.league = Some("Winter".to_string());
+
+        let mut target = Database::new(":memory:").unwrap();
+        target.init_schema().unwrap();
+        seed(target.get_connection(), false);
+        let err = import_game_archive(target.get_connection_mut(), &archive).unwrap_err();
+        assert_eq!(err.to_string(), "League Winter not found");
+        let games: i64 = target
+            .get_connection()
+            .query_row("SELECT COUNT(*) FROM games", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(games, 0);
+    }
+}
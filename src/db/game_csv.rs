@@ -0,0 +1,496 @@
+//! CSV export of the plays of one game or of a league season.
+//!
+//! Two files: one row per plate appearance, and one row per runner
+//! movement. Both start with the `game_id`, so the rows of many games can
+//! share a file.
+
+use crate::db::plate_appearances::{PlateAppearanceRow, list_plate_appearances};
+use crate::db::runner_movements::{RunnerMovementRow, list_runner_movements};
+use crate::engine::earned_runs::{Play, half_innings};
+use crate::engine::rbi::row_rbi;
+use crate::engine::reducer::{STANDALONE_TYPES, plate_appearance_from_row};
+use crate::models::plate_appearance::{
+    DroppedThirdStrike, PlateAppearanceOutcome, PlateAppearanceStep,
+};
+use crate::models::types::Pitch;
+use crate::stats::{StatsFilter, filtered_games};
+use anyhow::Result;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A row of the plate-appearance file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlateAppearanceCsv {
+    pub game_id: String,
+    pub date: String,
+    pub seq: i64,
+    pub inning: i64,
+    pub half: String,
+    pub batting_order: u8,
+    pub batter: String,
+    pub pitcher: String,
+    /// Balls and strikes before the last pitch: `"3-2"`.
+    pub count: String,
+    pub pitches: i64,
+    /// Pitch symbols in order: `"B K F X"`.
+    pub pitch_sequence: String,
+    /// `plate_appearances.outcome_type`: `"ground_out"`, `"double"`.
+    pub outcome: String,
+    /// Scorebook label: `"GO"`, `"2H"`, `"SF"`.
+    pub play: String,
+    pub zone: String,
+    /// Fielders who made the play: `"6-4-3"`, `"8"`.
+    pub fielders: String,
+    /// `"GDP"`, `"LTP"`, ... when a double or triple play was turned.
+    pub double_play: String,
+    pub outs_before: i64,
+    pub outs_after: i64,
+    pub runs: u32,
+    pub rbi: u8,
+}
+
+/// A row of the runner-movement file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunnerMovementCsv {
+    pub game_id: String,
+    /// Plate appearance the movement belongs to or happened during.
+    pub seq: Option<i64>,
+    pub inning: i64,
+    pub half: String,
+    pub batting_order: u8,
+    pub runner: String,
+    pub start_base: String,
+    pub end_base: String,
+    pub advancement: String,
+    pub out: bool,
+    pub scored: bool,
+    pub earned: bool,
+    pub fielders: String,
+}
+
+/// The rows of both files.
+#[derive(Debug, Clone, Default)]
+pub struct GameCsv {
+    pub plate_appearances: Vec<PlateAppearanceCsv>,
+    pub runner_movements: Vec<RunnerMovementCsv>,
+}
+
+impl GameCsv {
+    /// Rows of the games `game_pks` (`games` row ids), in that order.
+    pub fn load(conn: &Connection, game_pks: &[i64]) -> Result<Self> {
+        let names = full_names(conn)?;
+        let mut csv = Self::default();
+        for &game_pk in game_pks {
+            let (game_id, date): (String, String) = conn.query_row(
+                "SELECT game_id, game_date FROM games WHERE id = ?1",
+                params![game_pk],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )?;
+            csv.add_game(
+                &game_id,
+                &date,
+                &list_plate_appearances(conn, game_pk)?,
+                &list_runner_movements(conn, game_pk)?,
+                &names,
+            );
+        }
+        Ok(csv)
+    }
+
+    /// Rows of every game of league `league_id`, oldest first; `season`
+    /// keeps the games of one season only.
+    pub fn load_season(conn: &Connection, league_id: i64, season: Option<&str>) -> Result<Self> {
+        let filter = StatsFilter {
+            league_id: Some(league_id),
+            season: season.map(str::to_string),
+            ..Default::default()
+        };
+        Self::load(conn, &filtered_games(conn, &filter)?)
+    }
+
+    /// Append the rows of one game; `names` maps player ids to names.
+    pub fn add_game(
+        &mut self,
+        game_id: &str,
+        date: &str,
+        plate_appearances: &[PlateAppearanceRow],
+        movements: &[RunnerMovementRow],
+        names: &HashMap<i64, String>,
+    ) {
+        let name = |id: Option<i64>| {
+            id.and_then(|id| names.get(&id))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let mut outs_before: HashMap<i64, i64> = HashMap::new();
+        for plays in half_innings(plate_appearances, movements) {
+            let mut outs = 0;
+            for play in plays {
+                match play {
+                    Play::PlateAppearance(row, _) => {
+                        outs_before.insert(row.seq, outs);
+                        outs = row.outs;
+                    }
+                    Play::Between(rm) if rm.is_out => outs += 1,
+                    Play::Between(_) => {}
+                }
+            }
+        }
+
+        for row in plate_appearances {
+            let linked: Vec<&RunnerMovementRow> = movements
+                .iter()
+                .filter(|m| {
+                    m.pa_seq == Some(row.seq)
+                        && !STANDALONE_TYPES.contains(&m.advancement_type.as_str())
+                })
+                .collect();
+            let pa = plate_appearance_from_row(row);
+            let pitches: Vec<&Pitch> = pa
+                .pitches_sequence
+                .iter()
+                .filter_map(|step| match step {
+                    PlateAppearanceStep::Pitch(p) => Some(p),
+                    _ => None,
+                })
+                .collect();
+            self.plate_appearances.push(PlateAppearanceCsv {
+                game_id: game_id.to_string(),
+                date: date.to_string(),
+                seq: row.seq,
+                inning: row.inning,
+                half: row.half_inning.clone(),
+                batting_order: row.batter_order,
+                batter: name(Some(row.batter_id)),
+                pitcher: name(Some(row.pitcher_id)),
+                count: count(&pitches),
+                pitches: row.pitches,
+                pitch_sequence: pitches
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                outcome: row.outcome_type.clone(),
+                play: pa.outcome.label().to_string(),
+                zone: pa
+                    .outcome
+                    .zone()
+                    .map(|z| z.as_str().to_string())
+                    .unwrap_or_default(),
+                fielders: fielders(&pa.outcome),
+                double_play: pa
+                    .double_play
+                    .as_ref()
+                    .map(|dp| dp.label().to_string())
+                    .unwrap_or_default(),
+                outs_before: outs_before.get(&row.seq).copied().unwrap_or(0),
+                outs_after: row.outs,
+                runs: linked.iter().filter(|m| m.scored).count() as u32,
+                rbi: row_rbi(row, &linked),
+            });
+        }
+
+        self.runner_movements
+            .extend(movements.iter().map(|rm| RunnerMovementCsv {
+                game_id: game_id.to_string(),
+                seq: rm.pa_seq,
+                inning: rm.inning,
+                half: rm.half_inning.clone(),
+                batting_order: rm.batter_order,
+                runner: name(rm.runner_id),
+                start_base: rm.start_base.clone(),
+                end_base: rm.end_base.clone(),
+                advancement: rm.advancement_type.clone(),
+                out: rm.is_out,
+                scored: rm.scored,
+                earned: rm.is_earned,
+                fielders: rm.fielders.clone().unwrap_or_default(),
+            }));
+    }
+
+    /// The plate-appearance file, with its header.
+    pub fn plate_appearances_csv(&self) -> Result<String> {
+        write_csv(&self.plate_appearances)
+    }
+
+    /// The runner-movement file, with its header.
+    pub fn runner_movements_csv(&self) -> Result<String> {
+        write_csv(&self.runner_movements)
+    }
+}
+
+fn write_csv<T: Serialize>(rows: &[T]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// "First Last" of every player, by id.
+fn full_names(conn: &Connection) -> Result<HashMap<i64, String>> {
+    let mut stmt = conn.prepare("SELECT id, first_name, last_name FROM players")?;
+    let rows = stmt.query_map([], |r| {
+        let first: String = r.get(1)?;
+        let last: String = r.get(2)?;
+        Ok((r.get(0)?, format!("{first} {last}").trim().to_string()))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Balls and strikes before the last pitch: the fourth ball and the third
+/// strike are not counted, nor a foul with two strikes.
+fn count(pitches: &[&Pitch]) -> String {
+    let (mut balls, mut strikes) = (0u8, 0u8);
+    for pitch in pitches {
+        match pitch {
+            Pitch::Ball => balls = (balls + 1).min(3),
+            Pitch::Foul if strikes >= 2 => {}
+            Pitch::CalledStrike | Pitch::SwingingStrike | Pitch::Foul | Pitch::FoulBunt => {
+                strikes = (strikes + 1).min(2)
+            }
+            Pitch::InPlay | Pitch::HittedBy => {}
+        }
+    }
+    format!("{balls}-{strikes}")
+}
+
+/// Fielders credited with the batter's play, in the order they handled
+/// the ball.
+fn fielders(outcome: &PlateAppearanceOutcome) -> String {
+    match outcome {
+        PlateAppearanceOutcome::GroundOut { sequence, .. }
+        | PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { sequence }) => {
+            sequence.clone()
+        }
+        PlateAppearanceOutcome::UnassistedOut { fielder, .. }
+        | PlateAppearanceOutcome::FlyOut { fielder, .. }
+        | PlateAppearanceOutcome::LineOut { fielder }
+        | PlateAppearanceOutcome::InfieldFly { fielder }
+        | PlateAppearanceOutcome::FieldersChoice { fielder, .. }
+        | PlateAppearanceOutcome::ReachedOnError { fielder, .. } => fielder.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+    use crate::db::fixtures;
+    use crate::db::fixtures::{pa, rm};
+    use crate::db::plate_appearances::append_plate_appearance;
+    use crate::models::plate_appearance;
+
+    fn names() -> HashMap<i64, String> {
+        (11..=29).map(|id| (id, format!("Player {id}"))).collect()
+    }
+
+    #[test]
+    fn a_row_per_plate_appearance_with_runs_and_outs() {
+        let mut pas = vec![
+            pa(1, "Top", 1, 21, "double", Some(r#"{"zone":"LC"}"#), 0),
+            pa(
+                2,
+                "Top",
+                2,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                1,
+            ),
+            pa(3, "Top", 3, 21, "single", None, 2),
+        ];
+        pas[0].pitches_sequence = r#"[{"Pitch":"Ball"},{"Pitch":"CalledStrike"},{"Pitch":"Foul"},{"Pitch":"Foul"},"Double"]"#.to_string();
+        pas[2].rbi = Some(1);
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "2B", "hit_auto"),
+            rm(2, Some(2), "Top", 1, "2B", "3B", "ground_out"),
+            rm(3, Some(2), "Top", 1, "3B", "OUT", "pickoff"),
+            rm(4, Some(3), "Top", 3, "BAT", "1B", "hit_auto"),
+        ];
+        let mut csv = GameCsv::default();
+        csv.add_game("G1", "2026-04-18", &pas, &rows, &names());
+
+        let double = &csv.plate_appearances[0];
+        assert_eq!(double.batter, "Player 11");
+        assert_eq!(double.pitcher, "Player 21");
+        assert_eq!(double.count, "1-2");
+        assert_eq!(double.pitch_sequence, "B K F F");
+        assert_eq!((double.play.as_str(), double.zone.as_str()), ("2H", "LC"));
+        assert_eq!(csv.plate_appearances[1].fielders, "6-3");
+        // The pickoff after the ground out is the second out.
+        let single = &csv.plate_appearances[2];
+        assert_eq!((single.outs_before, single.outs_after), (2, 2));
+        assert_eq!(single.rbi, 1);
+
+        let text = csv.plate_appearances_csv().unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "game_id,date,seq,inning,half,batting_order,batter,pitcher,count,pitches,\
+                 pitch_sequence,outcome,play,zone,fielders,double_play,outs_before,outs_after,\
+                 runs,rbi"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "G1,2026-04-18,1,1,Top,1,Player 11,Player 21,1-2,3,B K F F,double,2H,LC,,,0,0,0,0"
+            )
+        );
+
+        let movements = csv.runner_movements_csv().unwrap();
+        assert_eq!(movements.lines().count(), 5);
+        assert!(
+            movements.contains("G1,2,1,Top,1,Player 11,3B,OUT,pickoff,true,false,true,"),
+            "{movements}"
+        );
+    }
+
+    #[test]
+    fn movements_between_plays_count_toward_the_outs_before() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "single", None, 0),
+            pa(2, "Top", 2, 21, "reached_on_error", None, 1),
+            pa(3, "Top", 3, 21, "strikeout", None, 2),
+            pa(4, "Top", 4, 21, "fly_out", None, 3),
+            pa(5, "Bottom", 1, 11, "double", None, 0),
+        ];
+        let mut scores = rm(9, Some(5), "Bottom", 9, "2B", "HOME", "hit_auto");
+        scores.is_earned = false;
+        let mut caught = rm(2, Some(1), "Top", 1, "1B", "OUT", "caught_stealing");
+        caught.fielders = Some("2-6".to_string());
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "hit_auto"),
+            caught,
+            rm(3, Some(2), "Top", 2, "BAT", "1B", "error"),
+            rm(4, Some(2), "Top", 2, "1B", "2B", "steal"),
+            // The tie-break runner is linked to the last play of the top.
+            rm(8, Some(4), "Bottom", 9, "BAT", "2B", "tie_break"),
+            scores,
+            rm(10, Some(5), "Bottom", 1, "BAT", "2B", "hit_auto"),
+        ];
+        let mut csv = GameCsv::default();
+        csv.add_game("G1", "2026-04-18", &pas, &rows, &names());
+
+        let outs: Vec<(i64, i64)> = csv
+            .plate_appearances
+            .iter()
+            .map(|r| (r.outs_before, r.outs_after))
+            .collect();
+        assert_eq!(outs, vec![(0, 0), (1, 1), (1, 2), (2, 3), (0, 0)]);
+        let double = &csv.plate_appearances[4];
+        assert_eq!((double.runs, double.rbi), (1, 1));
+
+        let text = csv.runner_movements_csv().unwrap();
+        let lines: Vec<&str> = text.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "G1,1,1,Top,1,Player 11,BAT,1B,hit_auto,false,false,true,",
+                "G1,1,1,Top,1,Player 11,1B,OUT,caught_stealing,true,false,true,2-6",
+                "G1,2,1,Top,2,Player 12,BAT,1B,error,false,false,true,",
+                "G1,2,1,Top,2,Player 12,1B,2B,steal,false,false,true,",
+                "G1,4,1,Bottom,9,Player 29,BAT,2B,tie_break,false,false,true,",
+                "G1,5,1,Bottom,9,Player 29,2B,HOME,hit_auto,false,true,false,",
+                "G1,5,1,Bottom,1,Player 21,BAT,2B,hit_auto,false,false,true,",
+            ]
+        );
+    }
+
+    #[test]
+    fn names_with_commas_are_quoted() {
+        let pas = vec![pa(1, "Top", 1, 21, "home_run", None, 0)];
+        let rows = vec![rm(1, Some(1), "Top", 1, "BAT", "HOME", "hit_auto")];
+        let mut names = names();
+        names.insert(11, "Ken Griffey, Jr.".to_string());
+        let mut csv = GameCsv::default();
+        csv.add_game("G1", "2026-04-18", &pas, &rows, &names);
+
+        let text = csv.plate_appearances_csv().unwrap();
+        assert!(
+            text.contains(",1,\"Ken Griffey, Jr.\",Player 21,"),
+            "{text}"
+        );
+        let text = csv.runner_movements_csv().unwrap();
+        assert!(
+            text.contains("G1,1,1,Top,1,\"Ken Griffey, Jr.\",BAT,HOME,"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn season_rows_follow_the_games_of_the_league_and_season() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let away = fixtures::team(conn, "Away", "AWY");
+        let home = fixtures::team(conn, "Home", "HOM");
+        let batter = fixtures::player(conn, away, 1, None, "Ada", "Lead", "CF");
+        let pitcher = fixtures::player(conn, home, 1, None, "Bo", "Arm", "P");
+        let mut leagues = Vec::new();
+        for name in ["Spring", "Fall"] {
+            conn.execute("INSERT INTO leagues (name) VALUES (?1)", params![name])
+                .unwrap();
+            leagues.push(conn.last_insert_rowid());
+        }
+        // Created out of date order; the league has no season, so the
+        // season is the year of the game.
+        for (game_id, date, league_id) in [
+            ("G-JUN", "2025-06-01", leagues[0]),
+            ("G-NEXT", "2026-04-01", leagues[0]),
+            ("G-FALL", "2025-05-01", leagues[1]),
+            ("G-APR", "2025-04-01", leagues[0]),
+        ] {
+            let game_pk = fixtures::game(conn, game_id, away, home);
+            conn.execute(
+                "UPDATE games SET game_date = ?1, league_id = ?2 WHERE id = ?3",
+                params![date, league_id, game_pk],
+            )
+            .unwrap();
+            let single = PlateAppearanceOutcome::Single {
+                zone: None,
+                modifier: None,
+            };
+            for _ in 0..2 {
+                append_plate_appearance(
+                    conn,
+                    game_pk,
+                    &plate_appearance::tests::pa(batter, 1, pitcher, single.clone(), &[], 0),
+                )
+                .unwrap();
+            }
+        }
+
+        let rows = |season: Option<&str>| -> Vec<(String, String, i64)> {
+            GameCsv::load_season(conn, leagues[0], season)
+                .unwrap()
+                .plate_appearances
+                .into_iter()
+                .map(|r| (r.game_id, r.date, r.seq))
+                .collect()
+        };
+        let row = |game_id: &str, date: &str, seq| (game_id.to_string(), date.to_string(), seq);
+        assert_eq!(
+            rows(Some("2025")),
+            vec![
+                row("G-APR", "2025-04-01", 1),
+                row("G-APR", "2025-04-01", 2),
+                row("G-JUN", "2025-06-01", 1),
+                row("G-JUN", "2025-06-01", 2),
+            ]
+        );
+        let games: Vec<String> = rows(None).into_iter().map(|r| r.0).collect();
+        assert_eq!(
+            games,
+            vec!["G-APR", "G-APR", "G-JUN", "G-JUN", "G-NEXT", "G-NEXT"]
+        );
+        let season = GameCsv::load_season(conn, leagues[0], Some("2025")).unwrap();
+        assert_eq!(season.plate_appearances[0].batter, "Ada Lead");
+        assert_eq!(season.plate_appearances[0].pitcher, "Bo Arm");
+    }
+}
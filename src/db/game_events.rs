@@ -13,6 +13,9 @@ pub struct GameEventRow {
     pub event_data: Option<String>,
     pub description: Option<String>,
     pub created_at: Option<String>,
+    /// Seq of the last plate appearance recorded before this event;
+    /// `None` before the first PA.
+    pub pa_seq: Option<i64>,
 }
 
 pub fn list_game_events(conn: &Connection, game_pk: i64) -> rusqlite::Result<Vec<GameEventRow>> {
@@ -24,7 +27,8 @@ pub fn list_game_events(conn: &Connection, game_pk: i64) -> rusqlite::Result<Vec
                    event_type,
                    event_data,
                    description,
-                   created_at
+                   created_at,
+                   pa_seq
             FROM game_events
             WHERE game_id = ?1
             ORDER BY id ASC
@@ -41,6 +45,7 @@ pub fn list_game_events(conn: &Connection, game_pk: i64) -> rusqlite::Result<Vec
                 event_data: r.get(4)?,
                 description: r.get(5)?,
                 created_at: r.get(6)?,
+                pa_seq: r.get(7)?,
             })
         })?
         .filter_map(Result::ok)
@@ -70,8 +75,9 @@ pub fn append_game_event(
     let data = serde_json::to_string(event).ok();
 
     conn.execute(
-        "INSERT INTO game_events (game_id, at_bat_id, inning, half_inning, event_type, event_data, description)\
-         VALUES (?1, NULL, ?2, ?3, ?4, ?5, ?6)",
+        "INSERT INTO game_events (game_id, at_bat_id, inning, half_inning, event_type, event_data, description, pa_seq)\
+         VALUES (?1, NULL, ?2, ?3, ?4, ?5, ?6,\
+                 (SELECT MAX(seq) FROM plate_appearances WHERE game_id = ?1))",
         params![
             game_pk,
             inning as i64,
@@ -85,6 +91,19 @@ pub fn append_game_event(
     Ok(())
 }
 
+/// Delete the events logged after plate appearance `pa_seq` was
+/// recorded (the undo of that PA). Returns the number of rows deleted.
+pub fn delete_game_events_from(
+    conn: &Connection,
+    game_pk: i64,
+    pa_seq: i64,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "DELETE FROM game_events WHERE game_id = ?1 AND pa_seq >= ?2",
+        params![game_pk, pa_seq],
+    )
+}
+
 pub fn get_lineup_batter_by_order(
     conn: &Connection,
     game_id_str: &str,
@@ -98,7 +117,7 @@ pub fn get_lineup_batter_by_order(
         JOIN players p ON gl.player_id = p.id
         WHERE gl.game_id = ?1
           AND gl.team_id = ?2
-          AND gl.is_starting = 1
+          AND gl.substituted_at_inning IS NULL
           AND gl.batting_order = ?3
         LIMIT 1
         "#,
@@ -159,7 +178,6 @@ pub fn refactor_batter_order(conn: &mut Connection) -> rusqlite::Result<()> {
                 SELECT team_id, player_id, batting_order
                 FROM game_lineups
                 WHERE game_id = ?1
-                  AND is_starting = 1
                 ORDER BY team_id, batting_order
                 "#,
             )?;
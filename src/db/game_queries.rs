@@ -1,10 +1,13 @@
 //! Game-level DB queries for the Play Ball session.
 //!
-//! Covers: listing playable games, gate-checking lineups, updating game status.
+//! Covers: listing playable games, gate-checking lineups, updating game status
+//! and final score, reading the rule profile.
 
+use crate::db::league::{League, rules_from_row};
+use crate::models::rules::RuleSet;
 use crate::models::session::{LineupSide, PlayBallGameContext, PlayBallGate};
 use crate::models::types::GameStatus;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
 /// List all games that can still be played (excludes Regulation, Cancelled, Forfeited).
 pub fn list_playable_games(conn: &Connection) -> rusqlite::Result<Vec<PlayBallGameContext>> {
@@ -113,7 +116,7 @@ pub fn gate_check_lineups(
 
 /// Update the game status in the DB. Returns true if a row was changed.
 pub fn set_game_status(
-    conn: &mut Connection,
+    conn: &Connection,
     game_id: &str,
     status: GameStatus,
 ) -> rusqlite::Result<bool> {
@@ -124,6 +127,55 @@ pub fn set_game_status(
     Ok(changed == 1)
 }
 
+/// Record the final score of the game and why it ended (`None` when the
+/// scorer ended it by hand).
+pub fn set_game_result(
+    conn: &Connection,
+    game_pk: i64,
+    away_score: u16,
+    home_score: u16,
+    end_reason: Option<&str>,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE games SET away_score = ?2, home_score = ?3, end_reason = ?4 WHERE id = ?1",
+        params![game_pk, away_score, home_score, end_reason],
+    )?;
+    Ok(())
+}
+
+/// Rules the game is played under.
+pub fn game_rules(conn: &Connection, game_pk: i64) -> rusqlite::Result<RuleSet> {
+    conn.query_row(
+        "SELECT game_kind, scheduled_innings, dh_allowed, mercy_rules,
+                max_runs_per_half, tie_break_inning
+         FROM games WHERE id = ?1",
+        params![game_pk],
+        |r| rules_from_row(r, 0),
+    )
+}
+
+/// League shared by both teams, with its rule profile. Games between teams
+/// of different leagues (or of none) have no league.
+pub fn shared_league_rules(
+    conn: &Connection,
+    away_team_id: i64,
+    home_team_id: i64,
+) -> rusqlite::Result<Option<(i64, RuleSet)>> {
+    let league_id: Option<i64> = conn
+        .query_row(
+            "SELECT a.league_id FROM teams a, teams h
+         WHERE a.id = ?1 AND h.id = ?2 AND a.league_id = h.league_id",
+            params![away_team_id, home_team_id],
+            |r| r.get(0),
+        )
+        .optional()?
+        .flatten();
+    match league_id {
+        Some(id) => Ok(Some((id, League::get_by_id(conn, id)?.rules))),
+        None => Ok(None),
+    }
+}
+
 fn starting_lineup_count(conn: &Connection, game_id: &str, team_id: i64) -> rusqlite::Result<i64> {
     conn.query_row(
         "SELECT COUNT(1)
@@ -1,11 +1,34 @@
+use crate::models::rules::{GameKind, RuleSet};
 use rusqlite::{Connection, Result, params};
 
+/// Columns of `leagues`; the rule profile starts at index 4, see
+/// [`rules_from_row`].
+const LEAGUE_COLUMNS: &str = "id, name, season, description, \
+     game_kind, innings, dh_allowed, mercy_rules, max_runs_per_half, tie_break_inning";
+
 #[derive(Debug, Clone)]
 pub struct League {
     pub id: Option<i64>,
     pub name: String,
     pub season: Option<String>,
     pub description: Option<String>,
+    /// Default rules of the league's games.
+    pub rules: RuleSet,
+}
+
+/// Read a rule profile from six columns starting at `first`: game kind,
+/// innings, DH allowed, run rules, max runs per half, tie-break inning.
+pub(crate) fn rules_from_row(row: &rusqlite::Row, first: usize) -> Result<RuleSet> {
+    let kind: String = row.get(first)?;
+    let mercy_rules: String = row.get(first + 3)?;
+    Ok(RuleSet {
+        kind: GameKind::from_str_loose(&kind),
+        innings: row.get(first + 1)?,
+        dh_allowed: row.get(first + 2)?,
+        mercy_rules: RuleSet::parse_mercy_rules_json(&mercy_rules),
+        max_runs_per_half: row.get(first + 4)?,
+        tie_break_inning: row.get(first + 5)?,
+    })
 }
 
 impl League {
@@ -15,6 +38,7 @@ impl League {
             name,
             season,
             description,
+            rules: RuleSet::default(),
         }
     }
 
@@ -25,14 +49,27 @@ impl League {
             name: row.get(1)?,
             season: row.get(2)?,
             description: row.get(3)?,
+            rules: rules_from_row(row, 4)?,
         })
     }
 
     /// Create a new league in the database
     pub fn create(&mut self, conn: &Connection) -> Result<i64> {
         conn.execute(
-            "INSERT INTO leagues (name, season, description) VALUES (?1, ?2, ?3)",
-            params![self.name, self.season, self.description],
+            "INSERT INTO leagues (name, season, description, game_kind, innings, dh_allowed,
+                                  mercy_rules, max_runs_per_half, tie_break_inning)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                self.name,
+                self.season,
+                self.description,
+                self.rules.kind.as_str(),
+                self.rules.innings,
+                self.rules.dh_allowed,
+                self.rules.mercy_rules_json(),
+                self.rules.max_runs_per_half,
+                self.rules.tie_break_inning
+            ],
         )?;
 
         let id = conn.last_insert_rowid();
@@ -43,16 +80,18 @@ impl League {
     /// Get a league by ID
     #[allow(dead_code)]
     pub fn get_by_id(conn: &Connection, id: i64) -> Result<League> {
-        let mut stmt =
-            conn.prepare("SELECT id, name, season, description FROM leagues WHERE id = ?1")?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {LEAGUE_COLUMNS} FROM leagues WHERE id = ?1"
+        ))?;
 
         stmt.query_row(params![id], Self::from_row)
     }
 
     /// Get all leagues
     pub fn get_all(conn: &Connection) -> Result<Vec<League>> {
-        let mut stmt =
-            conn.prepare("SELECT id, name, season, description FROM leagues ORDER BY name")?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {LEAGUE_COLUMNS} FROM leagues ORDER BY name"
+        ))?;
 
         let leagues = stmt.query_map([], Self::from_row)?;
 
@@ -63,8 +102,22 @@ impl League {
     pub fn update(&self, conn: &Connection) -> Result<()> {
         if let Some(id) = self.id {
             conn.execute(
-                "UPDATE leagues SET name = ?1, season = ?2, description = ?3 WHERE id = ?4",
-                params![self.name, self.season, self.description, id],
+                "UPDATE leagues SET name = ?1, season = ?2, description = ?3, game_kind = ?4,
+                        innings = ?5, dh_allowed = ?6, mercy_rules = ?7,
+                        max_runs_per_half = ?8, tie_break_inning = ?9
+                 WHERE id = ?10",
+                params![
+                    self.name,
+                    self.season,
+                    self.description,
+                    self.rules.kind.as_str(),
+                    self.rules.innings,
+                    self.rules.dh_allowed,
+                    self.rules.mercy_rules_json(),
+                    self.rules.max_runs_per_half,
+                    self.rules.tie_break_inning,
+                    id
+                ],
             )?;
         }
         Ok(())
@@ -101,10 +154,17 @@ mod tests {
         let retrieved = League::get_by_id(conn, id).unwrap();
         assert_eq!(retrieved.name, "MLB");
 
+        assert_eq!(retrieved.rules, RuleSet::default());
+
         // Update
         let mut updated = retrieved.clone();
         updated.season = Some("2027".to_string());
+        updated.rules = RuleSet::standard(GameKind::SlowPitchSoftball);
+        updated.rules.mercy_rules =
+            crate::models::rules::MercyRule::parse_list("15@5,10@7").unwrap();
+        updated.rules.max_runs_per_half = Some(5);
         updated.update(conn).unwrap();
+        assert_eq!(League::get_by_id(conn, id).unwrap().rules, updated.rules);
 
         // Delete
         League::delete(conn, id).unwrap();
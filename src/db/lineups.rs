@@ -0,0 +1,483 @@
+//! In-game lineup changes.
+//!
+//! `game_lineups` keeps every player who has held a lineup spot during
+//! the game. A player who leaves has their row stamped with
+//! `substituted_at_inning` / `substituted_at_half`; the rows where both
+//! are NULL form the active lineup, one per batting-order spot.
+
+use crate::models::types::{HalfInning, Position};
+use rusqlite::{Connection, OptionalExtension, Result, params};
+
+/// A roster player with the jersey they wear in this game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterPlayer {
+    pub id: i64,
+    pub jersey_no: i32,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+/// An active lineup spot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineupEntry {
+    pub player: RosterPlayer,
+    pub batting_order: u8,
+    pub position: Position,
+}
+
+/// Active players of `team_id`, with the jersey they wear in this game.
+/// The home team wears `number`; the away team wears `away_number` when
+/// set.
+pub fn team_roster(
+    conn: &Connection,
+    team_id: i64,
+    is_home_team: bool,
+) -> Result<Vec<RosterPlayer>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id,
+               CASE WHEN ?2 THEN number ELSE COALESCE(away_number, number) END,
+               first_name,
+               last_name
+        FROM players
+        WHERE team_id = ?1
+          AND is_active = 1
+        ORDER BY id
+        "#,
+    )?;
+    stmt.query_map(params![team_id, is_home_team], |r| {
+        Ok(RosterPlayer {
+            id: r.get(0)?,
+            jersey_no: r.get(1)?,
+            first_name: r.get(2)?,
+            last_name: r.get(3)?,
+        })
+    })?
+    .collect()
+}
+
+/// The active lineup of `team_id` for this game, in batting order (the
+/// non-batting pitcher of a DH lineup comes last, as spot 10).
+pub fn active_lineup(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+    is_home_team: bool,
+) -> Result<Vec<LineupEntry>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT p.id,
+               CASE WHEN ?3 THEN p.number ELSE COALESCE(p.away_number, p.number) END,
+               p.first_name,
+               p.last_name,
+               gl.batting_order,
+               gl.defensive_position
+        FROM game_lineups gl
+        JOIN players p ON gl.player_id = p.id
+        WHERE gl.game_id = ?1
+          AND gl.team_id = ?2
+          AND gl.substituted_at_inning IS NULL
+        ORDER BY gl.batting_order
+        "#,
+    )?;
+    stmt.query_map(params![game_id, team_id, is_home_team], |r| {
+        let position_raw: String = r.get(5)?;
+        let position = Position::from_db_value(&position_raw).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                5,
+                rusqlite::types::Type::Text,
+                format!("Invalid defensive_position value: {}", position_raw).into(),
+            )
+        })?;
+        Ok(LineupEntry {
+            player: RosterPlayer {
+                id: r.get(0)?,
+                jersey_no: r.get(1)?,
+                first_name: r.get(2)?,
+                last_name: r.get(3)?,
+            },
+            batting_order: r.get(4)?,
+            position,
+        })
+    })?
+    .collect()
+}
+
+/// The player now in `batting_order` for the team batting in `half` of
+/// game `game_pk` (the `games` row id). `None` for an empty spot.
+pub fn batting_slot_player(
+    conn: &Connection,
+    game_pk: i64,
+    half: HalfInning,
+    batting_order: u8,
+) -> Result<Option<i64>> {
+    conn.query_row(
+        r#"
+        SELECT gl.player_id
+        FROM games g
+        JOIN game_lineups gl
+          ON gl.game_id = g.game_id
+         AND gl.team_id = CASE WHEN ?2 = 'Top' THEN g.away_team_id ELSE g.home_team_id END
+        WHERE g.id = ?1
+          AND gl.batting_order = ?3
+          AND gl.substituted_at_inning IS NULL
+        "#,
+        params![game_pk, half.as_str(), batting_order as i64],
+        |r| r.get(0),
+    )
+    .optional()
+}
+
+/// A starter of the game: spot on the lineup card and the position on
+/// the `game_lineups` row, which a later position change rewrites.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartingSpot {
+    pub player_id: i64,
+    pub batting_order: u8,
+    pub position: Position,
+}
+
+/// The starters of `team_id` for this game, in batting order.
+pub fn starting_lineup(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+) -> Result<Vec<StartingSpot>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT player_id, batting_order, defensive_position
+        FROM game_lineups
+        WHERE game_id = ?1
+          AND team_id = ?2
+          AND is_starting = 1
+        ORDER BY batting_order
+        "#,
+    )?;
+    stmt.query_map(params![game_id, team_id], |r| {
+        let position_raw: String = r.get(2)?;
+        let position = Position::from_db_value(&position_raw).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                2,
+                rusqlite::types::Type::Text,
+                format!("Invalid defensive_position value: {}", position_raw).into(),
+            )
+        })?;
+        Ok(StartingSpot {
+            player_id: r.get(0)?,
+            batting_order: r.get(1)?,
+            position,
+        })
+    })?
+    .collect()
+}
+
+/// Players of `team_id` who were replaced earlier in this game.
+pub fn removed_players(conn: &Connection, game_id: &str, team_id: i64) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT DISTINCT player_id
+        FROM game_lineups
+        WHERE game_id = ?1
+          AND team_id = ?2
+          AND substituted_at_inning IS NOT NULL
+        "#,
+    )?;
+    stmt.query_map(params![game_id, team_id], |r| r.get(0))?
+        .collect()
+}
+
+/// Replace the active player in `batting_order` with `new_player_id`,
+/// who takes `position`. The outgoing row is stamped with the inning/half
+/// of the change and kept for the record. Callers that write several
+/// changes at once wrap them in a transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn substitute_player(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+    batting_order: u8,
+    new_player_id: i64,
+    position: Position,
+    inning: u32,
+    half: HalfInning,
+) -> Result<()> {
+    conn.execute(
+        r#"
+        UPDATE game_lineups
+        SET substituted_at_inning = ?4,
+            substituted_at_half = ?5
+        WHERE game_id = ?1
+          AND team_id = ?2
+          AND batting_order = ?3
+          AND substituted_at_inning IS NULL
+        "#,
+        params![game_id, team_id, batting_order, inning, half.as_str()],
+    )?;
+
+    conn.execute(
+        r#"
+        INSERT INTO game_lineups (
+            game_id, team_id, player_id, batting_order, defensive_position, is_starting
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, 0)
+        "#,
+        params![
+            game_id,
+            team_id,
+            new_player_id,
+            batting_order,
+            position.to_db_value()
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Undo a [`substitute_player`]: drop `player_in_id`'s active row and
+/// put `player_out_id` back in `batting_order`, in the position they
+/// held when they left.
+pub fn revert_substitution(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+    batting_order: u8,
+    player_out_id: i64,
+    player_in_id: i64,
+) -> Result<()> {
+    conn.execute(
+        r#"
+        DELETE FROM game_lineups
+        WHERE game_id = ?1
+          AND team_id = ?2
+          AND batting_order = ?3
+          AND player_id = ?4
+          AND substituted_at_inning IS NULL
+        "#,
+        params![game_id, team_id, batting_order, player_in_id],
+    )?;
+
+    conn.execute(
+        r#"
+        UPDATE game_lineups
+        SET substituted_at_inning = NULL,
+            substituted_at_half = NULL
+        WHERE id = (
+            SELECT MAX(id)
+            FROM game_lineups
+            WHERE game_id = ?1
+              AND team_id = ?2
+              AND batting_order = ?3
+              AND player_id = ?4
+              AND substituted_at_inning IS NOT NULL
+        )
+        "#,
+        params![game_id, team_id, batting_order, player_out_id],
+    )?;
+
+    Ok(())
+}
+
+/// Move the active player in `batting_order` to `position`.
+pub fn set_defensive_position(
+    conn: &Connection,
+    game_id: &str,
+    team_id: i64,
+    batting_order: u8,
+    position: Position,
+) -> Result<()> {
+    conn.execute(
+        r#"
+        UPDATE game_lineups
+        SET defensive_position = ?4
+        WHERE game_id = ?1
+          AND team_id = ?2
+          AND batting_order = ?3
+          AND substituted_at_inning IS NULL
+        "#,
+        params![game_id, team_id, batting_order, position.to_db_value()],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+    use crate::db::fixtures::{BullpenGame, bullpen_game};
+
+    const GAME_ID: &str = "G-LINEUPS";
+
+    /// Three home pitchers: #21 (#51 on the road) starting, #34 and #40
+    /// in the bullpen.
+    fn setup(conn: &Connection) -> BullpenGame {
+        bullpen_game(
+            conn,
+            GAME_ID,
+            &[
+                (21, Some(51), "Sam", "Starter"),
+                (34, None, "Rex", "Reliever"),
+                (40, None, "Carl", "Closer"),
+            ],
+            0,
+        )
+    }
+
+    #[test]
+    fn roster_jerseys_depend_on_the_team_side() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            home_team_id: team_id,
+            ..
+        } = setup(conn);
+
+        let jerseys = |is_home| -> Vec<i32> {
+            team_roster(conn, team_id, is_home)
+                .unwrap()
+                .iter()
+                .map(|p| p.jersey_no)
+                .collect()
+        };
+        assert_eq!(jerseys(true), vec![21, 34, 40]);
+        // No away number: the home number is worn on the road too.
+        assert_eq!(jerseys(false), vec![51, 34, 40]);
+    }
+
+    #[test]
+    fn substitution_keeps_the_replaced_player_as_removed() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            home_team_id: team_id,
+            pitchers: ids,
+            ..
+        } = setup(conn);
+
+        substitute_player(
+            conn,
+            GAME_ID,
+            team_id,
+            9,
+            ids[1],
+            Position::Pitcher,
+            6,
+            HalfInning::Top,
+        )
+        .unwrap();
+
+        let lineup = active_lineup(conn, GAME_ID, team_id, true).unwrap();
+        assert_eq!(lineup.len(), 1);
+        assert_eq!(lineup[0].player.id, ids[1]);
+        assert_eq!(lineup[0].batting_order, 9);
+        assert_eq!(lineup[0].position, Position::Pitcher);
+        assert_eq!(
+            removed_players(conn, GAME_ID, team_id).unwrap(),
+            vec![ids[0]]
+        );
+
+        let (inning, half): (u32, String) = conn
+            .query_row(
+                "SELECT substituted_at_inning, substituted_at_half
+                 FROM game_lineups WHERE player_id = ?1",
+                params![ids[0]],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((inning, half.as_str()), (6, "Top"));
+
+        set_defensive_position(conn, GAME_ID, team_id, 9, Position::LeftField).unwrap();
+        let lineup = active_lineup(conn, GAME_ID, team_id, true).unwrap();
+        assert_eq!(lineup[0].position, Position::LeftField);
+    }
+
+    #[test]
+    fn reverted_substitution_restores_the_replaced_player() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            home_team_id: team_id,
+            pitchers: ids,
+            ..
+        } = setup(conn);
+
+        substitute_player(
+            conn,
+            GAME_ID,
+            team_id,
+            9,
+            ids[1],
+            Position::Pitcher,
+            6,
+            HalfInning::Top,
+        )
+        .unwrap();
+        revert_substitution(conn, GAME_ID, team_id, 9, ids[0], ids[1]).unwrap();
+
+        let lineup = active_lineup(conn, GAME_ID, team_id, true).unwrap();
+        assert_eq!(lineup.len(), 1);
+        assert_eq!(lineup[0].player.id, ids[0]);
+        assert_eq!(lineup[0].position, Position::Pitcher);
+        assert!(removed_players(conn, GAME_ID, team_id).unwrap().is_empty());
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM game_lineups", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    #[test]
+    fn batting_slot_player_follows_the_batting_team() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            home_team_id: team_id,
+            pitchers: ids,
+            ..
+        } = setup(conn);
+        let game_pk: i64 = conn
+            .query_row("SELECT id FROM games WHERE game_id = ?1", [GAME_ID], |r| {
+                r.get(0)
+            })
+            .unwrap();
+
+        // The home team bats in the bottom half.
+        let slot = |half| batting_slot_player(conn, game_pk, half, 9).unwrap();
+        assert_eq!(slot(HalfInning::Bottom), Some(ids[0]));
+        assert_eq!(slot(HalfInning::Top), None);
+
+        substitute_player(
+            conn,
+            GAME_ID,
+            team_id,
+            9,
+            ids[1],
+            Position::Pitcher,
+            6,
+            HalfInning::Top,
+        )
+        .unwrap();
+        assert_eq!(slot(HalfInning::Bottom), Some(ids[1]));
+    }
+
+    #[test]
+    fn only_one_active_player_per_spot() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            home_team_id: team_id,
+            pitchers: ids,
+            ..
+        } = setup(conn);
+
+        let dup = conn.execute(
+            "INSERT INTO game_lineups (GAME_ID, team_id, player_id, batting_order, defensive_position)
+             VALUES (?1, ?2, ?3, 9, '1')",
+            params![GAME_ID, team_id, ids[2]],
+        );
+        assert!(dup.is_err());
+    }
+}
@@ -2,7 +2,7 @@ use chrono::Local;
 use rusqlite::{Connection, Result};
 
 /// Current schema version - increment this when adding migrations
-pub const CURRENT_SCHEMA_VERSION: i64 = 20;
+pub const CURRENT_SCHEMA_VERSION: i64 = 29;
 
 /// Migration structure
 pub struct Migration {
@@ -115,6 +115,51 @@ pub fn get_migrations() -> Vec<Migration> {
             description: "Migration player model fields position, bat and throw",
             up: migration_v20,
         },
+        Migration {
+            version: 21,
+            description: "Add fielders column to runner_movements for outs on the bases",
+            up: migration_v21,
+        },
+        Migration {
+            version: 22,
+            description: "Add double_play column to plate_appearances",
+            up: migration_v22,
+        },
+        Migration {
+            version: 23,
+            description: "Keep substituted players in game_lineups alongside their replacements",
+            up: migration_v23,
+        },
+        Migration {
+            version: 24,
+            description: "Rebuild tables whose foreign keys still point to players_old",
+            up: migration_v24,
+        },
+        Migration {
+            version: 25,
+            description: "Link game_events to the last plate appearance recorded before them",
+            up: migration_v25,
+        },
+        Migration {
+            version: 26,
+            description: "Add scheduled_innings to games",
+            up: migration_v26,
+        },
+        Migration {
+            version: 27,
+            description: "Add rule profiles to leagues and games",
+            up: migration_v27,
+        },
+        Migration {
+            version: 28,
+            description: "Add end_reason to games",
+            up: migration_v28,
+        },
+        Migration {
+            version: 29,
+            description: "Add rbi to plate_appearances",
+            up: migration_v29,
+        },
     ]
 }
 
@@ -1188,3 +1233,203 @@ fn migration_v20(conn: &Connection) -> Result<()> {
 
     Ok(())
 }
+
+fn migration_v21(conn: &Connection) -> Result<()> {
+    // Fielding chain of a runner put out between pitches (caught stealing,
+    // pickoff), stored hyphenated like the PA outcome sequences ("2-6",
+    // "1-3"): the last fielder gets the putout, the others an assist.
+    // NULL on every other movement.
+    if !check_column_exists(conn, "runner_movements", "fielders")? {
+        conn.execute("ALTER TABLE runner_movements ADD COLUMN fielders TEXT", [])?;
+    }
+    Ok(())
+}
+
+fn migration_v22(conn: &Connection) -> Result<()> {
+    // JSON-serialised DP/TP marker (kind, outs, participating fielders) on
+    // the PA that started the play. NULL on every other PA.
+    if !check_column_exists(conn, "plate_appearances", "double_play")? {
+        conn.execute(
+            "ALTER TABLE plate_appearances ADD COLUMN double_play TEXT",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn migration_v23(conn: &Connection) -> Result<()> {
+    // The (game_id, team_id, batting_order) primary key allowed a single
+    // player per lineup spot. In-game substitutions keep the replaced
+    // player's row (stamped with substituted_at_inning/half) and add one
+    // for the replacement, so the spot is only unique among active rows.
+    conn.execute_batch(
+        r#"
+        PRAGMA foreign_keys = OFF;
+
+        ALTER TABLE game_lineups RENAME TO game_lineups_old;
+
+        CREATE TABLE game_lineups (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id TEXT NOT NULL,
+            team_id INTEGER NOT NULL,
+            player_id INTEGER NOT NULL,
+            batting_order INTEGER NOT NULL CHECK(batting_order BETWEEN 1 AND 10),
+            defensive_position TEXT NOT NULL,
+            is_starting BOOLEAN DEFAULT 1,
+            substituted_at_inning INTEGER,
+            substituted_at_half TEXT CHECK(substituted_at_half IN ('Top', 'Bottom')),
+            FOREIGN KEY (game_id) REFERENCES games(game_id),
+            FOREIGN KEY (team_id) REFERENCES teams(id),
+            FOREIGN KEY (player_id) REFERENCES players(id)
+        );
+
+        INSERT INTO game_lineups (
+            game_id,
+            team_id,
+            player_id,
+            batting_order,
+            defensive_position,
+            is_starting,
+            substituted_at_inning,
+            substituted_at_half
+        )
+        SELECT
+            game_id,
+            team_id,
+            player_id,
+            batting_order,
+            defensive_position,
+            is_starting,
+            substituted_at_inning,
+            substituted_at_half
+        FROM game_lineups_old
+        ORDER BY game_id, team_id, batting_order;
+
+        DROP TABLE game_lineups_old;
+
+        CREATE INDEX IF NOT EXISTS idx_game_lineups_game ON game_lineups(game_id);
+        CREATE INDEX IF NOT EXISTS idx_game_lineups_team ON game_lineups(team_id);
+
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_game_lineups_active_spot
+            ON game_lineups(game_id, team_id, batting_order)
+            WHERE substituted_at_inning IS NULL;
+
+        PRAGMA foreign_keys = ON;
+        "#,
+    )?;
+
+    Ok(())
+}
+
+fn migration_v24(conn: &Connection) -> Result<()> {
+    // SQLite 3.26+ rewrites foreign-key references when a table is
+    // renamed, even with foreign_keys OFF, so the v20 rebuild of `players`
+    // left plate_appearances, at_bat_draft and at_bats referencing the
+    // dropped players_old: every insert into them failed with foreign keys
+    // on. Each of them is rebuilt from its stored definition with the
+    // references pointed back to `players`; legacy_alter_table keeps the
+    // rename below from rewriting other tables the same way.
+    let stale: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT name, sql FROM sqlite_master
+             WHERE type = 'table' AND sql LIKE '%\"players_old\"%'",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_>>()?;
+
+    for (table, sql) in stale {
+        let indexes: Vec<String> = conn
+            .prepare(
+                "SELECT sql FROM sqlite_master
+                 WHERE type IN ('index', 'trigger') AND tbl_name = ?1 AND sql IS NOT NULL",
+            )?
+            .query_map([&table], |r| r.get(0))?
+            .collect::<Result<_>>()?;
+
+        conn.execute_batch(&format!(
+            r#"
+            PRAGMA foreign_keys = OFF;
+            PRAGMA legacy_alter_table = ON;
+
+            ALTER TABLE {table} RENAME TO {table}_old;
+            {create};
+            INSERT INTO {table} SELECT * FROM {table}_old;
+            DROP TABLE {table}_old;
+            {indexes};
+
+            PRAGMA legacy_alter_table = OFF;
+            PRAGMA foreign_keys = ON;
+            "#,
+            create = sql.replace("\"players_old\"", "players"),
+            indexes = indexes.join(";\n"),
+        ))?;
+    }
+    Ok(())
+}
+
+fn migration_v25(conn: &Connection) -> Result<()> {
+    // Seq of the last plate appearance recorded before the event (NULL
+    // before the first PA), like runner_movements.pa_seq. Undoing a PA
+    // removes the events logged after it.
+    if !check_column_exists(conn, "game_events", "pa_seq")? {
+        conn.execute("ALTER TABLE game_events ADD COLUMN pa_seq INTEGER", [])?;
+    }
+    Ok(())
+}
+
+fn migration_v26(conn: &Connection) -> Result<()> {
+    // Regulation length of the game; the engine ends the game after it.
+    if !check_column_exists(conn, "games", "scheduled_innings")? {
+        conn.execute(
+            "ALTER TABLE games ADD COLUMN scheduled_innings INTEGER NOT NULL DEFAULT 9",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn migration_v27(conn: &Connection) -> Result<()> {
+    // Rule profile: a league carries the default, each game its own copy
+    // (the innings of a game are `games.scheduled_innings`).
+    let rule_columns = [
+        ("game_kind", "TEXT NOT NULL DEFAULT 'baseball'"),
+        ("dh_allowed", "BOOLEAN NOT NULL DEFAULT 1"),
+        ("mercy_rules", "TEXT NOT NULL DEFAULT '[]'"),
+        ("max_runs_per_half", "INTEGER"),
+        ("tie_break_inning", "INTEGER"),
+    ];
+    let mut columns: Vec<(&str, &str, &str)> = vec![
+        ("leagues", "innings", "INTEGER NOT NULL DEFAULT 9"),
+        ("games", "league_id", "INTEGER REFERENCES leagues(id)"),
+    ];
+    for (column, definition) in rule_columns {
+        columns.push(("leagues", column, definition));
+        columns.push(("games", column, definition));
+    }
+
+    for (table, column, definition) in columns {
+        if !check_column_exists(conn, table, column)? {
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn migration_v28(conn: &Connection) -> Result<()> {
+    // Why the engine ended the game (walk-off, run rule, ...).
+    if !check_column_exists(conn, "games", "end_reason")? {
+        conn.execute("ALTER TABLE games ADD COLUMN end_reason TEXT", [])?;
+    }
+    Ok(())
+}
+
+fn migration_v29(conn: &Connection) -> Result<()> {
+    // RBIs credited on each PA. NULL for rows recorded before RBIs were kept.
+    if !check_column_exists(conn, "plate_appearances", "rbi")? {
+        conn.execute("ALTER TABLE plate_appearances ADD COLUMN rbi INTEGER", [])?;
+    }
+    Ok(())
+}
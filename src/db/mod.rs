@@ -1,9 +1,14 @@
 pub mod at_bat_draft;
 pub mod config;
 pub mod database;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod game_archive;
+pub mod game_csv;
 pub mod game_events;
 pub mod game_queries;
 pub mod league;
+pub mod lineups;
 pub mod migrations;
 pub mod plate_appearances;
 pub mod player;
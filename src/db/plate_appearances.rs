@@ -1,6 +1,6 @@
 use crate::engine::apply::serialize_runner_dest;
 use crate::models::game_state::BatterOrder;
-use crate::models::plate_appearance::{HitOutcomeData, PlateAppearance};
+use crate::models::plate_appearance::{DoublePlay, HitModifier, HitOutcomeData, PlateAppearance};
 use crate::models::runner::RunnerOverride;
 use rusqlite::{Connection, Result, params};
 
@@ -22,6 +22,12 @@ pub struct PlateAppearanceRow {
     /// JSON-serialised `Vec<RunnerOverride>`. Empty array for legacy rows and
     /// any PA that used automatic (non-overridden) advancement.
     pub runner_overrides_json: String,
+    /// JSON-serialised `DoublePlay`. `None` unless the PA was part of a
+    /// double or triple play.
+    pub double_play_json: Option<String>,
+    /// RBIs credited to the batter. `None` for legacy rows recorded before
+    /// RBIs were kept.
+    pub rbi: Option<u8>,
 }
 
 impl PlateAppearanceRow {
@@ -30,21 +36,43 @@ impl PlateAppearanceRow {
     pub fn runner_overrides(&self) -> Vec<RunnerOverride> {
         serde_json::from_str(&self.runner_overrides_json).unwrap_or_default()
     }
+
+    /// Deserialise `double_play_json`. Returns `None` on parse failure.
+    pub fn double_play(&self) -> Option<DoublePlay> {
+        self.double_play_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+    }
 }
 
-fn serialize_hit_outcome_data(zone: &Option<crate::models::field_zone::FieldZone>) -> String {
-    serde_json::to_string(&HitOutcomeData { zone: *zone })
-        .unwrap_or_else(|_| r#"{"zone":null}"#.to_string())
+fn serialize_hit_outcome_data(
+    zone: &Option<crate::models::field_zone::FieldZone>,
+    modifier: &Option<HitModifier>,
+) -> String {
+    serde_json::to_string(&HitOutcomeData {
+        zone: *zone,
+        modifier: *modifier,
+    })
+    .unwrap_or_else(|_| r#"{"zone":null}"#.to_string())
 }
 
-pub fn append_plate_appearance(
-    conn: &Connection,
-    game_pk: i64,
-    pa: &PlateAppearance,
-) -> Result<i64> {
-    let (outcome_type, outcome_data) = match &pa.outcome {
+/// `(outcome_type, outcome_data)` columns of a PA.
+pub(crate) fn outcome_columns(pa: &PlateAppearance) -> (String, Option<String>) {
+    match &pa.outcome {
         crate::models::plate_appearance::PlateAppearanceOutcome::Walk => ("walk".to_string(), None),
 
+        crate::models::plate_appearance::PlateAppearanceOutcome::HitByPitch => {
+            ("hit_by_pitch".to_string(), None)
+        }
+
+        crate::models::plate_appearance::PlateAppearanceOutcome::IntentionalWalk => {
+            ("intentional_walk".to_string(), None)
+        }
+
+        crate::models::plate_appearance::PlateAppearanceOutcome::CatcherInterference => {
+            ("catcher_interference".to_string(), None)
+        }
+
         crate::models::plate_appearance::PlateAppearanceOutcome::Out => ("out".to_string(), None),
 
         crate::models::plate_appearance::PlateAppearanceOutcome::Strikeout(kind) => (
@@ -52,38 +80,54 @@ pub fn append_plate_appearance(
             Some(serde_json::to_string(kind).unwrap_or_else(|_| "null".to_string())),
         ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::Single { zone } => {
-            ("single".to_string(), Some(serialize_hit_outcome_data(zone)))
-        }
+        crate::models::plate_appearance::PlateAppearanceOutcome::DroppedThirdStrike(how) => (
+            "dropped_third_strike".to_string(),
+            Some(serde_json::to_string(how).unwrap_or_else(|_| "null".to_string())),
+        ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::Double { zone } => {
-            ("double".to_string(), Some(serialize_hit_outcome_data(zone)))
-        }
+        crate::models::plate_appearance::PlateAppearanceOutcome::Single { zone, modifier } => (
+            "single".to_string(),
+            Some(serialize_hit_outcome_data(zone, modifier)),
+        ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::Triple { zone } => {
-            ("triple".to_string(), Some(serialize_hit_outcome_data(zone)))
-        }
+        crate::models::plate_appearance::PlateAppearanceOutcome::Double { zone, modifier } => (
+            "double".to_string(),
+            Some(serialize_hit_outcome_data(zone, modifier)),
+        ),
+
+        crate::models::plate_appearance::PlateAppearanceOutcome::Triple { zone, modifier } => (
+            "triple".to_string(),
+            Some(serialize_hit_outcome_data(zone, modifier)),
+        ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::HomeRun { zone } => (
+        crate::models::plate_appearance::PlateAppearanceOutcome::HomeRun { zone, modifier } => (
             "home_run".to_string(),
-            Some(serialize_hit_outcome_data(zone)),
+            Some(serialize_hit_outcome_data(zone, modifier)),
         ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::UnassistedOut { fielder } => (
+        crate::models::plate_appearance::PlateAppearanceOutcome::UnassistedOut {
+            fielder,
+            sacrifice,
+        } => (
             "unassisted_out".to_string(),
             Some(
                 serde_json::json!({
                     "fielder": fielder,
+                    "sacrifice": sacrifice,
                 })
                 .to_string(),
             ),
         ),
 
-        crate::models::plate_appearance::PlateAppearanceOutcome::GroundOut { sequence } => (
+        crate::models::plate_appearance::PlateAppearanceOutcome::GroundOut {
+            sequence,
+            sacrifice,
+        } => (
             "ground_out".to_string(),
             Some(
                 serde_json::json!({
                     "sequence": sequence,
+                    "sacrifice": sacrifice,
                 })
                 .to_string(),
             ),
@@ -92,12 +136,14 @@ pub fn append_plate_appearance(
         crate::models::plate_appearance::PlateAppearanceOutcome::FlyOut {
             fielder,
             in_foul_territory,
+            sacrifice,
         } => (
             "fly_out".to_string(),
             Some(
                 serde_json::json!({
                     "fielder": fielder,
                     "in_foul_territory": in_foul_territory,
+                    "sacrifice": sacrifice,
                 })
                 .to_string(),
             ),
@@ -136,7 +182,31 @@ pub fn append_plate_appearance(
                 .to_string(),
             ),
         ),
-    };
+
+        crate::models::plate_appearance::PlateAppearanceOutcome::ReachedOnError {
+            fielder,
+            throwing,
+            reached_base,
+        } => (
+            "reached_on_error".to_string(),
+            Some(
+                serde_json::json!({
+                    "fielder": fielder,
+                    "throwing": throwing,
+                    "reached_base": serialize_runner_dest(*reached_base),
+                })
+                .to_string(),
+            ),
+        ),
+    }
+}
+
+pub fn append_plate_appearance(
+    conn: &Connection,
+    game_pk: i64,
+    pa: &PlateAppearance,
+) -> Result<i64> {
+    let (outcome_type, outcome_data) = outcome_columns(pa);
 
     let seq: i64 = conn.query_row(
         "SELECT COALESCE(MAX(seq), 0) + 1 FROM plate_appearances WHERE game_id = ?1",
@@ -150,6 +220,11 @@ pub fn append_plate_appearance(
     let runner_overrides_json =
         serde_json::to_string(&pa.runner_overrides).unwrap_or_else(|_| "[]".to_string());
 
+    let double_play_json = pa
+        .double_play
+        .as_ref()
+        .and_then(|dp| serde_json::to_string(dp).ok());
+
     conn.execute(
         r#"
         INSERT INTO plate_appearances (
@@ -157,8 +232,8 @@ pub fn append_plate_appearance(
             batter_id, batter_order,
             pitcher_id, pitches, pitches_sequence,
             outcome_type, outcome_data,
-            outs, runner_overrides_json
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            outs, runner_overrides_json, double_play
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
         "#,
         params![
             game_pk,
@@ -177,6 +252,7 @@ pub fn append_plate_appearance(
             outcome_data,
             pa.outs as i64,
             runner_overrides_json,
+            double_play_json,
         ],
     )?;
 
@@ -191,7 +267,8 @@ pub fn list_plate_appearances(conn: &Connection, game_pk: i64) -> Result<Vec<Pla
                pitcher_id, pitches, pitches_sequence,
                outcome_type, outcome_data,
                outs,
-               COALESCE(runner_overrides_json, '[]')
+               COALESCE(runner_overrides_json, '[]'),
+               double_play, rbi
         FROM plate_appearances
         WHERE game_id = ?1
         ORDER BY seq ASC
@@ -216,7 +293,101 @@ pub fn list_plate_appearances(conn: &Connection, game_pk: i64) -> Result<Vec<Pla
             outcome_data: r.get(11)?,
             outs: r.get(12)?,
             runner_overrides_json: r.get(13)?,
+            double_play_json: r.get(14)?,
+            rbi: r.get(15)?,
         });
     }
     Ok(out)
 }
+
+/// Delete plate appearance `seq` and every later one. Returns the number
+/// of rows deleted.
+pub fn delete_plate_appearances_from(conn: &Connection, game_pk: i64, seq: i64) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM plate_appearances WHERE game_id = ?1 AND seq >= ?2",
+        params![game_pk, seq],
+    )
+}
+
+/// Overwrite plate appearance `seq` with `pa` (a retroactive correction).
+/// Inning, half, batter and pitcher are kept as recorded.
+pub fn replace_plate_appearance(
+    conn: &Connection,
+    game_pk: i64,
+    seq: i64,
+    pa: &PlateAppearance,
+) -> Result<()> {
+    let (outcome_type, outcome_data) = outcome_columns(pa);
+
+    let pitches_sequence =
+        serde_json::to_string(&pa.pitches_sequence).unwrap_or_else(|_| "[]".to_string());
+
+    let runner_overrides_json =
+        serde_json::to_string(&pa.runner_overrides).unwrap_or_else(|_| "[]".to_string());
+
+    let double_play_json = pa
+        .double_play
+        .as_ref()
+        .and_then(|dp| serde_json::to_string(dp).ok());
+
+    conn.execute(
+        r#"
+        UPDATE plate_appearances
+        SET pitches = ?1, pitches_sequence = ?2,
+            outcome_type = ?3, outcome_data = ?4,
+            outs = ?5, runner_overrides_json = ?6, double_play = ?7
+        WHERE game_id = ?8 AND seq = ?9
+        "#,
+        params![
+            pa.pitches as i64,
+            pitches_sequence,
+            outcome_type,
+            outcome_data,
+            pa.outs as i64,
+            runner_overrides_json,
+            double_play_json,
+            game_pk,
+            seq,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Rewrite the outs recorded after plate appearance `seq`.
+pub fn set_plate_appearance_outs(
+    conn: &Connection,
+    game_pk: i64,
+    seq: i64,
+    outs: u8,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE plate_appearances SET outs = ?1 WHERE game_id = ?2 AND seq = ?3",
+        params![outs as i64, game_pk, seq],
+    )?;
+    Ok(())
+}
+
+/// Record a DP/TP marker recognised after the PA row was written (a
+/// strikeout whose runner is thrown out later on the same input line).
+pub fn set_plate_appearance_double_play(
+    conn: &Connection,
+    game_pk: i64,
+    seq: i64,
+    double_play: &DoublePlay,
+) -> Result<()> {
+    let json = serde_json::to_string(double_play).unwrap_or_else(|_| "null".to_string());
+    conn.execute(
+        "UPDATE plate_appearances SET double_play = ?1 WHERE game_id = ?2 AND seq = ?3",
+        params![json, game_pk, seq],
+    )?;
+    Ok(())
+}
+
+/// Record the RBIs credited to the batter of plate appearance `seq`.
+pub fn set_plate_appearance_rbi(conn: &Connection, game_pk: i64, seq: i64, rbi: u8) -> Result<()> {
+    conn.execute(
+        "UPDATE plate_appearances SET rbi = ?1 WHERE game_id = ?2 AND seq = ?3",
+        params![rbi as i64, game_pk, seq],
+    )?;
+    Ok(())
+}
@@ -4,6 +4,8 @@
 //! a row is written here. This is the authoritative source for replay of base
 //! state, separate from the plate appearance outcomes stored in `plate_appearances`.
 
+use crate::db::lineups::batting_slot_player;
+use crate::models::types::HalfInning;
 use rusqlite::{Connection, Result, params};
 
 // ─── Row type (for read / replay) ────────────────────────────────────────────
@@ -18,7 +20,7 @@ pub struct RunnerMovementRow {
     pub game_event_id: Option<i64>,
     pub inning: i64,
     pub half_inning: String,
-    /// Player id — None when identity not resolved at scoring time.
+    /// Player id — None on legacy rows and for an empty lineup spot.
     pub runner_id: Option<i64>,
     pub batter_order: u8,
     /// `"BAT"`, `"1B"`, `"2B"`, `"3B"`
@@ -29,7 +31,12 @@ pub struct RunnerMovementRow {
     pub advancement_type: String,
     pub is_out: bool,
     pub scored: bool,
+    /// On a scoring row, the run is earned for the pitcher it is charged
+    /// to; rewritten by `engine::earned_runs` after every play.
     pub is_earned: bool,
+    /// Hyphenated fielding chain (`"2-6"`, `"6-4"`) of an out, putout
+    /// last. `None` for safe movements and for outs recorded before 0.12.
+    pub fielders: Option<String>,
 }
 
 // ─── Write helpers ────────────────────────────────────────────────────────────
@@ -41,7 +48,8 @@ pub struct RunnerMovementInsert {
     pub game_event_id: Option<i64>,
     pub inning: u32,
     pub half_inning: String,
-    /// Player id — use None when identity not resolved.
+    /// Player id — None until [`resolve_runner_ids`] looks up the batting
+    /// slot.
     pub runner_id: Option<i64>,
     pub batter_order: u8,
     pub start_base: &'static str,
@@ -49,7 +57,11 @@ pub struct RunnerMovementInsert {
     pub advancement_type: &'static str,
     pub is_out: bool,
     pub scored: bool,
+    /// On a scoring row, the run is earned for the pitcher it is charged
+    /// to; rewritten by `engine::earned_runs` after every play.
     pub is_earned: bool,
+    /// Fielding chain of an out, e.g. `"2-6"` or `"6-4"`.
+    pub fielders: Option<String>,
 }
 
 /// Insert a single runner movement row. Returns the new row id.
@@ -61,8 +73,8 @@ pub fn append_runner_movement(conn: &Connection, m: &RunnerMovementInsert) -> Re
             inning, half_inning,
             runner_id, batter_order,
             start_base, end_base, advancement_type,
-            is_out, scored, is_earned
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            is_out, scored, is_earned, fielders
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
         "#,
         params![
             m.game_id,
@@ -78,11 +90,52 @@ pub fn append_runner_movement(conn: &Connection, m: &RunnerMovementInsert) -> Re
             m.is_out as i64,
             m.scored as i64,
             m.is_earned as i64,
+            m.fielders,
         ],
     )?;
     Ok(conn.last_insert_rowid())
 }
 
+/// Fill in the `runner_id` the reducer leaves unresolved with the player
+/// who holds the runner's batting slot. Call it before the lineup changes
+/// again, so a runner replaced later keeps their own runs.
+pub fn resolve_runner_ids(
+    conn: &Connection,
+    game_pk: i64,
+    movements: &mut [RunnerMovementInsert],
+) -> Result<()> {
+    for m in movements.iter_mut().filter(|m| m.runner_id.is_none()) {
+        let half = HalfInning::from_str_loose(&m.half_inning);
+        m.runner_id = batting_slot_player(conn, game_pk, half, m.batter_order)?;
+    }
+    Ok(())
+}
+
+/// Delete the movements linked to plate appearance `pa_seq` or a later
+/// one: the PA's own runners and the in-pitch movements recorded after
+/// it. Returns the number of rows deleted.
+pub fn delete_runner_movements_from(conn: &Connection, game_pk: i64, pa_seq: i64) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM runner_movements WHERE game_id = ?1 AND pa_seq >= ?2",
+        params![game_pk, pa_seq],
+    )
+}
+
+/// Set whether the run of a scoring row is earned.
+pub fn set_runner_movement_earned(conn: &Connection, id: i64, is_earned: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE runner_movements SET is_earned = ?2 WHERE id = ?1",
+        params![id, is_earned as i64],
+    )?;
+    Ok(())
+}
+
+/// Delete a single runner movement row.
+pub fn delete_runner_movement(conn: &Connection, id: i64) -> Result<()> {
+    conn.execute("DELETE FROM runner_movements WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
 // ─── Read helpers ─────────────────────────────────────────────────────────────
 
 /// Load all runner movements for a game, ordered for replay.
@@ -93,7 +146,7 @@ pub fn list_runner_movements(conn: &Connection, game_pk: i64) -> Result<Vec<Runn
                inning, half_inning,
                runner_id, batter_order,
                start_base, end_base, advancement_type,
-               is_out, scored, is_earned
+               is_out, scored, is_earned, fielders
         FROM runner_movements
         WHERE game_id = ?1
         ORDER BY inning ASC,
@@ -121,6 +174,7 @@ pub fn list_runner_movements(conn: &Connection, game_pk: i64) -> Result<Vec<Runn
                 is_out: r.get::<_, i64>(11)? != 0,
                 scored: r.get::<_, i64>(12)? != 0,
                 is_earned: r.get::<_, i64>(13)? != 0,
+                fielders: r.get(14)?,
             })
         })?
         .filter_map(Result::ok)
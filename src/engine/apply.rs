@@ -5,18 +5,19 @@
 //! No DB access — the engine loop handles persistence.
 
 use crate::db::runner_movements::RunnerMovementInsert;
-use crate::engine::commands::types::EngineCommand;
+use crate::engine::commands::types::{EngineCommand, PitchAdvanceReason, RunnerCaughtKind};
 use crate::engine::runners::add_runs_to_score;
 use crate::engine::scoring::BatterOutType;
 use crate::engine::scoring::batter_outs::{
-    DefensiveOutKind, DefensivePlayCommand, DefensivePlayTarget,
+    DefensiveOutKind, DefensivePlayCommand, DefensivePlayTarget, Sacrifice,
 };
 use crate::models::events::{
     DomainEvent, OutRecordedData, PersistedEvent, StatusChangedData, StrikeoutKind,
 };
 use crate::models::game_state::GameState;
 use crate::models::plate_appearance::{
-    PlateAppearance, PlateAppearanceOutcome, PlateAppearanceStep,
+    DoublePlay, DoublePlayKind, DroppedThirdStrike, PlateAppearance, PlateAppearanceOutcome,
+    PlateAppearanceStep,
 };
 use crate::models::runner::RunnerOverride;
 use crate::models::types::{GameStatus, Pitch};
@@ -56,6 +57,31 @@ pub fn apply_engine_command(state: &mut GameState, cmd: EngineCommand) -> ApplyR
             ..Default::default()
         },
 
+        EngineCommand::Undo { .. } => ApplyResult {
+            events: vec![UiEvent::Error(
+                "UNDO must be handled by the engine (DB-backed).".to_string(),
+            )],
+            ..Default::default()
+        },
+
+        EngineCommand::CreditRbi { .. } => ApplyResult {
+            events: vec![UiEvent::Error(
+                "RBI credits must be handled by the engine (DB-backed).".to_string(),
+            )],
+            ..Default::default()
+        },
+
+        EngineCommand::PitchingChange { .. }
+        | EngineCommand::PinchHitter { .. }
+        | EngineCommand::PinchRunner { .. }
+        | EngineCommand::DefensiveSubstitution { .. }
+        | EngineCommand::PositionChange { .. } => ApplyResult {
+            events: vec![UiEvent::Error(
+                "Substitutions must be handled by the engine (DB-backed).".to_string(),
+            )],
+            ..Default::default()
+        },
+
         EngineCommand::SetStatus(status) => {
             let msg = format!("{} Game set to {}.", status.icon(), status);
             ApplyResult {
@@ -76,37 +102,63 @@ pub fn apply_engine_command(state: &mut GameState, cmd: EngineCommand) -> ApplyR
 
         EngineCommand::Single {
             zone,
+            modifier,
             runner_overrides,
         } => apply_hit_command(
             state,
-            PlateAppearanceOutcome::Single { zone },
+            PlateAppearanceOutcome::Single { zone, modifier },
             &runner_overrides,
         ),
 
         EngineCommand::Double {
             zone,
+            modifier,
             runner_overrides,
         } => apply_hit_command(
             state,
-            PlateAppearanceOutcome::Double { zone },
+            PlateAppearanceOutcome::Double { zone, modifier },
             &runner_overrides,
         ),
 
         EngineCommand::Triple {
             zone,
+            modifier,
             runner_overrides,
         } => apply_hit_command(
             state,
-            PlateAppearanceOutcome::Triple { zone },
+            PlateAppearanceOutcome::Triple { zone, modifier },
             &runner_overrides,
         ),
 
         EngineCommand::HomeRun {
             zone,
+            modifier,
             runner_overrides,
         } => apply_hit_command(
             state,
-            PlateAppearanceOutcome::HomeRun { zone },
+            PlateAppearanceOutcome::HomeRun { zone, modifier },
+            &runner_overrides,
+        ),
+
+        EngineCommand::HitByPitch => apply_base_award(state, PlateAppearanceOutcome::HitByPitch),
+        EngineCommand::IntentionalWalk => {
+            apply_base_award(state, PlateAppearanceOutcome::IntentionalWalk)
+        }
+        EngineCommand::CatcherInterference => {
+            apply_base_award(state, PlateAppearanceOutcome::CatcherInterference)
+        }
+
+        EngineCommand::ReachedOnError {
+            error,
+            reached_base,
+            runner_overrides,
+        } => apply_hit_command(
+            state,
+            PlateAppearanceOutcome::ReachedOnError {
+                fielder: error.fielder,
+                throwing: error.throwing,
+                reached_base,
+            },
             &runner_overrides,
         ),
 
@@ -114,7 +166,24 @@ pub fn apply_engine_command(state: &mut GameState, cmd: EngineCommand) -> ApplyR
             apply_batter_out_command(state, order, out_type)
         }
 
+        EngineCommand::DroppedThirdStrike {
+            how,
+            runner_overrides,
+        } => apply_dropped_third_strike(state, how, &runner_overrides),
+
         EngineCommand::StealBase { order, dest } => apply_steal(state, order, dest),
+        EngineCommand::PitchAdvance {
+            order,
+            dest,
+            reason,
+        } => apply_pitch_advance(state, order, dest, reason),
+        EngineCommand::Balk => apply_balk(state),
+        EngineCommand::RunnerCaught {
+            order,
+            kind,
+            dest,
+            fielders,
+        } => apply_runner_caught(state, order, kind, dest, &fielders),
 
         EngineCommand::DefensivePlay(play) => apply_defensive_play_command(state, play),
     }
@@ -254,6 +323,7 @@ fn apply_pitch(state: &mut GameState, pitch: Pitch) -> ApplyResult {
             outcome,
             outs,
             runner_overrides: vec![],
+            double_play: None,
         }
     };
 
@@ -283,47 +353,9 @@ fn apply_pitch(state: &mut GameState, pitch: Pitch) -> ApplyResult {
 
         applied.push(DomainEvent::CountReset);
 
-        // Build walk movements using unified runner logic
-        // We read the state BEFORE mutation (state hasn't been changed yet by applied events)
-        {
-            let half_str = state.half.as_str();
-            let mk = |runner_id: Option<i64>,
-                      border: u8,
-                      start: &'static str,
-                      end: &'static str,
-                      scored: bool| {
-                RunnerMovementInsert {
-                    game_id: 0,
-                    pa_seq: None,
-                    game_event_id: None,
-                    inning: state.inning,
-                    half_inning: half_str.to_string(),
-                    runner_id,
-                    batter_order: border,
-                    start_base: start,
-                    end_base: end,
-                    advancement_type: "walk",
-                    is_out: false,
-                    scored,
-                    is_earned: true,
-                }
-            };
-            // Bases loaded: runner on 3B scores
-            if state.on_1b.is_some() && state.on_2b.is_some() && state.on_3b.is_some() {
-                let r3 = state.on_3b.unwrap_or(0);
-                walk_movements.push(mk(None, r3, "3B", "HOME", true));
-            }
-            if state.on_1b.is_some() && state.on_2b.is_some() {
-                let r2 = state.on_2b.unwrap_or(0);
-                walk_movements.push(mk(None, r2, "2B", "3B", false));
-            }
-            if state.on_1b.is_some() {
-                let r1 = state.on_1b.unwrap_or(0);
-                walk_movements.push(mk(None, r1, "1B", "2B", false));
-            }
-            // Batter to 1B
-            walk_movements.push(mk(Some(batter_id), batter_order, "BAT", "1B", false));
-        }
+        // Build walk movements from the state BEFORE mutation (the applied
+        // events have not been reduced yet).
+        walk_movements = forced_advance_movements(state, batter_id, batter_order, "walk");
 
         events_ui.push(UiEvent::Line("BB: batter to 1B".to_string()));
         needs_next_at_bat = true;
@@ -374,8 +406,243 @@ fn apply_pitch(state: &mut GameState, pitch: Pitch) -> ApplyResult {
     }
 }
 
+/// Movement rows for a batter awarded first base: the batter goes
+/// `BAT → 1B` and every runner forced by the batter moves up one base.
+///
+/// Reads the state BEFORE the award is applied; the base mutation itself
+/// happens when the reducer processes `DomainEvent::RunnerToFirst`.
+fn forced_advance_movements(
+    state: &GameState,
+    batter_id: i64,
+    batter_order: BatterOrder,
+    advancement_type: &'static str,
+) -> Vec<RunnerMovementInsert> {
+    let half_str = state.half.as_str();
+    let mk = |runner_id: Option<i64>,
+              border: u8,
+              start: &'static str,
+              end: &'static str,
+              scored: bool| {
+        RunnerMovementInsert {
+            game_id: 0,
+            pa_seq: None,
+            game_event_id: None,
+            inning: state.inning,
+            half_inning: half_str.to_string(),
+            runner_id,
+            batter_order: border,
+            start_base: start,
+            end_base: end,
+            advancement_type,
+            is_out: false,
+            scored,
+            is_earned: true,
+            fielders: None,
+        }
+    };
+
+    let mut movements = Vec::with_capacity(4);
+    // Bases loaded: runner on 3B scores
+    if state.on_1b.is_some() && state.on_2b.is_some() && state.on_3b.is_some() {
+        let r3 = state.on_3b.unwrap_or(0);
+        movements.push(mk(None, r3, "3B", "HOME", true));
+    }
+    if state.on_1b.is_some() && state.on_2b.is_some() {
+        let r2 = state.on_2b.unwrap_or(0);
+        movements.push(mk(None, r2, "2B", "3B", false));
+    }
+    if state.on_1b.is_some() {
+        let r1 = state.on_1b.unwrap_or(0);
+        movements.push(mk(None, r1, "1B", "2B", false));
+    }
+    // Batter to 1B
+    movements.push(mk(Some(batter_id), batter_order, "BAT", "1B", false));
+    movements
+}
+
+// ─── Base awards ──────────────────────────────────────────────────────────────
+
+/// HBP / IBB / CI: the batter takes first and forced runners advance, like
+/// a walk but without a fourth ball. An intentional walk needs no pitch.
+fn apply_base_award(state: &mut GameState, outcome: PlateAppearanceOutcome) -> ApplyResult {
+    let (batter_id, batter_order, pitcher_id) = require_batter!(state);
+
+    let (final_step, advancement_type) = match outcome {
+        PlateAppearanceOutcome::HitByPitch => (PlateAppearanceStep::HitByPitch, "hit_by_pitch"),
+        PlateAppearanceOutcome::IntentionalWalk => {
+            (PlateAppearanceStep::IntentionalWalk, "intentional_walk")
+        }
+        PlateAppearanceOutcome::CatcherInterference => (
+            PlateAppearanceStep::CatcherInterference,
+            "catcher_interference",
+        ),
+        _ => {
+            return ApplyResult {
+                events: vec![UiEvent::Error(
+                    "Invalid award outcome passed to apply_base_award.".to_string(),
+                )],
+                ..Default::default()
+            };
+        }
+    };
+
+    let final_sequence = build_pa_sequence_with_terminal_step(state, final_step);
+    let pitches_in_pa = match outcome {
+        PlateAppearanceOutcome::IntentionalWalk => final_sequence.len() as u32 - 1,
+        _ => final_sequence.len() as u32,
+    };
+
+    let movements = forced_advance_movements(state, batter_id, batter_order, advancement_type);
+
+    let mut applied = Vec::with_capacity(3);
+    if matches!(outcome, PlateAppearanceOutcome::HitByPitch) {
+        applied.push(DomainEvent::PitchRecorded {
+            pitcher_id,
+            batter_id,
+            pitch: Pitch::HittedBy,
+        });
+    }
+    applied.push(DomainEvent::RunnerToFirst {
+        runner_id: batter_id,
+        runner_jersey_no: state.current_batter_jersey_no.unwrap_or(0),
+        runner_first_name: state
+            .current_batter_first_name
+            .as_deref()
+            .unwrap_or("-")
+            .to_string(),
+        runner_last_name: state
+            .current_batter_last_name
+            .as_deref()
+            .unwrap_or("-")
+            .to_string(),
+        batter_order,
+    });
+    applied.push(DomainEvent::CountReset);
+
+    let plate_appearance = PlateAppearance {
+        inning: state.inning,
+        half: state.half,
+        batter_id,
+        batter_order,
+        pitcher_id,
+        pitches: pitches_in_pa,
+        pitches_sequence: final_sequence,
+        outcome: outcome.clone(),
+        outs: state.outs,
+        runner_overrides: vec![],
+        double_play: None,
+    };
+
+    ApplyResult {
+        events: vec![UiEvent::Line(format!("{}: batter to 1B", outcome.label()))],
+        applied,
+        plate_appearance: Some(plate_appearance),
+        runner_movements: movements,
+        needs_next_at_bat: true,
+        ..Default::default()
+    }
+}
+
+// ─── Dropped third strike ─────────────────────────────────────────────────────
+
+/// Strike three not caught. The pitch and the strikeout are credited as on
+/// a regular swinging strikeout; the batter then either takes first (wild
+/// pitch / passed ball) or is retired by the throw. Bases are moved later
+/// by the reducer from the returned PA, runner overrides included.
+fn apply_dropped_third_strike(
+    state: &mut GameState,
+    how: DroppedThirdStrike,
+    runner_overrides: &[RunnerOverride],
+) -> ApplyResult {
+    let (batter_id, batter_order, pitcher_id) = require_batter!(state);
+
+    if let Err(msg) =
+        crate::engine::runners::validate_runner_overrides(state, batter_order, runner_overrides)
+    {
+        return ApplyResult {
+            events: vec![UiEvent::Error(msg)],
+            ..Default::default()
+        };
+    }
+
+    let reaches = how.batter_reaches();
+    // The batter takes first: a runner there is forced along, and no
+    // override may hold or send anybody to it.
+    if reaches && runner_overrides.iter().any(|o| o.dest == RunnerDest::First) {
+        return ApplyResult {
+            events: vec![UiEvent::Error(
+                "The batter takes 1B on a dropped third strike; no runner can end there."
+                    .to_string(),
+            )],
+            ..Default::default()
+        };
+    }
+
+    let pitch = Pitch::SwingingStrike;
+    let final_sequence =
+        build_pa_sequence_with_terminal_step(state, PlateAppearanceStep::Pitch(pitch.clone()));
+
+    let outs_after = if reaches {
+        state.outs
+    } else {
+        state.outs.saturating_add(1)
+    };
+
+    let mut applied = vec![
+        DomainEvent::PitchRecorded {
+            pitcher_id,
+            batter_id,
+            pitch,
+        },
+        DomainEvent::Strikeout {
+            batter_id,
+            kind: StrikeoutKind::Swinging,
+        },
+    ];
+    if !reaches {
+        applied.push(DomainEvent::OutRecorded(OutRecordedData {
+            outs_before: state.outs,
+            outs_after,
+        }));
+    }
+    applied.push(DomainEvent::CountReset);
+
+    let outcome = PlateAppearanceOutcome::DroppedThirdStrike(how.clone());
+    let message = match &how {
+        DroppedThirdStrike::ThrownOut { sequence } => {
+            format!("{} ({sequence})", outcome.display_label())
+        }
+        _ => format!("{}: batter to 1B", outcome.display_label()),
+    };
+
+    let plate_appearance = PlateAppearance {
+        inning: state.inning,
+        half: state.half,
+        batter_id,
+        batter_order,
+        pitcher_id,
+        pitches: final_sequence.len() as u32,
+        pitches_sequence: final_sequence,
+        outcome,
+        outs: outs_after,
+        runner_overrides: runner_overrides.to_vec(),
+        double_play: None,
+    };
+
+    ApplyResult {
+        events: vec![UiEvent::Line(message)],
+        applied,
+        plate_appearance: Some(plate_appearance),
+        needs_next_at_bat: true,
+        ..Default::default()
+    }
+}
+
 // ─── Hit commands ─────────────────────────────────────────────────────────────
 
+/// Close the PA on a ball the batter reached base on: a hit, or a
+/// reached-on-error (same shape: batter placement plus runner overrides).
+/// Bases are moved later by the reducer from the returned PA.
 fn apply_hit_command(
     state: &mut GameState,
     outcome: PlateAppearanceOutcome,
@@ -389,6 +656,12 @@ fn apply_hit_command(
         PlateAppearanceOutcome::Double { .. } => PlateAppearanceStep::Double,
         PlateAppearanceOutcome::Triple { .. } => PlateAppearanceStep::Triple,
         PlateAppearanceOutcome::HomeRun { .. } => PlateAppearanceStep::HomeRun,
+        PlateAppearanceOutcome::ReachedOnError {
+            fielder, throwing, ..
+        } => PlateAppearanceStep::ReachedOnError {
+            fielder: *fielder,
+            throwing: *throwing,
+        },
         _ => {
             return ApplyResult {
                 events: vec![UiEvent::Error(
@@ -424,12 +697,24 @@ fn apply_hit_command(
         outcome: outcome.clone(),
         outs: state.outs,
         runner_overrides: runner_overrides.to_vec(),
+        double_play: None,
     };
 
     let message = {
         let label = outcome.display_label();
         if let Some(z) = outcome.zone() {
             format!("{label} to {}", z.as_str())
+        } else if let PlateAppearanceOutcome::ReachedOnError {
+            fielder,
+            throwing,
+            reached_base,
+        } = &outcome
+        {
+            let error = crate::models::runner::FieldingError {
+                fielder: *fielder,
+                throwing: *throwing,
+            };
+            format!("{label} ({error}), batter to {reached_base}")
         } else {
             label.to_string()
         }
@@ -542,6 +827,7 @@ fn apply_steal(
         is_out: false,
         scored,
         is_earned: true,
+        fielders: None,
     };
 
     ApplyResult {
@@ -551,6 +837,281 @@ fn apply_steal(
     }
 }
 
+/// Runner advance on a wild pitch, passed ball or defensive indifference.
+///
+/// Unlike a steal the runner may take more than one base (`1 wp 3b`),
+/// but never stand still or move backwards. The movement row carries the
+/// reason as its `advancement_type`; a run scored on a passed ball is
+/// flagged unearned.
+fn apply_pitch_advance(
+    state: &mut GameState,
+    order: u8,
+    dest: RunnerDest,
+    reason: PitchAdvanceReason,
+) -> ApplyResult {
+    let Some(start) = state.base_of(order) else {
+        return ApplyResult {
+            events: vec![UiEvent::Error(format!(
+                "Runner {order} is not on base — cannot advance on a {}",
+                reason.label()
+            ))],
+            ..Default::default()
+        };
+    };
+    if dest.bases() <= start {
+        return ApplyResult {
+            events: vec![UiEvent::Error(format!(
+                "Runner {order} is on {start}B — {dest} is not an advance"
+            ))],
+            ..Default::default()
+        };
+    }
+    // The validator hands the advances over lead runner first, so a
+    // runner still in the way holds.
+    let occupant = |base: u8| match base {
+        1 => state.on_1b,
+        2 => state.on_2b,
+        _ => state.on_3b,
+    };
+    if let Some((base, blocker)) =
+        (start + 1..=dest.bases().min(3)).find_map(|base| occupant(base).map(|r| (base, r)))
+    {
+        return ApplyResult {
+            events: vec![UiEvent::Error(format!(
+                "Runner {order} cannot pass runner {blocker} on {base}B"
+            ))],
+            ..Default::default()
+        };
+    }
+
+    let (runner_id, first_name, last_name) = resolve_runner_identity(state, order);
+    let rm = move_runner_between_pitches(
+        state,
+        order,
+        start,
+        dest,
+        runner_id,
+        reason.advancement_type(),
+        reason.is_earned(),
+    );
+    let log_msg = format!(
+        "[{order}] {first_name} {last_name} to {} on a {}",
+        rm.end_base,
+        reason.label()
+    );
+
+    ApplyResult {
+        events: vec![UiEvent::Line(log_msg)],
+        runner_movements: vec![rm],
+        ..Default::default()
+    }
+}
+
+/// Balk: every runner moves up one base, lead runner first so that each
+/// row finds its destination already vacated on replay.
+fn apply_balk(state: &mut GameState) -> ApplyResult {
+    let runners: Vec<(u8, u8)> = [(3, state.on_3b), (2, state.on_2b), (1, state.on_1b)]
+        .into_iter()
+        .filter_map(|(base, runner)| runner.map(|order| (order, base)))
+        .collect();
+    if runners.is_empty() {
+        return ApplyResult {
+            events: vec![UiEvent::Error(
+                "Balk with the bases empty — no runner to advance".to_string(),
+            )],
+            ..Default::default()
+        };
+    }
+
+    let mut events = vec![UiEvent::Line("Balk: runners advance one base.".to_string())];
+    let mut runner_movements = Vec::with_capacity(runners.len());
+    for (order, start) in runners {
+        let dest = match start {
+            1 => RunnerDest::Second,
+            2 => RunnerDest::Third,
+            _ => RunnerDest::Score,
+        };
+        let (runner_id, first_name, last_name) = resolve_runner_identity(state, order);
+        let rm = move_runner_between_pitches(state, order, start, dest, runner_id, "balk", true);
+        events.push(UiEvent::Line(format!(
+            "[{order}] {first_name} {last_name} to {}",
+            rm.end_base
+        )));
+        runner_movements.push(rm);
+    }
+
+    ApplyResult {
+        events,
+        runner_movements,
+        ..Default::default()
+    }
+}
+
+/// Runner put out between pitches: caught stealing, picked off, or
+/// picked off and caught stealing.
+///
+/// The movement row carries the fielding chain so the putout and assists
+/// can be credited. The plate appearance in progress is untouched: the
+/// batter keeps the count, which the at-bat draft already holds. When the
+/// out is the third one the PA is abandoned instead — the batter will
+/// lead off the team's next inning, so the batting-order cursor is handed
+/// back to them before the engine changes side.
+fn apply_runner_caught(
+    state: &mut GameState,
+    order: u8,
+    kind: RunnerCaughtKind,
+    dest: Option<RunnerDest>,
+    fielders: &[u8],
+) -> ApplyResult {
+    let Some(start) = state.base_of(order) else {
+        return ApplyResult {
+            events: vec![UiEvent::Error(format!(
+                "Runner {order} is not on base — cannot be {}",
+                kind.label()
+            ))],
+            ..Default::default()
+        };
+    };
+    if let Some(dest) = dest
+        && dest.bases() <= start
+    {
+        return ApplyResult {
+            events: vec![UiEvent::Error(format!(
+                "Runner {order} is on {start}B — cannot be caught stealing {dest}"
+            ))],
+            ..Default::default()
+        };
+    }
+
+    let (runner_id, first_name, last_name) = resolve_runner_identity(state, order);
+    let sequence = fielders
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join("-");
+    let start_base: &'static str = match start {
+        1 => "1B",
+        2 => "2B",
+        _ => "3B",
+    };
+
+    match start {
+        1 => state.on_1b = None,
+        2 => state.on_2b = None,
+        _ => state.on_3b = None,
+    }
+
+    let outs_before = state.outs;
+    let outs_after = outs_before.saturating_add(1);
+
+    let target = dest.map(|d| format!(" {d}")).unwrap_or_default();
+    let mut events = vec![UiEvent::Line(format!(
+        "[{order}] {first_name} {last_name} {}{target} ({sequence})",
+        kind.label()
+    ))];
+
+    let inning_over = outs_after >= 3;
+    if inning_over && let Some(batter_order) = state.current_batter_order {
+        match state.half {
+            HalfInning::Top => state.away_next_batting_order = batter_order,
+            HalfInning::Bottom => state.home_next_batting_order = batter_order,
+        }
+        events.push(UiEvent::Line(format!(
+            "Third out on the bases: batter #{batter_order} leads off next inning."
+        )));
+    }
+
+    let rm = RunnerMovementInsert {
+        game_id: 0,
+        pa_seq: None,
+        game_event_id: None,
+        inning: state.inning,
+        half_inning: state.half.as_str().to_string(),
+        runner_id: if runner_id != 0 {
+            Some(runner_id)
+        } else {
+            None
+        },
+        batter_order: order,
+        start_base,
+        end_base: "OUT",
+        advancement_type: kind.advancement_type(),
+        is_out: true,
+        scored: false,
+        is_earned: true,
+        fielders: Some(sequence),
+    };
+
+    ApplyResult {
+        events,
+        applied: vec![DomainEvent::OutRecorded(OutRecordedData {
+            outs_before,
+            outs_after,
+        })],
+        runner_movements: vec![rm],
+        needs_next_at_bat: inning_over,
+        ..Default::default()
+    }
+}
+
+/// Move a runner from `start` to `dest` outside a plate appearance,
+/// crediting the run when `dest` is home, and build the matching
+/// movement row.
+fn move_runner_between_pitches(
+    state: &mut GameState,
+    order: u8,
+    start: u8,
+    dest: RunnerDest,
+    runner_id: i64,
+    advancement_type: &'static str,
+    is_earned: bool,
+) -> RunnerMovementInsert {
+    let start_base: &'static str = match start {
+        1 => "1B",
+        2 => "2B",
+        _ => "3B",
+    };
+    let end_base: &'static str = match dest {
+        RunnerDest::First => "1B",
+        RunnerDest::Second => "2B",
+        RunnerDest::Third => "3B",
+        RunnerDest::Score => "HOME",
+    };
+
+    match start {
+        1 => state.on_1b = None,
+        2 => state.on_2b = None,
+        _ => state.on_3b = None,
+    }
+    match dest {
+        RunnerDest::First => state.on_1b = Some(order),
+        RunnerDest::Second => state.on_2b = Some(order),
+        RunnerDest::Third => state.on_3b = Some(order),
+        RunnerDest::Score => add_runs_to_score(state, 1),
+    }
+
+    RunnerMovementInsert {
+        game_id: 0,
+        pa_seq: None,
+        game_event_id: None,
+        inning: state.inning,
+        half_inning: state.half.as_str().to_string(),
+        runner_id: if runner_id != 0 {
+            Some(runner_id)
+        } else {
+            None
+        },
+        batter_order: order,
+        start_base,
+        end_base,
+        advancement_type,
+        is_out: false,
+        scored: dest == RunnerDest::Score,
+        is_earned,
+        fielders: None,
+    }
+}
+
 /// Resolve (runner_id, first_name, last_name) from batting order slot.
 fn resolve_runner_identity(state: &GameState, order: u8) -> (i64, String, String) {
     if state.current_batter_order == Some(order) {
@@ -610,13 +1171,17 @@ fn apply_batter_out_command(
             outcome,
             outs,
             runner_overrides: vec![],
+            double_play: None,
         }
     };
 
     let (description, pa_outcome) = match &out_type {
         BatterOutType::UnassistedOut { fielder } => (
             format!("Batter #{} out unassisted by {}.", batter_order, fielder),
-            PlateAppearanceOutcome::UnassistedOut { fielder: *fielder },
+            PlateAppearanceOutcome::UnassistedOut {
+                fielder: *fielder,
+                sacrifice: false,
+            },
         ),
 
         BatterOutType::GroundOut { sequence } => (
@@ -627,6 +1192,7 @@ fn apply_batter_out_command(
             ),
             PlateAppearanceOutcome::GroundOut {
                 sequence: sequence.as_hyphenated_string(),
+                sacrifice: false,
             },
         ),
 
@@ -638,6 +1204,7 @@ fn apply_batter_out_command(
             PlateAppearanceOutcome::FlyOut {
                 fielder: *fielder,
                 in_foul_territory: false,
+                sacrifice: false,
             },
         ),
 
@@ -649,6 +1216,7 @@ fn apply_batter_out_command(
             PlateAppearanceOutcome::FlyOut {
                 fielder: *fielder,
                 in_foul_territory: true,
+                sacrifice: false,
             },
         ),
 
@@ -821,12 +1389,15 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
     let pitches_in_pa = final_sequence.len() as u32;
 
     let pa_outcome = if let Some((_, out_kind)) = batter_out {
+        let bunt = play.sacrifice == Some(Sacrifice::Bunt);
         match out_kind {
-            DefensiveOutKind::UnassistedOut { fielder } => {
-                PlateAppearanceOutcome::UnassistedOut { fielder: *fielder }
-            }
+            DefensiveOutKind::UnassistedOut { fielder } => PlateAppearanceOutcome::UnassistedOut {
+                fielder: *fielder,
+                sacrifice: bunt,
+            },
             DefensiveOutKind::GroundOut { sequence } => PlateAppearanceOutcome::GroundOut {
                 sequence: sequence.as_hyphenated_string(),
+                sacrifice: bunt,
             },
             DefensiveOutKind::FlyOut {
                 fielder,
@@ -834,6 +1405,7 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
             } => PlateAppearanceOutcome::FlyOut {
                 fielder: *fielder,
                 in_foul_territory: *in_foul_territory,
+                sacrifice: play.sacrifice == Some(Sacrifice::Fly),
             },
             DefensiveOutKind::LineOut { fielder } => {
                 PlateAppearanceOutcome::LineOut { fielder: *fielder }
@@ -865,12 +1437,15 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
             }
         };
 
-        let advancement_type = match out_kind {
-            DefensiveOutKind::UnassistedOut { .. } => "unassisted_out",
-            DefensiveOutKind::GroundOut { .. } => "ground_out",
-            DefensiveOutKind::FlyOut { .. } => "fly_out",
-            DefensiveOutKind::LineOut { .. } => "line_out",
-            DefensiveOutKind::InfieldFly { .. } => "infield_fly",
+        let advancement_type = match (target, play.sacrifice, out_kind) {
+            (DefensivePlayTarget::Batter, Some(sacrifice), _) => {
+                sacrifice_advancement_type(sacrifice)
+            }
+            (_, _, DefensiveOutKind::UnassistedOut { .. }) => "unassisted_out",
+            (_, _, DefensiveOutKind::GroundOut { .. }) => "ground_out",
+            (_, _, DefensiveOutKind::FlyOut { .. }) => "fly_out",
+            (_, _, DefensiveOutKind::LineOut { .. }) => "line_out",
+            (_, _, DefensiveOutKind::InfieldFly { .. }) => "infield_fly",
         };
 
         runner_movements.push(RunnerMovementInsert {
@@ -887,6 +1462,14 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
             is_out: true,
             scored: false,
             is_earned: true,
+            fielders: Some(
+                out_kind
+                    .fielders()
+                    .iter()
+                    .map(u8::to_string)
+                    .collect::<Vec<_>>()
+                    .join("-"),
+            ),
         });
     }
 
@@ -912,7 +1495,71 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
             is_out: false,
             scored: matches!(reached_base, RunnerDest::Score),
             is_earned: true,
+            fielders: None,
+        });
+    }
+
+    // ─── Runners moving up on the out ───────────────────────────────────────
+    //
+    // Runners retired on this play are left alone. Everybody else follows
+    // their override, or the default: on a sacrifice fly only the runner
+    // on third scores, on a bunt every runner moves up one base, on any
+    // other play runners hold. Rows are typed after the batter's out or
+    // fielder's choice (`sacrifice_fly`, `ground_out`, ...) so replay
+    // treats them as part of the composite play.
+    let mut moved_runners: Vec<(u8, RunnerDest)> = Vec::new();
+    let advancement_type = match (play.sacrifice, batter_out) {
+        (Some(sacrifice), _) => sacrifice_advancement_type(sacrifice),
+        (None, Some((_, DefensiveOutKind::UnassistedOut { .. }))) => "unassisted_out",
+        (None, Some((_, DefensiveOutKind::GroundOut { .. }))) => "ground_out",
+        (None, Some((_, DefensiveOutKind::FlyOut { .. }))) => "fly_out",
+        (None, Some((_, DefensiveOutKind::LineOut { .. }))) => "line_out",
+        (None, Some((_, DefensiveOutKind::InfieldFly { .. }))) => "infield_fly",
+        (None, None) => "fielders_choice",
+    };
+    let retired = |order: u8| {
+        normalized_outs
+            .iter()
+            .any(|(target, _)| *target == DefensivePlayTarget::Runner(order))
+    };
+    for (occupant, base) in [(state.on_3b, 3u8), (state.on_2b, 2), (state.on_1b, 1)] {
+        let Some(order) = occupant else {
+            continue;
+        };
+        if retired(order) {
+            continue;
+        }
+        let dest = match play.runner_advances.iter().find(|o| o.order == order) {
+            Some(o) => o.dest,
+            None => match (play.sacrifice, base) {
+                (None, _) => continue,
+                (Some(_), 3) => RunnerDest::Score,
+                (Some(Sacrifice::Fly), _) => continue,
+                (Some(Sacrifice::Bunt), 2) => RunnerDest::Third,
+                (Some(Sacrifice::Bunt), _) => RunnerDest::Second,
+            },
+        };
+        if dest.bases() == base {
+            continue;
+        }
+
+        runner_movements.push(RunnerMovementInsert {
+            game_id: 0,
+            pa_seq: None,
+            game_event_id: None,
+            inning: state.inning,
+            half_inning: half_str.to_string(),
+            runner_id: None,
+            batter_order: order,
+            start_base: runner_start_base_label(state, order),
+            end_base: runner_dest_to_base_label(dest),
+            advancement_type,
+            is_out: false,
+            scored: matches!(dest, RunnerDest::Score),
+            is_earned: true,
+            fielders: None,
         });
+        moved_runners.push((order, dest));
     }
 
     // ─── Apply the composite play to the in-memory state ────────────────────
@@ -940,6 +1587,10 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
             clear_runner_from_bases(state, *order);
         }
     }
+    // Lead runner first, so nobody lands on a base that is still occupied.
+    for (order, dest) in &moved_runners {
+        place_runner_on_base(state, *order, *dest);
+    }
     for (target, _fielder, reached_base) in &normalized_fc {
         match target {
             DefensivePlayTarget::Runner(order) => {
@@ -954,6 +1605,10 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
     let mut events_ui: Vec<UiEvent> = Vec::new();
 
     for (target, out_kind) in &normalized_outs {
+        if let (DefensivePlayTarget::Batter, Some(sacrifice)) = (target, play.sacrifice) {
+            events_ui.push(UiEvent::Line(sacrifice_line(sacrifice, out_kind)));
+            continue;
+        }
         let line = match (target, out_kind) {
             (DefensivePlayTarget::Batter, DefensiveOutKind::UnassistedOut { fielder }) => {
                 format!("Batter out unassisted by {}.", fielder)
@@ -1017,6 +1672,20 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
         events_ui.push(UiEvent::Line(line));
     }
 
+    for (order, dest) in &moved_runners {
+        let on_play = if play.sacrifice.is_some() {
+            " on the sacrifice"
+        } else {
+            ""
+        };
+        events_ui.push(UiEvent::Line(format!(
+            "Runner #{} to {}{}.",
+            order,
+            runner_dest_to_base_label(*dest),
+            on_play
+        )));
+    }
+
     for (target, fielder, reached_base) in &normalized_fc {
         let line = match target {
             DefensivePlayTarget::Batter => format!(
@@ -1046,6 +1715,11 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
 
     applied.push(DomainEvent::CountReset);
 
+    let double_play = composite_double_play(&normalized_outs, &normalized_fc);
+    if let Some(dp) = &double_play {
+        events_ui.push(UiEvent::Line(format!("Double play: {dp}.")));
+    }
+
     let plate_appearance = PlateAppearance {
         inning: state.inning,
         half: state.half,
@@ -1056,7 +1730,10 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
         pitches_sequence: final_sequence,
         outcome: pa_outcome,
         outs: outs_after,
-        runner_overrides: vec![],
+        // Kept for the errors they may carry; the movements themselves
+        // are persisted as composite rows above.
+        runner_overrides: play.runner_advances.clone(),
+        double_play,
     };
 
     ApplyResult {
@@ -1071,6 +1748,83 @@ fn apply_defensive_play_command(state: &mut GameState, play: DefensivePlayComman
     }
 }
 
+/// DP/TP marker for a composite play retiring two or more players. The
+/// batter's ball decides the kind (a fielder's choice counts as a ground
+/// ball); fielders are listed in the order the scorer typed the outs,
+/// after the fielder who made the choice.
+fn composite_double_play(
+    outs: &[(DefensivePlayTarget, DefensiveOutKind)],
+    fc: &[(DefensivePlayTarget, u8, RunnerDest)],
+) -> Option<DoublePlay> {
+    if outs.len() < 2 {
+        return None;
+    }
+
+    let batter_out = outs
+        .iter()
+        .find(|(target, _)| matches!(target, DefensivePlayTarget::Batter));
+    let kind = match batter_out.map(|(_, kind)| kind) {
+        Some(DefensiveOutKind::LineOut { .. }) => DoublePlayKind::LineDrive,
+        Some(DefensiveOutKind::FlyOut { .. } | DefensiveOutKind::InfieldFly { .. }) => {
+            DoublePlayKind::FlyBall
+        }
+        Some(DefensiveOutKind::GroundOut { .. } | DefensiveOutKind::UnassistedOut { .. })
+        | None => DoublePlayKind::GroundBall,
+    };
+
+    let chooser = fc
+        .iter()
+        .filter(|(target, _, _)| matches!(target, DefensivePlayTarget::Batter))
+        .map(|(_, fielder, _)| *fielder);
+    let out_fielders = outs.iter().flat_map(|(_, kind)| kind.fielders());
+
+    Some(DoublePlay::new(
+        kind,
+        outs.len() as u8,
+        chooser.chain(out_fielders),
+    ))
+}
+
+/// DP marker for a strikeout followed, on the same line, by the runner
+/// caught stealing or picked off (`s, 5 cs 2b 26`): the catcher's putout
+/// on strike three, then the chain that retired the runner.
+pub(crate) fn strikeout_throw_out_double_play(runner_out: &RunnerMovementInsert) -> DoublePlay {
+    let chain = runner_out
+        .fielders
+        .as_deref()
+        .unwrap_or_default()
+        .split('-')
+        .filter_map(|f| f.parse::<u8>().ok());
+    DoublePlay::new(
+        DoublePlayKind::StrikeoutThrowOut,
+        2,
+        std::iter::once(2).chain(chain),
+    )
+}
+
+fn sacrifice_advancement_type(sacrifice: Sacrifice) -> &'static str {
+    match sacrifice {
+        Sacrifice::Fly => "sacrifice_fly",
+        Sacrifice::Bunt => "sacrifice_bunt",
+    }
+}
+
+fn sacrifice_line(sacrifice: Sacrifice, out_kind: &DefensiveOutKind) -> String {
+    match (sacrifice, out_kind) {
+        (Sacrifice::Fly, DefensiveOutKind::FlyOut { fielder, .. }) => {
+            format!("Sacrifice fly to F{}.", fielder)
+        }
+        (Sacrifice::Bunt, DefensiveOutKind::GroundOut { sequence }) => {
+            format!("Sacrifice bunt, out {}.", sequence.as_hyphenated_string())
+        }
+        (Sacrifice::Bunt, DefensiveOutKind::UnassistedOut { fielder }) => {
+            format!("Sacrifice bunt, out unassisted by {}.", fielder)
+        }
+        (Sacrifice::Fly, _) => "Sacrifice fly.".to_string(),
+        (Sacrifice::Bunt, _) => "Sacrifice bunt.".to_string(),
+    }
+}
+
 fn runner_dest_to_base_label(dest: RunnerDest) -> &'static str {
     match dest {
         RunnerDest::First => "1B",
@@ -1193,7 +1947,9 @@ fn place_runner_on_base(state: &mut GameState, order: u8, dest: RunnerDest) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::field_zone::FieldZone;
     use crate::models::game_state::GameState;
+    use crate::models::plate_appearance::HitModifier;
     use crate::models::runner::RunnerDest;
     use crate::models::types::HalfInning;
 
@@ -1330,6 +2086,8 @@ mod tests {
                 fielder: 6,
                 reached_base: RunnerDest::First,
             }],
+            sacrifice: None,
+            runner_advances: vec![],
         }
     }
 
@@ -1357,6 +2115,8 @@ mod tests {
                 },
             ],
             safe_advances: vec![],
+            sacrifice: None,
+            runner_advances: vec![],
         }
     }
 
@@ -1439,6 +2199,8 @@ mod tests {
                     reached_base: RunnerDest::First,
                 },
             ],
+            sacrifice: None,
+            runner_advances: vec![],
         };
 
         // This play has no outs; apply_defensive_play_command requires
@@ -1469,6 +2231,8 @@ mod tests {
                     },
                 }],
                 safe_advances: vec![],
+                sacrifice: None,
+                runner_advances: vec![],
             },
         );
 
@@ -1483,4 +2247,670 @@ mod tests {
         let out_data = out_event.expect("OutRecorded event present");
         assert_eq!(out_data.outs_after - out_data.outs_before, 1);
     }
+
+    // ─── Fielding errors ─────────────────────────────────────────────────
+
+    use crate::engine::reducer::{apply_live_plate_appearance, apply_plate_appearance};
+    use crate::models::runner::FieldingError;
+
+    #[test]
+    fn reached_on_error_places_batter_and_charges_fielding_team() {
+        // Top of the 2nd, runner #4 on 2B, batter #5 reaches on E6.
+        let mut state = fixture_state(5, 2, HalfInning::Top);
+        state.on_2b = Some(4);
+
+        let result = apply_engine_command(
+            &mut state,
+            EngineCommand::ReachedOnError {
+                error: FieldingError {
+                    fielder: 6,
+                    throwing: false,
+                },
+                reached_base: RunnerDest::First,
+                runner_overrides: vec![],
+            },
+        );
+        let pa = result.plate_appearance.expect("PA closed");
+        assert!(result.needs_next_at_bat);
+
+        let movements = apply_live_plate_appearance(&mut state, &pa);
+
+        assert_eq!(state.on_1b, Some(5));
+        assert_eq!(state.on_2b, Some(4), "an unforced runner holds");
+        assert_eq!(
+            state.score.home_errors, 1,
+            "home team fields in the top half"
+        );
+        assert_eq!(state.score.away_errors, 0);
+        assert_eq!(state.score.away_hits, 0, "no hit credited");
+        assert!(
+            movements
+                .iter()
+                .any(|m| m.start_base == "BAT" && m.advancement_type == "error")
+        );
+    }
+
+    /// The plate appearance of a reached-on-error `line`, not yet applied
+    /// to the bases.
+    fn reached_on_error(state: &mut GameState, line: &str) -> PlateAppearance {
+        let mut cmds = crate::engine::commands::parser::parse_engine_commands(line, state)
+            .expect("valid line");
+        assert_eq!(cmds.len(), 1);
+        apply_engine_command(state, cmds.remove(0))
+            .plate_appearance
+            .expect("PA closed")
+    }
+
+    #[test]
+    fn reached_on_error_moves_only_the_forced_runners() {
+        // Runners on first and third: #3 is forced to second, #4 holds.
+        let mut live = fixture_state(5, 2, HalfInning::Top);
+        live.on_1b = Some(3);
+        live.on_3b = Some(4);
+        let mut replay = live.clone();
+
+        let pa = reached_on_error(&mut live, "e6");
+        let movements = apply_live_plate_appearance(&mut live, &pa);
+        apply_plate_appearance(&mut replay, &pa);
+
+        for state in [&live, &replay] {
+            assert_eq!(
+                (state.on_1b, state.on_2b, state.on_3b),
+                (Some(5), Some(3), Some(4))
+            );
+            assert_eq!(state.score.away, 0, "nobody scores on e6");
+        }
+        let moved: Vec<(u8, &str, &str, &str)> = movements
+            .iter()
+            .map(|m| (m.batter_order, m.start_base, m.end_base, m.advancement_type))
+            .collect();
+        assert_eq!(moved, [(3, "1B", "2B", "error"), (5, "BAT", "1B", "error")]);
+
+        // Reaching second pushes the runner on first to third.
+        let mut state = fixture_state(6, 2, HalfInning::Top);
+        state.on_1b = Some(5);
+        let pa = reached_on_error(&mut state, "te6 2b");
+        let movements = apply_live_plate_appearance(&mut state, &pa);
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (None, Some(6), Some(5))
+        );
+        assert!(movements.iter().all(|m| m.advancement_type == "error"));
+    }
+
+    #[test]
+    fn runner_sent_on_a_reached_on_error_moves_on_the_play() {
+        let mut state = fixture_state(5, 2, HalfInning::Top);
+        state.on_3b = Some(4);
+
+        let pa = reached_on_error(&mut state, "e6, 4 sc");
+        let movements = apply_live_plate_appearance(&mut state, &pa);
+
+        assert_eq!(state.score.away, 1);
+        let scored = movements.iter().find(|m| m.scored).expect("run");
+        assert_eq!(scored.advancement_type, "hit_override");
+    }
+
+    #[test]
+    fn runner_error_on_hit_is_charged_and_survives_replay() {
+        // Bottom of the 1st: runner #2 on 1B, batter #3 singles and
+        // runner #2 takes third on a fielding error by the left fielder.
+        let overrides = vec![RunnerOverride {
+            order: 2,
+            dest: RunnerDest::Third,
+            error: Some(FieldingError {
+                fielder: 7,
+                throwing: false,
+            }),
+        }];
+
+        let mut live = fixture_state(3, 1, HalfInning::Bottom);
+        live.on_1b = Some(2);
+        let mut replay = live.clone();
+
+        let result = apply_engine_command(
+            &mut live,
+            EngineCommand::Single {
+                zone: None,
+                modifier: None,
+                runner_overrides: overrides,
+            },
+        );
+        let pa = result.plate_appearance.expect("PA closed");
+        let movements = apply_live_plate_appearance(&mut live, &pa);
+
+        assert_eq!(live.on_1b, Some(3));
+        assert_eq!(live.on_3b, Some(2));
+        assert_eq!(
+            live.score.away_errors, 1,
+            "away team fields in the bottom half"
+        );
+        assert_eq!(live.score.home_hits, 1);
+        assert!(
+            movements
+                .iter()
+                .any(|m| m.batter_order == 2 && m.advancement_type == "error")
+        );
+
+        apply_plate_appearance(&mut replay, &pa);
+        assert_eq!(replay.score.away_errors, live.score.away_errors);
+        assert_eq!(replay.on_3b, live.on_3b);
+    }
+
+    // ─── Base awards ─────────────────────────────────────────────────────
+
+    /// Drive a command through the same steps as the live engine loop:
+    /// applied events first, then the compact PA.
+    fn run_live(state: &mut GameState, cmd: EngineCommand) -> ApplyResult {
+        let result = apply_engine_command(state, cmd);
+        for ev in &result.applied {
+            crate::engine::reducer::apply_domain_event(state, ev);
+        }
+        if let Some(pa) = &result.plate_appearance {
+            apply_live_plate_appearance(state, pa);
+        }
+        result
+    }
+
+    #[test]
+    fn hit_by_pitch_with_bases_loaded_forces_in_a_run() {
+        let mut state = fixture_state(5, 1, HalfInning::Top);
+        state.on_1b = Some(4);
+        state.on_2b = Some(3);
+        state.on_3b = Some(2);
+        let mut replay = state.clone();
+
+        let result = run_live(&mut state, EngineCommand::HitByPitch);
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert!(matches!(pa.outcome, PlateAppearanceOutcome::HitByPitch));
+        assert!(!pa.outcome.is_at_bat());
+        assert_eq!(pa.pitches, 1);
+        assert_eq!(state.score.away, 1);
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (Some(5), Some(4), Some(3))
+        );
+        assert_eq!(state.pitcher_stats[&9000].balls, 1);
+        assert!(
+            result
+                .runner_movements
+                .iter()
+                .all(|m| m.advancement_type == "hit_by_pitch")
+        );
+        assert_eq!(result.runner_movements.len(), 4);
+
+        apply_plate_appearance(&mut replay, pa);
+        assert_eq!(replay.score.away, state.score.away);
+        assert_eq!(replay.on_1b, state.on_1b);
+        assert_eq!(replay.pitcher_stats[&9000].balls, 1);
+    }
+
+    #[test]
+    fn intentional_walk_needs_no_pitch_and_only_forced_runners_move() {
+        let mut state = fixture_state(5, 1, HalfInning::Bottom);
+        state.on_2b = Some(4);
+
+        let result = run_live(&mut state, EngineCommand::IntentionalWalk);
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert_eq!(pa.pitches, 0);
+        assert_eq!(state.on_1b, Some(5));
+        assert_eq!(state.on_2b, Some(4), "unforced runner stays put");
+        assert_eq!(
+            state
+                .pitcher_stats
+                .get(&9000)
+                .map_or(0, |s| s.balls + s.strikes),
+            0
+        );
+    }
+
+    // ─── Sacrifices ──────────────────────────────────────────────────────
+
+    fn run_line(state: &mut GameState, line: &str) -> ApplyResult {
+        let mut cmds = crate::engine::commands::parser::parse_engine_commands(line, state)
+            .expect("valid line");
+        assert_eq!(cmds.len(), 1);
+        run_live(state, cmds.remove(0))
+    }
+
+    #[test]
+    fn sacrifice_fly_scores_the_runner_from_third() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_1b = Some(5);
+        state.on_3b = Some(4);
+
+        let result = run_line(&mut state, "sf8");
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert!(pa.outcome.is_sacrifice_fly());
+        assert!(!pa.outcome.is_at_bat());
+        assert!(pa.outcome.counts_for_obp());
+        assert_eq!(pa.outcome.label(), "SF");
+        assert_eq!(state.score.away, 1);
+        assert_eq!(state.outs, 1);
+        assert_eq!((state.on_1b, state.on_3b), (Some(5), None));
+
+        // Every row belongs to the composite play so replay re-applies it.
+        let scored = result
+            .runner_movements
+            .iter()
+            .find(|m| m.batter_order == 4)
+            .expect("tag-up row");
+        assert_eq!((scored.start_base, scored.end_base), ("3B", "HOME"));
+        assert!(scored.scored);
+        assert!(
+            result
+                .runner_movements
+                .iter()
+                .all(|m| m.advancement_type == "sacrifice_fly")
+        );
+        assert_eq!(result.runner_movements.len(), 2, "batter out + tag-up");
+    }
+
+    #[test]
+    fn sacrifice_bunt_moves_every_runner_up_unless_overridden() {
+        let mut state = fixture_state(9, 2, HalfInning::Bottom);
+        state.on_1b = Some(8);
+        state.on_2b = Some(7);
+
+        let result = run_line(&mut state, "sh13, 8 1b");
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert!(pa.outcome.is_sacrifice_bunt());
+        assert!(!pa.outcome.counts_for_obp());
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (Some(8), None, Some(7)),
+            "runner #8 held, runner #7 moved up"
+        );
+        assert_eq!(state.outs, 1);
+    }
+
+    #[test]
+    fn ground_out_moves_only_the_named_runners() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_1b = Some(5);
+        state.on_2b = Some(4);
+
+        let result = run_line(&mut state, "63, 4 3b");
+
+        assert_eq!(state.outs, 1);
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (Some(5), None, Some(4)),
+            "runner #4 moved up, runner #5 held"
+        );
+        let advance = result
+            .runner_movements
+            .iter()
+            .find(|m| m.batter_order == 4)
+            .expect("advance row");
+        assert_eq!((advance.start_base, advance.end_base), ("2B", "3B"));
+        assert_eq!(advance.advancement_type, "ground_out");
+        assert_eq!(result.runner_movements.len(), 2, "batter out + advance");
+    }
+
+    #[test]
+    fn runner_scores_on_a_force_play() {
+        let mut state = fixture_state(5, 4, HalfInning::Bottom);
+        state.on_1b = Some(3);
+        state.on_3b = Some(4);
+
+        let result = run_line(&mut state, "o6 1b, 3 64, 4 sc");
+
+        assert_eq!(state.score.home, 1);
+        assert_eq!(state.outs, 1);
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (Some(5), None, None)
+        );
+        let scored = result
+            .runner_movements
+            .iter()
+            .find(|m| m.batter_order == 4)
+            .expect("scoring row");
+        assert_eq!(scored.advancement_type, "fielders_choice");
+        assert!(scored.scored);
+    }
+
+    #[test]
+    fn wild_pitch_scores_with_a_pitch_on_the_same_line() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_3b = Some(4);
+
+        let cmds = crate::engine::commands::parser::parse_engine_commands("b, 4 wp sc", &state)
+            .expect("valid line");
+        assert_eq!(cmds.len(), 2);
+        let mut movements = vec![];
+        for cmd in cmds {
+            movements.extend(run_live(&mut state, cmd).runner_movements);
+        }
+
+        assert_eq!(state.score.away, 1);
+        assert_eq!(state.on_3b, None);
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].advancement_type, "wild_pitch");
+        assert!(movements[0].scored && movements[0].is_earned);
+    }
+
+    #[test]
+    fn passed_ball_run_is_unearned() {
+        let mut state = fixture_state(6, 3, HalfInning::Bottom);
+        state.on_2b = Some(4);
+
+        let result = run_line(&mut state, "4 pb sc");
+        let rm = &result.runner_movements[0];
+        assert_eq!((rm.start_base, rm.end_base), ("2B", "HOME"));
+        assert_eq!(rm.advancement_type, "passed_ball");
+        assert!(!rm.is_earned);
+        assert_eq!(state.score.home, 1);
+
+        // Runners never move backwards or stand still.
+        state.on_3b = Some(5);
+        let result = run_line(&mut state, "5 di 2b");
+        assert!(matches!(result.events[0], UiEvent::Error(_)));
+        assert_eq!(state.on_3b, Some(5));
+    }
+
+    #[test]
+    fn pitch_advances_clear_the_bases_from_the_lead_runner_back() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_2b = Some(2);
+        state.on_3b = Some(1);
+
+        let cmds =
+            crate::engine::commands::parser::parse_engine_commands("2 wp 3b, 1 wp sc", &state)
+                .expect("valid line");
+        let mut movements = vec![];
+        for cmd in cmds {
+            let result = run_live(&mut state, cmd);
+            assert!(!result.events.iter().any(|e| matches!(e, UiEvent::Error(_))));
+            movements.extend(result.runner_movements);
+        }
+
+        assert_eq!(state.score.away, 1);
+        assert_eq!((state.on_2b, state.on_3b), (None, Some(2)));
+        let moved: Vec<(u8, &str)> = movements
+            .iter()
+            .map(|m| (m.batter_order, m.end_base))
+            .collect();
+        assert_eq!(moved, [(1, "HOME"), (2, "3B")]);
+    }
+
+    #[test]
+    fn pitch_advance_never_lands_on_an_occupied_base() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_2b = Some(2);
+        state.on_3b = Some(1);
+
+        let result = apply_pitch_advance(
+            &mut state,
+            2,
+            RunnerDest::Third,
+            PitchAdvanceReason::PassedBall,
+        );
+        assert!(matches!(result.events[0], UiEvent::Error(_)));
+        let result = apply_pitch_advance(
+            &mut state,
+            2,
+            RunnerDest::Score,
+            PitchAdvanceReason::WildPitch,
+        );
+        assert!(matches!(result.events[0], UiEvent::Error(_)));
+        assert!(result.runner_movements.is_empty());
+        assert_eq!((state.on_2b, state.on_3b), (Some(2), Some(1)));
+        assert_eq!(state.score.away, 0);
+    }
+
+    #[test]
+    fn balk_moves_every_runner_up_one_base() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_1b = Some(5);
+        state.on_3b = Some(4);
+
+        let result = run_line(&mut state, "bk");
+        let bases: Vec<_> = result
+            .runner_movements
+            .iter()
+            .map(|m| (m.batter_order, m.start_base, m.end_base))
+            .collect();
+        assert_eq!(bases, vec![(4, "3B", "HOME"), (5, "1B", "2B")]);
+        assert_eq!(
+            (state.on_1b, state.on_2b, state.on_3b),
+            (None, Some(5), None)
+        );
+        assert_eq!(state.score.away, 1);
+
+        let mut empty = fixture_state(6, 3, HalfInning::Top);
+        let result = run_line(&mut empty, "bk");
+        assert!(matches!(result.events[0], UiEvent::Error(_)));
+    }
+
+    #[test]
+    fn caught_stealing_records_the_out_and_keeps_the_count() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_1b = Some(5);
+        state.pitch_count.balls = 2;
+        state.pitch_count.strikes = 1;
+
+        let result = run_line(&mut state, "5 cs 2b 26");
+        let rm = &result.runner_movements[0];
+        assert_eq!((rm.start_base, rm.end_base), ("1B", "OUT"));
+        assert_eq!(rm.advancement_type, "caught_stealing");
+        assert_eq!(rm.fielders.as_deref(), Some("2-6"));
+        assert!(rm.is_out);
+        assert!(!result.needs_next_at_bat);
+        assert!(result.plate_appearance.is_none());
+
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.on_1b, None);
+        assert_eq!((state.pitch_count.balls, state.pitch_count.strikes), (2, 1));
+        assert_eq!(state.current_batter_order, Some(6));
+    }
+
+    #[test]
+    fn third_out_on_a_pickoff_hands_the_batter_back_for_next_inning() {
+        let mut state = fixture_state(6, 3, HalfInning::Bottom);
+        state.outs = 2;
+        state.on_2b = Some(4);
+        state.home_next_batting_order = 7;
+
+        let result = run_line(&mut state, "4 po 16");
+        assert_eq!(result.runner_movements[0].advancement_type, "pickoff");
+        assert_eq!(result.runner_movements[0].start_base, "2B");
+        assert!(result.needs_next_at_bat, "the engine must change side");
+        assert_eq!(state.outs, 3);
+        assert_eq!(state.home_next_batting_order, 6);
+    }
+
+    #[test]
+    fn caught_stealing_must_target_a_base_ahead() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_2b = Some(5);
+
+        let result = run_line(&mut state, "5 pocs 2b 14");
+        assert!(matches!(result.events[0], UiEvent::Error(_)));
+        assert_eq!((state.on_2b, state.outs), (Some(5), 0));
+    }
+
+    // ─── Dropped third strike ────────────────────────────────────────────
+
+    #[test]
+    fn dropped_third_strike_on_a_passed_ball_puts_the_batter_on_first() {
+        let mut state = fixture_state(6, 4, HalfInning::Top);
+        state.pitch_count.strikes = 2;
+        state.pitch_count.sequence = vec![Pitch::CalledStrike, Pitch::SwingingStrike];
+        state.on_3b = Some(4);
+        let mut replay = state.clone();
+
+        let mut cmds =
+            crate::engine::commands::parser::parse_engine_commands("kpb, 4 sc", &state).unwrap();
+        let result = apply_engine_command(&mut state, cmds.remove(0));
+        for ev in &result.applied {
+            crate::engine::reducer::apply_domain_event(&mut state, ev);
+        }
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+        let movements = apply_live_plate_appearance(&mut state, pa);
+
+        assert!(pa.outcome.is_strikeout());
+        assert!(pa.outcome.is_at_bat());
+        assert_eq!(pa.outcome.label(), "K+PB");
+        assert_eq!(pa.pitches, 3);
+        assert_eq!(state.outs, 0);
+        assert_eq!((state.on_1b, state.on_3b), (Some(6), None));
+        assert_eq!(state.score.away, 1);
+        assert_eq!(state.pitcher_stats[&9000].strikes, 1);
+
+        let batter = movements.iter().find(|m| m.start_base == "BAT").unwrap();
+        assert_eq!(batter.advancement_type, "dropped_third_strike");
+        assert!(!batter.is_earned, "reached on a passed ball");
+        assert_eq!(movements.len(), 2);
+
+        apply_plate_appearance(&mut replay, pa);
+        assert_eq!(replay.score.away, state.score.away);
+        assert_eq!((replay.on_1b, replay.on_3b), (state.on_1b, state.on_3b));
+        assert_eq!(replay.outs, 0);
+    }
+
+    #[test]
+    fn dropped_third_strike_with_a_throw_retires_the_batter() {
+        let mut state = fixture_state(6, 4, HalfInning::Bottom);
+        state.pitch_count.strikes = 2;
+        state.outs = 1;
+        state.on_2b = Some(5);
+
+        let result = run_line(&mut state, "k23");
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert!(matches!(
+            &pa.outcome,
+            PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { sequence })
+                if sequence == "2-3"
+        ));
+        assert_eq!(pa.outs, 2);
+        assert_eq!(state.outs, 2);
+        assert_eq!((state.on_1b, state.on_2b), (None, Some(5)));
+    }
+
+    #[test]
+    fn dropped_third_strike_with_two_outs_forces_the_runner_on_first() {
+        let mut state = fixture_state(6, 4, HalfInning::Top);
+        state.pitch_count.strikes = 2;
+        state.outs = 2;
+        state.on_1b = Some(5);
+
+        run_line(&mut state, "kwp");
+        assert_eq!((state.on_1b, state.on_2b), (Some(6), Some(5)));
+        assert_eq!(state.outs, 2);
+    }
+
+    // ─── Double and triple plays ─────────────────────────────────────────
+
+    #[test]
+    fn ground_ball_double_play_is_credited_on_the_pa() {
+        let mut state = fixture_state(6, 2, HalfInning::Top);
+        state.on_1b = Some(5);
+
+        let result = run_line(&mut state, "5 64, 63");
+        let dp = result
+            .plate_appearance
+            .as_ref()
+            .and_then(|pa| pa.double_play.clone())
+            .expect("DP marker");
+
+        assert_eq!(dp.kind, DoublePlayKind::GroundBall);
+        assert_eq!(dp.label(), "GDP");
+        assert_eq!(dp.fielders, vec![6, 4, 3]);
+        assert!(dp.involves(4) && !dp.involves(5));
+        assert_eq!(dp.to_string(), "GDP (6-4-3)");
+    }
+
+    #[test]
+    fn line_drive_triple_play_lists_each_fielder_once() {
+        let mut state = fixture_state(5, 1, HalfInning::Bottom);
+        state.on_1b = Some(3);
+        state.on_2b = Some(4);
+
+        let result = apply_defensive_play_command(&mut state, play_triple_play());
+        let dp = result
+            .plate_appearance
+            .and_then(|pa| pa.double_play)
+            .expect("TP marker");
+
+        assert!(dp.is_triple_play());
+        assert_eq!(dp.label(), "LTP");
+        assert_eq!(dp.fielders, vec![6, 4, 3]);
+    }
+
+    #[test]
+    fn single_out_and_fielders_choice_carry_no_double_play() {
+        let mut state = fixture_state(1, 3, HalfInning::Top);
+        state.on_1b = Some(9);
+
+        let result = apply_defensive_play_command(&mut state, play_runner_out_plus_batter_fc());
+        assert_eq!(result.plate_appearance.unwrap().double_play, None);
+    }
+
+    #[test]
+    fn strikeout_throw_out_credits_the_catcher_first() {
+        let mut state = fixture_state(6, 3, HalfInning::Top);
+        state.on_1b = Some(5);
+
+        let result = run_line(&mut state, "5 cs 2b 26");
+        let dp = strikeout_throw_out_double_play(&result.runner_movements[0]);
+
+        assert_eq!(dp.label(), "KS-CS DP");
+        assert_eq!(dp.fielders, vec![2, 6]);
+    }
+
+    // ─── Hit modifiers ───────────────────────────────────────────────────
+
+    #[test]
+    fn ground_rule_double_awards_every_runner_two_bases() {
+        let mut state = fixture_state(6, 4, HalfInning::Top);
+        state.on_1b = Some(5);
+        state.on_2b = Some(4);
+
+        // An override slipped past the validator is ignored all the same.
+        let result = run_live(
+            &mut state,
+            EngineCommand::Double {
+                zone: Some(FieldZone::LF),
+                modifier: Some(HitModifier::GroundRule),
+                runner_overrides: vec![RunnerOverride {
+                    order: 5,
+                    dest: RunnerDest::Score,
+                    error: None,
+                }],
+            },
+        );
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+
+        assert_eq!(pa.outcome.hit_modifier(), Some(HitModifier::GroundRule));
+        assert_eq!(pa.outcome.display_label(), "Ground-rule double");
+        assert_eq!(state.score.away, 1, "only the runner from second scores");
+        assert_eq!((state.on_2b, state.on_3b), (Some(6), Some(5)));
+    }
+
+    #[test]
+    fn inside_the_park_home_run_and_bunt_single_keep_their_qualifier() {
+        let mut state = fixture_state(6, 4, HalfInning::Top);
+        state.on_2b = Some(5);
+
+        let result = run_line(&mut state, "h bu");
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+        assert_eq!(pa.outcome.display_label(), "Bunt single");
+        assert_eq!(pa.outcome.label(), "H");
+        assert_eq!((state.on_1b, state.on_3b), (Some(6), Some(5)));
+
+        state.current_batter_order = Some(7);
+        state.current_batter_id = Some(1007);
+        let result = run_line(&mut state, "hr cf itp");
+        let pa = result.plate_appearance.as_ref().expect("PA closed");
+        assert_eq!(pa.outcome.display_label(), "Inside-the-park home run");
+        assert_eq!(pa.outcome.zone(), Some(FieldZone::CF));
+        assert_eq!(state.score.away, 3);
+    }
 }
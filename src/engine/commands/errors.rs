@@ -13,6 +13,7 @@
 //! `Vec<CommandError>` so the user sees **every** problem in a single pass,
 //! not just the first one.
 
+use crate::models::rules::GameKind;
 use std::error::Error;
 use std::fmt;
 
@@ -47,6 +48,9 @@ pub enum ParseError {
     /// A zone token (e.g. `lf`) was not one of the documented codes.
     InvalidZone { token: String },
 
+    /// A hit modifier that does not fit the hit (`h gr`, `2h itp`, `hr bu`).
+    InvalidHitModifier { verb: String, modifier: String },
+
     /// A base token (e.g. `2b`) was not one of the documented codes.
     InvalidBase { token: String },
 
@@ -58,6 +62,21 @@ pub enum ParseError {
 
     /// A fielder number is outside the 1–9 range.
     InvalidFielder { token: String },
+
+    /// A jersey number (`pc 21`) is not a whole number in the 0–99 range.
+    InvalidJersey { token: String },
+
+    /// A defensive position (`7 sub 22 lf`) is not one of `p`, `c`, `1b`,
+    /// `2b`, `3b`, `ss`, `lf`, `cf`, `rf`, `dh` or `1`–`9`.
+    InvalidPosition { token: String },
+
+    /// The number of plate appearances to undo (`undo 3`) is not a whole
+    /// number in the 1–99 range.
+    InvalidUndoCount { token: String },
+
+    /// The RBI count of `rbi <n>` is missing or not a whole number in the
+    /// 0–4 range.
+    InvalidRbiCount { token: String },
 }
 
 impl fmt::Display for ParseError {
@@ -78,6 +97,9 @@ impl fmt::Display for ParseError {
                 write!(f, "verb '{verb}' requires a {expected}")
             }
             Self::InvalidZone { token } => write!(f, "invalid field zone '{token}'"),
+            Self::InvalidHitModifier { verb, modifier } => {
+                write!(f, "modifier '{modifier}' cannot be used with hit '{verb}'")
+            }
             Self::InvalidBase { token } => write!(f, "invalid base '{token}'"),
             Self::ExtraTokens { verb, extra } => {
                 write!(f, "verb '{verb}' does not accept extra tokens: '{extra}'")
@@ -88,6 +110,18 @@ impl fmt::Display for ParseError {
             Self::InvalidFielder { token } => {
                 write!(f, "invalid fielder '{token}' (expected 1–9)")
             }
+            Self::InvalidJersey { token } => {
+                write!(f, "invalid jersey number '{token}' (expected 0–99)")
+            }
+            Self::InvalidPosition { token } => {
+                write!(f, "invalid defensive position '{token}'")
+            }
+            Self::InvalidUndoCount { token } => {
+                write!(f, "invalid undo count '{token}' (expected 1–99)")
+            }
+            Self::InvalidRbiCount { token } => {
+                write!(f, "invalid RBI count '{token}' (expected 0–4)")
+            }
         }
     }
 }
@@ -107,9 +141,14 @@ pub enum ValidationError {
     RunnerNotOnBase { order: u8 },
 
     /// A runner-advance override (`<n> <base>`) appeared in a line with no
-    /// triggering play (no hit, no FC).
+    /// triggering play (no hit, no FC, no batter reaching on error, no
+    /// sacrifice).
     AdvanceWithoutTrigger { order: u8 },
 
+    /// A runner-targeted error (`<n> e<f>`) did not name the base the
+    /// runner reached.
+    ErrorAdvanceWithoutBase { order: u8 },
+
     /// The same batting slot appears as both the batter on a hit and as a
     /// runner override in the same line.
     DuplicateSubject { order: u8 },
@@ -121,10 +160,47 @@ pub enum ValidationError {
     /// More than 3 outs would be recorded on a single play.
     TooManyOuts { count: usize },
 
+    /// A sacrifice (`sf<n>`, `sh<seq>`) was entered with two outs already
+    /// recorded: the batter's out would end the half-inning.
+    SacrificeWithTwoOuts,
+
+    /// A sacrifice fly where no runner scores.
+    SacrificeFlyWithoutRun,
+
+    /// A runner advance on a batter's out that retires the side.
+    AdvanceOnThirdOut { order: u8 },
+
+    /// A dropped third strike (`kwp`, `kpb`, `k<seq>`) entered when the
+    /// count does not have two strikes.
+    DroppedThirdStrikeWithoutTwoStrikes { strikes: u8 },
+
+    /// A dropped third strike with a runner on first and fewer than two
+    /// outs: the batter is out and cannot run.
+    DroppedThirdStrikeWithFirstOccupied,
+
+    /// A runner moving on a pitch would land on or pass a runner ahead of
+    /// them who does not move further on the same line.
+    RunnerBlocked { order: u8, blocker: u8 },
+
+    /// A runner advance on a ground-rule double: every runner is awarded
+    /// exactly two bases.
+    GroundRuleDoubleAdvance { order: u8 },
+
+    /// A play that is not part of the game being scored, e.g. a steal in
+    /// slow-pitch softball.
+    NotInGame { play: &'static str, kind: GameKind },
+
     /// Control/status/pitch command appeared mixed with action segments.
     /// Control and pitch segments cannot be combined with actions on the
     /// same line.
     ControlMixedWithActions { verb: String },
+
+    /// `rbi <n>` on a line where the batter does not complete their plate
+    /// appearance.
+    RbiWithoutBatterPlay,
+
+    /// More than one `rbi <n>` on the same line.
+    RepeatedRbi,
 }
 
 impl fmt::Display for ValidationError {
@@ -139,7 +215,11 @@ impl fmt::Display for ValidationError {
             }
             Self::AdvanceWithoutTrigger { order } => write!(
                 f,
-                "runner advance #{order} has no triggering play (hit or FC) in this line"
+                "runner advance #{order} has no triggering play (hit, out, FC or error) in this line"
+            ),
+            Self::ErrorAdvanceWithoutBase { order } => write!(
+                f,
+                "runner #{order} advanced on an error but no destination base was given"
             ),
             Self::DuplicateSubject { order } => write!(
                 f,
@@ -152,10 +232,46 @@ impl fmt::Display for ValidationError {
             Self::TooManyOuts { count } => {
                 write!(f, "play would record {count} outs (maximum is 3)")
             }
+            Self::SacrificeWithTwoOuts => {
+                write!(f, "a sacrifice requires fewer than two outs")
+            }
+            Self::SacrificeFlyWithoutRun => {
+                write!(f, "a sacrifice fly requires a runner to score")
+            }
+            Self::AdvanceOnThirdOut { order } => write!(
+                f,
+                "runner #{order} cannot advance on the out that ends the half-inning"
+            ),
+            Self::DroppedThirdStrikeWithoutTwoStrikes { strikes } => write!(
+                f,
+                "a dropped third strike needs two strikes in the count (currently {strikes})"
+            ),
+            Self::DroppedThirdStrikeWithFirstOccupied => write!(
+                f,
+                "the batter cannot run on a dropped third strike with first base occupied and fewer than two outs"
+            ),
+            Self::RunnerBlocked { order, blocker } => write!(
+                f,
+                "runner #{order} cannot pass or land on runner #{blocker}, who does not move ahead of them"
+            ),
+            Self::GroundRuleDoubleAdvance { order } => write!(
+                f,
+                "runner #{order} cannot be placed on a ground-rule double: every runner takes two bases"
+            ),
+            Self::NotInGame { play, kind } => write!(
+                f,
+                "{play} is not allowed in {}",
+                kind.to_string().to_lowercase()
+            ),
             Self::ControlMixedWithActions { verb } => write!(
                 f,
                 "'{verb}' is a control command and cannot be combined with action segments"
             ),
+            Self::RbiWithoutBatterPlay => write!(
+                f,
+                "an RBI credit needs the play that ends the plate appearance on the same line"
+            ),
+            Self::RepeatedRbi => write!(f, "the RBI count is given more than once"),
         }
     }
 }
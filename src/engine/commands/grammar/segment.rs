@@ -11,16 +11,29 @@
 //! # Grammar (informal)
 //!
 //! ```text
-//! Segment   := Control | Status | Pitch | Action
-//! Control   := 'exit' | 'quit' | 'playball'
+//! Segment   := Control | Status | Pitch | Balk | Sub | Credit | Action
+//! Control   := 'exit' | 'quit' | 'playball' | 'undo' Count?   -- Count := 1-99
+//! Credit    := 'rbi' Rbi                       -- Rbi := 0-4
+//! Sub       := 'pc' Jersey                     -- Jersey := [0-9]{1,2}
+//!            | Subject? 'ph' Jersey
+//!            | Subject 'pr' Jersey
+//!            | Subject 'sub' Jersey Position   -- Position := p c 1b … dh | 1-9
+//!            | Subject 'pos' Position
 //! Status    := 'regular' | 'post' | 'cancel' | 'susp' | 'forf' | 'protest'
 //! Pitch     := 'b' | 'k' | 's' | 'f' | 'fl'
+//! Balk      := 'bk'
 //! Action    := Subject? ActionVerb
 //! Subject   := [1-9]
-//! ActionVerb:= HitVerb Zone?
+//! ActionVerb:= HitVerb Zone? Modifier?           -- in either order
+//!            | AwardVerb                        -- hbp / ibb / ci
 //!            | OutVerb                         -- batter-only or runner with subject
+//!            | SacVerb                          -- sf<n> / sh<seq>, batter only
+//!            | DroppedKVerb                     -- kwp / kpb / k<seq>, batter only
 //!            | FcVerb Base
-//!            | StealVerb Base
+//!            | ErrorVerb Base?                  -- batter reaches, or runner with subject
+//!            | StealVerb Base                   -- st / wp / pb / di
+//!            | CaughtVerb Base Fielders         -- cs / pocs
+//!            | 'po' Fielders
 //!            | Base                             -- runner advance
 //! ```
 //!
@@ -31,24 +44,37 @@
 //! owns:
 //!
 //! - Hit verbs (`h`, `2h`, `3h`, `hr`)
+//! - Base awards (`hbp`, `ibb`, `ci`)
 //! - Batter out verbs with a multi-char shape: fielding sequence (`63`),
 //!   fly (`F8`), foul fly (`FF3`), line out (`L6`), infield fly (`IF4`)
+//! - Sacrifices (`sf8`, `sh13`) — these can only ever target the batter
+//! - Dropped third strikes (`kwp`, `kpb`, `k23`) — batter only as well
 //! - Fielder's choice (`o6 1b`)
+//! - Reached on error (`e6`, `te5 2b`)
 //!
 //! An unassisted-out verb is a single digit (`5`) and therefore collides with
 //! a lone subject. In that case the subject is required: the user writes
 //! `5 5` to mean "batter #5 unassisted by fielder #5".
 //!
-//! Steal (`st`), runner-advance (`2b`) and runner-targeted outs all REQUIRE
-//! a subject — there is no implicit-runner shortcut.
+//! Steal (`st`), the other in-pitch advances (`wp`, `pb`, `di`), caught
+//! stealing and pickoffs (`cs`, `po`, `pocs`), runner-advance (`2b`) and
+//! runner-targeted outs all REQUIRE a subject — there is no
+//! implicit-runner shortcut.
 //!
-//! Pitch and control verbs FORBID a subject.
+//! Pitch and control verbs FORBID a subject, and so does the balk (`bk`):
+//! it moves every runner at once. The pitching change (`pc`) names a
+//! jersey number, not a batting-order slot, and forbids a subject too.
+//! The pinch hitter (`ph`) defaults to the current batter; the pinch
+//! runner (`pr`), defensive substitution (`sub`) and position change
+//! (`pos`) require the batting slot they affect.
 
 use super::tokens::{TokenKind, classify};
 use crate::engine::commands::errors::ParseError;
 use crate::engine::commands::kind::CommandKind;
 use crate::models::field_zone::FieldZone;
+use crate::models::plate_appearance::HitModifier;
 use crate::models::runner::RunnerDest;
+use crate::models::types::Position;
 
 /// A parsed segment. Every `Segment` variant is the result of
 /// syntactically recognising one comma-separated chunk of an input line;
@@ -68,6 +94,12 @@ pub enum Segment {
     /// (`Exit` or `PlayBall`); the validator does a simple match.
     Control(CommandKind),
 
+    /// `undo` / `undo <n>`: remove the last `count` plate appearances.
+    Undo { count: u8 },
+
+    /// `rbi <n>`: the scorer's RBI count for the play on the same line.
+    Rbi { count: u8 },
+
     /// Game-status change. `CommandKind` is one of
     /// `Regular`/`Postponed`/`Cancelled`/`Suspended`/`Forfeited`/`Protested`.
     Status(CommandKind),
@@ -77,11 +109,21 @@ pub enum Segment {
     Pitch(CommandKind),
 
     /// Hit by a batter. `subject` is optional (implicit = current batter).
-    /// `kind` is one of `Single`/`Double`/`Triple`/`HomeRun`.
+    /// `kind` is one of `Single`/`Double`/`Triple`/`HomeRun`; `modifier`
+    /// (`gr`, `itp`, `bu`) has already been checked against it.
     Hit {
         subject: Option<u8>,
         kind: CommandKind,
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
+    },
+
+    /// First base awarded to the batter. `subject` is optional (implicit =
+    /// current batter). `kind` is one of
+    /// `HitByPitch`/`IntentionalWalk`/`CatcherInterference`.
+    Award {
+        subject: Option<u8>,
+        kind: CommandKind,
     },
 
     /// Batter retired on a batter-only out. `subject` is optional for all
@@ -102,11 +144,83 @@ pub enum Segment {
         base: RunnerDest,
     },
 
+    /// Fielding (`e<n>`) or throwing (`te<n>`) error. `subject` is optional
+    /// (implicit = current batter reaching base); an explicit subject may
+    /// also name a runner who advanced on the error, which the validator
+    /// resolves. `base` is optional here: the batter defaults to first,
+    /// while a runner must name a base (checked by the validator).
+    Error {
+        subject: Option<u8>,
+        fielder: u8,
+        throwing: bool,
+        base: Option<RunnerDest>,
+    },
+
+    /// Dropped third strike. `subject` is optional and, when given, can
+    /// only be the batter. `kind` is one of
+    /// `StrikeoutWildPitch`/`StrikeoutPassedBall`/`StrikeoutThrownOut`;
+    /// `fielders` is the throw that retired the batter and is empty unless
+    /// the batter was thrown out.
+    DroppedThirdStrike {
+        subject: Option<u8>,
+        kind: CommandKind,
+        fielders: Vec<u8>,
+    },
+
     /// Stolen base. `subject` is ALWAYS required (runner only).
     Steal { subject: u8, dest: RunnerDest },
 
+    /// Runner advance on a wild pitch, passed ball or defensive
+    /// indifference (`3 wp sc`). `subject` is ALWAYS required. `kind` is
+    /// one of `WildPitch`/`PassedBall`/`DefensiveIndifference`.
+    PitchAdvance {
+        subject: u8,
+        kind: CommandKind,
+        dest: RunnerDest,
+    },
+
+    /// Balk: every runner moves up one base. Takes no subject and no
+    /// object.
+    Balk,
+
+    /// Runner put out between pitches. `subject` is ALWAYS required.
+    /// `kind` is one of `CaughtStealing`/`Pickoff`/`PickoffCaughtStealing`;
+    /// `base` is the base the runner was trying to take and is `None` only
+    /// for a plain pickoff. `fielders` is the fielding chain, putout last.
+    RunnerCaught {
+        subject: u8,
+        kind: CommandKind,
+        base: Option<RunnerDest>,
+        fielders: Vec<u8>,
+    },
+
     /// Runner advance (`<n> <base>`). `subject` is ALWAYS required.
     Advance { subject: u8, dest: RunnerDest },
+
+    /// Pitching change (`pc 21`): the fielding team brings in the player
+    /// wearing `jersey`. Takes no subject; the roster lookup is left to
+    /// the engine.
+    PitchingChange { jersey: u8 },
+
+    /// Pinch hitter (`ph 12`) for the current batter. `subject` is
+    /// optional (implicit = current batter).
+    PinchHitter { subject: Option<u8>, jersey: u8 },
+
+    /// Pinch runner (`3 pr 27`) for the runner in batting slot `subject`.
+    /// `subject` is ALWAYS required.
+    PinchRunner { subject: u8, jersey: u8 },
+
+    /// Defensive substitution (`7 sub 22 lf`): `jersey` takes batting slot
+    /// `subject` and plays `position`. `subject` is ALWAYS required.
+    DefensiveSubstitution {
+        subject: u8,
+        jersey: u8,
+        position: Position,
+    },
+
+    /// Position change (`4 pos ss`): the player in batting slot `subject`
+    /// moves to `position`. `subject` is ALWAYS required.
+    PositionChange { subject: u8, position: Position },
 }
 
 /// Batter/runner out shape. Both `Segment::BatterOut` and
@@ -129,6 +243,11 @@ pub enum BatterOutKind {
     LineOut { fielder: u8 },
     /// Infield fly.
     InfieldFly { fielder: u8 },
+    /// Sacrifice fly (`sf8`). Batter only.
+    SacrificeFly { fielder: u8 },
+    /// Sacrifice bunt (`sh13`, `sh1`). One fielder means unassisted.
+    /// Batter only.
+    SacrificeBunt { fielders: Vec<u8> },
 }
 
 impl BatterOutKind {
@@ -143,6 +262,8 @@ impl BatterOutKind {
             Self::FlyOut { foul: true, .. } => CommandKind::FoulFlyOut,
             Self::LineOut { .. } => CommandKind::LineOut,
             Self::InfieldFly { .. } => CommandKind::InfieldFly,
+            Self::SacrificeFly { .. } => CommandKind::SacrificeFly,
+            Self::SacrificeBunt { .. } => CommandKind::SacrificeBunt,
         }
     }
 }
@@ -170,11 +291,19 @@ pub fn parse_segment(raw: &str) -> Result<Segment, ParseError> {
         // family, not on the individual variant, so we don't fan out across
         // 13 arms here.
         (TokenKind::Verb(ck), _) => match ck.family() {
+            CommandFamily::Control if *ck == CommandKind::Undo => parse_undo(&tokens),
             CommandFamily::Control | CommandFamily::Status => parse_keyword_segment(*ck, &tokens),
             CommandFamily::Pitch => parse_pitch_segment(*ck, &tokens),
             CommandFamily::Hit => parse_hit(None, *ck, &tokens, &kinds),
+            CommandFamily::Award => parse_award(None, *ck, &tokens),
+            CommandFamily::DroppedThirdStrike => {
+                parse_dropped_third_strike(None, *ck, Vec::new(), &tokens)
+            }
+            CommandFamily::Steal if *ck == CommandKind::Balk => parse_balk(&tokens),
+            CommandFamily::Substitution => parse_substitution(None, *ck, &tokens),
+            CommandFamily::Credit => parse_rbi(&tokens),
             CommandFamily::Steal => Err(ParseError::MissingSubject {
-                verb: "st".to_string(),
+                verb: ck.canonical_name().to_string(),
             }),
             // A parameter-less verb in families that require a subject or
             // follow a different path should never land here: the grammar
@@ -191,6 +320,9 @@ pub fn parse_segment(raw: &str) -> Result<Segment, ParseError> {
 
         // ── Implicit-batter paths for verbs with a numeric parameter ─────────
         (TokenKind::FcVerb { fielder }, _) => parse_fc(None, *fielder, &tokens, &kinds),
+        (TokenKind::ErrorVerb { fielder, throwing }, _) => {
+            parse_error(None, *fielder, *throwing, &tokens, &kinds)
+        }
         (TokenKind::FlyVerb { fielder, foul }, _) => parse_batter_out_implicit(
             BatterOutKind::FlyOut {
                 fielder: *fielder,
@@ -210,14 +342,29 @@ pub fn parse_segment(raw: &str) -> Result<Segment, ParseError> {
             },
             &tokens,
         ),
+        (TokenKind::SacFlyVerb { fielder }, _) => {
+            parse_batter_out_implicit(BatterOutKind::SacrificeFly { fielder: *fielder }, &tokens)
+        }
+        (TokenKind::SacBuntVerb { fielders }, _) => parse_batter_out_implicit(
+            BatterOutKind::SacrificeBunt {
+                fielders: fielders.clone(),
+            },
+            &tokens,
+        ),
+        (TokenKind::StrikeoutThrowVerb { fielders }, _) => parse_dropped_third_strike(
+            None,
+            CommandKind::StrikeoutThrownOut,
+            fielders.clone(),
+            &tokens,
+        ),
 
         // ── Base in first position: standalone advance without subject ───────
         (TokenKind::Base(_), first) => Err(ParseError::MissingSubject {
             verb: first.to_string(),
         }),
 
-        // ── Zone in first position is never valid alone ──────────────────────
-        (TokenKind::Zone(_), first) => Err(ParseError::UnknownVerb {
+        // ── Zone or modifier in first position is never valid alone ──────────
+        (TokenKind::Zone(_) | TokenKind::HitModifier(_), first) => Err(ParseError::UnknownVerb {
             token: first.to_string(),
         }),
 
@@ -247,6 +394,86 @@ fn parse_keyword_segment(ck: CommandKind, tokens: &[&str]) -> Result<Segment, Pa
     })
 }
 
+/// `pc <jersey>`, `[<n>] ph <jersey>`, `<n> pr <jersey>`,
+/// `<n> sub <jersey> <pos>`, `<n> pos <pos>`. `tokens[0]` is the verb.
+/// Jerseys and positions are read from the raw token text: `21` would
+/// otherwise classify as a fielding sequence and `lf` as a zone.
+fn parse_substitution(
+    subject: Option<u8>,
+    ck: CommandKind,
+    tokens: &[&str],
+) -> Result<Segment, ParseError> {
+    let verb = tokens[0].to_string();
+    let expected_len = match ck {
+        CommandKind::DefensiveSubstitution => 3,
+        _ => 2,
+    };
+    if tokens.len() > expected_len {
+        return Err(ParseError::ExtraTokens {
+            verb,
+            extra: tokens[expected_len..].join(" "),
+        });
+    }
+
+    let subject_required = || match subject {
+        Some(s) => Ok(s),
+        None => Err(ParseError::MissingSubject {
+            verb: ck.canonical_name().to_string(),
+        }),
+    };
+
+    match ck {
+        CommandKind::PitchingChange => Ok(Segment::PitchingChange {
+            jersey: parse_jersey(&verb, tokens.get(1))?,
+        }),
+        CommandKind::PinchHitter => Ok(Segment::PinchHitter {
+            subject,
+            jersey: parse_jersey(&verb, tokens.get(1))?,
+        }),
+        CommandKind::PinchRunner => Ok(Segment::PinchRunner {
+            subject: subject_required()?,
+            jersey: parse_jersey(&verb, tokens.get(1))?,
+        }),
+        CommandKind::DefensiveSubstitution => Ok(Segment::DefensiveSubstitution {
+            subject: subject_required()?,
+            jersey: parse_jersey(&verb, tokens.get(1))?,
+            position: parse_position(&verb, tokens.get(2))?,
+        }),
+        CommandKind::PositionChange => Ok(Segment::PositionChange {
+            subject: subject_required()?,
+            position: parse_position(&verb, tokens.get(1))?,
+        }),
+        _ => unreachable!("parse_substitution called with {:?}", ck),
+    }
+}
+
+fn parse_jersey(verb: &str, raw: Option<&&str>) -> Result<u8, ParseError> {
+    let Some(raw) = raw else {
+        return Err(ParseError::MissingObject {
+            verb: verb.to_string(),
+            expected: "jersey number",
+        });
+    };
+    if raw.is_empty() || raw.len() > 2 || !raw.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseError::InvalidJersey {
+            token: raw.to_string(),
+        });
+    }
+    Ok(raw.parse().unwrap())
+}
+
+fn parse_position(verb: &str, raw: Option<&&str>) -> Result<Position, ParseError> {
+    let Some(raw) = raw else {
+        return Err(ParseError::MissingObject {
+            verb: verb.to_string(),
+            expected: "defensive position",
+        });
+    };
+    Position::from_db_value(&raw.to_ascii_uppercase()).ok_or_else(|| ParseError::InvalidPosition {
+        token: raw.to_string(),
+    })
+}
+
 fn parse_pitch_segment(ck: CommandKind, tokens: &[&str]) -> Result<Segment, ParseError> {
     debug_assert_eq!(
         ck.family(),
@@ -282,7 +509,15 @@ fn parse_with_subject(
     if let TokenKind::Verb(ck) = &kinds[1] {
         use crate::engine::commands::kind::CommandFamily;
         match ck.family() {
-            CommandFamily::Pitch | CommandFamily::Control | CommandFamily::Status => {
+            CommandFamily::Pitch
+            | CommandFamily::Control
+            | CommandFamily::Status
+            | CommandFamily::Credit => {
+                return Err(ParseError::SubjectNotAllowed {
+                    verb: tokens[1].to_string(),
+                });
+            }
+            _ if matches!(ck, CommandKind::Balk | CommandKind::PitchingChange) => {
                 return Err(ParseError::SubjectNotAllowed {
                     verb: tokens[1].to_string(),
                 });
@@ -299,12 +534,30 @@ fn parse_with_subject(
             use crate::engine::commands::kind::CommandFamily;
             match ck.family() {
                 CommandFamily::Hit => parse_hit(Some(subject), *ck, rest_tokens, rest_kinds),
-                CommandFamily::Steal => parse_steal(subject, rest_tokens, rest_kinds),
+                CommandFamily::Award => parse_award(Some(subject), *ck, rest_tokens),
+                CommandFamily::DroppedThirdStrike => {
+                    parse_dropped_third_strike(Some(subject), *ck, Vec::new(), rest_tokens)
+                }
+                CommandFamily::Steal
+                    if matches!(
+                        ck,
+                        CommandKind::CaughtStealing
+                            | CommandKind::Pickoff
+                            | CommandKind::PickoffCaughtStealing
+                    ) =>
+                {
+                    parse_runner_caught(subject, *ck, rest_tokens, rest_kinds)
+                }
+                CommandFamily::Steal => parse_steal(subject, *ck, rest_tokens, rest_kinds),
+                CommandFamily::Substitution => parse_substitution(Some(subject), *ck, rest_tokens),
                 // Pitch/Control/Status already rejected above.
                 _ => unreachable!("unexpected verb family {:?} after subject", ck.family()),
             }
         }
         TokenKind::FcVerb { fielder } => parse_fc(Some(subject), *fielder, rest_tokens, rest_kinds),
+        TokenKind::ErrorVerb { fielder, throwing } => {
+            parse_error(Some(subject), *fielder, *throwing, rest_tokens, rest_kinds)
+        }
 
         // Runner-targeted OUTs.
         TokenKind::FlyVerb { fielder, foul } => parse_targeted_out(
@@ -333,6 +586,27 @@ fn parse_with_subject(
             rest_tokens,
         ),
 
+        // Sacrifices never retire a runner: the subject can only be the
+        // batter, which the validator checks against the current slot.
+        TokenKind::SacFlyVerb { fielder } => parse_sacrifice_with_subject(
+            subject,
+            BatterOutKind::SacrificeFly { fielder: *fielder },
+            rest_tokens,
+        ),
+        TokenKind::SacBuntVerb { fielders } => parse_sacrifice_with_subject(
+            subject,
+            BatterOutKind::SacrificeBunt {
+                fielders: fielders.clone(),
+            },
+            rest_tokens,
+        ),
+        TokenKind::StrikeoutThrowVerb { fielders } => parse_dropped_third_strike(
+            Some(subject),
+            CommandKind::StrikeoutThrownOut,
+            fielders.clone(),
+            rest_tokens,
+        ),
+
         // Single-digit second token: unassisted out by that fielder.
         TokenKind::Digit(fielder) => parse_targeted_out(
             subject,
@@ -354,7 +628,7 @@ fn parse_with_subject(
             })
         }
 
-        TokenKind::Zone(_) => Err(ParseError::UnknownVerb {
+        TokenKind::Zone(_) | TokenKind::HitModifier(_) => Err(ParseError::UnknownVerb {
             token: rest_tokens[0].to_string(),
         }),
         TokenKind::Unknown(_) => Err(ParseError::UnknownVerb {
@@ -366,7 +640,8 @@ fn parse_with_subject(
 // ─── Hit ─────────────────────────────────────────────────────────────────────
 
 /// `hit_tokens` starts at the hit verb (index 0 of the slice).
-/// Accepts optional zone as the next token.
+/// Accepts an optional zone and an optional modifier, in either order
+/// (`2h lf gr`, `hr itp cf`).
 ///
 /// `hit_kind` must be a variant of [`CommandKind`] in the `Hit` family
 /// (Single / Double / Triple / HomeRun). Other variants are rejected by
@@ -384,22 +659,32 @@ fn parse_hit(
         hit_kind
     );
 
-    let zone = match hit_tokens.get(1) {
-        None => None,
-        Some(_) => match &hit_kinds[1] {
-            TokenKind::Zone(z) => Some(*z),
-            _ => {
+    let mut zone = None;
+    let mut modifier = None;
+    for (i, kind) in hit_kinds.iter().enumerate().skip(1) {
+        match kind {
+            TokenKind::Zone(z) if zone.is_none() && i < 3 => zone = Some(*z),
+            TokenKind::HitModifier(m) if modifier.is_none() && i < 3 => modifier = Some(*m),
+            _ if i == 1 => {
                 return Err(ParseError::InvalidZone {
                     token: hit_tokens[1].to_string(),
                 });
             }
-        },
-    };
+            _ => {
+                return Err(ParseError::ExtraTokens {
+                    verb: hit_tokens[0].to_string(),
+                    extra: hit_tokens[i..].join(" "),
+                });
+            }
+        }
+    }
 
-    if hit_tokens.len() > 2 {
-        return Err(ParseError::ExtraTokens {
+    if let Some(m) = modifier
+        && !m.applies_to(hit_bases(hit_kind))
+    {
+        return Err(ParseError::InvalidHitModifier {
             verb: hit_tokens[0].to_string(),
-            extra: hit_tokens[2..].join(" "),
+            modifier: m.as_str().to_ascii_lowercase(),
         });
     }
 
@@ -407,6 +692,58 @@ fn parse_hit(
         subject,
         kind: hit_kind,
         zone,
+        modifier,
+    })
+}
+
+fn hit_bases(hit_kind: CommandKind) -> u8 {
+    match hit_kind {
+        CommandKind::Single => 1,
+        CommandKind::Double => 2,
+        CommandKind::Triple => 3,
+        _ => 4,
+    }
+}
+
+// ─── Base award ──────────────────────────────────────────────────────────────
+
+/// `award_tokens` starts at the award verb. No object is accepted: the
+/// batter always takes first and runners advance only if forced.
+fn parse_award(
+    subject: Option<u8>,
+    kind: CommandKind,
+    award_tokens: &[&str],
+) -> Result<Segment, ParseError> {
+    if award_tokens.len() > 1 {
+        return Err(ParseError::ExtraTokens {
+            verb: award_tokens[0].to_string(),
+            extra: award_tokens[1..].join(" "),
+        });
+    }
+    Ok(Segment::Award { subject, kind })
+}
+
+// ─── Dropped third strike ────────────────────────────────────────────────────
+
+/// `k_tokens` starts at the dropped-third-strike verb. No object is
+/// accepted: the batter either takes first or is retired by the throw
+/// carried in the verb itself.
+fn parse_dropped_third_strike(
+    subject: Option<u8>,
+    kind: CommandKind,
+    fielders: Vec<u8>,
+    k_tokens: &[&str],
+) -> Result<Segment, ParseError> {
+    if k_tokens.len() > 1 {
+        return Err(ParseError::ExtraTokens {
+            verb: k_tokens[0].to_string(),
+            extra: k_tokens[1..].join(" "),
+        });
+    }
+    Ok(Segment::DroppedThirdStrike {
+        subject,
+        kind,
+        fielders,
     })
 }
 
@@ -447,19 +784,59 @@ fn parse_fc(
     })
 }
 
+// ─── Fielding error ──────────────────────────────────────────────────────────
+
+/// `error_tokens` starts at the error verb (`e<n>` / `te<n>`). Base is
+/// optional.
+fn parse_error(
+    subject: Option<u8>,
+    fielder: u8,
+    throwing: bool,
+    error_tokens: &[&str],
+    error_kinds: &[TokenKind],
+) -> Result<Segment, ParseError> {
+    let base = match error_tokens.get(1) {
+        None => None,
+        Some(tok) => match &error_kinds[1] {
+            TokenKind::Base(b) => Some(*b),
+            _ => {
+                return Err(ParseError::InvalidBase {
+                    token: tok.to_string(),
+                });
+            }
+        },
+    };
+
+    if error_tokens.len() > 2 {
+        return Err(ParseError::ExtraTokens {
+            verb: error_tokens[0].to_string(),
+            extra: error_tokens[2..].join(" "),
+        });
+    }
+
+    Ok(Segment::Error {
+        subject,
+        fielder,
+        throwing,
+        base,
+    })
+}
+
 // ─── Steal ───────────────────────────────────────────────────────────────────
 
 /// Steal path, called after the subject has been consumed.
-/// `steal_tokens[0]` is `st`, followed by a mandatory base.
+/// `steal_tokens[0]` is `st`, `wp`, `pb` or `di`, followed by a mandatory
+/// base.
 fn parse_steal(
     subject: u8,
+    ck: CommandKind,
     steal_tokens: &[&str],
     steal_kinds: &[TokenKind],
 ) -> Result<Segment, ParseError> {
     let base_tok = steal_tokens
         .get(1)
         .ok_or_else(|| ParseError::MissingObject {
-            verb: "st".to_string(),
+            verb: ck.canonical_name().to_string(),
             expected: "destination base (1B / 2B / 3B / SC)",
         })?;
 
@@ -474,12 +851,129 @@ fn parse_steal(
 
     if steal_tokens.len() > 2 {
         return Err(ParseError::ExtraTokens {
-            verb: "st".to_string(),
+            verb: ck.canonical_name().to_string(),
             extra: steal_tokens[2..].join(" "),
         });
     }
 
-    Ok(Segment::Steal { subject, dest })
+    Ok(match ck {
+        CommandKind::Steal => Segment::Steal { subject, dest },
+        _ => Segment::PitchAdvance {
+            subject,
+            kind: ck,
+            dest,
+        },
+    })
+}
+
+/// Caught-stealing / pickoff path, called after the subject has been
+/// consumed. `cs` and `pocs` take the base the runner was going for, then
+/// the fielding chain (`cs 2b 26`); `po` takes the fielding chain only
+/// (`po 13`). A lone digit is accepted as an unassisted putout.
+fn parse_runner_caught(
+    subject: u8,
+    ck: CommandKind,
+    tokens: &[&str],
+    kinds: &[TokenKind],
+) -> Result<Segment, ParseError> {
+    let verb = ck.canonical_name();
+    let mut idx = 1;
+
+    let base = if ck == CommandKind::Pickoff {
+        None
+    } else {
+        let base_tok = tokens.get(idx).ok_or_else(|| ParseError::MissingObject {
+            verb: verb.to_string(),
+            expected: "destination base (2B / 3B / SC)",
+        })?;
+        idx += 1;
+        match &kinds[idx - 1] {
+            TokenKind::Base(b) => Some(*b),
+            _ => {
+                return Err(ParseError::InvalidBase {
+                    token: base_tok.to_string(),
+                });
+            }
+        }
+    };
+
+    let seq_tok = tokens.get(idx).ok_or_else(|| ParseError::MissingObject {
+        verb: verb.to_string(),
+        expected: "fielding sequence (26, 1-3)",
+    })?;
+    let fielders = match &kinds[idx] {
+        TokenKind::FieldingSeq(f) => f.clone(),
+        TokenKind::Digit(n) => vec![*n],
+        _ => {
+            return Err(ParseError::InvalidFieldingSequence {
+                token: seq_tok.to_string(),
+                reason: "expected the fielders who made the out, e.g. 26 or 1-3".to_string(),
+            });
+        }
+    };
+
+    if tokens.len() > idx + 1 {
+        return Err(ParseError::ExtraTokens {
+            verb: verb.to_string(),
+            extra: tokens[idx + 1..].join(" "),
+        });
+    }
+
+    Ok(Segment::RunnerCaught {
+        subject,
+        kind: ck,
+        base,
+        fielders,
+    })
+}
+
+/// `undo` or `undo <n>`, n in 1–99.
+fn parse_undo(tokens: &[&str]) -> Result<Segment, ParseError> {
+    if tokens.len() > 2 {
+        return Err(ParseError::ExtraTokens {
+            verb: CommandKind::Undo.canonical_name().to_string(),
+            extra: tokens[2..].join(" "),
+        });
+    }
+    let Some(raw) = tokens.get(1) else {
+        return Ok(Segment::Undo { count: 1 });
+    };
+    match raw.parse::<u8>() {
+        Ok(count @ 1..=99) if raw.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok(Segment::Undo { count })
+        }
+        _ => Err(ParseError::InvalidUndoCount {
+            token: raw.to_string(),
+        }),
+    }
+}
+
+/// `rbi <n>`, n in 0–4.
+fn parse_rbi(tokens: &[&str]) -> Result<Segment, ParseError> {
+    if tokens.len() > 2 {
+        return Err(ParseError::ExtraTokens {
+            verb: CommandKind::Rbi.canonical_name().to_string(),
+            extra: tokens[2..].join(" "),
+        });
+    }
+    let raw = tokens.get(1).copied().unwrap_or_default();
+    match raw.parse::<u8>() {
+        Ok(count @ 0..=4) if raw.bytes().all(|b| b.is_ascii_digit()) => Ok(Segment::Rbi { count }),
+        _ => Err(ParseError::InvalidRbiCount {
+            token: raw.to_string(),
+        }),
+    }
+}
+
+/// Balk path. `bk` stands alone: no subject, no base.
+fn parse_balk(tokens: &[&str]) -> Result<Segment, ParseError> {
+    if tokens.len() > 1 {
+        return Err(ParseError::ExtraTokens {
+            verb: CommandKind::Balk.canonical_name().to_string(),
+            extra: tokens[1..].join(" "),
+        });
+    }
+    Ok(Segment::Balk)
 }
 
 // ─── Batter-out / runner-out helpers ─────────────────────────────────────────
@@ -495,6 +989,25 @@ fn parse_batter_out_implicit(out: BatterOutKind, tokens: &[&str]) -> Result<Segm
     Ok(Segment::BatterOut { subject: None, out })
 }
 
+/// Sacrifice with an explicit subject. Unlike [`parse_targeted_out`] the
+/// subject is always the batter, so this emits a `BatterOut` directly.
+fn parse_sacrifice_with_subject(
+    subject: u8,
+    out: BatterOutKind,
+    rest_tokens: &[&str],
+) -> Result<Segment, ParseError> {
+    if rest_tokens.len() > 1 {
+        return Err(ParseError::ExtraTokens {
+            verb: rest_tokens[0].to_string(),
+            extra: rest_tokens[1..].join(" "),
+        });
+    }
+    Ok(Segment::BatterOut {
+        subject: Some(subject),
+        out,
+    })
+}
+
 /// Runner-targeted out (or explicit batter out): the subject has already been
 /// consumed; `rest_tokens[0]` is the verb token we already classified.
 ///
@@ -538,6 +1051,7 @@ mod tests {
                 subject: None,
                 kind: CommandKind::Single,
                 zone: None,
+                modifier: None,
             }
         );
     }
@@ -549,6 +1063,7 @@ mod tests {
                 subject: Some(5),
                 kind: CommandKind::Single,
                 zone: Some(FieldZone::LF),
+                modifier: None,
             }
         );
     }
@@ -581,6 +1096,98 @@ mod tests {
     fn hit_invalid_zone() {
         assert!(matches!(err("h xyz"), ParseError::InvalidZone { .. }));
     }
+    #[test]
+    fn hit_modifiers_in_either_order() {
+        assert_eq!(
+            seg("2h gr"),
+            Segment::Hit {
+                subject: None,
+                kind: CommandKind::Double,
+                zone: None,
+                modifier: Some(HitModifier::GroundRule),
+            }
+        );
+        assert_eq!(
+            seg("4 hr cf itp"),
+            Segment::Hit {
+                subject: Some(4),
+                kind: CommandKind::HomeRun,
+                zone: Some(FieldZone::CF),
+                modifier: Some(HitModifier::InsideThePark),
+            }
+        );
+        assert!(matches!(
+            seg("h bu ls"),
+            Segment::Hit {
+                modifier: Some(HitModifier::Bunt),
+                zone: Some(FieldZone::LS),
+                ..
+            }
+        ));
+    }
+    #[test]
+    fn hit_modifier_must_fit_the_hit() {
+        assert!(matches!(err("h gr"), ParseError::InvalidHitModifier { .. }));
+        assert!(matches!(
+            err("2h itp"),
+            ParseError::InvalidHitModifier { .. }
+        ));
+        assert!(matches!(
+            err("hr bu"),
+            ParseError::InvalidHitModifier { .. }
+        ));
+        assert!(matches!(err("2h gr bu"), ParseError::ExtraTokens { .. }));
+        assert!(matches!(err("2h gr lf cf"), ParseError::ExtraTokens { .. }));
+    }
+
+    // ── Base awards ──
+    #[test]
+    fn award_with_and_without_subject() {
+        assert_eq!(
+            seg("hbp"),
+            Segment::Award {
+                subject: None,
+                kind: CommandKind::HitByPitch,
+            }
+        );
+        assert_eq!(
+            seg("5 ibb"),
+            Segment::Award {
+                subject: Some(5),
+                kind: CommandKind::IntentionalWalk,
+            }
+        );
+        assert!(matches!(err("ci 1b"), ParseError::ExtraTokens { .. }));
+    }
+
+    #[test]
+    fn dropped_third_strike_targets_the_batter() {
+        assert_eq!(
+            seg("kwp"),
+            Segment::DroppedThirdStrike {
+                subject: None,
+                kind: CommandKind::StrikeoutWildPitch,
+                fielders: vec![],
+            }
+        );
+        assert_eq!(
+            seg("4 kpb"),
+            Segment::DroppedThirdStrike {
+                subject: Some(4),
+                kind: CommandKind::StrikeoutPassedBall,
+                fielders: vec![],
+            }
+        );
+        assert_eq!(
+            seg("k2-3"),
+            Segment::DroppedThirdStrike {
+                subject: None,
+                kind: CommandKind::StrikeoutThrownOut,
+                fielders: vec![2, 3],
+            }
+        );
+        assert!(matches!(err("kwp 1b"), ParseError::ExtraTokens { .. }));
+    }
 
     // ── Batter outs (implicit subject) ──
     #[test]
@@ -658,6 +1265,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sacrifices_always_target_the_batter() {
+        assert_eq!(
+            seg("sf8"),
+            Segment::BatterOut {
+                subject: None,
+                out: BatterOutKind::SacrificeFly { fielder: 8 },
+            }
+        );
+        assert_eq!(
+            seg("4 sh13"),
+            Segment::BatterOut {
+                subject: Some(4),
+                out: BatterOutKind::SacrificeBunt {
+                    fielders: vec![1, 3],
+                },
+            }
+        );
+        assert!(matches!(err("sf8 3b"), ParseError::ExtraTokens { .. }));
+    }
+
     // ── Unassisted requires explicit subject ──
     #[test]
     fn unassisted_alone_is_rejected() {
@@ -720,6 +1348,46 @@ mod tests {
         assert!(matches!(err("5 o6"), ParseError::MissingObject { .. }));
     }
 
+    // ── Errors ──
+    #[test]
+    fn error_implicit_batter() {
+        assert_eq!(
+            seg("e6"),
+            Segment::Error {
+                subject: None,
+                fielder: 6,
+                throwing: false,
+                base: None,
+            }
+        );
+        assert_eq!(
+            seg("te5 2b"),
+            Segment::Error {
+                subject: None,
+                fielder: 5,
+                throwing: true,
+                base: Some(RunnerDest::Second),
+            }
+        );
+    }
+    #[test]
+    fn error_with_subject() {
+        assert_eq!(
+            seg("2 e7 3b"),
+            Segment::Error {
+                subject: Some(2),
+                fielder: 7,
+                throwing: false,
+                base: Some(RunnerDest::Third),
+            }
+        );
+    }
+    #[test]
+    fn error_invalid_base_or_extra() {
+        assert!(matches!(err("e6 lf"), ParseError::InvalidBase { .. }));
+        assert!(matches!(err("e6 2b 3b"), ParseError::ExtraTokens { .. }));
+    }
+
     // ── Steal ──
     #[test]
     fn steal_requires_subject() {
@@ -740,6 +1408,73 @@ mod tests {
         assert!(matches!(err("5 st"), ParseError::MissingObject { .. }));
     }
 
+    // ── Wild pitch / passed ball / defensive indifference / balk ──
+    #[test]
+    fn pitch_advances_take_subject_and_base() {
+        assert_eq!(
+            seg("3 wp sc"),
+            Segment::PitchAdvance {
+                subject: 3,
+                kind: CommandKind::WildPitch,
+                dest: RunnerDest::Score,
+            }
+        );
+        assert_eq!(
+            seg("2 pb 3b"),
+            Segment::PitchAdvance {
+                subject: 2,
+                kind: CommandKind::PassedBall,
+                dest: RunnerDest::Third,
+            }
+        );
+        assert!(matches!(err("wp 2b"), ParseError::MissingSubject { .. }));
+        assert!(matches!(err("1 di"), ParseError::MissingObject { .. }));
+    }
+    #[test]
+    fn caught_stealing_and_pickoff_carry_the_fielders() {
+        assert_eq!(
+            seg("5 cs 2b 26"),
+            Segment::RunnerCaught {
+                subject: 5,
+                kind: CommandKind::CaughtStealing,
+                base: Some(RunnerDest::Second),
+                fielders: vec![2, 6],
+            }
+        );
+        assert_eq!(
+            seg("3 po 1-3"),
+            Segment::RunnerCaught {
+                subject: 3,
+                kind: CommandKind::Pickoff,
+                base: None,
+                fielders: vec![1, 3],
+            }
+        );
+        assert_eq!(
+            seg("5 poCS 2b 136"),
+            Segment::RunnerCaught {
+                subject: 5,
+                kind: CommandKind::PickoffCaughtStealing,
+                base: Some(RunnerDest::Second),
+                fielders: vec![1, 3, 6],
+            }
+        );
+        assert!(matches!(err("cs 2b 26"), ParseError::MissingSubject { .. }));
+        assert!(matches!(err("5 cs 26"), ParseError::InvalidBase { .. }));
+        assert!(matches!(err("5 cs 2b"), ParseError::MissingObject { .. }));
+        assert!(matches!(
+            err("3 po lf"),
+            ParseError::InvalidFieldingSequence { .. }
+        ));
+        assert!(matches!(err("3 po 13 2b"), ParseError::ExtraTokens { .. }));
+    }
+    #[test]
+    fn balk_stands_alone() {
+        assert_eq!(seg("bk"), Segment::Balk);
+        assert!(matches!(err("3 bk"), ParseError::SubjectNotAllowed { .. }));
+        assert!(matches!(err("bk 2b"), ParseError::ExtraTokens { .. }));
+    }
+
     // ── Advance ──
     #[test]
     fn advance_standalone() {
@@ -779,6 +1514,39 @@ mod tests {
         assert_eq!(seg("playball"), Segment::Control(CommandKind::PlayBall));
     }
     #[test]
+    fn undo_takes_an_optional_count() {
+        assert_eq!(seg("undo"), Segment::Undo { count: 1 });
+        assert_eq!(seg("UNDO 3"), Segment::Undo { count: 3 });
+        for bad in ["undo 0", "undo 100", "undo x", "undo -1"] {
+            assert!(
+                matches!(err(bad), ParseError::InvalidUndoCount { .. }),
+                "{bad}"
+            );
+        }
+        assert!(matches!(err("undo 2 3"), ParseError::ExtraTokens { .. }));
+        assert!(matches!(
+            err("3 undo"),
+            ParseError::SubjectNotAllowed { .. }
+        ));
+    }
+
+    #[test]
+    fn rbi_takes_a_count_from_zero_to_four() {
+        assert_eq!(seg("rbi 0"), Segment::Rbi { count: 0 });
+        assert_eq!(seg("RBI 2"), Segment::Rbi { count: 2 });
+        for bad in ["rbi", "rbi 5", "rbi x", "rbi -1"] {
+            assert!(
+                matches!(err(bad), ParseError::InvalidRbiCount { .. }),
+                "{bad}"
+            );
+        }
+        assert!(matches!(err("rbi 1 2"), ParseError::ExtraTokens { .. }));
+        assert!(matches!(
+            err("4 rbi 1"),
+            ParseError::SubjectNotAllowed { .. }
+        ));
+    }
+    #[test]
     fn status_keywords() {
         assert_eq!(seg("regular"), Segment::Status(CommandKind::Regular));
         assert_eq!(seg("post"), Segment::Status(CommandKind::Postponed));
@@ -788,6 +1556,83 @@ mod tests {
         assert_eq!(seg("protest"), Segment::Status(CommandKind::Protested));
     }
     #[test]
+    fn pitching_change_takes_a_jersey() {
+        assert_eq!(seg("pc 34"), Segment::PitchingChange { jersey: 34 });
+        assert_eq!(seg("PC 0"), Segment::PitchingChange { jersey: 0 });
+        assert_eq!(seg("pc 07"), Segment::PitchingChange { jersey: 7 });
+        assert!(matches!(err("pc"), ParseError::MissingObject { .. }));
+        assert!(matches!(err("pc 100"), ParseError::InvalidJersey { .. }));
+        assert!(matches!(err("pc +5"), ParseError::InvalidJersey { .. }));
+        assert!(matches!(err("pc lf"), ParseError::InvalidJersey { .. }));
+        assert!(matches!(err("pc 34 12"), ParseError::ExtraTokens { .. }));
+        assert!(matches!(
+            err("5 pc 34"),
+            ParseError::SubjectNotAllowed { .. }
+        ));
+    }
+    #[test]
+    fn substitution_shapes() {
+        assert_eq!(
+            seg("ph 12"),
+            Segment::PinchHitter {
+                subject: None,
+                jersey: 12
+            }
+        );
+        assert_eq!(
+            seg("5 ph 12"),
+            Segment::PinchHitter {
+                subject: Some(5),
+                jersey: 12
+            }
+        );
+        assert_eq!(
+            seg("3 pr 27"),
+            Segment::PinchRunner {
+                subject: 3,
+                jersey: 27
+            }
+        );
+        assert_eq!(
+            seg("7 sub 22 lf"),
+            Segment::DefensiveSubstitution {
+                subject: 7,
+                jersey: 22,
+                position: Position::LeftField
+            }
+        );
+        assert_eq!(
+            seg("9 SUB 34 1"),
+            Segment::DefensiveSubstitution {
+                subject: 9,
+                jersey: 34,
+                position: Position::Pitcher
+            }
+        );
+        assert_eq!(
+            seg("4 pos ss"),
+            Segment::PositionChange {
+                subject: 4,
+                position: Position::Shortstop
+            }
+        );
+        assert!(matches!(err("pr 27"), ParseError::MissingSubject { .. }));
+        assert!(matches!(err("pos ss"), ParseError::MissingSubject { .. }));
+        assert!(matches!(err("7 sub 22"), ParseError::MissingObject { .. }));
+        assert!(matches!(
+            err("7 sub 22 xx"),
+            ParseError::InvalidPosition { .. }
+        ));
+        assert!(matches!(
+            err("4 pos 11"),
+            ParseError::InvalidPosition { .. }
+        ));
+        assert!(matches!(
+            err("7 sub 22 lf 3"),
+            ParseError::ExtraTokens { .. }
+        ));
+    }
+    #[test]
     fn keyword_with_subject_rejected() {
         assert!(matches!(
             err("5 playball"),
@@ -14,17 +14,25 @@
 //! | Fly verb           | `^ff?[1-9]$`             | `f8`, `ff3`                |
 //! | Line verb          | `^l[1-9]$`               | `l6`                       |
 //! | Infield-fly        | `^iff?[1-9]$`            | `if4`, `iff4`              |
+//! | Error verb         | `^t?e[1-9]$`             | `e6`, `te5`                |
+//! | Sacrifice fly      | `^sf[1-9]$`              | `sf8`                      |
+//! | Sacrifice bunt     | `^sh` + fielder(s)       | `sh1`, `sh13`, `sh5-4`     |
+//! | K, batter thrown   | `^k` + fielder(s)        | `k23`, `k3`, `k2-3`        |
 //! | Fielding seq       | `^[1-9]{2,}$` or dashed  | `63`, `862`, `6-3`, `8-6-2`|
 //! | Unassisted         | `^[1-9]$` (single digit) | `5` (same shape as subject)|
 //! | Zone               | enumerated               | `lf`, `rc`, `gll`          |
+//! | Hit modifier       | enumerated               | `gr`, `itp`, `bu`          |
 //! | Base               | enumerated               | `1b`, `sc`, `home`         |
 //!
 //! Parameter-less verbs cover every command whose token is a fixed
 //! keyword with no numeric payload (hit verbs `h`/`2h`/`3h`/`hr`, pitches
-//! `b`/`k`/`s`/`f`/`fl`, steal `st`, engine control `exit`/`playball`,
-//! status `regular`/`post`/…). They all classify into
-//! [`TokenKind::Verb`] parameterised by the matching [`CommandKind`]
-//! variant. The full list lives in [`CommandKind`] itself — see
+//! `b`/`k`/`s`/`f`/`fl`, dropped third strikes `kwp`/`kpb`, base awards
+//! `hbp`/`ibb`/`ci`, steal `st` and the
+//! other in-pitch runner events `wp`/`pb`/`di`/`bk`/`cs`/`po`/`pocs`,
+//! engine control `exit`/`playball`/`undo`, status `regular`/`post`/…, the
+//! substitutions `pc`/`ph`/`pr`/`sub`/`pos`, the RBI credit `rbi`). They
+//! all classify into [`TokenKind::Verb`] parameterised by the matching
+//! [`CommandKind`] variant. The full list lives in [`CommandKind`] itself — see
 //! `crate::engine::commands::kind`.
 //!
 //! Ambiguity note: `^[1-9]$` matches both *subject* and *unassisted-out
@@ -35,6 +43,7 @@ use std::sync::LazyLock;
 
 use crate::engine::commands::kind::CommandKind;
 use crate::models::field_zone::FieldZone;
+use crate::models::plate_appearance::HitModifier;
 use crate::models::runner::RunnerDest;
 
 // ─── Regex patterns (compiled once) ──────────────────────────────────────────
@@ -72,6 +81,24 @@ pub(super) static RE_FIELDING_SEQ_COMPACT: LazyLock<Regex> =
 pub(super) static RE_FIELDING_SEQ_DASHED: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[1-9](-[1-9])+$").unwrap());
 
+/// Error verb: `e<fielder>` (fielding) or `te<fielder>` (throwing).
+pub(super) static RE_ERROR_VERB: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?i)(t?)e([1-9])$").unwrap());
+
+/// Sacrifice-fly verb: `sf<fielder>`.
+pub(super) static RE_SAC_FLY_VERB: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?i)sf([1-9])$").unwrap());
+
+/// Sacrifice-bunt verb: `sh` followed by one fielder or a fielding
+/// sequence, compact or dashed (`sh1`, `sh13`, `sh5-4`).
+pub(super) static RE_SAC_BUNT_VERB: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?i)sh([1-9]+|[1-9](?:-[1-9])+)$").unwrap());
+
+/// Dropped-third-strike verb with the batter thrown out: `k` followed by
+/// the fielders who made the play (`k23`, `k3`, `k2-3`).
+pub(super) static RE_K_THROW_VERB: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?i)k([1-9]+|[1-9](?:-[1-9])+)$").unwrap());
+
 // ─── Lexical kinds ───────────────────────────────────────────────────────────
 
 /// What kind of token a single whitespace-separated chunk of a segment
@@ -85,7 +112,7 @@ pub(super) static RE_FIELDING_SEQ_DASHED: LazyLock<Regex> =
 /// (hit verbs, pitches, steal, control / status keywords) collapse into
 /// a single [`TokenKind::Verb`] variant parameterised by
 /// [`CommandKind`]. Verbs that do carry a parameter (`f<n>`, `l<n>`,
-/// `if<n>`, `o<n>`) keep their own variants because the parameter is
+/// `if<n>`, `o<n>`, `e<n>`, `sf<n>`, `sh<seq>`, `k<seq>`) keep their own variants because the parameter is
 /// part of the token's lexical shape, not a separate field downstream.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) enum TokenKind {
@@ -101,10 +128,21 @@ pub(super) enum TokenKind {
     LineVerb { fielder: u8 },
     /// Infield fly `if<n>` / `iff<n>`.
     InfieldFlyVerb { fielder: u8 },
+    /// Fielding error `e<n>` or throwing error `te<n>`.
+    ErrorVerb { fielder: u8, throwing: bool },
+    /// Sacrifice fly `sf<n>`.
+    SacFlyVerb { fielder: u8 },
+    /// Sacrifice bunt `sh<n>` or `sh<seq>`; one or more fielders.
+    SacBuntVerb { fielders: Vec<u8> },
+    /// Dropped third strike, batter thrown out: `k<n>` or `k<seq>`.
+    StrikeoutThrowVerb { fielders: Vec<u8> },
     /// Multi-fielder sequence (`63`, `6-3`, `862`, `8-6-2`).
     FieldingSeq(Vec<u8>),
     /// Zone tag (only valid as object of a hit verb).
     Zone(FieldZone),
+    /// Ground-rule / inside-the-park / bunt qualifier (only valid as object
+    /// of a hit verb).
+    HitModifier(HitModifier),
     /// Base tag (only valid as object of FC / steal / advance).
     Base(RunnerDest),
     /// Anything that did not match a known pattern. Carries the raw token.
@@ -134,6 +172,8 @@ pub(super) fn classify(raw: &str) -> TokenKind {
     match lower.as_str() {
         "exit" | "quit" => return TokenKind::Verb(CommandKind::Exit),
         "playball" => return TokenKind::Verb(CommandKind::PlayBall),
+        "undo" => return TokenKind::Verb(CommandKind::Undo),
+        "rbi" => return TokenKind::Verb(CommandKind::Rbi),
 
         "regular" => return TokenKind::Verb(CommandKind::Regular),
         "post" => return TokenKind::Verb(CommandKind::Postponed),
@@ -147,13 +187,32 @@ pub(super) fn classify(raw: &str) -> TokenKind {
         "s" => return TokenKind::Verb(CommandKind::SwingingStrike),
         "f" => return TokenKind::Verb(CommandKind::Foul),
         "fl" => return TokenKind::Verb(CommandKind::FoulBunt),
+        "kwp" => return TokenKind::Verb(CommandKind::StrikeoutWildPitch),
+        "kpb" => return TokenKind::Verb(CommandKind::StrikeoutPassedBall),
 
         "h" => return TokenKind::Verb(CommandKind::Single),
         "2h" => return TokenKind::Verb(CommandKind::Double),
         "3h" => return TokenKind::Verb(CommandKind::Triple),
         "hr" => return TokenKind::Verb(CommandKind::HomeRun),
 
+        "hbp" => return TokenKind::Verb(CommandKind::HitByPitch),
+        "ibb" => return TokenKind::Verb(CommandKind::IntentionalWalk),
+        "ci" => return TokenKind::Verb(CommandKind::CatcherInterference),
+
         "st" => return TokenKind::Verb(CommandKind::Steal),
+        "wp" => return TokenKind::Verb(CommandKind::WildPitch),
+        "pb" => return TokenKind::Verb(CommandKind::PassedBall),
+        "di" => return TokenKind::Verb(CommandKind::DefensiveIndifference),
+        "bk" => return TokenKind::Verb(CommandKind::Balk),
+        "cs" => return TokenKind::Verb(CommandKind::CaughtStealing),
+        "po" => return TokenKind::Verb(CommandKind::Pickoff),
+        "pocs" => return TokenKind::Verb(CommandKind::PickoffCaughtStealing),
+
+        "pc" => return TokenKind::Verb(CommandKind::PitchingChange),
+        "ph" => return TokenKind::Verb(CommandKind::PinchHitter),
+        "pr" => return TokenKind::Verb(CommandKind::PinchRunner),
+        "sub" => return TokenKind::Verb(CommandKind::DefensiveSubstitution),
+        "pos" => return TokenKind::Verb(CommandKind::PositionChange),
 
         _ => {}
     }
@@ -189,6 +248,35 @@ pub(super) fn classify(raw: &str) -> TokenKind {
         return TokenKind::LineVerb { fielder };
     }
 
+    if let Some(caps) = RE_ERROR_VERB.captures(trimmed) {
+        let throwing = !caps[1].is_empty(); // "te"
+        let fielder = caps[2].parse::<u8>().unwrap();
+        return TokenKind::ErrorVerb { fielder, throwing };
+    }
+
+    if let Some(caps) = RE_SAC_FLY_VERB.captures(trimmed) {
+        let fielder = caps[1].parse::<u8>().unwrap();
+        return TokenKind::SacFlyVerb { fielder };
+    }
+
+    if let Some(caps) = RE_SAC_BUNT_VERB.captures(trimmed) {
+        let fielders: Vec<u8> = caps[1]
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| d as u8)
+            .collect();
+        return TokenKind::SacBuntVerb { fielders };
+    }
+
+    if let Some(caps) = RE_K_THROW_VERB.captures(trimmed) {
+        let fielders: Vec<u8> = caps[1]
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| d as u8)
+            .collect();
+        return TokenKind::StrikeoutThrowVerb { fielders };
+    }
+
     // Fielding sequence: dashed first (wouldn't match compact anyway).
     if RE_FIELDING_SEQ_DASHED.is_match(trimmed) {
         let fielders: Vec<u8> = trimmed
@@ -205,10 +293,13 @@ pub(super) fn classify(raw: &str) -> TokenKind {
         return TokenKind::FieldingSeq(fielders);
     }
 
-    // Zone / base tags — these match by exact keyword.
+    // Zone / modifier / base tags — these match by exact keyword.
     if let Some(zone) = FieldZone::parse(trimmed) {
         return TokenKind::Zone(zone);
     }
+    if let Some(modifier) = HitModifier::parse(trimmed) {
+        return TokenKind::HitModifier(modifier);
+    }
     if let Some(base) = RunnerDest::parse(trimmed) {
         return TokenKind::Base(base);
     }
@@ -253,6 +344,19 @@ mod tests {
         assert_eq!(classify("fl"), TokenKind::Verb(CommandKind::FoulBunt));
     }
 
+    #[test]
+    fn award_verbs_recognised() {
+        assert_eq!(classify("hbp"), TokenKind::Verb(CommandKind::HitByPitch));
+        assert_eq!(
+            classify("IBB"),
+            TokenKind::Verb(CommandKind::IntentionalWalk)
+        );
+        assert_eq!(
+            classify("ci"),
+            TokenKind::Verb(CommandKind::CatcherInterference)
+        );
+    }
+
     #[test]
     fn fc_verb_extracts_fielder() {
         assert_eq!(classify("o6"), TokenKind::FcVerb { fielder: 6 });
@@ -289,6 +393,80 @@ mod tests {
         assert_eq!(classify("iff4"), TokenKind::InfieldFlyVerb { fielder: 4 });
     }
 
+    #[test]
+    fn error_verb_fielding_vs_throwing() {
+        assert_eq!(
+            classify("e6"),
+            TokenKind::ErrorVerb {
+                fielder: 6,
+                throwing: false,
+            }
+        );
+        assert_eq!(
+            classify("TE5"),
+            TokenKind::ErrorVerb {
+                fielder: 5,
+                throwing: true,
+            }
+        );
+        assert!(matches!(classify("e0"), TokenKind::Unknown(_)));
+        assert!(matches!(classify("ee6"), TokenKind::Unknown(_)));
+    }
+
+    #[test]
+    fn sacrifice_verbs() {
+        assert_eq!(classify("sf8"), TokenKind::SacFlyVerb { fielder: 8 });
+        assert_eq!(
+            classify("SH13"),
+            TokenKind::SacBuntVerb {
+                fielders: vec![1, 3],
+            }
+        );
+        assert_eq!(
+            classify("sh5-4"),
+            TokenKind::SacBuntVerb {
+                fielders: vec![5, 4],
+            }
+        );
+        assert_eq!(
+            classify("sh1"),
+            TokenKind::SacBuntVerb { fielders: vec![1] }
+        );
+        assert!(matches!(classify("sf89"), TokenKind::Unknown(_)));
+        assert!(matches!(classify("sh"), TokenKind::Unknown(_)));
+    }
+
+    #[test]
+    fn dropped_third_strike_verbs() {
+        assert_eq!(
+            classify("kwp"),
+            TokenKind::Verb(CommandKind::StrikeoutWildPitch)
+        );
+        assert_eq!(
+            classify("KPB"),
+            TokenKind::Verb(CommandKind::StrikeoutPassedBall)
+        );
+        assert_eq!(
+            classify("k23"),
+            TokenKind::StrikeoutThrowVerb {
+                fielders: vec![2, 3],
+            }
+        );
+        assert_eq!(
+            classify("k2-3"),
+            TokenKind::StrikeoutThrowVerb {
+                fielders: vec![2, 3],
+            }
+        );
+        assert_eq!(
+            classify("k3"),
+            TokenKind::StrikeoutThrowVerb { fielders: vec![3] }
+        );
+        // A bare `k` is still the called strike.
+        assert_eq!(classify("k"), TokenKind::Verb(CommandKind::CalledStrike));
+        assert!(matches!(classify("k0"), TokenKind::Unknown(_)));
+    }
+
     #[test]
     fn fielding_sequence_compact_and_dashed() {
         assert_eq!(classify("63"), TokenKind::FieldingSeq(vec![6, 3]));
@@ -321,6 +499,16 @@ mod tests {
             classify("home"),
             TokenKind::Base(RunnerDest::Score)
         ));
+        assert!(matches!(classify("grl"), TokenKind::Zone(FieldZone::GRL)));
+        assert_eq!(
+            classify("GR"),
+            TokenKind::HitModifier(HitModifier::GroundRule)
+        );
+        assert_eq!(
+            classify("itp"),
+            TokenKind::HitModifier(HitModifier::InsideThePark)
+        );
+        assert_eq!(classify("bu"), TokenKind::HitModifier(HitModifier::Bunt));
     }
 
     #[test]
@@ -328,6 +516,7 @@ mod tests {
         assert_eq!(classify("exit"), TokenKind::Verb(CommandKind::Exit));
         assert_eq!(classify("quit"), TokenKind::Verb(CommandKind::Exit));
         assert_eq!(classify("playball"), TokenKind::Verb(CommandKind::PlayBall));
+        assert_eq!(classify("undo"), TokenKind::Verb(CommandKind::Undo));
         assert_eq!(classify("regular"), TokenKind::Verb(CommandKind::Regular));
         assert_eq!(classify("post"), TokenKind::Verb(CommandKind::Postponed));
         assert_eq!(classify("cancel"), TokenKind::Verb(CommandKind::Cancelled));
@@ -335,6 +524,30 @@ mod tests {
         assert_eq!(classify("forf"), TokenKind::Verb(CommandKind::Forfeited));
         assert_eq!(classify("protest"), TokenKind::Verb(CommandKind::Protested));
         assert_eq!(classify("st"), TokenKind::Verb(CommandKind::Steal));
+        assert_eq!(classify("wp"), TokenKind::Verb(CommandKind::WildPitch));
+        assert_eq!(classify("PB"), TokenKind::Verb(CommandKind::PassedBall));
+        assert_eq!(
+            classify("di"),
+            TokenKind::Verb(CommandKind::DefensiveIndifference)
+        );
+        assert_eq!(classify("bk"), TokenKind::Verb(CommandKind::Balk));
+        assert_eq!(classify("cs"), TokenKind::Verb(CommandKind::CaughtStealing));
+        assert_eq!(classify("po"), TokenKind::Verb(CommandKind::Pickoff));
+        assert_eq!(
+            classify("poCS"),
+            TokenKind::Verb(CommandKind::PickoffCaughtStealing)
+        );
+        assert_eq!(classify("pc"), TokenKind::Verb(CommandKind::PitchingChange));
+        assert_eq!(classify("ph"), TokenKind::Verb(CommandKind::PinchHitter));
+        assert_eq!(classify("pr"), TokenKind::Verb(CommandKind::PinchRunner));
+        assert_eq!(
+            classify("sub"),
+            TokenKind::Verb(CommandKind::DefensiveSubstitution)
+        );
+        assert_eq!(
+            classify("pos"),
+            TokenKind::Verb(CommandKind::PositionChange)
+        );
     }
 
     #[test]
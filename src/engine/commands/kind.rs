@@ -39,14 +39,19 @@
 ///
 /// This is the axis used by the validator's mixing rules:
 /// - `Control` and `Status` are single-segment lines, never mixed.
+/// - `Substitution` segments only share a line with each other.
 /// - `Pitch` is an in-pitch event; it can coexist with `Steal`.
-/// - `Hit`, `BatterOut`, `FielderChoice`, `Advance` are end-of-PA
-///   actions; they cannot coexist with a `Pitch` or a `Steal`.
-/// - `Steal` is in-pitch; it can coexist with a `Pitch` but not with
-///   end-of-PA actions.
+/// - `Hit`, `Award`, `BatterOut`, `FielderChoice`, `Error`,
+///   `DroppedThirdStrike`, `Advance` are end-of-PA actions; they cannot
+///   coexist with a `Pitch` or a `Steal`.
+/// - `Steal` (including wild pitch, passed ball, defensive indifference,
+///   balk, caught stealing and pickoff) is in-pitch; it can coexist with
+///   a `Pitch` but not with end-of-PA actions.
+/// - `Credit` rides along with the play the batter completes on the same
+///   line.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CommandFamily {
-    /// Engine control (`exit`, `playball`).
+    /// Engine control (`exit`, `playball`, `undo`).
     Control,
     /// Game status change (`regular`, `post`, …).
     Status,
@@ -55,14 +60,34 @@ pub enum CommandFamily {
     Pitch,
     /// Hit by a batter (`h`, `2h`, `3h`, `hr`).
     Hit,
-    /// Out on a batter or a runner (`63`, `f8`, `ff3`, `l6`, `if4`, `5`).
+    /// First base awarded to the batter without a ball in play (`hbp`,
+    /// `ibb`, `ci`).
+    Award,
+    /// Out on a batter or a runner (`63`, `f8`, `ff3`, `l6`, `if4`, `5`),
+    /// including batter sacrifices (`sf8`, `sh13`).
     BatterOut,
     /// Fielder's choice (`o6 1b`).
     FielderChoice,
-    /// Stolen base (`5 st 2b`).
+    /// Fielding or throwing error charged to a fielder (`e6`, `te5`,
+    /// `2 e7 3b`).
+    Error,
+    /// Strike three not caught: the batter reaches first on a wild pitch
+    /// or passed ball (`kwp`, `kpb`) or is thrown out (`k23`).
+    DroppedThirdStrike,
+    /// Stolen base (`5 st 2b`) and the other in-pitch runner events:
+    /// wild pitch, passed ball, defensive indifference (`3 wp sc`), balk
+    /// (`bk`), caught stealing (`5 cs 2b 26`) and pickoff (`3 po 13`).
     Steal,
     /// Standalone runner-advance override (`3 2b`).
     Advance,
+    /// Lineup change: pitching change (`pc 21`), pinch hitter (`ph 12`),
+    /// pinch runner (`3 pr 27`), defensive substitution (`7 sub 22 lf`)
+    /// or position change (`4 pos ss`). Only mixes with other
+    /// substitutions, so a double switch fits on one line.
+    Substitution,
+    /// Scorer's ruling on the batter's statistics (`rbi 2`), overriding
+    /// the computed one for the play on the same line.
+    Credit,
 }
 
 // ─── The vocabulary ──────────────────────────────────────────────────────────
@@ -76,6 +101,7 @@ pub enum CommandKind {
     // ── Engine control ─────────────────────────────────────────────
     Exit,
     PlayBall,
+    Undo,
 
     // ── Game status ────────────────────────────────────────────────
     Regular,
@@ -98,6 +124,11 @@ pub enum CommandKind {
     Triple,
     HomeRun,
 
+    // ── Base awards ────────────────────────────────────────────────
+    HitByPitch,
+    IntentionalWalk,
+    CatcherInterference,
+
     // ── Batter / runner outs ───────────────────────────────────────
     //
     // These variants are lexical verbs, not semantic targets: the same
@@ -110,13 +141,56 @@ pub enum CommandKind {
     FoulFlyOut,
     LineOut,
     InfieldFly,
+    // Sacrifices are batter-only: a fly (`sf<n>`) that lets a runner
+    // tag up and score, or a bunt (`sh<seq>`) that moves runners along.
+    SacrificeFly,
+    SacrificeBunt,
 
     // ── Composite base play ────────────────────────────────────────
     FielderChoice,
 
+    // ── Fielding errors ────────────────────────────────────────────
+    //
+    // Like the out verbs, these are lexical: the subject rule decides
+    // whether the batter reached on the error or a runner advanced on it.
+    FieldingError,
+    ThrowingError,
+
+    // ── Dropped third strike ───────────────────────────────────────
+    //
+    // The pitcher is credited with the strikeout either way; the batter
+    // reaches on a wild pitch (`kwp`) or passed ball (`kpb`), or is
+    // retired by a throw (`k23`, `k3`).
+    StrikeoutWildPitch,
+    StrikeoutPassedBall,
+    StrikeoutThrownOut,
+
     // ── Runner actions ─────────────────────────────────────────────
     Steal,
+    // In-pitch advances that are not steals: the runner moves up on a
+    // wild pitch, a passed ball or defensive indifference (`3 wp sc`),
+    // or every runner moves up on a balk (`bk`).
+    WildPitch,
+    PassedBall,
+    DefensiveIndifference,
+    Balk,
+    // Runner put out between pitches: caught stealing (`5 cs 2b 26`),
+    // picked off (`3 po 13`) or picked off and caught stealing
+    // (`5 pocs 2b 136`).
+    CaughtStealing,
+    Pickoff,
+    PickoffCaughtStealing,
     Advance,
+
+    // ── Substitutions ──────────────────────────────────────────────
+    PitchingChange,
+    PinchHitter,
+    PinchRunner,
+    DefensiveSubstitution,
+    PositionChange,
+
+    // ── Scorer's credit ────────────────────────────────────────────
+    Rbi,
 }
 
 // ─── Behaviour ───────────────────────────────────────────────────────────────
@@ -125,7 +199,7 @@ impl CommandKind {
     /// The family this command belongs to.
     pub const fn family(self) -> CommandFamily {
         match self {
-            Self::Exit | Self::PlayBall => CommandFamily::Control,
+            Self::Exit | Self::PlayBall | Self::Undo => CommandFamily::Control,
 
             Self::Regular
             | Self::Postponed
@@ -142,16 +216,39 @@ impl CommandKind {
 
             Self::Single | Self::Double | Self::Triple | Self::HomeRun => CommandFamily::Hit,
 
+            Self::HitByPitch | Self::IntentionalWalk | Self::CatcherInterference => {
+                CommandFamily::Award
+            }
+
             Self::Unassisted
             | Self::GroundOut
             | Self::FlyOut
             | Self::FoulFlyOut
             | Self::LineOut
-            | Self::InfieldFly => CommandFamily::BatterOut,
+            | Self::InfieldFly
+            | Self::SacrificeFly
+            | Self::SacrificeBunt => CommandFamily::BatterOut,
 
             Self::FielderChoice => CommandFamily::FielderChoice,
-            Self::Steal => CommandFamily::Steal,
+            Self::FieldingError | Self::ThrowingError => CommandFamily::Error,
+            Self::StrikeoutWildPitch | Self::StrikeoutPassedBall | Self::StrikeoutThrownOut => {
+                CommandFamily::DroppedThirdStrike
+            }
+            Self::Steal
+            | Self::WildPitch
+            | Self::PassedBall
+            | Self::DefensiveIndifference
+            | Self::Balk
+            | Self::CaughtStealing
+            | Self::Pickoff
+            | Self::PickoffCaughtStealing => CommandFamily::Steal,
             Self::Advance => CommandFamily::Advance,
+            Self::PitchingChange
+            | Self::PinchHitter
+            | Self::PinchRunner
+            | Self::DefensiveSubstitution
+            | Self::PositionChange => CommandFamily::Substitution,
+            Self::Rbi => CommandFamily::Credit,
         }
     }
 
@@ -166,6 +263,7 @@ impl CommandKind {
         match self {
             Self::Exit => "exit",
             Self::PlayBall => "playball",
+            Self::Undo => "undo",
 
             Self::Regular => "regular",
             Self::Postponed => "post",
@@ -185,16 +283,40 @@ impl CommandKind {
             Self::Triple => "3h",
             Self::HomeRun => "hr",
 
+            Self::HitByPitch => "hbp",
+            Self::IntentionalWalk => "ibb",
+            Self::CatcherInterference => "ci",
+
             Self::Unassisted => "<digit>",
             Self::GroundOut => "<sequence>",
             Self::FlyOut => "f<n>",
             Self::FoulFlyOut => "ff<n>",
             Self::LineOut => "l<n>",
             Self::InfieldFly => "if<n>",
+            Self::SacrificeFly => "sf<n>",
+            Self::SacrificeBunt => "sh<sequence>",
 
             Self::FielderChoice => "o<n>",
+            Self::FieldingError => "e<n>",
+            Self::ThrowingError => "te<n>",
+            Self::StrikeoutWildPitch => "kwp",
+            Self::StrikeoutPassedBall => "kpb",
+            Self::StrikeoutThrownOut => "k<sequence>",
             Self::Steal => "st",
+            Self::WildPitch => "wp",
+            Self::PassedBall => "pb",
+            Self::DefensiveIndifference => "di",
+            Self::Balk => "bk",
+            Self::CaughtStealing => "cs",
+            Self::Pickoff => "po",
+            Self::PickoffCaughtStealing => "pocs",
             Self::Advance => "<base>",
+            Self::PitchingChange => "pc",
+            Self::PinchHitter => "ph",
+            Self::PinchRunner => "pr",
+            Self::DefensiveSubstitution => "sub",
+            Self::PositionChange => "pos",
+            Self::Rbi => "rbi",
         }
     }
 }
@@ -211,6 +333,7 @@ mod tests {
     const ALL: &[CommandKind] = &[
         CommandKind::Exit,
         CommandKind::PlayBall,
+        CommandKind::Undo,
         CommandKind::Regular,
         CommandKind::Postponed,
         CommandKind::Cancelled,
@@ -226,24 +349,47 @@ mod tests {
         CommandKind::Double,
         CommandKind::Triple,
         CommandKind::HomeRun,
+        CommandKind::HitByPitch,
+        CommandKind::IntentionalWalk,
+        CommandKind::CatcherInterference,
         CommandKind::Unassisted,
         CommandKind::GroundOut,
         CommandKind::FlyOut,
         CommandKind::FoulFlyOut,
         CommandKind::LineOut,
         CommandKind::InfieldFly,
+        CommandKind::SacrificeFly,
+        CommandKind::SacrificeBunt,
         CommandKind::FielderChoice,
+        CommandKind::FieldingError,
+        CommandKind::ThrowingError,
+        CommandKind::StrikeoutWildPitch,
+        CommandKind::StrikeoutPassedBall,
+        CommandKind::StrikeoutThrownOut,
         CommandKind::Steal,
+        CommandKind::WildPitch,
+        CommandKind::PassedBall,
+        CommandKind::DefensiveIndifference,
+        CommandKind::Balk,
+        CommandKind::CaughtStealing,
+        CommandKind::Pickoff,
+        CommandKind::PickoffCaughtStealing,
         CommandKind::Advance,
+        CommandKind::PitchingChange,
+        CommandKind::PinchHitter,
+        CommandKind::PinchRunner,
+        CommandKind::DefensiveSubstitution,
+        CommandKind::PositionChange,
+        CommandKind::Rbi,
     ];
 
     /// If you add a variant to `CommandKind`, extend `ALL` above.
     /// This test will otherwise remind you by failing.
     #[test]
     fn all_variants_are_listed_here() {
-        // 26 variants as of v0.11.1. If this count diverges from ALL,
+        // 50 variants as of v0.12.0. If this count diverges from ALL,
         // the test setup is out of date.
-        assert_eq!(ALL.len(), 26);
+        assert_eq!(ALL.len(), 50);
     }
 
     #[test]
@@ -270,13 +416,18 @@ mod tests {
 
         // These counts encode the current partition. If you move a
         // variant between families, update this test deliberately.
-        assert_eq!(family_counts(CommandFamily::Control), 2);
+        assert_eq!(family_counts(CommandFamily::Control), 3);
         assert_eq!(family_counts(CommandFamily::Status), 6);
         assert_eq!(family_counts(CommandFamily::Pitch), 5);
         assert_eq!(family_counts(CommandFamily::Hit), 4);
-        assert_eq!(family_counts(CommandFamily::BatterOut), 6);
+        assert_eq!(family_counts(CommandFamily::Award), 3);
+        assert_eq!(family_counts(CommandFamily::BatterOut), 8);
         assert_eq!(family_counts(CommandFamily::FielderChoice), 1);
-        assert_eq!(family_counts(CommandFamily::Steal), 1);
+        assert_eq!(family_counts(CommandFamily::Error), 2);
+        assert_eq!(family_counts(CommandFamily::DroppedThirdStrike), 3);
+        assert_eq!(family_counts(CommandFamily::Steal), 8);
         assert_eq!(family_counts(CommandFamily::Advance), 1);
+        assert_eq!(family_counts(CommandFamily::Substitution), 5);
+        assert_eq!(family_counts(CommandFamily::Credit), 1);
     }
 }
@@ -104,6 +104,7 @@ mod tests {
             EngineCommand::Single {
                 zone: Some(FieldZone::LF),
                 runner_overrides,
+                ..
             } => {
                 assert_eq!(runner_overrides.len(), 1);
                 assert_eq!(runner_overrides[0].order, 5);
@@ -1,31 +1,99 @@
 use crate::engine::scoring::BatterOutType;
 use crate::engine::scoring::batter_outs::DefensivePlayCommand;
 use crate::models::field_zone::FieldZone;
-use crate::models::runner::RunnerOverride;
+use crate::models::plate_appearance::{DroppedThirdStrike, HitModifier};
+use crate::models::runner::{FieldingError, RunnerDest, RunnerOverride};
 use crate::models::types::GameStatus;
-use crate::{BatterOrder, Pitch};
+use crate::{BatterOrder, Pitch, Position};
 
 #[derive(Debug, Clone)]
 pub enum EngineCommand {
     Exit,
     SetStatus(GameStatus),
     PlayBall,
+    /// `undo [n]`: remove the last `count` plate appearances. DB-backed,
+    /// handled by the engine loop.
+    Undo {
+        count: u8,
+    },
+    /// `rbi <n>`: the scorer's RBI count for the plate appearance settled
+    /// on the same line, in place of the computed one. Handled by the
+    /// engine loop.
+    CreditRbi {
+        rbi: u8,
+    },
+    /// Pitching change: `pc <jersey>`. The fielding team's roster lives
+    /// in the database, so the engine loop resolves the jersey; the same
+    /// holds for the other substitutions below.
+    PitchingChange {
+        jersey: u8,
+    },
+    /// Pinch hitter for the current batter: `ph <jersey>`.
+    PinchHitter {
+        jersey: u8,
+    },
+    /// Pinch runner for the runner in batting slot `order`:
+    /// `3 pr <jersey>`.
+    PinchRunner {
+        order: u8,
+        jersey: u8,
+    },
+    /// Fielding-team substitution into batting slot `order`:
+    /// `7 sub <jersey> lf`.
+    DefensiveSubstitution {
+        order: u8,
+        jersey: u8,
+        position: Position,
+    },
+    /// Fielding-team player in batting slot `order` moves to `position`:
+    /// `4 pos ss`.
+    PositionChange {
+        order: u8,
+        position: Position,
+    },
     Pitch(Pitch),
 
     Single {
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
         runner_overrides: Vec<RunnerOverride>,
     },
     Double {
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
         runner_overrides: Vec<RunnerOverride>,
     },
     Triple {
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
         runner_overrides: Vec<RunnerOverride>,
     },
     HomeRun {
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
+        runner_overrides: Vec<RunnerOverride>,
+    },
+
+    /// First base awarded to the batter; runners advance only if forced.
+    HitByPitch,
+    /// Intentional walk: no pitches required.
+    IntentionalWalk,
+    CatcherInterference,
+
+    /// Batter reaches on an error: `e6`, `te5 2b`, `e6, 3 sc`.
+    /// Runners not named in `runner_overrides` advance as on a hit
+    /// covering the same number of bases.
+    ReachedOnError {
+        error: FieldingError,
+        reached_base: RunnerDest,
+        runner_overrides: Vec<RunnerOverride>,
+    },
+
+    /// Strike three not caught: `kwp`, `kpb`, `k23`. The pitcher gets
+    /// the strikeout; the batter reaches first unless thrown out. Runners
+    /// move only if forced, unless named in `runner_overrides`.
+    DroppedThirdStrike {
+        how: DroppedThirdStrike,
         runner_overrides: Vec<RunnerOverride>,
     },
 
@@ -33,7 +101,29 @@ pub enum EngineCommand {
     /// e.g. `6 st 2b` — runner in batting slot 6 steals second.
     StealBase {
         order: u8,
-        dest: crate::models::runner::RunnerDest,
+        dest: RunnerDest,
+    },
+
+    /// Runner moves up between pitches without a steal:
+    /// `3 wp sc`, `2 pb 3b`, `1 di 2b`.
+    PitchAdvance {
+        order: u8,
+        dest: RunnerDest,
+        reason: PitchAdvanceReason,
+    },
+
+    /// Balk: every runner moves up one base.
+    Balk,
+
+    /// Runner put out between pitches: `5 cs 2b 26`, `3 po 13`,
+    /// `5 pocs 2b 136`. `dest` is the base the runner was going for
+    /// (`None` on a plain pickoff); the last fielder gets the putout,
+    /// the others an assist.
+    RunnerCaught {
+        order: u8,
+        kind: RunnerCaughtKind,
+        dest: Option<RunnerDest>,
+        fielders: Vec<u8>,
     },
 
     BatterOut {
@@ -43,3 +133,80 @@ pub enum EngineCommand {
 
     DefensivePlay(DefensivePlayCommand),
 }
+
+impl EngineCommand {
+    /// Lineup changes: the validator keeps them on lines of their own.
+    pub fn is_substitution(&self) -> bool {
+        matches!(
+            self,
+            Self::PitchingChange { .. }
+                | Self::PinchHitter { .. }
+                | Self::PinchRunner { .. }
+                | Self::DefensiveSubstitution { .. }
+                | Self::PositionChange { .. }
+        )
+    }
+}
+
+/// Why a runner moved up on a [`EngineCommand::PitchAdvance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PitchAdvanceReason {
+    WildPitch,
+    PassedBall,
+    DefensiveIndifference,
+}
+
+impl PitchAdvanceReason {
+    /// `advancement_type` stored on the runner movement row.
+    pub fn advancement_type(self) -> &'static str {
+        match self {
+            Self::WildPitch => "wild_pitch",
+            Self::PassedBall => "passed_ball",
+            Self::DefensiveIndifference => "defensive_indifference",
+        }
+    }
+
+    /// Human-readable label for the play-by-play log.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WildPitch => "wild pitch",
+            Self::PassedBall => "passed ball",
+            Self::DefensiveIndifference => "defensive indifference",
+        }
+    }
+
+    /// A passed ball is a catcher's misplay: a run that scores because of
+    /// it is unearned. Wild pitches and defensive indifference are charged
+    /// to the pitcher like any other advance.
+    pub fn is_earned(self) -> bool {
+        !matches!(self, Self::PassedBall)
+    }
+}
+
+/// How a runner was put out on a [`EngineCommand::RunnerCaught`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunnerCaughtKind {
+    CaughtStealing,
+    Pickoff,
+    PickoffCaughtStealing,
+}
+
+impl RunnerCaughtKind {
+    /// `advancement_type` stored on the runner movement row.
+    pub fn advancement_type(self) -> &'static str {
+        match self {
+            Self::CaughtStealing => "caught_stealing",
+            Self::Pickoff => "pickoff",
+            Self::PickoffCaughtStealing => "pickoff_caught_stealing",
+        }
+    }
+
+    /// Human-readable label for the play-by-play log.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CaughtStealing => "caught stealing",
+            Self::Pickoff => "picked off",
+            Self::PickoffCaughtStealing => "picked off, caught stealing",
+        }
+    }
+}
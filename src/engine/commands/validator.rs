@@ -11,12 +11,27 @@
 //!   base; if the subject equals the current batter, the segment is
 //!   reinterpreted as a batter-out;
 //! - a lone runner-advance override (`<n> <base>`) needs a triggering
-//!   play (hit or FC) on the same line;
+//!   play (hit, FC, batter reaching on error, batter out or dropped third
+//!   strike) on the same
+//!   line; the same holds for a runner advancing on an error
+//!   (`<n> e<f> <base>`);
+//! - runners cannot advance on a batter's out that retires the side;
+//! - a runner moving on a pitch cannot land on or pass a runner ahead
+//!   who does not move further on the same line;
+//! - a sacrifice needs fewer than two outs, and a sacrifice fly needs a
+//!   runner to score;
+//! - a dropped third strike needs two strikes in the count and either
+//!   first base open or two outs;
 //! - the infield-fly rule requires fewer than two outs and runners on
 //!   both 1B and 2B simultaneously;
 //! - no single action may record more than three outs;
 //! - control / status / pitch segments cannot be mixed with action
-//!   segments on the same line.
+//!   segments on the same line;
+//! - substitutions share a line only with other substitutions; a pinch
+//!   hitter must replace the current batter and a pinch runner a runner
+//!   on base;
+//! - an RBI credit (`rbi <n>`) is given once, on the line of the play that
+//!   ends the plate appearance.
 //!
 //! Errors are **accumulated**: when per-segment semantic checks spot a
 //! problem, the offending segment is dropped but validation continues for
@@ -28,14 +43,16 @@
 use crate::engine::commands::errors::{CommandError, CommandErrorKind, ValidationError};
 use crate::engine::commands::grammar::{BatterOutKind, Segment};
 use crate::engine::commands::kind::CommandKind;
-use crate::engine::commands::types::EngineCommand;
+use crate::engine::commands::types::{EngineCommand, PitchAdvanceReason, RunnerCaughtKind};
 use crate::engine::scoring::batter_outs::{
     DefensiveOutKind, DefensiveOutRecord, DefensivePlayCommand, DefensivePlayTarget,
-    FielderChoiceAdvance, FieldingSequence,
+    FielderChoiceAdvance, FieldingSequence, Sacrifice,
 };
 use crate::models::field_zone::FieldZone;
 use crate::models::game_state::GameState;
-use crate::models::runner::{RunnerDest, RunnerOverride};
+use crate::models::plate_appearance::{DroppedThirdStrike, HitModifier};
+use crate::models::rules::GameKind;
+use crate::models::runner::{FieldingError, RunnerDest, RunnerOverride};
 use crate::models::types::{GameStatus, Pitch};
 
 /// A parsed [`Segment`] paired with its original position in the line.
@@ -83,6 +100,19 @@ pub fn validate(
         return Ok(vec![cmd]);
     }
 
+    // ── Substitution line (every segment is a lineup change) ───────────────
+    if let Some(result) = try_build_substitution_line(&indexed, state) {
+        return result;
+    }
+
+    // ── RBI credit: the rest of the line must end the plate appearance ─────
+    if indexed
+        .iter()
+        .any(|s| matches!(s.segment, Segment::Rbi { .. }))
+    {
+        return validate_rbi_line(indexed, state);
+    }
+
     // ── Per-segment classification and validation ─────────────────────────
     let mut resolved: Vec<Option<Resolved>> = Vec::with_capacity(indexed.len());
     let current_batter: Option<u8> = state.current_batter_order;
@@ -93,7 +123,35 @@ pub fn validate(
     let has_fc = indexed
         .iter()
         .any(|s| matches!(s.segment, Segment::FielderChoice { .. }));
-    let has_trigger_for_advance = has_hit || has_fc;
+    let has_batter_error = indexed.iter().any(|s| {
+        matches!(
+            s.segment,
+            Segment::Error { subject, .. } if subject.is_none() || subject == current_batter
+        )
+    });
+    let has_sacrifice = indexed.iter().any(|s| {
+        matches!(
+            s.segment,
+            Segment::BatterOut {
+                out: BatterOutKind::SacrificeFly { .. } | BatterOutKind::SacrificeBunt { .. },
+                ..
+            }
+        )
+    });
+    let has_dropped_third_strike = indexed
+        .iter()
+        .any(|s| matches!(s.segment, Segment::DroppedThirdStrike { .. }));
+    let has_batter_out = indexed.iter().any(|s| match s.segment {
+        Segment::BatterOut { subject, .. } => subject.is_none() || subject == current_batter,
+        Segment::RunnerOut { subject, .. } => Some(subject) == current_batter,
+        _ => false,
+    });
+    let has_trigger_for_advance = has_hit
+        || has_fc
+        || has_batter_error
+        || has_sacrifice
+        || has_dropped_third_strike
+        || has_batter_out;
 
     for seg in &indexed {
         match classify_segment(&seg.segment, current_batter, state, has_trigger_for_advance) {
@@ -113,7 +171,7 @@ pub fn validate(
     }
 
     // ── Coalesce valid segments into EngineCommand values ──────────────────
-    build_commands(&resolved)
+    build_commands(&resolved, state)
 }
 
 // ─── Internal intermediate form ──────────────────────────────────────────────
@@ -128,12 +186,23 @@ enum Resolved {
         batter: u8,
         kind: CommandKind,
         zone: Option<FieldZone>,
+        modifier: Option<HitModifier>,
+    },
+    Award {
+        #[allow(dead_code)]
+        batter: u8,
+        kind: CommandKind,
     },
     BatterOut {
         #[allow(dead_code)]
         batter: u8,
         out: BatterOutKind,
     },
+    DroppedThirdStrike {
+        batter: u8,
+        kind: CommandKind,
+        fielders: Vec<u8>,
+    },
     RunnerOut {
         subject: u8,
         out: BatterOutKind,
@@ -144,10 +213,33 @@ enum Resolved {
         fielder: u8,
         base: RunnerDest,
     },
+    BatterError {
+        #[allow(dead_code)]
+        batter: u8,
+        error: FieldingError,
+        base: RunnerDest,
+    },
+    RunnerError {
+        subject: u8,
+        error: FieldingError,
+        dest: RunnerDest,
+    },
     Steal {
         subject: u8,
         dest: RunnerDest,
     },
+    PitchAdvance {
+        subject: u8,
+        kind: CommandKind,
+        dest: RunnerDest,
+    },
+    Balk,
+    RunnerCaught {
+        subject: u8,
+        kind: CommandKind,
+        dest: Option<RunnerDest>,
+        fielders: Vec<u8>,
+    },
     Advance {
         subject: u8,
         dest: RunnerDest,
@@ -159,21 +251,41 @@ enum Resolved {
 fn check_mixing(indexed: &[IndexedSegment]) -> Result<(), Vec<CommandError>> {
     let mut errors = Vec::new();
 
-    let has_control_or_status = indexed
-        .iter()
-        .any(|s| matches!(s.segment, Segment::Control(_) | Segment::Status(_)));
+    let has_control_or_status = indexed.iter().any(|s| {
+        matches!(
+            s.segment,
+            Segment::Control(_) | Segment::Undo { .. } | Segment::Status(_)
+        )
+    });
 
     if has_control_or_status && indexed.len() > 1 {
         for seg in indexed {
-            if matches!(seg.segment, Segment::Control(_) | Segment::Status(_)) {
+            if matches!(
+                seg.segment,
+                Segment::Control(_) | Segment::Undo { .. } | Segment::Status(_)
+            ) {
+                errors.push(mixing_err(seg, seg.text.clone()));
+            }
+        }
+    }
+
+    // Substitutions are made between pitches: they share a line only with
+    // other substitutions (a double switch is `7 sub 34 p, 9 sub 22 lf`).
+    let substitutions = indexed
+        .iter()
+        .filter(|s| is_substitution(&s.segment))
+        .count();
+    if substitutions > 0 && substitutions < indexed.len() && !has_control_or_status {
+        for seg in indexed {
+            if is_substitution(&seg.segment) {
                 errors.push(mixing_err(seg, seg.text.clone()));
             }
         }
     }
 
     // "End-of-PA" actions are those that settle the plate appearance:
-    // hits, outs on batter or runner, fielder's choice, standalone
-    // runner-advance overrides. They cannot coexist with pitches (which
+    // hits, base awards, outs on batter or runner, fielder's choice,
+    // errors, dropped third strikes, standalone runner-advance overrides. They cannot coexist with pitches (which
     // are in-pitch events) or with steals (which are also in-pitch — a
     // runner who moves on a ball in play belongs in the hit's override
     // list, not in a separate `st` segment).
@@ -181,9 +293,12 @@ fn check_mixing(indexed: &[IndexedSegment]) -> Result<(), Vec<CommandError>> {
         matches!(
             s.segment,
             Segment::Hit { .. }
+                | Segment::Award { .. }
                 | Segment::BatterOut { .. }
                 | Segment::RunnerOut { .. }
                 | Segment::FielderChoice { .. }
+                | Segment::Error { .. }
+                | Segment::DroppedThirdStrike { .. }
                 | Segment::Advance { .. }
         )
     });
@@ -205,12 +320,22 @@ fn check_mixing(indexed: &[IndexedSegment]) -> Result<(), Vec<CommandError>> {
     // movement during a ball in play must be expressed as a runner
     // override on the hit (see SCORING_GUIDE section 6.3), not as an
     // independent `st` command that would double-apply the movement.
-    let has_steal = indexed
-        .iter()
-        .any(|s| matches!(s.segment, Segment::Steal { .. }));
+    // Wild pitches, passed balls, defensive indifference, balks, caught
+    // stealing and pickoffs are in-pitch runner events too and follow the
+    // same rule.
+    let is_in_pitch_runner_event = |seg: &Segment| {
+        matches!(
+            seg,
+            Segment::Steal { .. }
+                | Segment::PitchAdvance { .. }
+                | Segment::Balk
+                | Segment::RunnerCaught { .. }
+        )
+    };
+    let has_steal = indexed.iter().any(|s| is_in_pitch_runner_event(&s.segment));
     if has_steal && has_end_of_pa_action {
         for seg in indexed {
-            if matches!(seg.segment, Segment::Steal { .. }) {
+            if is_in_pitch_runner_event(&seg.segment) {
                 errors.push(mixing_err(seg, seg.text.clone()));
             }
         }
@@ -240,11 +365,151 @@ fn try_build_control_line(indexed: &[IndexedSegment]) -> Option<EngineCommand> {
     match &indexed[0].segment {
         Segment::Control(CommandKind::Exit) => Some(EngineCommand::Exit),
         Segment::Control(CommandKind::PlayBall) => Some(EngineCommand::PlayBall),
+        Segment::Undo { count } => Some(EngineCommand::Undo { count: *count }),
         Segment::Status(sk) => Some(EngineCommand::SetStatus(status_to_game(*sk))),
         _ => None,
     }
 }
 
+// ─── Substitution line ───────────────────────────────────────────────────────
+
+fn is_substitution(seg: &Segment) -> bool {
+    matches!(
+        seg,
+        Segment::PitchingChange { .. }
+            | Segment::PinchHitter { .. }
+            | Segment::PinchRunner { .. }
+            | Segment::DefensiveSubstitution { .. }
+            | Segment::PositionChange { .. }
+    )
+}
+
+/// A line made only of substitutions (the mixing check guarantees it is
+/// all or nothing). Each segment maps to its own command; roster and
+/// lineup rules are checked by the engine, which owns the database.
+fn try_build_substitution_line(
+    indexed: &[IndexedSegment],
+    state: &GameState,
+) -> Option<Result<Vec<EngineCommand>, Vec<CommandError>>> {
+    if !indexed.iter().all(|s| is_substitution(&s.segment)) {
+        return None;
+    }
+
+    let mut cmds = Vec::with_capacity(indexed.len());
+    let mut errors = Vec::new();
+    for seg in indexed {
+        let cmd = match &seg.segment {
+            Segment::PitchingChange { jersey } => {
+                Ok(EngineCommand::PitchingChange { jersey: *jersey })
+            }
+            Segment::PinchHitter { subject, jersey } => {
+                resolve_batter_subject(*subject, state.current_batter_order)
+                    .map(|_| EngineCommand::PinchHitter { jersey: *jersey })
+            }
+            Segment::PinchRunner { subject, jersey } => {
+                if state.is_on_base(*subject) {
+                    Ok(EngineCommand::PinchRunner {
+                        order: *subject,
+                        jersey: *jersey,
+                    })
+                } else {
+                    Err(ValidationError::RunnerNotOnBase { order: *subject })
+                }
+            }
+            Segment::DefensiveSubstitution {
+                subject,
+                jersey,
+                position,
+            } => Ok(EngineCommand::DefensiveSubstitution {
+                order: *subject,
+                jersey: *jersey,
+                position: *position,
+            }),
+            Segment::PositionChange { subject, position } => Ok(EngineCommand::PositionChange {
+                order: *subject,
+                position: *position,
+            }),
+            _ => unreachable!("non-substitution segment on a substitution line"),
+        };
+        match cmd {
+            Ok(c) => cmds.push(c),
+            Err(e) => errors.push(to_err(seg, e)),
+        }
+    }
+
+    Some(if errors.is_empty() {
+        Ok(cmds)
+    } else {
+        Err(errors)
+    })
+}
+
+// ─── RBI credit ──────────────────────────────────────────────────────────────
+
+/// A line carrying `rbi <n>`: the other segments are validated on their
+/// own and the credit is appended as [`EngineCommand::CreditRbi`].
+fn validate_rbi_line(
+    indexed: Vec<IndexedSegment>,
+    state: &GameState,
+) -> Result<Vec<EngineCommand>, Vec<CommandError>> {
+    let (credits, rest): (Vec<_>, Vec<_>) = indexed
+        .into_iter()
+        .partition(|s| matches!(s.segment, Segment::Rbi { .. }));
+
+    let mut errors: Vec<CommandError> = credits
+        .iter()
+        .skip(1)
+        .map(|seg| to_err(seg, ValidationError::RepeatedRbi))
+        .collect();
+
+    let mut cmds = match validate(rest, state) {
+        Ok(cmds) => cmds,
+        Err(mut e) => {
+            errors.append(&mut e);
+            errors.sort_by_key(|e| e.segment_index);
+            return Err(errors);
+        }
+    };
+    if !cmds.iter().any(ends_plate_appearance) {
+        errors.push(to_err(&credits[0], ValidationError::RbiWithoutBatterPlay));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let Segment::Rbi { count } = credits[0].segment else {
+        unreachable!("partitioned on Segment::Rbi");
+    };
+    cmds.push(EngineCommand::CreditRbi { rbi: count });
+    Ok(cmds)
+}
+
+/// The command settles the batter's plate appearance.
+fn ends_plate_appearance(cmd: &EngineCommand) -> bool {
+    match cmd {
+        EngineCommand::Single { .. }
+        | EngineCommand::Double { .. }
+        | EngineCommand::Triple { .. }
+        | EngineCommand::HomeRun { .. }
+        | EngineCommand::HitByPitch
+        | EngineCommand::IntentionalWalk
+        | EngineCommand::CatcherInterference
+        | EngineCommand::ReachedOnError { .. }
+        | EngineCommand::DroppedThirdStrike { .. }
+        | EngineCommand::BatterOut { .. } => true,
+        EngineCommand::DefensivePlay(play) => {
+            play.outs
+                .iter()
+                .any(|o| o.target == DefensivePlayTarget::Batter)
+                || play
+                    .safe_advances
+                    .iter()
+                    .any(|a| a.target == DefensivePlayTarget::Batter)
+        }
+        _ => false,
+    }
+}
+
 // ─── Per-segment classification ──────────────────────────────────────────────
 
 fn classify_segment(
@@ -256,7 +521,15 @@ fn classify_segment(
     match seg {
         Segment::Pitch(pk) => Ok(Resolved::Pitch(*pk)),
 
-        Segment::Control(_) | Segment::Status(_) => {
+        Segment::Control(_)
+        | Segment::Undo { .. }
+        | Segment::Rbi { .. }
+        | Segment::Status(_)
+        | Segment::PitchingChange { .. }
+        | Segment::PinchHitter { .. }
+        | Segment::PinchRunner { .. }
+        | Segment::DefensiveSubstitution { .. }
+        | Segment::PositionChange { .. } => {
             // Already handled before this pass.
             Err(ValidationError::ControlMixedWithActions {
                 verb: "control/status".to_string(),
@@ -267,12 +540,22 @@ fn classify_segment(
             subject,
             kind,
             zone,
+            modifier,
         } => {
             let batter = resolve_batter_subject(*subject, current_batter)?;
             Ok(Resolved::Hit {
                 batter,
                 kind: *kind,
                 zone: *zone,
+                modifier: *modifier,
+            })
+        }
+
+        Segment::Award { subject, kind } => {
+            let batter = resolve_batter_subject(*subject, current_batter)?;
+            Ok(Resolved::Award {
+                batter,
+                kind: *kind,
             })
         }
 
@@ -284,6 +567,19 @@ fn classify_segment(
             })
         }
 
+        Segment::DroppedThirdStrike {
+            subject,
+            kind,
+            fielders,
+        } => {
+            let batter = resolve_batter_subject(*subject, current_batter)?;
+            Ok(Resolved::DroppedThirdStrike {
+                batter,
+                kind: *kind,
+                fielders: fielders.clone(),
+            })
+        }
+
         Segment::RunnerOut { subject, out } => {
             if Some(*subject) == current_batter {
                 Ok(Resolved::BatterOut {
@@ -313,6 +609,43 @@ fn classify_segment(
             })
         }
 
+        Segment::Error {
+            subject,
+            fielder,
+            throwing,
+            base,
+        } => {
+            let error = FieldingError {
+                fielder: *fielder,
+                throwing: *throwing,
+            };
+            match subject {
+                Some(runner) if Some(*runner) != current_batter => {
+                    if !has_trigger_for_advance {
+                        return Err(ValidationError::AdvanceWithoutTrigger { order: *runner });
+                    }
+                    if !state.is_on_base(*runner) {
+                        return Err(ValidationError::RunnerNotOnBase { order: *runner });
+                    }
+                    let dest =
+                        base.ok_or(ValidationError::ErrorAdvanceWithoutBase { order: *runner })?;
+                    Ok(Resolved::RunnerError {
+                        subject: *runner,
+                        error,
+                        dest,
+                    })
+                }
+                _ => {
+                    let batter = resolve_batter_subject(*subject, current_batter)?;
+                    Ok(Resolved::BatterError {
+                        batter,
+                        error,
+                        base: base.unwrap_or(RunnerDest::First),
+                    })
+                }
+            }
+        }
+
         Segment::Steal { subject, dest } => {
             if !state.is_on_base(*subject) {
                 return Err(ValidationError::RunnerNotOnBase { order: *subject });
@@ -323,6 +656,40 @@ fn classify_segment(
             })
         }
 
+        Segment::PitchAdvance {
+            subject,
+            kind,
+            dest,
+        } => {
+            if !state.is_on_base(*subject) {
+                return Err(ValidationError::RunnerNotOnBase { order: *subject });
+            }
+            Ok(Resolved::PitchAdvance {
+                subject: *subject,
+                kind: *kind,
+                dest: *dest,
+            })
+        }
+
+        Segment::Balk => Ok(Resolved::Balk),
+
+        Segment::RunnerCaught {
+            subject,
+            kind,
+            base,
+            fielders,
+        } => {
+            if !state.is_on_base(*subject) {
+                return Err(ValidationError::RunnerNotOnBase { order: *subject });
+            }
+            Ok(Resolved::RunnerCaught {
+                subject: *subject,
+                kind: *kind,
+                dest: *base,
+                fielders: fielders.clone(),
+            })
+        }
+
         Segment::Advance { subject, dest } => {
             if !has_trigger_for_advance {
                 return Err(ValidationError::AdvanceWithoutTrigger { order: *subject });
@@ -361,16 +728,19 @@ fn run_line_level_checks(
     state: &GameState,
     errors: &mut Vec<CommandError>,
 ) {
-    // 1) Duplicate subject: batter slot cannot appear as both hitter/FC
-    //    and as a runner advance override on the same line.
+    // 1) Duplicate subject: batter slot cannot appear as both hitter/FC/
+    //    error and as a runner advance override on the same line.
     let batter_slot: Option<u8> = resolved.iter().find_map(|r| match r {
         Some(Resolved::Hit { batter, .. }) => Some(*batter),
         Some(Resolved::Fc { batter, .. }) => Some(*batter),
+        Some(Resolved::BatterError { batter, .. }) => Some(*batter),
+        Some(Resolved::DroppedThirdStrike { batter, .. }) => Some(*batter),
         _ => None,
     });
     if let Some(b) = batter_slot {
         for (i, r) in resolved.iter().enumerate() {
-            if let Some(Resolved::Advance { subject, .. }) = r
+            if let Some(Resolved::Advance { subject, .. } | Resolved::RunnerError { subject, .. }) =
+                r
                 && *subject == b
             {
                 errors.push(CommandError {
@@ -406,7 +776,9 @@ fn run_line_level_checks(
         .filter(|r| {
             matches!(
                 r,
-                Some(Resolved::BatterOut { .. }) | Some(Resolved::RunnerOut { .. })
+                Some(Resolved::BatterOut { .. })
+                    | Some(Resolved::RunnerOut { .. })
+                    | Some(Resolved::RunnerCaught { .. })
             )
         })
         .count();
@@ -475,6 +847,82 @@ fn run_line_level_checks(
         }
     }
 
+    // A base award settles the PA on its own: runners move only if
+    // forced, so there is nothing else to combine it with.
+    if resolved.len() > 1
+        && let Some(i) = resolved
+            .iter()
+            .position(|r| matches!(r, Some(Resolved::Award { .. })))
+    {
+        errors.push(CommandError {
+            segment_index: indexed[i].index,
+            segment_text: indexed[i].text.clone(),
+            kind: CommandErrorKind::Validation(ValidationError::ControlMixedWithActions {
+                verb: "a base award cannot be combined with other segments".to_string(),
+            }),
+        });
+    }
+
+    // A dropped third strike settles the batter by itself: only runner
+    // advances (plain or on an error) may ride along.
+    if let Some(i) = resolved
+        .iter()
+        .position(|r| matches!(r, Some(Resolved::DroppedThirdStrike { .. })))
+        && resolved.iter().enumerate().any(|(j, r)| {
+            j != i
+                && !matches!(
+                    r,
+                    None | Some(Resolved::Advance { .. }) | Some(Resolved::RunnerError { .. })
+                )
+        })
+    {
+        errors.push(CommandError {
+            segment_index: indexed[i].index,
+            segment_text: indexed[i].text.clone(),
+            kind: CommandErrorKind::Validation(ValidationError::ControlMixedWithActions {
+                verb: "a dropped third strike can only be combined with runner advances"
+                    .to_string(),
+            }),
+        });
+    }
+
+    // The batter reaches on an error OR on a hit / FC / out, never on two.
+    let batter_error_count = resolved
+        .iter()
+        .filter(|r| matches!(r, Some(Resolved::BatterError { .. })))
+        .count();
+    if batter_error_count > 0 {
+        let conflict = if batter_error_count > 1 {
+            Some("multiple batter errors on the same line")
+        } else if has_hit {
+            Some("batter cannot reach on both a hit and an error")
+        } else if has_fc {
+            Some("batter cannot reach on both an FC and an error")
+        } else if resolved.iter().any(|r| {
+            matches!(
+                r,
+                Some(Resolved::BatterOut { .. }) | Some(Resolved::RunnerOut { .. })
+            )
+        }) {
+            Some("an out cannot be combined with a batter reaching on error")
+        } else {
+            None
+        };
+        if let Some(verb) = conflict {
+            let i = resolved
+                .iter()
+                .rposition(|r| matches!(r, Some(Resolved::BatterError { .. })))
+                .unwrap();
+            errors.push(CommandError {
+                segment_index: indexed[i].index,
+                segment_text: indexed[i].text.clone(),
+                kind: CommandErrorKind::Validation(ValidationError::ControlMixedWithActions {
+                    verb: verb.to_string(),
+                }),
+            });
+        }
+    }
+
     if has_hit && has_fc {
         let i = resolved
             .iter()
@@ -536,59 +984,249 @@ fn run_line_level_checks(
         });
     }
 
-    // FC + Advance: out of scope for this alpha; emit an explicit error so
-    // callers are not surprised when it "silently" does nothing.
-    if has_fc
-        && resolved
-            .iter()
-            .any(|r| matches!(r, Some(Resolved::Advance { .. })))
-    {
-        let i = resolved
-            .iter()
-            .position(|r| matches!(r, Some(Resolved::Advance { .. })))
-            .unwrap();
-        errors.push(CommandError {
-            segment_index: indexed[i].index,
-            segment_text: indexed[i].text.clone(),
-            kind: CommandErrorKind::Validation(ValidationError::ControlMixedWithActions {
-                verb: "runner advance alongside FC is not supported in this version".to_string(),
-            }),
-        });
+    // 5) Sacrifices: never with two outs (the batter's out would end the
+    //    half-inning), and a sacrifice fly must bring a run home — the
+    //    runner on third by default, or whoever is sent to `sc`.
+    for (i, r) in resolved.iter().enumerate() {
+        let Some(Resolved::BatterOut { out, .. }) = r else {
+            continue;
+        };
+        let is_fly = matches!(out, BatterOutKind::SacrificeFly { .. });
+        if !is_fly && !matches!(out, BatterOutKind::SacrificeBunt { .. }) {
+            continue;
+        }
+        let error = if state.outs >= 2 {
+            Some(ValidationError::SacrificeWithTwoOuts)
+        } else if is_fly && !sacrifice_fly_scores(resolved, state) {
+            Some(ValidationError::SacrificeFlyWithoutRun)
+        } else {
+            None
+        };
+        if let Some(e) = error {
+            errors.push(to_err(&indexed[i], e));
+        }
     }
-}
-
-fn infield_fly_allowed(state: &GameState) -> bool {
-    state.outs < 2 && state.on_1b.is_some() && state.on_2b.is_some()
-}
 
-fn to_err(seg: &IndexedSegment, e: ValidationError) -> CommandError {
-    CommandError {
-        segment_index: seg.index,
-        segment_text: seg.text.clone(),
-        kind: CommandErrorKind::Validation(e),
+    // 6) Dropped third strike: it has to be strike three, and the batter
+    //    may only run with first base open or two outs.
+    for (i, r) in resolved.iter().enumerate() {
+        if !matches!(r, Some(Resolved::DroppedThirdStrike { .. })) {
+            continue;
+        }
+        let error = if state.pitch_count.strikes != 2 {
+            Some(ValidationError::DroppedThirdStrikeWithoutTwoStrikes {
+                strikes: state.pitch_count.strikes,
+            })
+        } else if state.on_1b.is_some() && state.outs < 2 {
+            Some(ValidationError::DroppedThirdStrikeWithFirstOccupied)
+        } else {
+            None
+        };
+        if let Some(e) = error {
+            errors.push(to_err(&indexed[i], e));
+        }
     }
-}
 
-// ─── Coalescing resolved segments into EngineCommand values ─────────────────
+    // 7) Ground-rule double: the ball is dead and every runner is awarded
+    //    exactly two bases, so no runner can be placed by hand.
+    let ground_rule = resolved.iter().any(|r| {
+        matches!(
+            r,
+            Some(Resolved::Hit {
+                modifier: Some(HitModifier::GroundRule),
+                ..
+            })
+        )
+    });
+    if ground_rule {
+        for (i, r) in resolved.iter().enumerate() {
+            if let Some(Resolved::Advance { subject, .. } | Resolved::RunnerError { subject, .. }) =
+                r
+            {
+                errors.push(to_err(
+                    &indexed[i],
+                    ValidationError::GroundRuleDoubleAdvance { order: *subject },
+                ));
+            }
+        }
+    }
 
-fn build_commands(resolved: &[Option<Resolved>]) -> Result<Vec<EngineCommand>, Vec<CommandError>> {
-    let items: Vec<&Resolved> = resolved.iter().flatten().collect();
-    let mut out: Vec<EngineCommand> = Vec::new();
+    // 8) Runners moving up on a batter's out: not when the play retires the
+    //    side, the half-inning is over before anybody can advance.
+    let batter_out = resolved
+        .iter()
+        .any(|r| matches!(r, Some(Resolved::BatterOut { .. })));
+    let outs_on_play = resolved
+        .iter()
+        .filter(|r| {
+            matches!(
+                r,
+                Some(Resolved::BatterOut { .. }) | Some(Resolved::RunnerOut { .. })
+            )
+        })
+        .count();
+    if batter_out && usize::from(state.outs) + outs_on_play >= 3 {
+        for (i, r) in resolved.iter().enumerate() {
+            if let Some(Resolved::Advance { subject, .. } | Resolved::RunnerError { subject, .. }) =
+                r
+            {
+                errors.push(to_err(
+                    &indexed[i],
+                    ValidationError::AdvanceOnThirdOut { order: *subject },
+                ));
+            }
+        }
+    }
 
-    let overrides: Vec<RunnerOverride> = items
+    // 9) Runners moving on a pitch: nobody lands on or runs past a runner
+    //    ahead unless that runner moves further on the same line.
+    let pitch_advances: Vec<(u8, RunnerDest)> = resolved
         .iter()
+        .flatten()
         .filter_map(|r| match r {
-            Resolved::Advance { subject, dest } => Some(RunnerOverride {
-                order: *subject,
-                dest: *dest,
-            }),
+            Resolved::PitchAdvance { subject, dest, .. } => Some((*subject, *dest)),
             _ => None,
         })
         .collect();
-
-    let mut def_outs: Vec<DefensiveOutRecord> = Vec::new();
-    for r in &items {
-        match r {
+    let moves_past = |order: u8, dest: RunnerDest| {
+        pitch_advances
+            .iter()
+            .any(|&(o, d)| o == order && (d.bases() > dest.bases() || d == RunnerDest::Score))
+    };
+    for (i, r) in resolved.iter().enumerate() {
+        let Some(Resolved::PitchAdvance { subject, dest, .. }) = r else {
+            continue;
+        };
+        let Some(start) = state.base_of(*subject) else {
+            continue;
+        };
+        let blocker = [(1u8, state.on_1b), (2, state.on_2b), (3, state.on_3b)]
+            .into_iter()
+            .filter(|&(base, _)| base > start && base <= dest.bases())
+            .find_map(|(_, runner)| runner.filter(|&order| !moves_past(order, *dest)));
+        if let Some(blocker) = blocker {
+            errors.push(to_err(
+                &indexed[i],
+                ValidationError::RunnerBlocked {
+                    order: *subject,
+                    blocker,
+                },
+            ));
+        }
+    }
+
+    // 10) Game kind: slow-pitch softball has no running on pitches, bunts or
+    //    dropped third strikes; only baseball has balks.
+    let kind = state.rules.kind;
+    for (i, r) in resolved.iter().enumerate() {
+        if let Some(play) = r.as_ref().and_then(|r| play_not_in_game(r, kind)) {
+            errors.push(to_err(
+                &indexed[i],
+                ValidationError::NotInGame { play, kind },
+            ));
+        }
+    }
+}
+
+/// The play `r` stands for when the game kind does not allow it.
+fn play_not_in_game(r: &Resolved, kind: GameKind) -> Option<&'static str> {
+    let play = match r {
+        Resolved::Steal { .. } => "stealing",
+        Resolved::RunnerCaught { .. } => "a pickoff or caught stealing",
+        Resolved::PitchAdvance { .. } => "advancing on a pitch",
+        Resolved::Balk => "a balk",
+        Resolved::DroppedThirdStrike { .. } => "a dropped third strike",
+        Resolved::BatterOut {
+            out: BatterOutKind::SacrificeBunt { .. },
+            ..
+        }
+        | Resolved::Hit {
+            modifier: Some(HitModifier::Bunt),
+            ..
+        } => "bunting",
+        _ => return None,
+    };
+    let allowed = match r {
+        Resolved::Steal { .. } | Resolved::RunnerCaught { .. } | Resolved::PitchAdvance { .. } => {
+            kind.allows_running_on_pitches()
+        }
+        Resolved::Balk => kind.allows_balk(),
+        _ => kind.allows_bunts_and_dropped_third_strike(),
+    };
+    (!allowed).then_some(play)
+}
+
+/// Whether a run scores on the sacrifice fly described by `resolved`:
+/// either a runner is sent home explicitly, or the runner on third is
+/// neither retired nor held by an override.
+fn sacrifice_fly_scores(resolved: &[Option<Resolved>], state: &GameState) -> bool {
+    let mut third_runner_moves = state.on_3b.is_some();
+    for r in resolved.iter().flatten() {
+        match r {
+            Resolved::Advance { dest, .. } | Resolved::RunnerError { dest, .. }
+                if *dest == RunnerDest::Score =>
+            {
+                return true;
+            }
+            Resolved::Advance { subject, .. }
+            | Resolved::RunnerError { subject, .. }
+            | Resolved::RunnerOut { subject, .. }
+                if state.on_3b == Some(*subject) =>
+            {
+                third_runner_moves = false;
+            }
+            _ => {}
+        }
+    }
+    third_runner_moves
+}
+
+fn infield_fly_allowed(state: &GameState) -> bool {
+    state.outs < 2 && state.on_1b.is_some() && state.on_2b.is_some()
+}
+
+fn to_err(seg: &IndexedSegment, e: ValidationError) -> CommandError {
+    CommandError {
+        segment_index: seg.index,
+        segment_text: seg.text.clone(),
+        kind: CommandErrorKind::Validation(e),
+    }
+}
+
+// ─── Coalescing resolved segments into EngineCommand values ─────────────────
+
+fn build_commands(
+    resolved: &[Option<Resolved>],
+    state: &GameState,
+) -> Result<Vec<EngineCommand>, Vec<CommandError>> {
+    let mut items: Vec<&Resolved> = resolved.iter().flatten().collect();
+    lead_runner_first(&mut items, state);
+    let mut out: Vec<EngineCommand> = Vec::new();
+
+    let overrides: Vec<RunnerOverride> = items
+        .iter()
+        .filter_map(|r| match r {
+            Resolved::Advance { subject, dest } => Some(RunnerOverride {
+                order: *subject,
+                dest: *dest,
+                error: None,
+            }),
+            Resolved::RunnerError {
+                subject,
+                error,
+                dest,
+            } => Some(RunnerOverride {
+                order: *subject,
+                dest: *dest,
+                error: Some(*error),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let mut def_outs: Vec<DefensiveOutRecord> = Vec::new();
+    for r in &items {
+        match r {
             Resolved::BatterOut { out: k, .. } => {
                 def_outs.push(DefensiveOutRecord {
                     target: DefensivePlayTarget::Batter,
@@ -606,42 +1244,91 @@ fn build_commands(resolved: &[Option<Resolved>]) -> Result<Vec<EngineCommand>, V
     }
 
     let hit = items.iter().find_map(|r| match r {
-        Resolved::Hit { kind, zone, .. } => Some((*kind, *zone)),
+        Resolved::Hit {
+            kind,
+            zone,
+            modifier,
+            ..
+        } => Some((*kind, *zone, *modifier)),
         _ => None,
     });
     let fc = items.iter().find_map(|r| match r {
         Resolved::Fc { fielder, base, .. } => Some((*fielder, *base)),
         _ => None,
     });
+    let batter_error = items.iter().find_map(|r| match r {
+        Resolved::BatterError { error, base, .. } => Some((*error, *base)),
+        _ => None,
+    });
 
     let steals: Vec<EngineCommand> = items
         .iter()
-        .filter_map(|r| match r {
-            Resolved::Steal { subject, dest } => Some(EngineCommand::StealBase {
-                order: *subject,
-                dest: *dest,
-            }),
-            _ => None,
-        })
+        .filter_map(|r| runner_event_command(r))
         .collect();
 
+    // Route 0: a BASE AWARD is always alone on its line.
+    if let Some(kind) = items.iter().find_map(|r| match r {
+        Resolved::Award { kind, .. } => Some(*kind),
+        _ => None,
+    }) {
+        out.push(match kind {
+            CommandKind::HitByPitch => EngineCommand::HitByPitch,
+            CommandKind::IntentionalWalk => EngineCommand::IntentionalWalk,
+            CommandKind::CatcherInterference => EngineCommand::CatcherInterference,
+            // Enforced upstream: only Award-family CommandKind reaches
+            // Resolved::Award.
+            _ => unreachable!("non-Award CommandKind {:?} in award Resolved variant", kind),
+        });
+        return Ok(out);
+    }
+
+    // Route 0b: a DROPPED THIRD STRIKE takes the runner overrides along.
+    if let Some((kind, fielders)) = items.iter().find_map(|r| match r {
+        Resolved::DroppedThirdStrike { kind, fielders, .. } => Some((*kind, fielders)),
+        _ => None,
+    }) {
+        let how = match kind {
+            CommandKind::StrikeoutWildPitch => DroppedThirdStrike::WildPitch,
+            CommandKind::StrikeoutPassedBall => DroppedThirdStrike::PassedBall,
+            CommandKind::StrikeoutThrownOut => DroppedThirdStrike::ThrownOut {
+                sequence: fielders
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect::<Vec<_>>()
+                    .join("-"),
+            },
+            // Enforced upstream: only DroppedThirdStrike-family CommandKind
+            // reaches Resolved::DroppedThirdStrike.
+            _ => unreachable!("non-dropped-third-strike CommandKind {:?}", kind),
+        };
+        out.push(EngineCommand::DroppedThirdStrike {
+            how,
+            runner_overrides: overrides,
+        });
+        return Ok(out);
+    }
+
     // Route 1: line contains a HIT.
-    if let Some((kind, zone)) = hit {
+    if let Some((kind, zone, modifier)) = hit {
         let hit_cmd = match kind {
             CommandKind::Single => EngineCommand::Single {
                 zone,
+                modifier,
                 runner_overrides: overrides,
             },
             CommandKind::Double => EngineCommand::Double {
                 zone,
+                modifier,
                 runner_overrides: overrides,
             },
             CommandKind::Triple => EngineCommand::Triple {
                 zone,
+                modifier,
                 runner_overrides: overrides,
             },
             CommandKind::HomeRun => EngineCommand::HomeRun {
                 zone,
+                modifier,
                 runner_overrides: overrides,
             },
             // Enforced upstream: only Hit-family CommandKind reaches
@@ -653,7 +1340,18 @@ fn build_commands(resolved: &[Option<Resolved>]) -> Result<Vec<EngineCommand>, V
         return Ok(out);
     }
 
-    // Route 2: line contains a FIELDER'S CHOICE.
+    // Route 2: batter REACHED ON ERROR. Runner errors ride along as
+    // attributed overrides, exactly like on a hit.
+    if let Some((error, reached_base)) = batter_error {
+        out.push(EngineCommand::ReachedOnError {
+            error,
+            reached_base,
+            runner_overrides: overrides,
+        });
+        return Ok(out);
+    }
+
+    // Route 3: line contains a FIELDER'S CHOICE.
     if let Some((fielder, base)) = fc {
         let fc_adv = FielderChoiceAdvance {
             target: DefensivePlayTarget::Batter,
@@ -663,38 +1361,117 @@ fn build_commands(resolved: &[Option<Resolved>]) -> Result<Vec<EngineCommand>, V
         out.push(EngineCommand::DefensivePlay(DefensivePlayCommand {
             outs: def_outs,
             safe_advances: vec![fc_adv],
+            sacrifice: None,
+            runner_advances: overrides,
         }));
         out.extend(steals);
         return Ok(out);
     }
 
-    // Route 3: no hit, no FC — outs / steals / pitches.
+    // Route 4: no hit, no error, no FC — outs / steals / pitches.
     //
     // Defensive outs coalesce into a single DefensivePlay at the head of
     // the command stream (it has to be applied before the live
     // pitches/steals can touch the resulting state). After that, emit
     // pitches and steals in the original segment order so the at-bat log
     // preserves what the scorer actually typed.
+    //
+    // The out takes the runner overrides along: they are the tag-ups and
+    // advances the composite play has to persist.
     if !def_outs.is_empty() {
+        let sacrifice = items.iter().find_map(|r| match r {
+            Resolved::BatterOut {
+                out: BatterOutKind::SacrificeFly { .. },
+                ..
+            } => Some(Sacrifice::Fly),
+            Resolved::BatterOut {
+                out: BatterOutKind::SacrificeBunt { .. },
+                ..
+            } => Some(Sacrifice::Bunt),
+            _ => None,
+        });
         out.push(EngineCommand::DefensivePlay(DefensivePlayCommand {
             outs: def_outs,
             safe_advances: vec![],
+            sacrifice,
+            runner_advances: overrides,
         }));
     }
     for r in &items {
         match r {
             Resolved::Pitch(pk) => out.push(EngineCommand::Pitch(pitch_to_engine(*pk))),
-            Resolved::Steal { subject, dest } => out.push(EngineCommand::StealBase {
-                order: *subject,
-                dest: *dest,
-            }),
-            _ => {}
+            _ => out.extend(runner_event_command(r)),
         }
     }
 
     Ok(out)
 }
 
+/// Reorder the pitch advances of a line so the lead runner moves first:
+/// in `2 wp 3b, 1 wp sc` runner #1 leaves third before #2 reaches it.
+/// Every other segment keeps its place.
+fn lead_runner_first(items: &mut [&Resolved], state: &GameState) {
+    let slots: Vec<usize> = (0..items.len())
+        .filter(|&i| matches!(items[i], Resolved::PitchAdvance { .. }))
+        .collect();
+    let mut advances: Vec<&Resolved> = slots.iter().map(|&i| items[i]).collect();
+    advances.sort_by_key(|r| match r {
+        Resolved::PitchAdvance { subject, .. } => std::cmp::Reverse(state.base_of(*subject)),
+        _ => std::cmp::Reverse(None),
+    });
+    for (slot, r) in slots.into_iter().zip(advances) {
+        items[slot] = r;
+    }
+}
+
+/// Engine command for an in-pitch runner event (steal, wild pitch,
+/// passed ball, defensive indifference, balk, caught stealing, pickoff);
+/// `None` for anything else.
+fn runner_event_command(r: &Resolved) -> Option<EngineCommand> {
+    match r {
+        Resolved::Steal { subject, dest } => Some(EngineCommand::StealBase {
+            order: *subject,
+            dest: *dest,
+        }),
+        Resolved::PitchAdvance {
+            subject,
+            kind,
+            dest,
+        } => Some(EngineCommand::PitchAdvance {
+            order: *subject,
+            dest: *dest,
+            reason: match kind {
+                CommandKind::WildPitch => PitchAdvanceReason::WildPitch,
+                CommandKind::PassedBall => PitchAdvanceReason::PassedBall,
+                CommandKind::DefensiveIndifference => PitchAdvanceReason::DefensiveIndifference,
+                // Enforced upstream: only the segment parser builds
+                // PitchAdvance, and only from these three verbs.
+                _ => unreachable!("non-pitch-advance CommandKind {:?}", kind),
+            },
+        }),
+        Resolved::Balk => Some(EngineCommand::Balk),
+        Resolved::RunnerCaught {
+            subject,
+            kind,
+            dest,
+            fielders,
+        } => Some(EngineCommand::RunnerCaught {
+            order: *subject,
+            kind: match kind {
+                CommandKind::CaughtStealing => RunnerCaughtKind::CaughtStealing,
+                CommandKind::Pickoff => RunnerCaughtKind::Pickoff,
+                CommandKind::PickoffCaughtStealing => RunnerCaughtKind::PickoffCaughtStealing,
+                // Enforced upstream: only the segment parser builds
+                // RunnerCaught, and only from these three verbs.
+                _ => unreachable!("non-caught-runner CommandKind {:?}", kind),
+            },
+            dest: *dest,
+            fielders: fielders.clone(),
+        }),
+        _ => None,
+    }
+}
+
 // ─── Conversion helpers ──────────────────────────────────────────────────────
 
 fn status_to_game(ck: CommandKind) -> GameStatus {
@@ -731,7 +1508,18 @@ fn batter_out_kind_to_defensive(
         BatterOutKind::Unassisted { fielder } => {
             Ok(DefensiveOutKind::UnassistedOut { fielder: *fielder })
         }
-        BatterOutKind::GroundOut { fielders } => {
+        // A sacrifice keeps the shape of the out it was; the designation
+        // travels on the `DefensivePlayCommand` instead.
+        BatterOutKind::SacrificeFly { fielder } => Ok(DefensiveOutKind::FlyOut {
+            fielder: *fielder,
+            in_foul_territory: false,
+        }),
+        BatterOutKind::SacrificeBunt { fielders } if fielders.len() == 1 => {
+            Ok(DefensiveOutKind::UnassistedOut {
+                fielder: fielders[0],
+            })
+        }
+        BatterOutKind::GroundOut { fielders } | BatterOutKind::SacrificeBunt { fielders } => {
             let seq = FieldingSequence::new(fielders.clone()).map_err(|e| {
                 // Token regexes already exclude < 2 fielders and values
                 // outside 1..=9, so this branch should be unreachable; we
@@ -810,6 +1598,7 @@ mod tests {
             &cmds[0],
             EngineCommand::Single {
                 zone: Some(FieldZone::LF),
+                modifier: None,
                 runner_overrides,
             } if runner_overrides.is_empty()
         ));
@@ -824,6 +1613,7 @@ mod tests {
             &cmds[0],
             EngineCommand::Single {
                 zone: None,
+                modifier: None,
                 runner_overrides,
             } if runner_overrides.is_empty()
         ));
@@ -955,6 +1745,125 @@ mod tests {
         );
     }
 
+    // ── Errors ──
+    #[test]
+    fn batter_reaches_on_error_defaults_to_first() {
+        let st = make_state(Some(5));
+        let cmds = run("e6", &st).unwrap();
+        assert_eq!(cmds.len(), 1);
+        assert!(matches!(
+            &cmds[0],
+            EngineCommand::ReachedOnError {
+                error: FieldingError {
+                    fielder: 6,
+                    throwing: false,
+                },
+                reached_base: RunnerDest::First,
+                runner_overrides,
+            } if runner_overrides.is_empty()
+        ));
+    }
+
+    #[test]
+    fn throwing_error_with_runner_advance() {
+        let mut st = make_state(Some(5));
+        st.on_3b = Some(4);
+        let cmds = run("5 te5 2b, 4 sc", &st).unwrap();
+        match &cmds[0] {
+            EngineCommand::ReachedOnError {
+                error,
+                reached_base,
+                runner_overrides,
+            } => {
+                assert!(error.throwing);
+                assert_eq!(*reached_base, RunnerDest::Second);
+                assert_eq!(runner_overrides.len(), 1);
+                assert_eq!(runner_overrides[0].error, None);
+            }
+            _ => panic!("expected ReachedOnError"),
+        }
+    }
+
+    #[test]
+    fn runner_error_on_hit_becomes_attributed_override() {
+        let mut st = make_state(Some(6));
+        st.on_2b = Some(2);
+        let cmds = run("h, 2 e7 3b", &st).unwrap();
+        match &cmds[0] {
+            EngineCommand::Single {
+                runner_overrides, ..
+            } => {
+                assert_eq!(runner_overrides.len(), 1);
+                assert_eq!(runner_overrides[0].order, 2);
+                assert_eq!(runner_overrides[0].dest, RunnerDest::Third);
+                assert_eq!(
+                    runner_overrides[0].error,
+                    Some(FieldingError {
+                        fielder: 7,
+                        throwing: false,
+                    })
+                );
+            }
+            _ => panic!("expected Single"),
+        }
+    }
+
+    #[test]
+    fn runner_error_needs_trigger_and_base() {
+        let mut st = make_state(Some(6));
+        st.on_2b = Some(2);
+        let errs = run("2 e7 3b", &st).expect_err("no trigger");
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::AdvanceWithoutTrigger { order: 2 })
+        ));
+        let errs = run("h, 2 e7", &st).expect_err("no base");
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::ErrorAdvanceWithoutBase { order: 2 })
+        ));
+    }
+
+    #[test]
+    fn batter_error_conflicts_rejected() {
+        let st = make_state(Some(5));
+        assert!(run("e6, h", &st).is_err());
+        assert!(run("e6, 63", &st).is_err());
+        assert!(run("e6, te5", &st).is_err());
+        assert!(run("b, e6", &st).is_err());
+    }
+
+    // ── Base awards ──
+    #[test]
+    fn base_awards_map_to_engine_commands() {
+        let st = make_state(Some(5));
+        assert!(matches!(
+            run("hbp", &st).unwrap()[0],
+            EngineCommand::HitByPitch
+        ));
+        assert!(matches!(
+            run("5 ibb", &st).unwrap()[0],
+            EngineCommand::IntentionalWalk
+        ));
+        assert!(matches!(
+            run("ci", &st).unwrap()[0],
+            EngineCommand::CatcherInterference
+        ));
+    }
+
+    #[test]
+    fn base_award_cannot_be_combined() {
+        let mut st = make_state(Some(5));
+        st.on_1b = Some(4);
+        assert!(run("hbp, 4 3b", &st).is_err());
+        assert!(run("b, hbp", &st).is_err());
+        assert!(run("hbp, ibb", &st).is_err());
+        assert!(matches!(
+            run("8 hbp", &st).unwrap_err()[0].kind,
+            CommandErrorKind::Validation(ValidationError::BatterSlotMismatch { .. })
+        ));
+    }
+
     // ── Pitch + steal ──
     #[test]
     fn pitch_and_steal_combined() {
@@ -964,6 +1873,166 @@ mod tests {
         assert_eq!(cmds.len(), 2);
     }
 
+    #[test]
+    fn pitch_advances_and_balk_ride_with_pitches_only() {
+        let mut st = make_state(Some(6));
+        st.on_1b = Some(5);
+        st.on_3b = Some(3);
+        let cmds = run("b, 3 wp sc, 5 di 2b", &st).unwrap();
+        assert!(matches!(
+            cmds[1],
+            EngineCommand::PitchAdvance {
+                order: 3,
+                dest: RunnerDest::Score,
+                reason: PitchAdvanceReason::WildPitch,
+            }
+        ));
+        assert!(matches!(
+            cmds[2],
+            EngineCommand::PitchAdvance {
+                reason: PitchAdvanceReason::DefensiveIndifference,
+                ..
+            }
+        ));
+        assert!(matches!(run("bk", &st).unwrap()[..], [EngineCommand::Balk]));
+
+        assert!(run("6 h, 3 pb sc", &st).is_err(), "hit + passed ball");
+        assert!(run("6 63, bk", &st).is_err(), "out + balk");
+        assert!(run("4 wp 2b", &st).is_err(), "runner not on base");
+    }
+
+    #[test]
+    fn pitch_advance_cannot_land_on_or_pass_a_runner_who_holds() {
+        let mut st = make_state(Some(6));
+        st.on_2b = Some(2);
+        st.on_3b = Some(1);
+        let blocked = |line: &str| {
+            run(line, &st).unwrap_err().iter().any(|e| {
+                e.kind
+                    == CommandErrorKind::Validation(ValidationError::RunnerBlocked {
+                        order: 2,
+                        blocker: 1,
+                    })
+            })
+        };
+        assert!(blocked("2 pb 3b"), "third is taken");
+        assert!(blocked("2 wp sc"), "#2 would score ahead of #1");
+        assert!(blocked("2 wp sc, 1 wp 3b"), "#1 stays on third");
+    }
+
+    #[test]
+    fn pitch_advances_move_the_lead_runner_first() {
+        let mut st = make_state(Some(6));
+        st.on_1b = Some(2);
+        st.on_3b = Some(1);
+        let cmds = run("b, 2 wp 3b, 1 wp sc", &st).unwrap();
+        let orders: Vec<u8> = cmds
+            .iter()
+            .filter_map(|c| match c {
+                EngineCommand::PitchAdvance { order, .. } => Some(*order),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(orders, [1, 2]);
+        assert!(matches!(cmds[0], EngineCommand::Pitch(Pitch::Ball)));
+
+        assert!(run("2 wp sc, 1 wp sc", &st).is_ok(), "both runners score");
+    }
+
+    #[test]
+    fn caught_stealing_rides_with_pitches_only() {
+        let mut st = make_state(Some(6));
+        st.on_1b = Some(5);
+        st.on_3b = Some(3);
+        let cmds = run("s, 5 cs 2b 26", &st).unwrap();
+        assert!(matches!(
+            &cmds[1],
+            EngineCommand::RunnerCaught {
+                order: 5,
+                kind: RunnerCaughtKind::CaughtStealing,
+                dest: Some(RunnerDest::Second),
+                fielders,
+            } if fielders == &vec![2, 6]
+        ));
+        assert!(matches!(
+            run("3 po 15", &st).unwrap()[..],
+            [EngineCommand::RunnerCaught {
+                kind: RunnerCaughtKind::Pickoff,
+                dest: None,
+                ..
+            }]
+        ));
+
+        assert!(
+            run("6 63, 5 cs 2b 26", &st).is_err(),
+            "out + caught stealing"
+        );
+        assert!(run("4 po 13", &st).is_err(), "runner not on base");
+    }
+
+    #[test]
+    fn dropped_third_strike_needs_two_strikes_and_an_open_first() {
+        let mut state = make_state(Some(5));
+        state.pitch_count.strikes = 2;
+        state.on_3b = Some(3);
+        let cmds = run("kwp, 3 sc", &state).unwrap();
+        match &cmds[..] {
+            [
+                EngineCommand::DroppedThirdStrike {
+                    how: DroppedThirdStrike::WildPitch,
+                    runner_overrides,
+                },
+            ] => assert_eq!(runner_overrides.len(), 1),
+            other => panic!("unexpected commands: {other:?}"),
+        }
+
+        state.pitch_count.strikes = 1;
+        let errs = run("kpb", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::DroppedThirdStrikeWithoutTwoStrikes {
+                strikes: 1
+            })
+        ));
+
+        state.pitch_count.strikes = 2;
+        state.on_1b = Some(4);
+        let errs = run("kpb", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::DroppedThirdStrikeWithFirstOccupied)
+        ));
+        state.outs = 2;
+        assert!(run("kpb", &state).is_ok());
+
+        let errs = run("kpb, 4 63", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::ControlMixedWithActions { .. })
+        ));
+    }
+
+    #[test]
+    fn ground_rule_double_takes_no_runner_advances() {
+        let mut state = make_state(Some(5));
+        state.on_1b = Some(4);
+        assert!(matches!(
+            &run("2h gr lf", &state).unwrap()[..],
+            [EngineCommand::Double {
+                modifier: Some(HitModifier::GroundRule),
+                zone: Some(FieldZone::LF),
+                ..
+            }]
+        ));
+
+        let errs = run("2h gr, 4 sc", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::GroundRuleDoubleAdvance { order: 4 })
+        ));
+        assert!(run("2h, 4 sc", &state).is_ok());
+    }
+
     #[test]
     fn pitch_and_hit_rejected() {
         let st = make_state(Some(5));
@@ -1048,6 +2117,71 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn undo_must_be_alone() {
+        let st = make_state(Some(5));
+        let cmds = run("undo 2", &st).unwrap();
+        assert!(matches!(cmds[0], EngineCommand::Undo { count: 2 }));
+
+        let errs = run("undo, b", &st).expect_err("mix");
+        assert!(errs.iter().any(|e| matches!(
+            e.kind,
+            CommandErrorKind::Validation(ValidationError::ControlMixedWithActions { .. })
+        )));
+    }
+
+    #[test]
+    fn pitching_change_must_be_alone() {
+        let st = make_state(Some(5));
+        let cmds = run("pc 34", &st).unwrap();
+        assert!(matches!(
+            cmds[0],
+            EngineCommand::PitchingChange { jersey: 34 }
+        ));
+
+        let errs = run("b, pc 34", &st).expect_err("mix");
+        assert!(errs.iter().any(|e| matches!(
+            e.kind,
+            CommandErrorKind::Validation(ValidationError::ControlMixedWithActions { .. })
+        )));
+    }
+
+    #[test]
+    fn substitution_lines() {
+        let mut st = make_state(Some(5));
+        st.on_1b = Some(3);
+        assert!(matches!(
+            run("ph 12", &st).unwrap()[0],
+            EngineCommand::PinchHitter { jersey: 12 }
+        ));
+        assert!(matches!(
+            run("3 pr 27", &st).unwrap()[0],
+            EngineCommand::PinchRunner {
+                order: 3,
+                jersey: 27
+            }
+        ));
+        let cmds = run("7 sub 34 p, 9 sub 22 lf", &st).unwrap();
+        assert_eq!(cmds.len(), 2);
+        assert!(cmds.iter().all(EngineCommand::is_substitution));
+
+        let errs = run("4 ph 12", &st).expect_err("not the batter");
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::BatterSlotMismatch { .. })
+        ));
+        let errs = run("4 pr 27", &st).expect_err("not on base");
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::RunnerNotOnBase { order: 4 })
+        ));
+        let errs = run("b, 3 pr 27", &st).expect_err("mix");
+        assert!(errs.iter().any(|e| matches!(
+            e.kind,
+            CommandErrorKind::Validation(ValidationError::ControlMixedWithActions { .. })
+        )));
+    }
+
     // ── Infield fly rule ──
     #[test]
     fn infield_fly_requires_runners_and_outs_lt_2() {
@@ -1113,4 +2247,177 @@ mod tests {
         let errs = run("8 h, 4 2b", &st).expect_err("two issues");
         assert!(errs.len() >= 2);
     }
+
+    #[test]
+    fn sacrifice_fly_carries_runner_overrides() {
+        let mut state = make_state(Some(5));
+        state.on_2b = Some(3);
+        state.on_3b = Some(4);
+        let cmds = run("sf9, 3 3b", &state).expect("valid");
+        match &cmds[..] {
+            [EngineCommand::DefensivePlay(play)] => {
+                assert_eq!(play.sacrifice, Some(Sacrifice::Fly));
+                assert_eq!(play.runner_advances.len(), 1);
+                assert_eq!(play.runner_advances[0].dest, RunnerDest::Third);
+                assert!(matches!(
+                    play.outs[0].kind,
+                    DefensiveOutKind::FlyOut {
+                        fielder: 9,
+                        in_foul_territory: false,
+                    }
+                ));
+            }
+            other => panic!("unexpected commands: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn batter_out_carries_runner_overrides() {
+        let mut state = make_state(Some(5));
+        state.on_2b = Some(3);
+        let cmds = run("63, 3 3b", &state).expect("valid");
+        match &cmds[..] {
+            [EngineCommand::DefensivePlay(play)] => {
+                assert_eq!(play.sacrifice, None);
+                assert_eq!(play.runner_advances.len(), 1);
+                assert_eq!(play.runner_advances[0].dest, RunnerDest::Third);
+            }
+            other => panic!("unexpected commands: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn no_advance_on_the_third_out() {
+        let mut state = make_state(Some(5));
+        state.outs = 2;
+        state.on_3b = Some(3);
+        let errs = run("f8, 3 sc", &state).expect_err("side retired");
+        assert!(errs.iter().any(|e| matches!(
+            e.kind,
+            CommandErrorKind::Validation(ValidationError::AdvanceOnThirdOut { order: 3 })
+        )));
+    }
+
+    #[test]
+    fn sacrifice_bunt_with_one_fielder_is_unassisted() {
+        let mut state = make_state(Some(2));
+        state.on_1b = Some(1);
+        let cmds = run("sh3", &state).expect("valid");
+        match &cmds[..] {
+            [EngineCommand::DefensivePlay(play)] => {
+                assert_eq!(play.sacrifice, Some(Sacrifice::Bunt));
+                assert!(matches!(
+                    play.outs[0].kind,
+                    DefensiveOutKind::UnassistedOut { fielder: 3 }
+                ));
+            }
+            other => panic!("unexpected commands: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sacrifice_preconditions() {
+        let mut state = make_state(Some(5));
+        state.on_3b = Some(4);
+        state.outs = 2;
+        let errs = run("sf8", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::SacrificeWithTwoOuts)
+        ));
+
+        state.outs = 0;
+        let errs = run("sf8, 4 3b", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::SacrificeFlyWithoutRun)
+        ));
+
+        state.on_3b = None;
+        let errs = run("sf8", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::SacrificeFlyWithoutRun)
+        ));
+
+        // A sacrifice is batter-only: a runner slot is a batter mismatch.
+        state.on_1b = Some(4);
+        let errs = run("4 sh13", &state).unwrap_err();
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::BatterSlotMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn game_kind_rules_out_plays_it_does_not_have() {
+        let mut state = make_state(Some(5));
+        state.on_1b = Some(4);
+        let not_in_game = |errs: Vec<CommandError>| {
+            matches!(
+                errs[0].kind,
+                CommandErrorKind::Validation(ValidationError::NotInGame { .. })
+            )
+        };
+
+        state.rules.kind = GameKind::SlowPitchSoftball;
+        assert!(not_in_game(run("4 st 2b", &state).unwrap_err()));
+        assert!(not_in_game(run("4 wp 2b", &state).unwrap_err()));
+        assert!(not_in_game(run("sh13", &state).unwrap_err()));
+        assert!(not_in_game(run("bk", &state).unwrap_err()));
+        assert!(run("h", &state).is_ok());
+
+        state.rules.kind = GameKind::FastPitchSoftball;
+        assert!(run("4 st 2b", &state).is_ok());
+        assert!(run("sh13", &state).is_ok());
+        assert!(not_in_game(run("bk", &state).unwrap_err()));
+
+        state.rules.kind = GameKind::Baseball;
+        assert!(run("bk", &state).is_ok());
+    }
+
+    // ── RBI credit ──
+    #[test]
+    fn rbi_credit_rides_along_with_the_play() {
+        let mut st = make_state(Some(4));
+        st.on_1b = Some(1);
+        st.on_2b = Some(2);
+        st.on_3b = Some(3);
+
+        let cmds = run("hbp, rbi 0", &st).unwrap();
+        assert_eq!(cmds.len(), 2);
+        assert!(matches!(cmds[0], EngineCommand::HitByPitch));
+        assert!(matches!(cmds[1], EngineCommand::CreditRbi { rbi: 0 }));
+
+        let cmds = run("rbi 2, 2h, 1 sc", &st).unwrap();
+        assert!(matches!(
+            cmds.last(),
+            Some(EngineCommand::CreditRbi { rbi: 2 })
+        ));
+    }
+
+    #[test]
+    fn rbi_credit_needs_the_play_that_ends_the_at_bat() {
+        let mut st = make_state(Some(4));
+        st.on_3b = Some(3);
+
+        for line in ["rbi 1", "b, rbi 1", "3 st sc, rbi 1"] {
+            let errs = run(line, &st).expect_err(line);
+            assert!(
+                errs.iter().any(|e| matches!(
+                    e.kind,
+                    CommandErrorKind::Validation(ValidationError::RbiWithoutBatterPlay)
+                )),
+                "{line}"
+            );
+        }
+
+        let errs = run("h, rbi 1, rbi 0", &st).expect_err("repeated");
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].segment_index, 3);
+        assert!(matches!(
+            errs[0].kind,
+            CommandErrorKind::Validation(ValidationError::RepeatedRbi)
+        ));
+    }
 }
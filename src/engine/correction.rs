@@ -0,0 +1,731 @@
+//! Retroactive correction of a recorded plate appearance.
+//!
+//! Official scorers change rulings after the fact: a hit becomes an
+//! error, a putout goes to another fielder. A correction replaces the
+//! outcome, runner overrides and runner movements of one
+//! `plate_appearances` row, identified by `seq`. The replacement is
+//! entered with the live command grammar, against the state the game was
+//! in when the play happened: same batter and pitcher, and the count
+//! before the play.
+//!
+//! Later rows were recorded against the old outcome, so every later PA
+//! and in-pitch movement is replayed through the reducer on top of the
+//! corrected one. The correction is rejected when one of them no longer
+//! fits:
+//!
+//! - a runner it moves is not on the base it was recorded on;
+//! - the half-inning ends earlier or later than the rows say;
+//! - the game ends earlier or later than the rows say.
+//!
+//! When the corrected play only changes the outs of the half, the outs
+//! recorded on the later PAs of that half are rewritten. When it changes
+//! the score of a finished game, the final score is rewritten.
+
+use crate::db::game_queries::{game_rules, set_game_result};
+use crate::db::plate_appearances::{
+    PlateAppearanceRow, list_plate_appearances, outcome_columns, replace_plate_appearance,
+    set_plate_appearance_outs, set_plate_appearance_rbi,
+};
+use crate::db::runner_movements::{
+    RunnerMovementInsert, RunnerMovementRow, append_runner_movement, delete_runner_movement,
+    list_runner_movements, resolve_runner_ids,
+};
+use crate::engine::apply::apply_engine_command;
+use crate::engine::commands::parser::parse_engine_commands;
+use crate::engine::commands::types::EngineCommand;
+use crate::engine::earned_runs::update_earned_runs;
+use crate::engine::game_end::{batting_team_runs, game_end, half_over, runs_limited};
+use crate::engine::rbi::credited_rbi;
+use crate::engine::reducer::{
+    STANDALONE_TYPES, apply_domain_event, apply_live_plate_appearance, apply_standalone_movement,
+    replay_plate_appearance, split_replay_movements,
+};
+use crate::engine::runners::{
+    drop_departed_tie_break_runners, hold_uncounted_runners, mark_tie_break_runs,
+};
+use crate::engine::undo::count_before_play;
+use crate::engine::{get_sacrifice_flag, parse_outcome_json};
+use crate::models::game_state::{BatterOrder, GameState};
+use crate::models::plate_appearance::{PlateAppearance, PlateAppearanceOutcome};
+use crate::models::types::HalfInning;
+use crate::ui::events::UiEvent;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// A checked correction, ready to be written.
+pub struct CorrectionPlan {
+    /// The row as recorded.
+    pub original: PlateAppearanceRow,
+    /// The corrected plate appearance.
+    pub plate_appearance: PlateAppearance,
+    /// Runner movements of the corrected plate appearance.
+    pub movements: Vec<RunnerMovementInsert>,
+    /// RBIs credited on the corrected plate appearance.
+    pub rbi: u8,
+    /// Later PAs whose recorded outs change, as `(seq, outs)`.
+    pub outs_changes: Vec<(i64, u8)>,
+    /// Final score `(away, home)` and how the game ended, when it is over.
+    pub final_score: Option<(u16, u16, String)>,
+    /// Movement rows of the original PA, replaced by `movements`.
+    replaced_movement_ids: Vec<i64>,
+}
+
+impl CorrectionPlan {
+    /// The corrected row as it will be stored.
+    pub fn corrected_row(&self) -> PlateAppearanceRow {
+        let pa = &self.plate_appearance;
+        let (outcome_type, outcome_data) = outcome_columns(pa);
+        PlateAppearanceRow {
+            pitches: i64::from(pa.pitches),
+            pitches_sequence: serde_json::to_string(&pa.pitches_sequence)
+                .unwrap_or_else(|_| "[]".to_string()),
+            outcome_type,
+            outcome_data,
+            outs: i64::from(pa.outs),
+            runner_overrides_json: serde_json::to_string(&pa.runner_overrides)
+                .unwrap_or_else(|_| "[]".to_string()),
+            double_play_json: pa
+                .double_play
+                .as_ref()
+                .and_then(|dp| serde_json::to_string(dp).ok()),
+            rbi: Some(self.rbi),
+            ..self.original.clone()
+        }
+    }
+}
+
+/// Inning, half, outs and runners: what a later row was recorded against.
+type Situation = (u32, HalfInning, u8, [Option<BatterOrder>; 3]);
+
+fn situation(state: &GameState) -> Situation {
+    (
+        state.inning,
+        state.half,
+        state.outs,
+        [state.on_1b, state.on_2b, state.on_3b],
+    )
+}
+
+/// Score `line` as the new play of plate appearance `seq` and check that
+/// every later row still fits the rebuilt game.
+pub fn plan_correction(
+    conn: &Connection,
+    game_pk: i64,
+    seq: i64,
+    line: &str,
+) -> Result<CorrectionPlan, String> {
+    let pas = list_plate_appearances(conn, game_pk)
+        .map_err(|e| format!("Failed to load plate appearances: {e}"))?;
+    let Some(idx) = pas.iter().position(|pa| pa.seq == seq) else {
+        return Err(format!("No plate appearance #{seq} in this game."));
+    };
+    let all_movements = list_runner_movements(conn, game_pk)
+        .map_err(|e| format!("Failed to load runner movements: {e}"))?;
+    let replaced: Vec<_> = all_movements
+        .iter()
+        .filter(|rm| {
+            rm.pa_seq == Some(seq) && !STANDALONE_TYPES.contains(&rm.advancement_type.as_str())
+        })
+        .collect();
+    let replaced_movement_ids = replaced.iter().map(|rm| rm.id).collect();
+    // The runners keep the identity recorded at the time: the lineup may
+    // have changed since.
+    let runner_ids: HashMap<BatterOrder, i64> = replaced
+        .iter()
+        .filter_map(|rm| Some((rm.batter_order, rm.runner_id?)))
+        .collect();
+    let (standalone, composite) = split_replay_movements(all_movements);
+
+    // The game up to the at-bat being corrected, in-pitch movements
+    // recorded during it included.
+    let mut before = GameState::new();
+    before.rules =
+        game_rules(conn, game_pk).map_err(|e| format!("Failed to load the game rules: {e}"))?;
+    for rm in standalone.iter().filter(|rm| rm.pa_seq.is_none()) {
+        apply_standalone_movement(&mut before, rm);
+    }
+    for pa in &pas[..idx] {
+        replay_plate_appearance(&mut before, pa, &composite);
+        for rm in standalone.iter().filter(|rm| rm.pa_seq == Some(pa.seq)) {
+            apply_standalone_movement(&mut before, rm);
+        }
+    }
+
+    let original = pas[idx].clone();
+    let mut old = before.clone();
+    replay_plate_appearance(&mut old, &original, &composite);
+
+    let mut new = at_bat_state(&before, &original);
+    let (mut plate_appearance, mut movements, rbi) = score_play(&mut new, line)?;
+    for rm in &mut movements {
+        rm.game_id = game_pk;
+        rm.pa_seq = Some(seq);
+        rm.runner_id = runner_ids.get(&rm.batter_order).copied();
+    }
+    resolve_runner_ids(conn, game_pk, &mut movements)
+        .map_err(|e| format!("Failed to identify runners: {e}"))?;
+    if plate_appearance.double_play.is_none()
+        && matches!(
+            plate_appearance.outcome,
+            PlateAppearanceOutcome::Strikeout(_)
+        )
+        && original.outcome_type == "strikeout"
+    {
+        // A strikeout-throw-out DP marker comes from the runner thrown
+        // out after the PA, which the correction keeps.
+        plate_appearance.double_play = original.double_play();
+    }
+
+    let mut outs_changes = Vec::new();
+    let mut prev_seq = seq;
+    for next in pas[idx + 1..].iter().map(Some).chain([None]) {
+        // In-pitch movements recorded after the previous PA.
+        for rm in standalone.iter().filter(|rm| rm.pa_seq == Some(prev_seq)) {
+            let same_half = (new.inning, new.half)
+                == (
+                    rm.inning as u32,
+                    HalfInning::from_str_loose(&rm.half_inning),
+                );
+            if same_half && situation(&old) != situation(&new) {
+                check_runner_on_base(&new, rm, prev_seq)?;
+            }
+            if !same_half && !half_over(&new) {
+                return Err(format!(
+                    "The correction leaves the half-inning with {} out(s), but the next one was started after plate appearance #{prev_seq}.",
+                    new.outs
+                ));
+            }
+            apply_standalone_movement(&mut old, rm);
+            apply_standalone_movement(&mut new, rm);
+        }
+
+        let Some(pa) = next else {
+            // Nothing recorded after: the game and the half must be over
+            // (or not) as the rows recorded them.
+            if game_end(&old).is_some() && game_end(&new).is_none() {
+                return Err("The correction no longer ends the game.".to_string());
+            }
+            if half_over(&old) != half_over(&new) {
+                return Err(if half_over(&new) {
+                    "The correction ends the half-inning, but play went on in it.".to_string()
+                } else {
+                    "The correction leaves the half-inning open, but play moved on to the next one."
+                        .to_string()
+                });
+            }
+            break;
+        };
+        if situation(&old) == situation(&new) && runs(&old) == runs(&new) {
+            // Same inning, outs, runners and score: the rest of the game
+            // was recorded against exactly this state.
+            break;
+        }
+        if game_end(&new).is_some() {
+            return Err(format!(
+                "The correction ends the game before plate appearance #{}.",
+                pa.seq
+            ));
+        }
+
+        let same_half = (new.inning, new.half)
+            == (
+                pa.inning as u32,
+                HalfInning::from_str_loose(&pa.half_inning),
+            );
+        if same_half && half_over(&new) {
+            return Err(format!(
+                "The correction ends the half-inning before plate appearance #{}.",
+                pa.seq
+            ));
+        }
+        if !same_half && !half_over(&new) {
+            return Err(format!(
+                "The correction leaves the half-inning with {} out(s), but plate appearance #{} starts the next one.",
+                new.outs, pa.seq
+            ));
+        }
+        // A new half starts with empty bases.
+        if same_half && runner_bases(&old) != runner_bases(&new) && moves_runners(pa, &composite) {
+            return Err(format!(
+                "Plate appearance #{} moves runners the correction puts elsewhere.",
+                pa.seq
+            ));
+        }
+
+        let outs_made = pa.outs - i64::from(outs_before(&old, pa));
+        let outs = i64::from(outs_before(&new, pa)) + outs_made;
+        if outs > 3 {
+            return Err(format!(
+                "Plate appearance #{} would make more than three outs.",
+                pa.seq
+            ));
+        }
+
+        replay_plate_appearance(&mut old, pa, &composite);
+        let mut rewritten = pa.clone();
+        rewritten.outs = outs;
+        replay_plate_appearance(&mut new, &rewritten, &composite);
+        if outs != pa.outs {
+            outs_changes.push((pa.seq, outs as u8));
+        }
+        prev_seq = pa.seq;
+    }
+
+    // After an early break the score is unchanged and `new` is not at
+    // the end of the game, so nothing is rewritten.
+    let final_score = game_end(&new).map(|end| {
+        let (away, home) = runs(&new);
+        (away, home, end.description())
+    });
+
+    Ok(CorrectionPlan {
+        original,
+        plate_appearance,
+        movements,
+        rbi,
+        outs_changes,
+        final_score,
+        replaced_movement_ids,
+    })
+}
+
+/// Write `plan` in one transaction.
+pub fn apply_correction(
+    conn: &mut Connection,
+    game_pk: i64,
+    plan: &CorrectionPlan,
+) -> rusqlite::Result<()> {
+    let seq = plan.original.seq;
+    let tx = conn.transaction()?;
+
+    replace_plate_appearance(&tx, game_pk, seq, &plan.plate_appearance)?;
+    set_plate_appearance_rbi(&tx, game_pk, seq, plan.rbi)?;
+    for id in &plan.replaced_movement_ids {
+        delete_runner_movement(&tx, *id)?;
+    }
+    for rm in &plan.movements {
+        append_runner_movement(&tx, rm)?;
+    }
+    for (later_seq, outs) in &plan.outs_changes {
+        set_plate_appearance_outs(&tx, game_pk, *later_seq, *outs)?;
+    }
+    if let Some((away, home, reason)) = &plan.final_score {
+        set_game_result(&tx, game_pk, *away, *home, Some(reason))?;
+    }
+    update_earned_runs(&tx, game_pk)?;
+
+    tx.commit()
+}
+
+/// The state live scoring had when the at-bat of `row` reached its last
+/// pitch or play.
+fn at_bat_state(before: &GameState, row: &PlateAppearanceRow) -> GameState {
+    let mut state = before.clone();
+    let inning = row.inning as u32;
+    let half = HalfInning::from_str_loose(&row.half_inning);
+    if state.inning != inning || state.half != half {
+        state.inning = inning;
+        state.half = half;
+        state.outs = 0;
+        state.on_1b = None;
+        state.on_2b = None;
+        state.on_3b = None;
+        state.tie_break_runners.clear();
+    }
+
+    state.started = true;
+    state.current_batter_id = Some(row.batter_id);
+    state.current_batter_order = Some(row.batter_order);
+    state.current_pitcher_id = Some(row.pitcher_id);
+    state.pitch_count = count_before_play(row);
+    state
+}
+
+/// Apply the replacement line the way the live loop does and return the
+/// plate appearance it completes with its runner movements and RBIs.
+fn score_play(
+    state: &mut GameState,
+    line: &str,
+) -> Result<(PlateAppearance, Vec<RunnerMovementInsert>, u8), String> {
+    let commands = parse_engine_commands(line, state).map_err(|errors| {
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    })?;
+
+    let rbi_credit = commands.iter().find_map(|cmd| match cmd {
+        EngineCommand::CreditRbi { rbi } => Some(*rbi),
+        _ => None,
+    });
+
+    let runs_limited = runs_limited(state);
+    let runs_before = batting_team_runs(state);
+    let mut completed = None;
+    for cmd in commands {
+        if let EngineCommand::CreditRbi { .. } = cmd {
+            continue;
+        }
+        if !is_play_command(&cmd) {
+            return Err(
+                "A correction can only re-enter pitches and the play that ended the plate appearance."
+                    .to_string(),
+            );
+        }
+        if completed.is_some() {
+            return Err("Nothing can follow the play that ends the plate appearance.".to_string());
+        }
+
+        let mut result = apply_engine_command(state, cmd);
+        if let Some(msg) = result.events.into_iter().find_map(|ev| match ev {
+            UiEvent::Error(msg) => Some(msg),
+            _ => None,
+        }) {
+            return Err(msg);
+        }
+        for ev in &result.applied {
+            apply_domain_event(state, ev);
+        }
+
+        if let Some(pa) = result.plate_appearance {
+            let hit_movements = apply_live_plate_appearance(state, &pa);
+            let mut movements = if !hit_movements.is_empty() {
+                hit_movements
+            } else {
+                result.runner_movements.drain(..).collect()
+            };
+            if runs_limited {
+                let mut counted = u32::from(batting_team_runs(state) - runs_before);
+                hold_uncounted_runners(&mut movements, &mut counted);
+            }
+            mark_tie_break_runs(state, &mut movements);
+            drop_departed_tie_break_runners(state);
+            completed = Some((pa, movements));
+        } else if !result.runner_movements.is_empty() {
+            return Err(
+                "Runner events between pitches cannot be part of a correction.".to_string(),
+            );
+        }
+    }
+
+    let (pa, movements) = completed
+        .ok_or_else(|| "The replacement does not complete the plate appearance.".to_string())?;
+    let rbi = rbi_credit.unwrap_or_else(|| credited_rbi(&pa, &movements));
+    Ok((pa, movements, rbi))
+}
+
+/// Pitches and plays. Game control, lineup changes and runner events
+/// between pitches are recorded on their own and cannot be corrected here.
+fn is_play_command(cmd: &EngineCommand) -> bool {
+    !matches!(
+        cmd,
+        EngineCommand::Exit
+            | EngineCommand::SetStatus(_)
+            | EngineCommand::PlayBall
+            | EngineCommand::Undo { .. }
+            | EngineCommand::CreditRbi { .. }
+            | EngineCommand::StealBase { .. }
+            | EngineCommand::PitchAdvance { .. }
+            | EngineCommand::Balk
+            | EngineCommand::RunnerCaught { .. }
+    ) && !cmd.is_substitution()
+}
+
+/// Score as `(away, home)`.
+fn runs(state: &GameState) -> (u16, u16) {
+    (state.score.away, state.score.home)
+}
+
+fn runner_bases(state: &GameState) -> [Option<BatterOrder>; 3] {
+    [state.on_1b, state.on_2b, state.on_3b]
+}
+
+/// Outs on the board when `pa` started.
+fn outs_before(state: &GameState, pa: &PlateAppearanceRow) -> u8 {
+    let same_half = state.inning == pa.inning as u32
+        && state.half == HalfInning::from_str_loose(&pa.half_inning);
+    if same_half { state.outs } else { 0 }
+}
+
+/// Whether replaying `pa` depends on who is on base. Only a plain batter
+/// out leaves the runners where they are.
+fn moves_runners(pa: &PlateAppearanceRow, composite: &[RunnerMovementRow]) -> bool {
+    let batter_out = matches!(
+        pa.outcome_type.as_str(),
+        "strikeout"
+            | "out"
+            | "ground_out"
+            | "fly_out"
+            | "line_out"
+            | "infield_fly"
+            | "unassisted_out"
+    );
+    let sacrifice =
+        parse_outcome_json(pa.outcome_data.as_deref()).is_some_and(|v| get_sacrifice_flag(&v));
+
+    !batter_out
+        || sacrifice
+        || !pa.runner_overrides().is_empty()
+        || composite.iter().any(|rm| rm.pa_seq == Some(pa.seq))
+}
+
+/// An in-pitch movement recorded after plate appearance `pa_seq` needs
+/// its runner where the row says.
+fn check_runner_on_base(
+    state: &GameState,
+    rm: &RunnerMovementRow,
+    pa_seq: i64,
+) -> Result<(), String> {
+    let on_base = match rm.start_base.as_str() {
+        "1B" => state.on_1b,
+        "2B" => state.on_2b,
+        "3B" => state.on_3b,
+        _ => None,
+    };
+    if on_base == Some(rm.batter_order) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Runner #{} is no longer on {} for the {} after plate appearance #{pa_seq}.",
+            rm.batter_order,
+            rm.start_base,
+            rm.advancement_type.replace('_', " ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+    use crate::db::fixtures::bullpen_game;
+    use crate::db::plate_appearances::append_plate_appearance;
+    use crate::models::events::StrikeoutKind;
+    use crate::models::plate_appearance::PlateAppearanceStep;
+
+    /// A home pitcher and three away batters. Returns (game_pk, pitcher
+    /// id, batter ids).
+    fn setup(conn: &Connection) -> (i64, i64, Vec<i64>) {
+        let game = bullpen_game(conn, "G-FIX", &[(21, None, "Pat", "Starter")], 3);
+        (game.game_pk, game.pitchers[0], game.batters)
+    }
+
+    fn single(batter_id: i64, order: u8, pitcher_id: i64) -> PlateAppearance {
+        PlateAppearance {
+            inning: 1,
+            half: HalfInning::Top,
+            batter_id,
+            batter_order: order,
+            pitcher_id,
+            pitches: 1,
+            pitches_sequence: vec![PlateAppearanceStep::Single],
+            outcome: PlateAppearanceOutcome::Single {
+                zone: None,
+                modifier: None,
+            },
+            outs: 0,
+            runner_overrides: vec![],
+            double_play: None,
+        }
+    }
+
+    fn strikeout(batter_id: i64, order: u8, pitcher_id: i64, outs: u8) -> PlateAppearance {
+        PlateAppearance {
+            pitches_sequence: vec![PlateAppearanceStep::Strikeout],
+            outcome: PlateAppearanceOutcome::Strikeout(StrikeoutKind::Swinging),
+            outs,
+            ..single(batter_id, order, pitcher_id)
+        }
+    }
+
+    fn movement(
+        game_pk: i64,
+        pa_seq: i64,
+        order: u8,
+        from: &'static str,
+        to: &'static str,
+        kind: &'static str,
+    ) -> RunnerMovementInsert {
+        RunnerMovementInsert {
+            game_id: game_pk,
+            pa_seq: Some(pa_seq),
+            game_event_id: None,
+            inning: 1,
+            half_inning: "Top".to_string(),
+            runner_id: None,
+            batter_order: order,
+            start_base: from,
+            end_base: to,
+            advancement_type: kind,
+            is_out: false,
+            scored: false,
+            is_earned: false,
+            fielders: None,
+        }
+    }
+
+    #[test]
+    fn hit_changed_to_error_keeps_the_rest_of_the_game() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection_mut();
+        let (game_pk, pitcher, batters) = setup(conn);
+
+        append_plate_appearance(conn, game_pk, &single(batters[0], 1, pitcher)).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 1, 1, "BAT", "1B", "hit_auto")).unwrap();
+        append_plate_appearance(conn, game_pk, &single(batters[1], 2, pitcher)).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 2, 1, "1B", "2B", "hit_auto")).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 2, 2, "BAT", "1B", "hit_auto")).unwrap();
+
+        let plan = plan_correction(conn, game_pk, 1, "e6").unwrap();
+        assert!(plan.outs_changes.is_empty());
+        apply_correction(conn, game_pk, &plan).unwrap();
+
+        let pas = list_plate_appearances(conn, game_pk).unwrap();
+        assert_eq!(pas[0].outcome_type, "reached_on_error");
+        assert_eq!(pas[1].outcome_type, "single");
+        let first: Vec<_> = list_runner_movements(conn, game_pk)
+            .unwrap()
+            .into_iter()
+            .filter(|rm| rm.pa_seq == Some(1))
+            .map(|rm| (rm.start_base, rm.end_base))
+            .collect();
+        assert_eq!(first, vec![("BAT".to_string(), "1B".to_string())]);
+    }
+
+    #[test]
+    fn later_play_moving_a_runner_who_is_gone_rejects_the_correction() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let (game_pk, pitcher, batters) = setup(conn);
+
+        append_plate_appearance(conn, game_pk, &single(batters[0], 1, pitcher)).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 1, 1, "BAT", "1B", "hit_auto")).unwrap();
+        append_plate_appearance(conn, game_pk, &single(batters[1], 2, pitcher)).unwrap();
+
+        let err = plan_correction(conn, game_pk, 1, "f8").err().unwrap();
+        assert!(err.contains("#2"), "{err}");
+    }
+
+    #[test]
+    fn steal_by_a_runner_who_is_gone_rejects_the_correction() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let (game_pk, pitcher, batters) = setup(conn);
+
+        append_plate_appearance(conn, game_pk, &single(batters[0], 1, pitcher)).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 1, 1, "1B", "2B", "steal")).unwrap();
+
+        let err = plan_correction(conn, game_pk, 1, "f8").err().unwrap();
+        assert!(err.contains("Runner #1"), "{err}");
+    }
+
+    #[test]
+    fn extra_out_is_carried_to_the_later_plate_appearances() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection_mut();
+        let (game_pk, pitcher, batters) = setup(conn);
+
+        append_plate_appearance(conn, game_pk, &single(batters[0], 1, pitcher)).unwrap();
+        append_plate_appearance(conn, game_pk, &strikeout(batters[1], 2, pitcher, 1)).unwrap();
+
+        let plan = plan_correction(conn, game_pk, 1, "f8").unwrap();
+        assert_eq!(plan.outs_changes, vec![(2, 2)]);
+        apply_correction(conn, game_pk, &plan).unwrap();
+        let outs: Vec<_> = list_plate_appearances(conn, game_pk)
+            .unwrap()
+            .iter()
+            .map(|pa| pa.outs)
+            .collect();
+        assert_eq!(outs, vec![1, 2]);
+
+        // Once a third strikeout closes the half, taking the out back
+        // would leave it open.
+        append_plate_appearance(conn, game_pk, &strikeout(batters[2], 3, pitcher, 3)).unwrap();
+        let err = plan_correction(conn, game_pk, 1, "h").err().unwrap();
+        assert!(err.contains("half-inning"), "{err}");
+    }
+
+    /// Three strikeouts in the top of the 1st, then a tie-break runner
+    /// (slot 9) on second and a single in the bottom half.
+    fn tie_break_game(conn: &Connection) -> i64 {
+        let (game_pk, pitcher, batters) = setup(conn);
+        for (i, batter) in batters.iter().enumerate() {
+            let order = i as u8 + 1;
+            append_plate_appearance(conn, game_pk, &strikeout(*batter, order, pitcher, order))
+                .unwrap();
+        }
+        let bottom = |mut rm: RunnerMovementInsert| {
+            rm.half_inning = "Bottom".to_string();
+            rm
+        };
+        append_runner_movement(
+            conn,
+            &bottom(movement(game_pk, 3, 9, "BAT", "2B", "tie_break")),
+        )
+        .unwrap();
+        let single_bottom = PlateAppearance {
+            half: HalfInning::Bottom,
+            ..single(batters[0], 1, pitcher)
+        };
+        append_plate_appearance(conn, game_pk, &single_bottom).unwrap();
+        append_runner_movement(
+            conn,
+            &bottom(movement(game_pk, 4, 9, "2B", "3B", "hit_auto")),
+        )
+        .unwrap();
+        append_runner_movement(
+            conn,
+            &bottom(movement(game_pk, 4, 1, "BAT", "1B", "hit_auto")),
+        )
+        .unwrap();
+        game_pk
+    }
+
+    #[test]
+    fn tie_break_runner_is_replayed_and_scores_unearned() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let game_pk = tie_break_game(conn);
+
+        let plan = plan_correction(conn, game_pk, 4, "2h").unwrap();
+        let runner = plan
+            .movements
+            .iter()
+            .find(|rm| rm.batter_order == 9)
+            .unwrap();
+        assert_eq!(runner.end_base, "HOME");
+        assert!(runner.scored && !runner.is_earned);
+    }
+
+    #[test]
+    fn correction_cannot_reopen_a_half_after_the_tie_break_runner() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let game_pk = tie_break_game(conn);
+
+        let err = plan_correction(conn, game_pk, 3, "h").err().unwrap();
+        assert!(err.contains("next one was started"), "{err}");
+    }
+    #[test]
+    fn correction_recomputes_the_rbis_unless_the_scorer_sets_them() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection_mut();
+        let game_pk = tie_break_game(conn);
+
+        assert_eq!(plan_correction(conn, game_pk, 4, "2h").unwrap().rbi, 1);
+
+        let plan = plan_correction(conn, game_pk, 4, "2h, rbi 0").unwrap();
+        assert_eq!(plan.rbi, 0);
+        apply_correction(conn, game_pk, &plan).unwrap();
+        let pas = list_plate_appearances(conn, game_pk).unwrap();
+        assert_eq!(pas[3].rbi, Some(0));
+    }
+}
@@ -0,0 +1,366 @@
+//! Earned-run reconstruction (Rule 9.16).
+//!
+//! Each half-inning is replayed from the stored plate appearances and
+//! runner movements as if it had been played without errors and passed
+//! balls:
+//!
+//! - a runner who reached on an error, catcher's interference, a passed
+//!   ball on strike three or the tie-break rule, or who moved up on an
+//!   error or a passed ball, scores an unearned run;
+//! - a batter who reached on an error, or on a passed ball on strike three,
+//!   would have been out: once those outs and the real ones reach three, no
+//!   later run is earned;
+//! - a run is charged to the pitcher who let the runner on base. A relief
+//!   pitcher gets no benefit from the outs missed before they came in, so
+//!   a run can be earned for them and unearned for the team. A runner who
+//!   reaches on a fielder's choice that retires an inherited runner is
+//!   charged to the pitcher of the runner put out.
+//!
+//! `runner_movements.is_earned` on a scoring row is whether the run is
+//! earned for the pitcher it is charged to, which is what ERA needs.
+
+use crate::db::plate_appearances::{PlateAppearanceRow, list_plate_appearances};
+use crate::db::runner_movements::{
+    RunnerMovementRow, list_runner_movements, set_runner_movement_earned,
+};
+use crate::engine::reducer::STANDALONE_TYPES;
+use crate::models::game_state::BatterOrder;
+use crate::models::plate_appearance::DroppedThirdStrike;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// A run and the pitcher it is charged to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunCharge {
+    /// The scoring `runner_movements` row.
+    pub movement_id: i64,
+    pub pitcher_id: i64,
+    /// Earned for `pitcher_id`.
+    pub earned: bool,
+}
+
+/// Something recorded in a half-inning, in the order it happened.
+pub(crate) enum Play<'a> {
+    PlateAppearance(&'a PlateAppearanceRow, Vec<&'a RunnerMovementRow>),
+    /// A movement between pitches (steal, wild pitch, tie-break runner, ...).
+    Between(&'a RunnerMovementRow),
+}
+
+struct Runner {
+    /// Pitcher charged if the runner scores.
+    pitcher_id: i64,
+    /// Reached base and moved up without the help of an error or a passed
+    /// ball.
+    clean: bool,
+}
+
+/// Charge every run of the game to a pitcher and tell whether it is earned.
+pub fn charge_runs(
+    plate_appearances: &[PlateAppearanceRow],
+    movements: &[RunnerMovementRow],
+) -> Vec<RunCharge> {
+    let mut charges = Vec::new();
+    for plays in half_innings(plate_appearances, movements) {
+        charge_half(&plays, &mut charges);
+    }
+    charges
+}
+
+/// Recompute the earned flag of every run of the game and write the ones
+/// that changed.
+pub fn update_earned_runs(conn: &Connection, game_pk: i64) -> rusqlite::Result<()> {
+    let pas = list_plate_appearances(conn, game_pk)?;
+    let movements = list_runner_movements(conn, game_pk)?;
+    let flags: HashMap<i64, bool> = movements.iter().map(|rm| (rm.id, rm.is_earned)).collect();
+    for charge in charge_runs(&pas, &movements) {
+        if flags.get(&charge.movement_id) != Some(&charge.earned) {
+            set_runner_movement_earned(conn, charge.movement_id, charge.earned)?;
+        }
+    }
+    Ok(())
+}
+
+/// Group the plays by half-inning. A movement recorded between pitches is
+/// linked to the PA before it, but belongs to the half written on the row:
+/// the tie-break runner opens the next half.
+pub(crate) fn half_innings<'a>(
+    plate_appearances: &'a [PlateAppearanceRow],
+    movements: &'a [RunnerMovementRow],
+) -> Vec<Vec<Play<'a>>> {
+    let mut halves: Vec<((i64, bool), Vec<Play<'a>>)> = Vec::new();
+    let mut push = |key: (i64, bool), play: Play<'a>| match halves.last_mut() {
+        Some((last, plays)) if *last == key => plays.push(play),
+        _ => halves.push((key, vec![play])),
+    };
+    let row_key = |rm: &RunnerMovementRow| (rm.inning, rm.half_inning == "Bottom");
+    let between = |seq: Option<i64>| {
+        movements.iter().filter(move |rm| {
+            rm.pa_seq == seq && STANDALONE_TYPES.contains(&rm.advancement_type.as_str())
+        })
+    };
+
+    for rm in between(None) {
+        push(row_key(rm), Play::Between(rm));
+    }
+    for pa in plate_appearances {
+        let rows = movements
+            .iter()
+            .filter(|rm| {
+                rm.pa_seq == Some(pa.seq)
+                    && !STANDALONE_TYPES.contains(&rm.advancement_type.as_str())
+            })
+            .collect();
+        push(
+            (pa.inning, pa.half_inning == "Bottom"),
+            Play::PlateAppearance(pa, rows),
+        );
+        for rm in between(Some(pa.seq)) {
+            push(row_key(rm), Play::Between(rm));
+        }
+    }
+    halves.into_iter().map(|(_, plays)| plays).collect()
+}
+
+fn charge_half(plays: &[Play], charges: &mut Vec<RunCharge>) {
+    let mut runners: HashMap<BatterOrder, Runner> = HashMap::new();
+    let mut outs: u8 = 0;
+    // Outs a play without errors would have made.
+    let mut missed_outs: u8 = 0;
+    // `missed_outs` when each pitcher took over in this half.
+    let mut missed_at_entry: HashMap<i64, u8> = HashMap::new();
+
+    for (i, play) in plays.iter().enumerate() {
+        let pitcher_id = pitcher_on_the_mound(plays, i);
+        missed_at_entry.entry(pitcher_id).or_insert(missed_outs);
+
+        let (rows, batter) = match play {
+            Play::PlateAppearance(pa, rows) => {
+                outs = pa.outs.clamp(0, 3) as u8;
+                if batter_would_be_out(pa) {
+                    missed_outs += 1;
+                }
+                (rows.as_slice(), Some(*pa))
+            }
+            Play::Between(rm) => {
+                if rm.is_out {
+                    outs = outs.saturating_add(1);
+                }
+                (std::slice::from_ref(rm), None)
+            }
+        };
+
+        // Runner forced out on a fielder's choice: the batter who takes
+        // their place is charged to the same pitcher.
+        let replaced_pitcher = batter
+            .filter(|pa| pa.outcome_type == "fielders_choice")
+            .and_then(|_| {
+                rows.iter()
+                    .filter(|rm| rm.is_out && rm.start_base != "BAT")
+                    .find_map(|rm| runners.get(&rm.batter_order))
+                    .map(|r| r.pitcher_id)
+            });
+
+        for rm in rows {
+            let tainted = matches!(rm.advancement_type.as_str(), "error" | "passed_ball");
+            if rm.start_base == "BAT" {
+                let clean = !tainted
+                    && match batter {
+                        Some(pa) => batter_reached_clean(pa),
+                        None => rm.advancement_type != "tie_break",
+                    };
+                runners.insert(
+                    rm.batter_order,
+                    Runner {
+                        pitcher_id: replaced_pitcher.unwrap_or(pitcher_id),
+                        clean,
+                    },
+                );
+            }
+            if rm.is_out {
+                runners.remove(&rm.batter_order);
+                continue;
+            }
+            let runner = runners.entry(rm.batter_order).or_insert(Runner {
+                pitcher_id,
+                clean: true,
+            });
+            runner.clean &= !tainted;
+            if !rm.scored {
+                continue;
+            }
+
+            let charged = runner.pitcher_id;
+            let missed_before = missed_at_entry.get(&charged).copied().unwrap_or(0);
+            let would_be_outs = outs + missed_outs.saturating_sub(missed_before);
+            charges.push(RunCharge {
+                movement_id: rm.id,
+                pitcher_id: charged,
+                earned: runner.clean && would_be_outs < 3,
+            });
+            runners.remove(&rm.batter_order);
+        }
+    }
+}
+
+/// The pitcher of a PA, or for a movement between pitches the pitcher of
+/// the at-bat it happened in: the next PA of the half (the previous one
+/// when none follows).
+pub(crate) fn pitcher_on_the_mound(plays: &[Play], i: usize) -> i64 {
+    let pitcher = |play: &Play| match play {
+        Play::PlateAppearance(pa, _) => Some(pa.pitcher_id),
+        Play::Between(_) => None,
+    };
+    plays[i..]
+        .iter()
+        .find_map(pitcher)
+        .or_else(|| plays[..i].iter().rev().find_map(pitcher))
+        .unwrap_or_default()
+}
+
+/// Without the error or the passed ball the batter would have been out.
+fn batter_would_be_out(pa: &PlateAppearanceRow) -> bool {
+    match pa.outcome_type.as_str() {
+        "reached_on_error" => true,
+        "dropped_third_strike" => {
+            matches!(
+                dropped_third_strike(pa),
+                Some(DroppedThirdStrike::PassedBall)
+            )
+        }
+        _ => false,
+    }
+}
+
+/// The batter reached base on their own: not on an error, catcher's
+/// interference or a passed ball on strike three.
+fn batter_reached_clean(pa: &PlateAppearanceRow) -> bool {
+    match pa.outcome_type.as_str() {
+        "reached_on_error" | "catcher_interference" => false,
+        "dropped_third_strike" => !matches!(
+            dropped_third_strike(pa),
+            Some(DroppedThirdStrike::PassedBall)
+        ),
+        _ => true,
+    }
+}
+
+fn dropped_third_strike(pa: &PlateAppearanceRow) -> Option<DroppedThirdStrike> {
+    serde_json::from_str(pa.outcome_data.as_deref()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{pa, rm};
+
+    const STARTER: i64 = 100;
+    const RELIEVER: i64 = 200;
+
+    fn earned(charges: &[RunCharge]) -> Vec<(i64, i64, bool)> {
+        charges
+            .iter()
+            .map(|c| (c.movement_id, c.pitcher_id, c.earned))
+            .collect()
+    }
+
+    #[test]
+    fn runner_who_reached_on_an_error_scores_unearned() {
+        let pas = [
+            pa(1, "Top", 1, STARTER, "reached_on_error", None, 0),
+            pa(2, "Top", 2, STARTER, "home_run", None, 0),
+        ];
+        let rows = [
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "error"),
+            rm(2, Some(2), "Top", 1, "1B", "HOME", "hit_auto"),
+            rm(3, Some(2), "Top", 2, "BAT", "HOME", "hit_auto"),
+        ];
+        assert_eq!(
+            earned(&charge_runs(&pas, &rows)),
+            vec![(2, STARTER, false), (3, STARTER, true)]
+        );
+    }
+
+    #[test]
+    fn runs_after_the_would_be_third_out_are_unearned_except_for_a_reliever() {
+        let mut pas = vec![
+            pa(1, "Top", 1, STARTER, "strikeout", None, 1),
+            pa(2, "Top", 2, STARTER, "strikeout", None, 2),
+            pa(3, "Top", 3, STARTER, "reached_on_error", None, 2),
+            pa(4, "Top", 4, STARTER, "home_run", None, 2),
+        ];
+        let rows = [
+            rm(1, Some(3), "Top", 3, "BAT", "1B", "error"),
+            rm(2, Some(4), "Top", 3, "1B", "HOME", "hit_auto"),
+            rm(3, Some(4), "Top", 4, "BAT", "HOME", "hit_auto"),
+        ];
+        assert_eq!(
+            earned(&charge_runs(&pas, &rows)),
+            vec![(2, STARTER, false), (3, STARTER, false)]
+        );
+
+        // The reliever did not have the chance of the missed out: the home
+        // run is earned for them, the inherited runner is still unearned.
+        pas[3].pitcher_id = RELIEVER;
+        assert_eq!(
+            earned(&charge_runs(&pas, &rows)),
+            vec![(2, STARTER, false), (3, RELIEVER, true)]
+        );
+    }
+
+    #[test]
+    fn advance_on_a_passed_ball_makes_the_run_unearned() {
+        let pas = [
+            pa(1, "Top", 1, STARTER, "single", None, 0),
+            pa(2, "Top", 2, STARTER, "single", None, 0),
+        ];
+        let rows = [
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "hit_auto"),
+            rm(2, Some(1), "Top", 1, "1B", "2B", "passed_ball"),
+            rm(3, Some(2), "Top", 1, "2B", "HOME", "hit_auto"),
+            rm(4, Some(2), "Top", 2, "BAT", "1B", "hit_auto"),
+        ];
+        assert_eq!(earned(&charge_runs(&pas, &rows)), vec![(3, STARTER, false)]);
+
+        let rows = [
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "hit_auto"),
+            rm(2, Some(1), "Top", 1, "1B", "2B", "wild_pitch"),
+            rm(3, Some(2), "Top", 1, "2B", "HOME", "hit_auto"),
+            rm(4, Some(2), "Top", 2, "BAT", "1B", "hit_auto"),
+        ];
+        assert_eq!(earned(&charge_runs(&pas, &rows)), vec![(3, STARTER, true)]);
+    }
+
+    #[test]
+    fn fielders_choice_runner_replaces_the_inherited_one() {
+        let pas = [
+            pa(1, "Top", 1, STARTER, "walk", None, 0),
+            pa(2, "Top", 2, RELIEVER, "fielders_choice", None, 1),
+            pa(3, "Top", 3, RELIEVER, "home_run", None, 1),
+        ];
+        let rows = [
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "walk"),
+            rm(2, Some(2), "Top", 1, "1B", "OUT", "fielders_choice"),
+            rm(3, Some(2), "Top", 2, "BAT", "1B", "fielders_choice"),
+            rm(4, Some(3), "Top", 2, "1B", "HOME", "hit_auto"),
+            rm(5, Some(3), "Top", 3, "BAT", "HOME", "hit_auto"),
+        ];
+        assert_eq!(
+            earned(&charge_runs(&pas, &rows)),
+            vec![(4, STARTER, true), (5, RELIEVER, true)]
+        );
+    }
+
+    #[test]
+    fn tie_break_runner_opens_the_next_half_and_scores_unearned() {
+        let pas = [
+            pa(1, "Top", 3, STARTER, "strikeout", None, 3),
+            pa(2, "Bottom", 1, RELIEVER, "double", None, 0),
+        ];
+        let tie_break = rm(1, Some(1), "Bottom", 9, "BAT", "2B", "tie_break");
+        let scores = rm(2, Some(2), "Bottom", 9, "2B", "HOME", "hit_auto");
+        assert_eq!(
+            earned(&charge_runs(&pas, &[tie_break, scores])),
+            vec![(2, RELIEVER, false)]
+        );
+    }
+}
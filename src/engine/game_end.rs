@@ -0,0 +1,312 @@
+//! End-of-game and end-of-half rules: when a game is over after the
+//! scheduled innings or by the run rule, when a half ends on the run cap,
+//! and how many runs still count on a play.
+
+use crate::models::game_state::GameState;
+use crate::models::rules::MercyRule;
+use crate::models::types::HalfInning;
+
+/// Why the game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEnd {
+    /// The home team went ahead in the bottom of the last inning.
+    WalkOff,
+    /// The home team led after the top of the last inning: the bottom half
+    /// is not played.
+    HomeLeadsAfterTop,
+    /// The last inning was completed with the visitors ahead.
+    Completed,
+    /// A team reached the lead of a run rule.
+    RunRule(MercyRule),
+}
+
+impl GameEnd {
+    /// Reason recorded with the final score.
+    pub fn description(&self) -> String {
+        match self {
+            GameEnd::WalkOff => "walk-off".to_string(),
+            GameEnd::HomeLeadsAfterTop => "home team leads, bottom half not needed".to_string(),
+            GameEnd::Completed => "final".to_string(),
+            GameEnd::RunRule(rule) => format!(
+                "run rule, {} runs after {} innings",
+                rule.run_lead, rule.after_inning
+            ),
+        }
+    }
+}
+
+/// Runs the batting team scored in the current half.
+fn half_runs(state: &GameState) -> u16 {
+    let innings = match state.half {
+        HalfInning::Top => &state.score.away_innings,
+        HalfInning::Bottom => &state.score.home_innings,
+    };
+    innings
+        .get(state.inning.saturating_sub(1) as usize)
+        .copied()
+        .unwrap_or(0)
+}
+
+/// How many more runs the batting team may score in the current half under
+/// the max-runs-per-half rule. `None` means no limit.
+pub fn half_run_limit(state: &GameState) -> Option<u32> {
+    let max = state.rules.max_runs_per_half?;
+    Some(u32::from(max.saturating_sub(half_runs(state))))
+}
+
+/// Runs of the batting team.
+pub fn batting_team_runs(state: &GameState) -> u16 {
+    match state.half {
+        HalfInning::Top => state.score.away,
+        HalfInning::Bottom => state.score.home,
+    }
+}
+
+/// Whether some of the runs scoring on the next play may not count, see
+/// [`walk_off_run_limit`] and [`half_run_limit`].
+pub fn runs_limited(state: &GameState) -> bool {
+    walk_off_run_limit(state).is_some() || half_run_limit(state).is_some()
+}
+
+/// Whether the current half is over: three outs, or the run cap reached.
+pub fn half_over(state: &GameState) -> bool {
+    state.outs >= 3 || half_run_limit(state) == Some(0)
+}
+
+/// Whether the current half is in the last scheduled inning or an extra one.
+fn in_last_inning(state: &GameState) -> bool {
+    state.inning >= state.rules.innings
+}
+
+/// Check whether the game is over. Call it after every play: a tie keeps
+/// the game going into extra innings.
+pub fn game_end(state: &GameState) -> Option<GameEnd> {
+    if !state.started {
+        return None;
+    }
+    let (away, home) = (state.score.away, state.score.home);
+    let half_over = half_over(state);
+    if in_last_inning(state) {
+        let end = match state.half {
+            HalfInning::Bottom if home > away => Some(GameEnd::WalkOff),
+            HalfInning::Bottom if half_over && away > home => Some(GameEnd::Completed),
+            HalfInning::Top if half_over && home > away => Some(GameEnd::HomeLeadsAfterTop),
+            _ => None,
+        };
+        if end.is_some() {
+            return end;
+        }
+    }
+
+    // Run rule: the home team wins as soon as it has the lead (it bats
+    // last), the visitors once the home team has had its turn.
+    state
+        .rules
+        .mercy_rules
+        .iter()
+        .find(|rule| {
+            state.inning >= rule.after_inning
+                && match state.half {
+                    HalfInning::Top => half_over && home >= away + rule.run_lead,
+                    HalfInning::Bottom => {
+                        home >= away + rule.run_lead || (half_over && away >= home + rule.run_lead)
+                    }
+                }
+        })
+        .map(|rule| GameEnd::RunRule(*rule))
+}
+
+/// How many more runs count in the current half. In the bottom of the last
+/// inning the game ends as soon as the home team goes ahead, so only the
+/// runs needed to win are credited. `None` means no limit.
+pub fn walk_off_run_limit(state: &GameState) -> Option<u32> {
+    if state.half != HalfInning::Bottom || !in_last_inning(state) {
+        return None;
+    }
+    let needed = (state.score.away + 1).saturating_sub(state.score.home);
+    Some(needed as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::runners::{apply_hit, hold_uncounted_runners};
+    use crate::models::plate_appearance::HitModifier;
+
+    fn late_game(inning: u32, half: HalfInning, away: u16, home: u16, outs: u8) -> GameState {
+        let mut s = GameState::new();
+        s.started = true;
+        s.inning = inning;
+        s.half = half;
+        s.score.away = away;
+        s.score.home = home;
+        s.outs = outs;
+        s
+    }
+
+    #[test]
+    fn game_goes_on_before_the_last_inning() {
+        let s = late_game(8, HalfInning::Bottom, 1, 5, 3);
+        assert_eq!(game_end(&s), None);
+        assert_eq!(walk_off_run_limit(&s), None);
+    }
+
+    #[test]
+    fn bottom_half_is_skipped_when_home_leads() {
+        let s = late_game(9, HalfInning::Top, 2, 3, 3);
+        assert_eq!(game_end(&s), Some(GameEnd::HomeLeadsAfterTop));
+        let s = late_game(9, HalfInning::Top, 2, 3, 2);
+        assert_eq!(game_end(&s), None);
+    }
+
+    #[test]
+    fn visitors_win_after_three_outs_in_the_bottom() {
+        let s = late_game(9, HalfInning::Bottom, 4, 3, 3);
+        assert_eq!(game_end(&s), Some(GameEnd::Completed));
+        let s = late_game(9, HalfInning::Bottom, 4, 3, 2);
+        assert_eq!(game_end(&s), None);
+    }
+
+    #[test]
+    fn tie_goes_to_extra_innings() {
+        let s = late_game(9, HalfInning::Bottom, 3, 3, 3);
+        assert_eq!(game_end(&s), None);
+        let s = late_game(10, HalfInning::Top, 3, 3, 3);
+        assert_eq!(game_end(&s), None);
+    }
+
+    #[test]
+    fn home_run_ahead_in_the_last_inning_is_a_walk_off() {
+        let s = late_game(11, HalfInning::Bottom, 5, 6, 1);
+        assert_eq!(game_end(&s), Some(GameEnd::WalkOff));
+    }
+
+    #[test]
+    fn walk_off_limit_counts_the_runs_needed_to_win() {
+        assert_eq!(
+            walk_off_run_limit(&late_game(9, HalfInning::Bottom, 4, 3, 1)),
+            Some(2)
+        );
+        assert_eq!(
+            walk_off_run_limit(&late_game(9, HalfInning::Bottom, 3, 3, 1)),
+            Some(1)
+        );
+        assert_eq!(
+            walk_off_run_limit(&late_game(9, HalfInning::Top, 3, 3, 1)),
+            None
+        );
+    }
+
+    fn bases_loaded(mut s: GameState) -> GameState {
+        s.on_1b = Some(7);
+        s.on_2b = Some(6);
+        s.on_3b = Some(5);
+        s
+    }
+
+    #[test]
+    fn walk_off_single_counts_only_the_winning_run() {
+        let mut s = bases_loaded(late_game(9, HalfInning::Bottom, 3, 3, 1));
+        let mut hit = apply_hit(&mut s, 8, 1, None, &[]);
+        assert_eq!(hit.runs_scored, 1);
+        assert_eq!(s.score.home, 4);
+        assert_eq!(s.score.home_innings[8], 1);
+        assert_eq!(game_end(&s), Some(GameEnd::WalkOff));
+
+        let mut counted = 1;
+        hold_uncounted_runners(&mut hit.movements, &mut counted);
+        let ends: Vec<_> = hit
+            .movements
+            .iter()
+            .map(|m| (m.start_base, m.end_base, m.scored))
+            .collect();
+        assert_eq!(
+            ends,
+            vec![
+                ("3B", "HOME", true),
+                ("2B", "3B", false),
+                ("1B", "2B", false),
+                ("BAT", "1B", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_off_home_run_counts_every_run() {
+        let mut s = bases_loaded(late_game(9, HalfInning::Bottom, 3, 3, 1));
+        let hit = apply_hit(&mut s, 8, 4, None, &[]);
+        assert_eq!(hit.runs_scored, 4);
+        assert_eq!(s.score.home, 7);
+
+        let mut s = bases_loaded(late_game(9, HalfInning::Bottom, 3, 3, 1));
+        let hit = apply_hit(&mut s, 8, 4, Some(HitModifier::InsideThePark), &[]);
+        assert_eq!(hit.runs_scored, 1);
+        assert_eq!(s.score.home, 4);
+    }
+
+    fn with_run_rule(mut s: GameState, rules: &str) -> GameState {
+        s.rules.mercy_rules = MercyRule::parse_list(rules).unwrap();
+        s
+    }
+
+    #[test]
+    fn run_rule_ends_the_game_once_its_inning_is_reached() {
+        let rule = MercyRule::parse_list("10@7").unwrap()[0];
+
+        // Visitors ahead: the home team bats in the bottom half first.
+        let s = with_run_rule(late_game(7, HalfInning::Top, 12, 1, 3), "10@7");
+        assert_eq!(game_end(&s), None);
+        let s = with_run_rule(late_game(7, HalfInning::Bottom, 12, 1, 3), "10@7");
+        assert_eq!(game_end(&s), Some(GameEnd::RunRule(rule)));
+
+        // Home team ahead: after the top half, or as soon as the lead is reached.
+        let s = with_run_rule(late_game(7, HalfInning::Top, 0, 10, 3), "10@7");
+        assert_eq!(game_end(&s), Some(GameEnd::RunRule(rule)));
+        let s = with_run_rule(late_game(7, HalfInning::Bottom, 2, 12, 0), "10@7");
+        assert_eq!(game_end(&s), Some(GameEnd::RunRule(rule)));
+
+        // Not yet in the inning, or not enough of a lead.
+        let s = with_run_rule(late_game(6, HalfInning::Bottom, 20, 1, 3), "10@7");
+        assert_eq!(game_end(&s), None);
+        let s = with_run_rule(late_game(7, HalfInning::Bottom, 10, 1, 3), "10@7");
+        assert_eq!(game_end(&s), None);
+    }
+
+    #[test]
+    fn earlier_run_rule_needs_a_bigger_lead() {
+        let s = with_run_rule(late_game(5, HalfInning::Bottom, 15, 0, 3), "15@5,10@7");
+        assert_eq!(
+            game_end(&s),
+            Some(GameEnd::RunRule(MercyRule::parse_list("15@5").unwrap()[0]))
+        );
+        let s = with_run_rule(late_game(5, HalfInning::Bottom, 12, 0, 3), "15@5,10@7");
+        assert_eq!(game_end(&s), None);
+    }
+
+    #[test]
+    fn run_cap_ends_the_half_and_stops_the_runs() {
+        let mut s = bases_loaded(late_game(3, HalfInning::Top, 3, 0, 0));
+        s.rules.max_runs_per_half = Some(5);
+        s.score.away_innings = vec![0, 0, 3];
+        assert_eq!(half_run_limit(&s), Some(2));
+        assert!(!half_over(&s));
+
+        let hit = apply_hit(&mut s, 8, 4, None, &[]);
+        assert_eq!(hit.runs_scored, 2);
+        assert_eq!(s.score.away, 5);
+        assert_eq!(s.score.away_innings[2], 5);
+        assert!(half_over(&s));
+        assert_eq!(game_end(&s), None);
+
+        // The next half starts from zero.
+        s.half = HalfInning::Bottom;
+        assert_eq!(half_run_limit(&s), Some(5));
+    }
+
+    #[test]
+    fn scheduled_innings_sets_the_last_inning() {
+        let mut s = late_game(7, HalfInning::Top, 0, 2, 3);
+        s.rules.innings = 7;
+        assert_eq!(game_end(&s), Some(GameEnd::HomeLeadsAfterTop));
+    }
+}
@@ -15,3 +15,9 @@ pub(crate) fn get_foul_flag(v: &serde_json::Value) -> bool {
         .and_then(|x| x.as_bool())
         .unwrap_or(false)
 }
+
+pub(crate) fn get_sacrifice_flag(v: &serde_json::Value) -> bool {
+    v.get("sacrifice")
+        .and_then(|x| x.as_bool())
+        .unwrap_or(false)
+}
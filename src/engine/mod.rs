@@ -7,15 +7,33 @@
 //! - `runners` — base-runner movement logic.
 //! - `apply` — applies a single `EngineCommand` onto the `GameState`.
 //! - `reducer` — higher-level reducer stitching a plate-appearance together.
+//! - `substitutions` — lineup rules for pitching changes, pinch hitters and
+//!   runners, defensive substitutions and position changes.
+//! - `undo` — removes the last plate appearances and what was recorded
+//!   after them.
+//! - `correction` — retroactive correction of a recorded plate
+//!   appearance, checked against every later one.
+//! - `game_end` — end-of-game rules after the scheduled innings.
+//! - `earned_runs` — earned-run reconstruction and the pitcher charged
+//!   with each run.
+//! - `rbi` — runs batted in credited on each plate appearance.
 //! - `play_ball` — top-level play-by-play loop orchestrating engine + UI.
 
 pub mod apply;
 pub mod commands;
+pub mod correction;
+pub mod earned_runs;
+pub mod game_end;
 pub(crate) mod helpers;
 pub mod notation;
 pub mod play_ball;
+pub mod rbi;
 pub mod reducer;
 pub mod runners;
 pub mod scoring;
+pub mod substitutions;
+pub mod undo;
 
-pub(crate) use helpers::{get_fielder, get_foul_flag, get_sequence, parse_outcome_json};
+pub(crate) use helpers::{
+    get_fielder, get_foul_flag, get_sacrifice_flag, get_sequence, parse_outcome_json,
+};
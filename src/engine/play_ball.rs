@@ -2,24 +2,42 @@ use crate::db::at_bat_draft::{
     AtBatDraftRow, clear_at_bat_draft, load_at_bat_draft, upsert_at_bat_draft,
 };
 use crate::db::game_events::{GameEventRow, append_game_event, list_game_events};
-use crate::db::game_queries::set_game_status;
+use crate::db::game_queries::{game_rules, set_game_result, set_game_status};
+use crate::db::lineups::{
+    active_lineup, removed_players, set_defensive_position, substitute_player, team_roster,
+};
 use crate::db::plate_appearances::{
     PlateAppearanceRow, append_plate_appearance, list_plate_appearances,
+    set_plate_appearance_double_play, set_plate_appearance_rbi,
 };
-use crate::engine::apply::apply_engine_command;
+use crate::db::runner_movements::{RunnerMovementInsert, resolve_runner_ids};
+use crate::engine::apply::{apply_engine_command, strikeout_throw_out_double_play};
 use crate::engine::commands::parser::parse_engine_commands;
 use crate::engine::commands::types::EngineCommand;
+use crate::engine::earned_runs::update_earned_runs;
+use crate::engine::game_end::{
+    GameEnd, batting_team_runs, game_end, half_over, half_run_limit, runs_limited,
+};
+use crate::engine::rbi::credited_rbi;
 use crate::engine::reducer::{
-    apply_domain_event, apply_live_plate_appearance, apply_plate_appearance_row,
+    STANDALONE_TYPES, apply_domain_event, apply_live_plate_appearance, apply_standalone_movement,
+    replay_plate_appearance, split_replay_movements,
+};
+use crate::engine::runners::{
+    drop_departed_tie_break_runners, hold_uncounted_runners, mark_tie_break_runs,
+};
+use crate::engine::substitutions::{LineupChange, TeamLineup, plan_substitutions};
+use crate::engine::undo::{apply_undo, plan_undo};
+use crate::engine::{
+    get_fielder, get_foul_flag, get_sacrifice_flag, get_sequence, parse_outcome_json,
 };
-use crate::engine::runners::add_runs_to_score;
-use crate::engine::{get_fielder, get_foul_flag, get_sequence, parse_outcome_json};
-use crate::models::events::{DomainEvent, SideChangeData};
+use crate::models::events::{DomainEvent, SideChangeData, StatusChangedData};
 use crate::models::game_state::{BatterOrder, GameState};
-use crate::models::plate_appearance::PlateAppearanceStep;
+use crate::models::plate_appearance::{PlateAppearanceOutcome, PlateAppearanceStep};
+use crate::models::rules::GameKind;
 use crate::ui::Ui;
 use crate::ui::events::UiEvent;
-use crate::{HalfInning, Pitch, Position};
+use crate::{GameStatus, HalfInning, Pitch, Position};
 use rusqlite::{Connection, params};
 
 pub enum EngineExit {
@@ -37,131 +55,16 @@ pub fn run_play_ball_engine(
     home_team_id: i64,
 ) -> EngineExit {
     // Rebuild state from persisted events (resume-friendly).
-    let mut state = GameState::new();
-
-    // Track whether we already have any events (if yes, PLAYBALL is not allowed).
-    let mut has_events = false;
-
-    // Track the seq of the last committed PA so standalone movements (steals)
-    // can be linked to it in the DB.
-    let mut last_pa_seq: Option<i64> = None;
-
-    // --------- Replay persisted events + deterministic rebuild (resume) ----------
-    // --------- Resume ----------
-    match list_game_events(conn, game_pk) {
-        Ok(rows) => {
-            has_events = !rows.is_empty();
-            replay_admin_logs(ui, &rows);
-
-            // Load every runner_movements row for replay. Three kinds of
-            // rows exist; only two are used for state reconstruction:
-            //
-            // - Steal rows (`advancement_type = "steal"`) are interlaced
-            //   between plate appearances (they fire on pitches, not at
-            //   the end of a PA).
-            //
-            // - Composite-defensive rows (`advancement_type` in
-            //   {`ground_out`, `fly_out`, `line_out`, `infield_fly`,
-            //   `unassisted_out`, `fielders_choice`}) carry the
-            //   per-runner segments of a composite play such as
-            //   `5 l6, 3 64, 4 43` or `9 64, 1 o6 1b`. They must be
-            //   applied to the in-memory state when the matching PA is
-            //   replayed, otherwise eliminated runners stay on base and
-            //   FC-safe advances are missed.
-            //
-            // - Normal PA movement rows (`advancement_type` in
-            //   {`walk`, `hit_auto`, `hit_override`}) are intentionally
-            //   excluded: they are already applied by
-            //   apply_plate_appearance_row and must not be re-applied.
-            let all_movements =
-                match crate::db::runner_movements::list_runner_movements(conn, game_pk) {
-                    Ok(rms) => rms,
-                    Err(e) => {
-                        ui.emit(UiEvent::Error(format!(
-                            "Failed to load runner movements: {e}"
-                        )));
-                        vec![]
-                    }
-                };
-            // Classify runner_movements rows into three buckets:
-            //
-            // • standalone_movements  — steals; interlaced between PAs.
-            // • composite_movements   — defensive-play rows that carry
-            //   per-runner segments of a composite play (ground-out,
-            //   fly-out, line-out, infield-fly, unassisted-out,
-            //   fielder's-choice). These must be re-applied when the
-            //   matching PA is replayed.
-            // • (discarded)           — "walk", "hit_auto", "hit_override"
-            //   rows that were already applied by apply_plate_appearance_row;
-            //   re-applying them would double-count scoring movements.
-            const COMPOSITE_TYPES: &[&str] = &[
-                "ground_out",
-                "fly_out",
-                "line_out",
-                "infield_fly",
-                "unassisted_out",
-                "fielders_choice",
-            ];
-            let (standalone_movements, composite_movements): (Vec<_>, Vec<_>) = all_movements
-                .into_iter()
-                .filter(|r| {
-                    r.advancement_type == "steal"
-                        || COMPOSITE_TYPES.contains(&r.advancement_type.as_str())
-                })
-                .partition(|r| r.advancement_type == "steal");
-
-            // 1) deterministic rebuild from plate appearances + interlaced standalone movements
-            match list_plate_appearances(conn, game_pk) {
-                Ok(pas) => {
-                    if !pas.is_empty() {
-                        has_events = true;
-                    }
-                    replay_plate_appearances_and_log(
-                        ui,
-                        &mut state,
-                        &pas,
-                        &standalone_movements,
-                        &composite_movements,
-                    );
-                }
-                Err(e) => ui.emit(UiEvent::Error(format!(
-                    "Failed to load plate appearances: {e}"
-                ))),
-            }
-
-            // 3) restore in-progress at-bat (draft)
-            let draft_opt = load_and_apply_draft(conn, ui, game_pk, &mut state);
-
-            // 4) if draft exists, ensure cursor is not behind (avoid repeating batter)
-            if let Some(draft) = &draft_opt
-                && let Some(batter_id) = draft.batter_id
-            {
-                let batting_team_id = match state.half {
-                    HalfInning::Top => away_team_id,
-                    HalfInning::Bottom => home_team_id,
-                };
-
-                if let Some(order) =
-                    find_order_for_batter(conn, game_id, batting_team_id, batter_id)
-                {
-                    match state.half {
-                        HalfInning::Top => state.away_next_batting_order = bump_order(order),
-                        HalfInning::Bottom => state.home_next_batting_order = bump_order(order),
-                    }
-                }
-            }
-
-            // 5) hydrate display fields
-            if let Err(e) =
-                hydrate_current_matchup(conn, game_id, &mut state, away_team_id, home_team_id)
-            {
-                ui.emit(UiEvent::Error(format!("Failed to hydrate matchup: {e}")));
-            }
-
-            state.started = has_events;
-            ui.set_state(&state);
-        }
-        Err(e) => ui.emit(UiEvent::Error(format!("Failed to load game events: {e}"))),
+    let RestoredGame {
+        mut state,
+        mut has_events,
+        mut last_pa_seq,
+    } = restore_game(conn, ui, game_pk, game_id, away_team_id, home_team_id);
+
+    // A correction of the last play can leave a game that is already over.
+    if let Some(end) = game_end(&state) {
+        finish_game(conn, ui, game_pk, game_id, &mut state, end);
+        return EngineExit::ExitToMenu;
     }
 
     // ---------------- Engine loop ----------------
@@ -191,7 +94,55 @@ pub fn run_play_ball_engine(
             }
         };
 
+        // ---------------- Special: UNDO (DB-backed) ----------------
+        if let Some(EngineCommand::Undo { count }) = commands.first() {
+            if undo_plate_appearances(conn, ui, game_pk, game_id, *count) {
+                let restored = restore_game(
+                    conn,
+                    &mut ErrorsOnly(ui),
+                    game_pk,
+                    game_id,
+                    away_team_id,
+                    home_team_id,
+                );
+                state = restored.state;
+                has_events = restored.has_events;
+                last_pa_seq = restored.last_pa_seq;
+                emit_current_at_bat(ui, &state);
+            }
+            continue;
+        }
+
+        // ---------------- Special: SUBSTITUTIONS (DB-backed) ----------------
+        if commands.first().is_some_and(EngineCommand::is_substitution) {
+            run_substitution_line(
+                conn,
+                ui,
+                game_id,
+                game_pk,
+                &mut state,
+                away_team_id,
+                home_team_id,
+                &commands,
+            );
+            continue;
+        }
+
+        // Seq of a strikeout PA closed earlier on this line: a runner thrown
+        // out later on the same line turns it into a strikeout-throw-out DP.
+        let mut strikeout_pa_seq: Option<i64> = None;
+
+        // The scorer's RBI count for the PA settled on this line.
+        let rbi_credit = commands.iter().find_map(|cmd| match cmd {
+            EngineCommand::CreditRbi { rbi } => Some(*rbi),
+            _ => None,
+        });
+
         for cmd in commands {
+            if let EngineCommand::CreditRbi { .. } = cmd {
+                continue;
+            }
+
             // ---------------- Special: PLAYBALL (DB-backed) ----------------
             if let EngineCommand::PlayBall = cmd {
                 if has_events {
@@ -299,6 +250,11 @@ pub fn run_play_ball_engine(
             }
 
             // ---------------- Default path (apply -> emit -> persist -> reduce -> status -> exit) ----------------
+            // Runs before the play: when a walk-off or the run cap limits
+            // them, only the runs counted by the engine are persisted as
+            // scored.
+            let runs_limited = runs_limited(&state);
+            let runs_before = batting_team_runs(&state);
             let mut result = apply_engine_command(&mut state, cmd);
 
             for ev in result.events {
@@ -349,6 +305,7 @@ pub fn run_play_ball_engine(
             // apply_live_plate_appearance(), so we must NOT call start_next_at_bat(),
             // otherwise we would skip one hitter.
             let mut pa_applied_live = false;
+            let mut counted_runs: Option<u32> = None;
 
             // If a PA was completed, persist a single compact record
             if let Some(pa) = &result.plate_appearance {
@@ -367,11 +324,21 @@ pub fn run_play_ball_engine(
                         // and are stored in result.runner_movements.
                         // Hit movements come from apply_live_plate_appearance.
                         // They are mutually exclusive per PA.
-                        let pa_movements: Vec<_> = if !hit_movements.is_empty() {
+                        let mut pa_movements: Vec<_> = if !hit_movements.is_empty() {
                             hit_movements
                         } else {
                             result.runner_movements.drain(..).collect()
                         };
+                        if runs_limited {
+                            let counted = counted_runs
+                                .insert(u32::from(batting_team_runs(&state) - runs_before));
+                            hold_uncounted_runners(&mut pa_movements, counted);
+                        }
+                        mark_tie_break_runs(&state, &mut pa_movements);
+                        if let Err(e) = resolve_runner_ids(conn, game_pk, &mut pa_movements) {
+                            ui.emit(UiEvent::Error(format!("Failed to identify runners: {e}")));
+                        }
+                        let rbi = rbi_credit.unwrap_or_else(|| credited_rbi(pa, &pa_movements));
 
                         for mut rm in pa_movements {
                             rm.game_id = game_pk;
@@ -384,10 +351,16 @@ pub fn run_play_ball_engine(
                                 )));
                             }
                         }
+                        if let Err(e) = set_plate_appearance_rbi(conn, game_pk, pa_seq, rbi) {
+                            ui.emit(UiEvent::Error(format!("Failed to record RBIs: {e}")));
+                        }
 
                         // PA is over, clear the draft now.
                         let _ = clear_at_bat_draft(conn, game_pk);
                         last_pa_seq = Some(pa_seq);
+                        if matches!(pa.outcome, PlateAppearanceOutcome::Strikeout(_)) {
+                            strikeout_pa_seq = Some(pa_seq);
+                        }
                         has_events = true;
                         pa_applied_live = true;
                     }
@@ -397,6 +370,17 @@ pub fn run_play_ball_engine(
             // Persist standalone runner movements (steal and future non-PA events).
             // Walk/hit movements were already drained into the PA block above.
             // Link the steal to the last completed PA so replay can order it correctly.
+            if runs_limited {
+                let counted =
+                    counted_runs.get_or_insert(u32::from(batting_team_runs(&state) - runs_before));
+                hold_uncounted_runners(&mut result.runner_movements, counted);
+            }
+            mark_tie_break_runs(&state, &mut result.runner_movements);
+            drop_departed_tie_break_runners(&mut state);
+            if let Err(e) = resolve_runner_ids(conn, game_pk, &mut result.runner_movements) {
+                ui.emit(UiEvent::Error(format!("Failed to identify runners: {e}")));
+            }
+            let play_recorded = pa_applied_live || !result.runner_movements.is_empty();
             for mut rm in result.runner_movements {
                 rm.game_id = game_pk;
                 rm.pa_seq = last_pa_seq; // links steal to the PA after which it occurred
@@ -405,17 +389,76 @@ pub fn run_play_ball_engine(
                         "Failed to persist runner movement: {e}"
                     )));
                 }
+                if rm.is_out
+                    && STANDALONE_TYPES.contains(&rm.advancement_type)
+                    && let Some(seq) = strikeout_pa_seq.take()
+                {
+                    let dp = strikeout_throw_out_double_play(&rm);
+                    match set_plate_appearance_double_play(conn, game_pk, seq, &dp) {
+                        Ok(()) => ui.emit(UiEvent::Line(format!("Double play: {dp}."))),
+                        Err(e) => {
+                            ui.emit(UiEvent::Error(format!("Failed to record double play: {e}")))
+                        }
+                    }
+                }
                 has_events = true;
             }
 
+            if play_recorded && let Err(e) = update_earned_runs(conn, game_pk) {
+                ui.emit(UiEvent::Error(format!("Failed to update earned runs: {e}")));
+            }
+
+            if let Some(end) = game_end(&state) {
+                finish_game(conn, ui, game_pk, game_id, &mut state, end);
+                return EngineExit::ExitToMenu;
+            }
+
+            // The run cap ends the half like a third out.
+            if state.outs < 3 && half_run_limit(&state) == Some(0) {
+                ui.emit(UiEvent::Line(format!(
+                    "Run limit reached ({} runs): side retired.",
+                    state.rules.max_runs_per_half.unwrap_or_default()
+                )));
+                // Reached between pitches: the batter leads off next inning.
+                if !pa_applied_live && let Some(batter_order) = state.current_batter_order {
+                    match state.half {
+                        HalfInning::Top => state.away_next_batting_order = batter_order,
+                        HalfInning::Bottom => state.home_next_batting_order = batter_order,
+                    }
+                    result.needs_next_at_bat = true;
+                }
+            }
+
             let should_start_next_at_bat = result.needs_next_at_bat || pa_applied_live;
 
             if should_start_next_at_bat {
                 if pa_applied_live {
-                    let inning_ended = state.outs >= 3;
+                    let inning_ended = half_over(&state);
+
+                    // The cursor already pointed past this batter when the
+                    // PA bumped it: the one after them leads off next time.
+                    if inning_ended && let Some(pa) = &result.plate_appearance {
+                        match state.half {
+                            HalfInning::Top => {
+                                state.away_next_batting_order = bump_order(pa.batter_order)
+                            }
+                            HalfInning::Bottom => {
+                                state.home_next_batting_order = bump_order(pa.batter_order)
+                            }
+                        }
+                    }
 
                     if inning_ended
-                        && !handle_three_outs_and_change_side(conn, ui, game_pk, &mut state)
+                        && !handle_three_outs_and_change_side(
+                            conn,
+                            ui,
+                            game_pk,
+                            game_id,
+                            &mut state,
+                            away_team_id,
+                            home_team_id,
+                            last_pa_seq,
+                        )
                     {
                         ui.emit(UiEvent::Error(
                             "Failed to change side after 3 outs.".to_string(),
@@ -570,6 +613,7 @@ pub fn run_play_ball_engine(
                     &mut state,
                     away_team_id,
                     home_team_id,
+                    last_pa_seq,
                 ) {
                     ui.emit(UiEvent::Error("Failed to start next at-bat.".to_string()));
                 }
@@ -582,6 +626,12 @@ pub fn run_play_ball_engine(
 
             // Status change (DB)
             if let Some(status) = result.status_change {
+                if status == GameStatus::Regulation
+                    && let Err(e) =
+                        set_game_result(conn, game_pk, state.score.away, state.score.home, None)
+                {
+                    ui.emit(UiEvent::Error(format!("Failed to save final score: {e}")));
+                }
                 match set_game_status(conn, game_id, status) {
                     Ok(true) => {}
                     Ok(false) => ui.emit(UiEvent::Error(
@@ -598,6 +648,286 @@ pub fn run_play_ball_engine(
     }
 }
 
+/// Game state rebuilt from the database.
+struct RestoredGame {
+    state: GameState,
+    /// Whether anything was recorded yet (if yes, PLAYBALL is not allowed).
+    has_events: bool,
+    /// Seq of the last recorded PA.
+    last_pa_seq: Option<i64>,
+}
+
+/// Replay the persisted plate appearances and runner movements, then
+/// restore the in-progress at-bat from the draft. Used on resume and after
+/// an undo.
+fn restore_game(
+    conn: &mut Connection,
+    ui: &mut dyn Ui,
+    game_pk: i64,
+    game_id: &str,
+    away_team_id: i64,
+    home_team_id: i64,
+) -> RestoredGame {
+    // Rebuild state from persisted events (resume-friendly).
+    let mut state = GameState::new();
+    match game_rules(conn, game_pk) {
+        Ok(rules) => state.rules = rules,
+        Err(e) => ui.emit(UiEvent::Error(format!(
+            "Failed to load the game rules: {e}"
+        ))),
+    }
+    // Games scored before the earned-run reconstruction carry the flag
+    // of each play on their runs.
+    if let Err(e) = update_earned_runs(conn, game_pk) {
+        ui.emit(UiEvent::Error(format!("Failed to update earned runs: {e}")));
+    }
+
+    // Track whether we already have any events (if yes, PLAYBALL is not allowed).
+    let mut has_events = false;
+
+    // Track the seq of the last committed PA so standalone movements (steals)
+    // can be linked to it in the DB.
+    let mut last_pa_seq: Option<i64> = None;
+
+    // --------- Replay persisted events + deterministic rebuild (resume) ----------
+    // --------- Resume ----------
+    match list_game_events(conn, game_pk) {
+        Ok(rows) => {
+            has_events = !rows.is_empty();
+            replay_admin_logs(ui, &rows);
+
+            // Load every runner_movements row for replay. Three kinds of
+            // rows exist; only two are used for state reconstruction:
+            //
+            // - Standalone rows (steals, wild pitches, passed balls,
+            //   defensive indifference, balks and tie-break runners, see
+            //   STANDALONE_TYPES)
+            //   are interlaced between plate appearances (they fire on
+            //   pitches, not at the end of a PA).
+            //
+            // - Composite-defensive rows (`advancement_type` in
+            //   {`ground_out`, `fly_out`, `line_out`, `infield_fly`,
+            //   `unassisted_out`, `fielders_choice`, `sacrifice_fly`,
+            //   `sacrifice_bunt`}) carry the per-runner segments of a
+            //   composite play such as `5 l6, 3 64, 4 43`, `9 64, 1 o6 1b`
+            //   or the tag-up on `sf8`. They must be
+            //   applied to the in-memory state when the matching PA is
+            //   replayed, otherwise eliminated runners stay on base and
+            //   FC-safe advances are missed.
+            //
+            // - Normal PA movement rows (`advancement_type` in
+            //   {`walk`, `hit_by_pitch`, `intentional_walk`,
+            //   `catcher_interference`, `dropped_third_strike`, `hit_auto`,
+            //   `hit_override`, `error`})
+            //   are intentionally
+            //   excluded: they are already applied by
+            //   apply_plate_appearance_row and must not be re-applied.
+            let all_movements =
+                match crate::db::runner_movements::list_runner_movements(conn, game_pk) {
+                    Ok(rms) => rms,
+                    Err(e) => {
+                        ui.emit(UiEvent::Error(format!(
+                            "Failed to load runner movements: {e}"
+                        )));
+                        vec![]
+                    }
+                };
+            let (standalone_movements, composite_movements) = split_replay_movements(all_movements);
+
+            // 1) deterministic rebuild from plate appearances + interlaced standalone movements
+            match list_plate_appearances(conn, game_pk) {
+                Ok(pas) => {
+                    if !pas.is_empty() {
+                        has_events = true;
+                    }
+                    last_pa_seq = pas.last().map(|pa| pa.seq);
+                    replay_plate_appearances_and_log(
+                        ui,
+                        &mut state,
+                        &pas,
+                        &standalone_movements,
+                        &composite_movements,
+                    );
+                }
+                Err(e) => ui.emit(UiEvent::Error(format!(
+                    "Failed to load plate appearances: {e}"
+                ))),
+            }
+
+            // 3) restore in-progress at-bat (draft)
+            let draft_opt = load_and_apply_draft(conn, ui, game_pk, &mut state);
+
+            // 4) if draft exists, ensure cursor is not behind (avoid repeating batter)
+            if let Some(draft) = &draft_opt
+                && let Some(batter_id) = draft.batter_id
+            {
+                let batting_team_id = match state.half {
+                    HalfInning::Top => away_team_id,
+                    HalfInning::Bottom => home_team_id,
+                };
+
+                if let Some(order) =
+                    find_order_for_batter(conn, game_id, batting_team_id, batter_id)
+                {
+                    match state.half {
+                        HalfInning::Top => state.away_next_batting_order = bump_order(order),
+                        HalfInning::Bottom => state.home_next_batting_order = bump_order(order),
+                    }
+                }
+            }
+
+            // 5) hydrate display fields
+            if let Err(e) =
+                hydrate_current_matchup(conn, game_id, &mut state, away_team_id, home_team_id)
+            {
+                ui.emit(UiEvent::Error(format!("Failed to hydrate matchup: {e}")));
+            }
+
+            state.started = has_events;
+            ui.set_state(&state);
+        }
+        Err(e) => ui.emit(UiEvent::Error(format!("Failed to load game events: {e}"))),
+    }
+
+    RestoredGame {
+        state,
+        has_events,
+        last_pa_seq,
+    }
+}
+
+/// Game state of game `game_pk` rebuilt from the database, the way a
+/// resume rebuilds it, without a screen.
+pub fn replayed_state(conn: &mut Connection, game_pk: i64) -> rusqlite::Result<GameState> {
+    let (game_id, away_team_id, home_team_id): (String, i64, i64) = conn.query_row(
+        "SELECT game_id, away_team_id, home_team_id FROM games WHERE id = ?1",
+        params![game_pk],
+        |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+    )?;
+    let restored = restore_game(
+        conn,
+        &mut Headless,
+        game_pk,
+        &game_id,
+        away_team_id,
+        home_team_id,
+    );
+    Ok(restored.state)
+}
+
+/// Discards the output and answers no: a rebuild nobody watches.
+struct Headless;
+
+impl Ui for Headless {
+    fn emit(&mut self, _event: UiEvent) {}
+
+    fn read_command_line(&mut self, _prompt: &str) -> Option<String> {
+        None
+    }
+
+    fn confirm(&mut self, _question: &str) -> bool {
+        false
+    }
+}
+
+/// Forwards only errors. The rebuild after an undo replays the whole
+/// game, whose log is already on screen.
+struct ErrorsOnly<'a>(&'a mut dyn Ui);
+
+impl Ui for ErrorsOnly<'_> {
+    fn emit(&mut self, event: UiEvent) {
+        if let UiEvent::Error(_) = event {
+            self.0.emit(event);
+        }
+    }
+
+    fn read_command_line(&mut self, prompt: &str) -> Option<String> {
+        self.0.read_command_line(prompt)
+    }
+
+    fn confirm(&mut self, question: &str) -> bool {
+        self.0.confirm(question)
+    }
+}
+
+/// `undo [n]`: show what goes, ask for confirmation and delete it.
+/// Returns true when the database changed and the state must be rebuilt.
+fn undo_plate_appearances(
+    conn: &mut Connection,
+    ui: &mut dyn Ui,
+    game_pk: i64,
+    game_id: &str,
+    count: u8,
+) -> bool {
+    let plan = match plan_undo(conn, game_pk, count) {
+        Ok(plan) => plan,
+        Err(msg) => {
+            ui.emit(UiEvent::Error(msg));
+            return false;
+        }
+    };
+
+    for pa in &plan.plate_appearances {
+        ui.emit(UiEvent::Line(format!("↩ {}", plate_appearance_summary(pa))));
+    }
+    for ev in &plan.events {
+        if let Some(desc) = &ev.description {
+            ui.emit(UiEvent::Line(format!("↩ {desc}")));
+        }
+    }
+
+    let n = plan.plate_appearances.len();
+    let question = if n == 1 {
+        "Undo the last plate appearance?".to_string()
+    } else {
+        format!("Undo the last {n} plate appearances?")
+    };
+    if !ui.confirm(&question) {
+        ui.emit(UiEvent::Line("Undo cancelled.".to_string()));
+        return false;
+    }
+
+    match apply_undo(conn, game_pk, game_id, &plan) {
+        Ok(()) => {
+            ui.emit(UiEvent::Success(if n == 1 {
+                "Plate appearance undone.".to_string()
+            } else {
+                format!("{n} plate appearances undone.")
+            }));
+            true
+        }
+        Err(e) => {
+            ui.emit(UiEvent::Error(format!("Failed to undo: {e}")));
+            false
+        }
+    }
+}
+
+/// The at-bat line for the batter the state points at, e.g. after an
+/// undo put the scorer back in an earlier at-bat.
+fn emit_current_at_bat(ui: &mut dyn Ui, state: &GameState) {
+    let (Some(order), Some(first), Some(last), Some(jersey), Some(pos)) = (
+        state.current_batter_order,
+        state.current_batter_first_name.clone(),
+        state.current_batter_last_name.clone(),
+        state.current_batter_jersey_no,
+        state.current_batter_position,
+    ) else {
+        return;
+    };
+
+    ui.emit(UiEvent::Line(format_live_at_bat(&FormatLiveAtBatInput {
+        inning: state.inning,
+        half: state.half,
+        outs: state.outs,
+        order,
+        first,
+        last,
+        jersey,
+        pos,
+    })));
+}
+
 fn persist_event(
     conn: &mut Connection,
     ui: &mut dyn Ui,
@@ -655,7 +985,7 @@ fn get_batter_order_and_position(
         WHERE gl.game_id = ?1
           AND gl.team_id = ?2
           AND gl.player_id = ?3
-          AND gl.is_starting = 1
+          AND gl.substituted_at_inning IS NULL
         LIMIT 1
         "#,
     )?;
@@ -714,19 +1044,22 @@ fn hydrate_current_matchup(
         state.current_batter_position = Some(batter_position);
     }
 
-    if let Some(pid) = state.current_pitcher_id {
-        if let Ok((num, first, last)) = get_player_basic(conn, pid, home_team_id) {
-            state.current_pitcher_jersey_no = Some(num);
-            state.current_pitcher_first_name = Some(first);
-            state.current_pitcher_last_name = Some(last);
-        }
-    } else if let Ok((pid, num, first, last)) =
+    // The active lineup row wins over the replayed pitcher: it already
+    // reflects any pitching change made since the last plate appearance.
+    if let Ok((pid, num, first, last)) =
         get_current_pitcher(conn, game_id, fielding_team_id, home_team_id)
     {
         state.current_pitcher_id = Some(pid);
         state.current_pitcher_jersey_no = Some(num);
         state.current_pitcher_first_name = Some(first);
         state.current_pitcher_last_name = Some(last);
+        state.pitcher_stats.entry(pid).or_default();
+    } else if let Some(pid) = state.current_pitcher_id
+        && let Ok((num, first, last)) = get_player_basic(conn, pid, home_team_id)
+    {
+        state.current_pitcher_jersey_no = Some(num);
+        state.current_pitcher_first_name = Some(first);
+        state.current_pitcher_last_name = Some(last);
     }
 
     Ok(())
@@ -752,7 +1085,7 @@ fn get_current_pitcher(
         JOIN players p ON gl.player_id = p.id
         WHERE gl.game_id = ?1
           AND gl.team_id = ?2
-          AND gl.is_starting = 1
+          AND gl.substituted_at_inning IS NULL
           AND gl.defensive_position IN ('1', 'P')
         LIMIT 1
         "#,
@@ -763,6 +1096,117 @@ fn get_current_pitcher(
     })
 }
 
+/// Check and write a line of substitutions (validated to contain nothing
+/// else): both lineups are loaded, the line is planned as a whole so a
+/// double switch only has to be consistent at the end, and the lineup
+/// rows and `player_substituted` events are written in one transaction.
+#[allow(clippy::too_many_arguments)]
+fn run_substitution_line(
+    conn: &mut Connection,
+    ui: &mut dyn Ui,
+    game_id: &str,
+    game_pk: i64,
+    state: &mut GameState,
+    away_team_id: i64,
+    home_team_id: i64,
+    cmds: &[EngineCommand],
+) {
+    if !state.started {
+        ui.emit(UiEvent::Error(
+            "Substitutions are only allowed after PLAYBALL.".to_string(),
+        ));
+        return;
+    }
+
+    let (batting_team_id, fielding_team_id) = match state.half {
+        HalfInning::Top => (away_team_id, home_team_id),
+        HalfInning::Bottom => (home_team_id, away_team_id),
+    };
+    let load = |team_id: i64| -> rusqlite::Result<TeamLineup> {
+        let is_home = team_id == home_team_id;
+        Ok(TeamLineup {
+            team_id,
+            entries: active_lineup(conn, game_id, team_id, is_home)?,
+            roster: team_roster(conn, team_id, is_home)?,
+            removed: removed_players(conn, game_id, team_id)?,
+        })
+    };
+    let (batting, fielding) = match (load(batting_team_id), load(fielding_team_id)) {
+        (Ok(b), Ok(f)) => (b, f),
+        (Err(e), _) | (_, Err(e)) => {
+            ui.emit(UiEvent::Error(format!("Failed to load lineups: {e}")));
+            return;
+        }
+    };
+
+    let plan = match plan_substitutions(state, batting, fielding, cmds) {
+        Ok(p) => p,
+        Err(e) => {
+            ui.emit(UiEvent::Error(e));
+            return;
+        }
+    };
+
+    // A savepoint, so the Retrosheet import can replay a game inside its
+    // own.
+    let write = |conn: &mut Connection| -> rusqlite::Result<()> {
+        let tx = conn.savepoint()?;
+        for change in &plan.changes {
+            match *change {
+                LineupChange::Substitute {
+                    team_id,
+                    batting_order,
+                    player_in_id,
+                    position,
+                } => substitute_player(
+                    &tx,
+                    game_id,
+                    team_id,
+                    batting_order,
+                    player_in_id,
+                    position,
+                    state.inning,
+                    state.half,
+                )?,
+                LineupChange::Move {
+                    team_id,
+                    batting_order,
+                    position,
+                } => set_defensive_position(&tx, game_id, team_id, batting_order, position)?,
+            }
+        }
+        for (ev, msg) in &plan.persisted {
+            append_game_event(&tx, game_pk, state.inning, state.half, ev, msg)?;
+        }
+        tx.commit()
+    };
+    if let Err(e) = write(conn) {
+        ui.emit(UiEvent::Error(format!("Failed to save substitution: {e}")));
+        return;
+    }
+
+    for (ev, msg) in &plan.persisted {
+        ui.emit(UiEvent::Line(msg.clone()));
+        apply_domain_event(state, ev);
+    }
+    if let Some(ev) = &plan.pitcher_changed {
+        apply_domain_event(state, ev);
+    }
+
+    // Keep the draft on the new batter / pitcher so a resume mid-at-bat
+    // does not fall back to the players who left.
+    let _ = upsert_at_bat_draft(
+        conn,
+        game_pk,
+        state.inning,
+        state.half,
+        state.current_batter_id,
+        state.current_pitcher_id,
+        &state.pitch_count,
+    );
+    ui.set_state(state);
+}
+
 /// Returns (batter_id, team_abbrv, jersey_no, first_name, last_name)
 fn get_batter_by_order(
     conn: &Connection,
@@ -789,7 +1233,7 @@ fn get_batter_by_order(
         JOIN teams t ON gl.team_id = t.id
         WHERE gl.game_id = ?1
           AND gl.team_id = ?2
-          AND gl.is_starting = 1
+          AND gl.substituted_at_inning IS NULL
           AND gl.batting_order = ?3
         LIMIT 1
         "#,
@@ -837,7 +1281,7 @@ fn find_order_for_batter(
         WHERE game_id = ?1
           AND team_id = ?2
           AND player_id = ?3
-          AND is_starting = 1
+          AND substituted_at_inning IS NULL
         LIMIT 1
         "#,
         params![game_id, batting_team_id, batter_id],
@@ -851,6 +1295,11 @@ pub fn bump_order(x: u8) -> u8 {
     if x >= 9 { 1 } else { x + 1 }
 }
 
+/// The batting order slot before `x`, wrapping 1 → 9.
+pub fn previous_order(x: u8) -> u8 {
+    if x <= 1 { 9 } else { x - 1 }
+}
+
 pub fn bump_order_str(order: &str) -> u8 {
     match order.parse::<u8>() {
         Ok(n) if (1..=9).contains(&n) => bump_order(n),
@@ -893,6 +1342,7 @@ fn format_live_at_bat(flabi: &FormatLiveAtBatInput) -> String {
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn start_next_at_bat(
     conn: &mut Connection,
     ui: &mut dyn Ui,
@@ -901,9 +1351,21 @@ fn start_next_at_bat(
     state: &mut GameState,
     away_team_id: i64,
     home_team_id: i64,
+    last_pa_seq: Option<i64>,
 ) -> bool {
     // 1) If 3 outs -> change side (and maybe inning)
-    if state.outs >= 3 && !handle_three_outs_and_change_side(conn, ui, game_pk, state) {
+    if half_over(state)
+        && !handle_three_outs_and_change_side(
+            conn,
+            ui,
+            game_pk,
+            game_id,
+            state,
+            away_team_id,
+            home_team_id,
+            last_pa_seq,
+        )
+    {
         return false;
     }
 
@@ -989,11 +1451,64 @@ fn start_next_at_bat(
     true
 }
 
+/// Close a game that reached its end after the scheduled innings: record
+/// the status change to Regulation and the final score.
+fn finish_game(
+    conn: &mut Connection,
+    ui: &mut dyn Ui,
+    game_pk: i64,
+    game_id: &str,
+    state: &mut GameState,
+    end: GameEnd,
+) {
+    let status = GameStatus::Regulation;
+    let msg = format!(
+        "🏁 Game over ({}): away {} - home {}. {} Game set to {}.",
+        end.description(),
+        state.score.away,
+        state.score.home,
+        status.icon(),
+        status
+    );
+    ui.emit(UiEvent::Line(msg.clone()));
+
+    let ev = DomainEvent::StatusChanged(StatusChangedData { to: status });
+    if let Err(e) = append_game_event(conn, game_pk, state.inning, state.half, &ev, &msg) {
+        ui.emit(UiEvent::Error(format!("Failed to append game event: {e}")));
+    }
+    apply_domain_event(state, &ev);
+
+    let reason = end.description();
+    if let Err(e) = set_game_result(
+        conn,
+        game_pk,
+        state.score.away,
+        state.score.home,
+        Some(&reason),
+    ) {
+        ui.emit(UiEvent::Error(format!("Failed to save final score: {e}")));
+    }
+    match set_game_status(conn, game_id, status) {
+        Ok(true) => {}
+        Ok(false) => ui.emit(UiEvent::Error(
+            "Game status was not updated (game not found?)".to_string(),
+        )),
+        Err(e) => ui.emit(UiEvent::Error(format!("Failed to update status: {e}"))),
+    }
+    let _ = clear_at_bat_draft(conn, game_pk);
+    ui.set_state(state);
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_three_outs_and_change_side(
-    _conn: &mut Connection,
+    conn: &mut Connection,
     ui: &mut dyn Ui,
-    _game_pk: i64,
+    game_pk: i64,
+    game_id: &str,
     state: &mut GameState,
+    away_team_id: i64,
+    home_team_id: i64,
+    last_pa_seq: Option<i64>,
 ) -> bool {
     // Compute next half + inning
     let (next_inning, next_half) = match state.half {
@@ -1033,9 +1548,107 @@ fn handle_three_outs_and_change_side(
     state.on_2b = None;
     state.on_3b = None;
 
+    if state
+        .rules
+        .tie_break_inning
+        .is_some_and(|from| next_inning >= from)
+    {
+        let batting_team_id = match next_half {
+            HalfInning::Top => away_team_id,
+            HalfInning::Bottom => home_team_id,
+        };
+        place_tie_break_runners(
+            conn,
+            ui,
+            game_pk,
+            game_id,
+            state,
+            batting_team_id,
+            home_team_id,
+            last_pa_seq,
+        );
+    }
+
     true
 }
 
+/// Start an extra half with the tie-break runners on base: the batter
+/// before the leadoff hitter on second and, in softball, the one before
+/// them on first. Each runner is persisted as a `tie_break` movement
+/// linked to the last PA, so resume puts them back on base.
+#[allow(clippy::too_many_arguments)]
+fn place_tie_break_runners(
+    conn: &mut Connection,
+    ui: &mut dyn Ui,
+    game_pk: i64,
+    game_id: &str,
+    state: &mut GameState,
+    batting_team_id: i64,
+    home_team_id: i64,
+    last_pa_seq: Option<i64>,
+) {
+    let leadoff = match state.half {
+        HalfInning::Top => state.away_next_batting_order,
+        HalfInning::Bottom => state.home_next_batting_order,
+    };
+    let on_second = previous_order(leadoff);
+    let mut placements = vec![(on_second, "2B")];
+    if state.rules.kind != GameKind::Baseball {
+        placements.push((previous_order(on_second), "1B"));
+    }
+
+    for (order, base) in placements {
+        let runner = get_batter_by_order(conn, game_id, batting_team_id, home_team_id, order).ok();
+        let rm = RunnerMovementInsert {
+            game_id: game_pk,
+            pa_seq: last_pa_seq,
+            game_event_id: None,
+            inning: state.inning,
+            half_inning: state.half.as_str().to_string(),
+            runner_id: runner.as_ref().map(|r| r.0),
+            batter_order: order,
+            start_base: "BAT",
+            end_base: base,
+            advancement_type: "tie_break",
+            is_out: false,
+            scored: false,
+            is_earned: false,
+            fielders: None,
+        };
+        if let Err(e) = crate::db::runner_movements::append_runner_movement(conn, &rm) {
+            ui.emit(UiEvent::Error(format!(
+                "Failed to persist tie-break runner: {e}"
+            )));
+        }
+
+        match base {
+            "1B" => state.on_1b = Some(order),
+            _ => state.on_2b = Some(order),
+        }
+        state.tie_break_runners.push(order);
+
+        let name = runner
+            .map(|r| format!(" {} {}", r.3, r.4))
+            .unwrap_or_default();
+        ui.emit(UiEvent::Line(format!(
+            "Tie-break: {order}.{name} starts on {base}."
+        )));
+    }
+}
+
+/// One-line summary of a recorded PA, e.g. `3↑   1 out    4 -> H`.
+pub(crate) fn plate_appearance_summary(pa: &PlateAppearanceRow) -> String {
+    let prefix = format_replay_prefix(
+        pa.inning,
+        &pa.half_inning,
+        pa.outs,
+        &replay_batter_label(pa),
+        true,
+        true,
+    );
+    format!("{prefix} -> {}", outcome_symbol_from_row(pa))
+}
+
 fn replay_batter_label(pa: &PlateAppearanceRow) -> String {
     pa.batter_order.to_string()
 }
@@ -1087,7 +1700,11 @@ fn format_pitch_sequence(seq: &[PlateAppearanceStep]) -> String {
 fn outcome_symbol_from_row(pa: &PlateAppearanceRow) -> String {
     let base = match pa.outcome_type.as_str() {
         "walk" | "bb" => "BB".to_string(),
+        "hit_by_pitch" | "hbp" => "HBP".to_string(),
+        "intentional_walk" | "ibb" => "IBB".to_string(),
+        "catcher_interference" | "ci" => "CI".to_string(),
         "strikeout" | "k" => "K".to_string(),
+        "dropped_third_strike" => "K".to_string(),
         "in_play" | "inplay" => "IP".to_string(),
         "out" => "OUT".to_string(),
         "single" | "h" => "H".to_string(),
@@ -1100,25 +1717,34 @@ fn outcome_symbol_from_row(pa: &PlateAppearanceRow) -> String {
         "line_out" | "lo" => "LO".to_string(),
         "infield_fly" | "if" => "IF".to_string(),
         "fielders_choice" | "fc" => "FC".to_string(),
+        "reached_on_error" | "e" => "E".to_string(),
         _ => "OUT".to_string(),
     };
 
     match pa.outcome_type.as_str() {
         "single" | "double" | "triple" | "home_run" => {
-            if let Some(raw) = pa.outcome_data.as_deref()
-                && let Ok(data) =
-                    serde_json::from_str::<crate::models::plate_appearance::HitOutcomeData>(raw)
-                && let Some(zone) = data.zone
-            {
-                return format!("{} {}", base, zone.as_str());
+            let Some(data) = pa.outcome_data.as_deref().and_then(|raw| {
+                serde_json::from_str::<crate::models::plate_appearance::HitOutcomeData>(raw).ok()
+            }) else {
+                return base;
+            };
+            let mut symbol = base;
+            if let Some(modifier) = data.modifier {
+                symbol = format!("{} {}", symbol, modifier.as_str());
             }
-            base
+            if let Some(zone) = data.zone {
+                symbol = format!("{} {}", symbol, zone.as_str());
+            }
+            symbol
         }
 
         "unassisted_out" => {
             if let Some(v) = parse_outcome_json(pa.outcome_data.as_deref())
                 && let Some(fielder) = get_fielder(&v)
             {
+                if get_sacrifice_flag(&v) {
+                    return format!("Sacrifice bunt: {fielder}");
+                }
                 return format!("Unassisted out: {fielder}");
             }
             "Unassisted out".to_string()
@@ -1128,6 +1754,9 @@ fn outcome_symbol_from_row(pa: &PlateAppearanceRow) -> String {
             if let Some(v) = parse_outcome_json(pa.outcome_data.as_deref())
                 && let Some(sequence) = get_sequence(&v)
             {
+                if get_sacrifice_flag(&v) {
+                    return format!("Sacrifice bunt: {sequence}");
+                }
                 return format!("Ground out: {sequence}");
             }
             "Ground out".to_string()
@@ -1139,7 +1768,9 @@ fn outcome_symbol_from_row(pa: &PlateAppearanceRow) -> String {
             {
                 let foul = get_foul_flag(&v);
 
-                return if foul {
+                return if get_sacrifice_flag(&v) {
+                    format!("Sacrifice fly: F{fielder}")
+                } else if foul {
                     format!("Foul fly: FF{fielder}")
                 } else {
                     format!("Fly out: F{fielder}")
@@ -1175,6 +1806,37 @@ fn outcome_symbol_from_row(pa: &PlateAppearanceRow) -> String {
             "Fielder's choice".to_string()
         }
 
+        "dropped_third_strike" => {
+            use crate::models::plate_appearance::{DroppedThirdStrike, PlateAppearanceOutcome};
+            match pa
+                .outcome_data
+                .as_deref()
+                .and_then(|raw| serde_json::from_str::<DroppedThirdStrike>(raw).ok())
+            {
+                Some(DroppedThirdStrike::ThrownOut { sequence }) => {
+                    format!("Strikeout, batter thrown out: {sequence}")
+                }
+                Some(how) => PlateAppearanceOutcome::DroppedThirdStrike(how)
+                    .display_label()
+                    .to_string(),
+                None => base,
+            }
+        }
+
+        "reached_on_error" => {
+            if let Some(v) = parse_outcome_json(pa.outcome_data.as_deref())
+                && let Some(fielder) = get_fielder(&v)
+            {
+                let throwing = v.get("throwing").and_then(|t| t.as_bool()) == Some(true);
+                return if throwing {
+                    format!("Reached on error: TE{fielder}")
+                } else {
+                    format!("Reached on error: E{fielder}")
+                };
+            }
+            "Reached on error".to_string()
+        }
+
         _ => base,
     }
 }
@@ -1216,8 +1878,6 @@ fn replay_plate_appearances_and_log(
     standalone_movements: &[crate::db::runner_movements::RunnerMovementRow],
     composite_movements: &[crate::db::runner_movements::RunnerMovementRow],
 ) {
-    use crate::db::runner_movements::RunnerMovementRow;
-
     let mut last_inning: Option<i64> = None;
     let mut last_half: Option<String> = None;
     let mut last_outs: Option<i64> = None;
@@ -1225,117 +1885,6 @@ fn replay_plate_appearances_and_log(
     // Index of next standalone movement to apply
     let mut sm_idx = 0;
 
-    let apply_steal_state = |state: &mut GameState, rm: &RunnerMovementRow| {
-        if rm.advancement_type.as_str() != "steal" {
-            return;
-        }
-        let order = rm.batter_order;
-        match rm.start_base.as_str() {
-            "1B" if state.on_1b == Some(order) => {
-                state.on_1b = None;
-            }
-
-            "2B" if state.on_2b == Some(order) => {
-                state.on_2b = None;
-            }
-
-            "3B" if state.on_3b == Some(order) => {
-                state.on_3b = None;
-            }
-
-            _ => {}
-        }
-        match rm.end_base.as_str() {
-            "1B" => state.on_1b = Some(order),
-            "2B" => state.on_2b = Some(order),
-            "3B" => state.on_3b = Some(order),
-            "HOME" => {
-                // Credit the run using the inning/half recorded in the row,
-                // not state.half/state.inning: a steal can be linked to the
-                // last PA of the previous half, so state may still reflect
-                // the old half when this closure executes.
-                let saved_inning = state.inning;
-                let saved_half = state.half;
-                state.inning = rm.inning as u32;
-                state.half = if rm.half_inning == "Top" {
-                    HalfInning::Top
-                } else {
-                    HalfInning::Bottom
-                };
-                add_runs_to_score(state, 1);
-                state.inning = saved_inning;
-                state.half = saved_half;
-            }
-            _ => {}
-        }
-    };
-
-    // Apply a composite defensive-play movement (runner-out, FC-safe
-    // advance, etc.) to the in-memory state. Mirror of the in-memory
-    // updates done by `apply_defensive_play_command` on the live path, so
-    // that replay converges on the same GameState.
-    //
-    // The row's `start_base` is NOT used as an authoritative source to
-    // clear the previous base, because the row only represents the
-    // runner's starting base at the time the play was recorded. If the
-    // runner has been subsequently moved (impossible today but defensive
-    // against future features like caught stealing followed by an FC on
-    // the same line), we always clear the slot from every base before
-    // applying the destination — matching `place_runner_on_base` in
-    // apply.rs.
-    let apply_composite_state = |state: &mut GameState, rm: &RunnerMovementRow| {
-        let order = rm.batter_order;
-
-        // Runner-out row: clear the slot from every base and be done.
-        if rm.is_out {
-            if state.on_1b == Some(order) {
-                state.on_1b = None;
-            }
-            if state.on_2b == Some(order) {
-                state.on_2b = None;
-            }
-            if state.on_3b == Some(order) {
-                state.on_3b = None;
-            }
-            return;
-        }
-
-        // FC-safe advance: first clear the slot, then place on
-        // destination.
-        if state.on_1b == Some(order) {
-            state.on_1b = None;
-        }
-        if state.on_2b == Some(order) {
-            state.on_2b = None;
-        }
-        if state.on_3b == Some(order) {
-            state.on_3b = None;
-        }
-        match rm.end_base.as_str() {
-            "1B" => state.on_1b = Some(order),
-            "2B" => state.on_2b = Some(order),
-            "3B" => state.on_3b = Some(order),
-            "HOME" => {
-                // Credit the run using the inning/half recorded in the row
-                // rather than state.half/state.inning for the same reason as
-                // apply_steal_state: the row is the authoritative source for
-                // when this movement was recorded.
-                let saved_inning = state.inning;
-                let saved_half = state.half;
-                state.inning = rm.inning as u32;
-                state.half = if rm.half_inning == "Top" {
-                    HalfInning::Top
-                } else {
-                    HalfInning::Bottom
-                };
-                add_runs_to_score(state, 1);
-                state.inning = saved_inning;
-                state.half = saved_half;
-            }
-            _ => {}
-        }
-    };
-
     // Helper: apply a standalone movement (steal) to state and log it.
     // pending_steal_logs: messaggi di steal da emettere prima della prossima riga PA
     let mut pending_steal_logs: Vec<String> = vec![];
@@ -1345,40 +1894,15 @@ fn replay_plate_appearances_and_log(
         let score_before_away = state.score.away;
         let score_before_home = state.score.home;
 
-        // applica PA (ricostruisce lo stato)
-        apply_plate_appearance_row(state, pa);
-
-        // Apply composite-defensive movements linked to this PA (runner
-        // outs and FC-safe advances recorded in runner_movements rows
-        // with pa_seq == pa.seq and advancement_type != "steal"). This is
-        // the replay mirror of the in-memory state mutation performed by
-        // `apply_defensive_play_command` on the live path.
-        //
-        // Ordering: clear every runner-out before placing any FC-safe
-        // advance, so that a runner advancing to a base just vacated by
-        // another runner who was put out sees an empty slot.
-        for rm in composite_movements
-            .iter()
-            .filter(|r| r.pa_seq == Some(pa.seq) && r.is_out)
-        {
-            apply_composite_state(state, rm);
-        }
-        for rm in composite_movements
-            .iter()
-            .filter(|r| r.pa_seq == Some(pa.seq) && !r.is_out)
-        {
-            apply_composite_state(state, rm);
-        }
+        // applica PA (ricostruisce lo stato), composite-play rows included
+        replay_plate_appearance(state, pa, composite_movements);
 
         // Applica steal linkati a questa PA allo stato; accumula i log
         while sm_idx < standalone_movements.len() {
             let rm = &standalone_movements[sm_idx];
             if rm.pa_seq == Some(pa.seq) {
-                apply_steal_state(state, rm);
-                pending_steal_logs.push(format!(
-                    "  [resume] [{}] steal {}",
-                    rm.batter_order, rm.end_base
-                ));
+                apply_standalone_movement(state, rm);
+                pending_steal_logs.push(standalone_resume_line(rm));
                 sm_idx += 1;
             } else {
                 break;
@@ -1401,6 +1925,10 @@ fn replay_plate_appearances_and_log(
         } else {
             String::new()
         };
+        let dp_text = pa
+            .double_play()
+            .map(|dp| format!(" [{dp}]"))
+            .unwrap_or_default();
 
         // label battitore
         let batter_label = replay_batter_label(pa);
@@ -1433,8 +1961,8 @@ fn replay_plate_appearances_and_log(
         );
 
         ui.emit(UiEvent::Line(format!(
-            "{} -> {} -> {}{}",
-            prefix, seq_text, outcome_sym, run_text
+            "{} -> {} -> {}{}{}",
+            prefix, seq_text, outcome_sym, dp_text, run_text
         )));
 
         // Emetti i log degli steal avvenuti durante questa PA (dopo la riga PA)
@@ -1454,15 +1982,29 @@ fn replay_plate_appearances_and_log(
     // Apply any remaining standalone movements after all PAs (e.g. steal with no PA after)
     while sm_idx < standalone_movements.len() {
         let rm = &standalone_movements[sm_idx];
-        apply_steal_state(state, rm);
-        ui.emit(UiEvent::Line(format!(
-            "  [resume] [{}] ruba {}",
-            rm.batter_order, rm.end_base
-        )));
+        apply_standalone_movement(state, rm);
+        ui.emit(UiEvent::Line(standalone_resume_line(rm)));
         sm_idx += 1;
     }
 }
 
+/// Resume log line for a standalone movement: the destination base for an
+/// advance (`steal 2B`), the fielding chain for an out
+/// (`caught stealing 2-6`).
+fn standalone_resume_line(rm: &crate::db::runner_movements::RunnerMovementRow) -> String {
+    let what = if rm.is_out {
+        rm.fielders.as_deref().unwrap_or("OUT")
+    } else {
+        rm.end_base.as_str()
+    };
+    format!(
+        "  [resume] [{}] {} {}",
+        rm.batter_order,
+        rm.advancement_type.replace('_', " "),
+        what
+    )
+}
+
 fn load_and_apply_draft(
     conn: &mut Connection,
     ui: &mut dyn Ui,
@@ -1498,6 +2040,7 @@ fn load_and_apply_draft(
         state.on_1b = None;
         state.on_2b = None;
         state.on_3b = None;
+        state.tie_break_runners.clear();
     }
 
     state.current_batter_id = draft.batter_id;
@@ -1532,3 +2075,140 @@ fn load_and_apply_draft(
 
     draft_opt
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+    use crate::db::fixtures::{NineVsNine, nine_vs_nine};
+    use crate::db::runner_movements::list_runner_movements;
+    use std::collections::VecDeque;
+
+    const GAME_ID: &str = "PB-TEST";
+
+    /// Types the scripted lines, then leaves; keeps the errors and the
+    /// last state shown.
+    struct Script {
+        lines: VecDeque<String>,
+        errors: Vec<String>,
+        state: GameState,
+    }
+
+    impl Ui for Script {
+        fn emit(&mut self, event: UiEvent) {
+            if let UiEvent::Error(e) = event {
+                self.errors.push(e);
+            }
+        }
+
+        fn read_command_line(&mut self, _prompt: &str) -> Option<String> {
+            self.lines.pop_front()
+        }
+
+        fn confirm(&mut self, _question: &str) -> bool {
+            false
+        }
+
+        fn set_state(&mut self, state: &GameState) {
+            self.state = state.clone();
+        }
+    }
+
+    /// A nine-against-nine game with the tie-break rule from `tie_break`.
+    fn new_game(tie_break: Option<u32>) -> (Database, NineVsNine) {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let game = nine_vs_nine(db.get_connection(), GAME_ID);
+        db.get_connection()
+            .execute(
+                "UPDATE games SET tie_break_inning = ?1 WHERE id = ?2",
+                params![tie_break, game.game_pk],
+            )
+            .unwrap();
+        (db, game)
+    }
+
+    /// Open Play Ball on `game` and type `lines`; resumes when the game
+    /// has already started.
+    fn run(db: &mut Database, game: &NineVsNine, lines: &[&str]) -> Script {
+        let mut ui = Script {
+            lines: lines.iter().map(|l| l.to_string()).collect(),
+            errors: Vec::new(),
+            state: GameState::new(),
+        };
+        run_play_ball_engine(
+            db.get_connection_mut(),
+            &mut ui,
+            game.game_pk,
+            GAME_ID,
+            game.away_team_id,
+            game.home_team_id,
+        );
+        assert!(ui.errors.is_empty(), "{:?}", ui.errors);
+        ui
+    }
+
+    /// Play `lines` after `playball` in a fresh game.
+    fn play(lines: &[&str]) -> Script {
+        let (mut db, game) = new_game(None);
+        let lines: Vec<&str> = std::iter::once("playball")
+            .chain(lines.iter().copied())
+            .collect();
+        run(&mut db, &game, &lines)
+    }
+
+    /// `playball` and a 1-2-3 first inning on both sides: the away #3
+    /// starts the second on second base, #4 at bat.
+    const TO_THE_TIE_BREAK: [&str; 7] = ["playball", "63", "63", "63", "63", "63", "63"];
+
+    #[test]
+    fn the_batter_after_the_third_out_leads_off_next_inning() {
+        // 1-2-3 in the first for both teams, then the away #4 grounds out
+        // leading off the second.
+        let ui = play(&["63", "63", "63", "63", "63", "63", "63"]);
+        assert_eq!((ui.state.inning, ui.state.half), (2, HalfInning::Top));
+        assert_eq!(ui.state.outs, 1);
+        assert_eq!(ui.state.current_batter_order, Some(5));
+        assert_eq!(ui.state.home_next_batting_order, 4);
+    }
+
+    #[test]
+    fn tie_break_runner_who_scored_is_not_tracked_when_the_slot_comes_around() {
+        let (mut db, game) = new_game(Some(2));
+        let mut lines = TO_THE_TIE_BREAK.to_vec();
+        // #4 drives the tie-break runner in, #5 to #2 homer, #3 singles.
+        lines.push("h, 3 sc");
+        lines.extend(["hr"; 7]);
+        lines.push("h");
+        let ui = run(&mut db, &game, &lines);
+        assert_eq!((ui.state.inning, ui.state.half), (2, HalfInning::Top));
+        assert_eq!(ui.state.on_1b, Some(3));
+        assert!(ui.state.tie_break_runners.is_empty());
+
+        // Same after a resume, then #4 drives #3 in: an earned run.
+        let ui = run(&mut db, &game, &["hr"]);
+        assert!(ui.state.tie_break_runners.is_empty());
+        let runs: Vec<bool> = list_runner_movements(db.get_connection(), game.game_pk)
+            .unwrap()
+            .iter()
+            .filter(|rm| rm.batter_order == 3 && rm.scored)
+            .map(|rm| rm.is_earned)
+            .collect();
+        assert_eq!(runs, vec![false, true]);
+    }
+
+    #[test]
+    fn tie_break_runner_put_out_is_not_tracked() {
+        let (mut db, game) = new_game(Some(2));
+        let ui = run(&mut db, &game, &TO_THE_TIE_BREAK);
+        assert_eq!(ui.state.on_2b, Some(3));
+        assert_eq!(ui.state.tie_break_runners, vec![3]);
+
+        // Picked off, then the state rebuilt on resume.
+        let ui = run(&mut db, &game, &["3 po 16"]);
+        assert_eq!((ui.state.outs, ui.state.on_2b), (1, None));
+        assert!(ui.state.tie_break_runners.is_empty());
+        let ui = run(&mut db, &game, &[]);
+        assert!(ui.state.tie_break_runners.is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Runs batted in (Rule 9.04).
+//!
+//! The batter is credited with every run that scores on the plate
+//! appearance, including their own on a home run, except:
+//!
+//! - a run that scores on an error (`"error"` movement);
+//! - any run on a ground ball the defence turns into a double or triple
+//!   play;
+//! - any run on a dropped third strike, which scores on the wild pitch or
+//!   passed ball;
+//! - when the batter reaches on an error, every run but that of a runner
+//!   from third who scores with fewer than two outs and without the help
+//!   of the error.
+//!
+//! Runs on a wild pitch, passed ball, balk or steal are recorded between
+//! pitches with no plate appearance, so they never count. The scorer can
+//! overrule the count with `rbi <n>`; the stored value is then kept as is.
+
+use crate::db::plate_appearances::{PlateAppearanceRow, outcome_columns};
+use crate::db::runner_movements::{RunnerMovementInsert, RunnerMovementRow};
+use crate::models::plate_appearance::PlateAppearance;
+
+/// What the RBI count needs from a movement row.
+struct Movement<'a> {
+    start_base: &'a str,
+    advancement_type: &'a str,
+    is_out: bool,
+    scored: bool,
+}
+
+/// RBIs credited to the batter of a live plate appearance, from the
+/// movements it produced.
+pub fn credited_rbi(pa: &PlateAppearance, movements: &[RunnerMovementInsert]) -> u8 {
+    let (outcome_type, _) = outcome_columns(pa);
+    let movements: Vec<Movement> = movements
+        .iter()
+        .map(|m| Movement {
+            start_base: m.start_base,
+            advancement_type: m.advancement_type,
+            is_out: m.is_out,
+            scored: m.scored,
+        })
+        .collect();
+    count(&outcome_type, pa.double_play.is_some(), pa.outs, &movements)
+}
+
+/// RBIs of a stored plate appearance: the recorded value, or the count
+/// rebuilt from `movements` (the rows linked to it) for legacy rows.
+pub fn row_rbi(pa: &PlateAppearanceRow, movements: &[&RunnerMovementRow]) -> u8 {
+    if let Some(rbi) = pa.rbi {
+        return rbi;
+    }
+    let movements: Vec<Movement> = movements
+        .iter()
+        .filter(|m| m.pa_seq == Some(pa.seq))
+        .map(|m| Movement {
+            start_base: &m.start_base,
+            advancement_type: &m.advancement_type,
+            is_out: m.is_out,
+            scored: m.scored,
+        })
+        .collect();
+    count(
+        &pa.outcome_type,
+        pa.double_play_json.is_some(),
+        u8::try_from(pa.outs).unwrap_or(3),
+        &movements,
+    )
+}
+
+fn count(outcome_type: &str, double_play: bool, outs_after: u8, movements: &[Movement]) -> u8 {
+    let ground_ball = matches!(
+        outcome_type,
+        "ground_out" | "fielders_choice" | "unassisted_out"
+    );
+    if outcome_type == "dropped_third_strike" || (double_play && ground_ball) {
+        return 0;
+    }
+
+    let runner_outs = movements.iter().filter(|m| m.is_out).count() as u8;
+    let outs_before = outs_after.saturating_sub(runner_outs);
+
+    movements
+        .iter()
+        .filter(|m| m.scored && m.advancement_type != "error")
+        .filter(|m| outcome_type != "reached_on_error" || (m.start_base == "3B" && outs_before < 2))
+        .count() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+
+    /// The batter in slot 4's plate appearance, the first of the game.
+    fn pa(outcome: &str, outs: i64) -> PlateAppearanceRow {
+        fixtures::pa(1, "Top", 4, 100, outcome, None, outs)
+    }
+
+    fn rm(order: u8, from: &str, to: &str, kind: &str) -> RunnerMovementRow {
+        fixtures::rm(i64::from(order), Some(1), "Top", order, from, to, kind)
+    }
+
+    fn rbi(pa: &PlateAppearanceRow, rows: &[RunnerMovementRow]) -> u8 {
+        let rows: Vec<&RunnerMovementRow> = rows.iter().collect();
+        row_rbi(pa, &rows)
+    }
+
+    #[test]
+    fn home_run_credits_every_run_including_the_batter() {
+        let rows = [
+            rm(1, "2B", "HOME", "hit_auto"),
+            rm(3, "1B", "HOME", "hit_auto"),
+            rm(4, "BAT", "HOME", "hit_auto"),
+        ];
+        assert_eq!(rbi(&pa("home_run", 0), &rows), 3);
+    }
+
+    #[test]
+    fn run_scoring_on_an_error_is_not_credited() {
+        let rows = [
+            rm(1, "3B", "HOME", "hit_auto"),
+            rm(2, "1B", "HOME", "error"),
+            rm(4, "BAT", "1B", "hit_auto"),
+        ];
+        assert_eq!(rbi(&pa("single", 0), &rows), 1);
+    }
+
+    #[test]
+    fn bases_loaded_walk_forces_in_a_run() {
+        let rows = [
+            rm(1, "3B", "HOME", "walk"),
+            rm(2, "2B", "3B", "walk"),
+            rm(3, "1B", "2B", "walk"),
+            rm(4, "BAT", "1B", "walk"),
+        ];
+        assert_eq!(rbi(&pa("walk", 1), &rows), 1);
+    }
+
+    #[test]
+    fn ground_ball_double_play_credits_no_rbi() {
+        let mut row = pa("ground_out", 2);
+        row.double_play_json = Some("{}".to_string());
+        let rows = [
+            rm(1, "3B", "HOME", "ground_out"),
+            rm(2, "1B", "OUT", "ground_out"),
+        ];
+        assert_eq!(rbi(&row, &rows), 0);
+
+        // Without the double play the sacrifice grounder drives the run in.
+        assert_eq!(rbi(&pa("ground_out", 1), &rows[..1]), 1);
+    }
+
+    #[test]
+    fn reached_on_error_credits_only_the_runner_from_third_with_less_than_two_outs() {
+        let rows = [
+            rm(1, "3B", "HOME", "hit_auto"),
+            rm(2, "2B", "HOME", "hit_auto"),
+            rm(4, "BAT", "1B", "error"),
+        ];
+        assert_eq!(rbi(&pa("reached_on_error", 1), &rows), 1);
+        assert_eq!(rbi(&pa("reached_on_error", 2), &rows), 0);
+    }
+
+    #[test]
+    fn dropped_third_strike_credits_no_rbi() {
+        let rows = [
+            rm(1, "3B", "HOME", "dropped_third_strike"),
+            rm(4, "BAT", "1B", "dropped_third_strike"),
+        ];
+        assert_eq!(rbi(&pa("dropped_third_strike", 0), &rows), 0);
+    }
+
+    #[test]
+    fn recorded_value_wins_over_the_count() {
+        let mut row = pa("single", 0);
+        row.rbi = Some(0);
+        assert_eq!(rbi(&row, &[rm(1, "3B", "HOME", "hit_auto")]), 0);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::db::plate_appearances::PlateAppearanceRow;
+use crate::db::runner_movements::RunnerMovementRow;
 use crate::engine::play_ball::{bump_order, parse_pa_sequence};
-use crate::models::events::{DomainEvent, StrikeoutKind};
+use crate::engine::runners::{add_runs_to_score, drop_departed_tie_break_runners};
+use crate::models::events::{DomainEvent, StrikeoutKind, SubstitutionKind};
 use crate::models::game_state::{BatterOrder, GameState};
-use crate::models::plate_appearance::PlateAppearanceStep;
+use crate::models::plate_appearance::{DroppedThirdStrike, HitModifier, PlateAppearanceStep};
 use crate::models::runner::{RunnerDest, RunnerOverride};
 use crate::models::types::{HalfInning, Pitch};
 
@@ -17,6 +19,7 @@ pub fn apply_domain_event(state: &mut GameState, ev: &DomainEvent) {
             state.on_1b = None;
             state.on_2b = None;
             state.on_3b = None;
+            state.tie_break_runners.clear();
 
             state.current_batter_id = None;
             state.current_batter_jersey_no = None;
@@ -98,13 +101,28 @@ pub fn apply_domain_event(state: &mut GameState, ev: &DomainEvent) {
             state.pitcher_stats.entry(*pitcher_id).or_default();
         }
 
+        DomainEvent::PlayerSubstituted(d) => {
+            // A pinch hitter takes over the at-bat and its count. Runners
+            // are tracked by batting slot, so a pinch runner needs no
+            // change here; a new pitcher arrives as `PitcherChanged`.
+            if d.kind == SubstitutionKind::PinchHitter
+                && state.current_batter_order == Some(d.batting_order)
+            {
+                state.current_batter_id = Some(d.player_in_id);
+                state.current_batter_jersey_no = Some(d.player_in_jersey_no);
+                state.current_batter_first_name = Some(d.player_in_first_name.clone());
+                state.current_batter_last_name = Some(d.player_in_last_name.clone());
+                state.current_batter_position = Some(d.position);
+            }
+        }
+
         DomainEvent::PitchRecorded {
             pitcher_id, pitch, ..
         } => {
             let stats = state.pitcher_stats.entry(*pitcher_id).or_default();
 
             match pitch {
-                Pitch::Ball => stats.balls += 1,
+                Pitch::Ball | Pitch::HittedBy => stats.balls += 1,
                 _ => stats.strikes += 1,
             }
 
@@ -174,16 +192,17 @@ pub fn apply_hit_with_overrides(
     state: &mut GameState,
     batter_order: BatterOrder,
     bases: u8,
+    modifier: Option<HitModifier>,
     overrides: &[RunnerOverride],
 ) -> Vec<crate::db::runner_movements::RunnerMovementInsert> {
-    let result = crate::engine::runners::apply_hit(state, batter_order, bases, overrides);
+    let result = crate::engine::runners::apply_hit(state, batter_order, bases, modifier, overrides);
     result.movements
 }
 
 /// Legacy automatic-only hit advancement (used by PA replay where we don't have override data).
 pub fn apply_hit_advancement(state: &mut GameState, bases: u8) {
     let batter_order: BatterOrder = 0;
-    let _ = crate::engine::runners::apply_hit(state, batter_order, bases, &[]);
+    let _ = crate::engine::runners::apply_hit(state, batter_order, bases, None, &[]);
 }
 
 // ─── Walk advancement ─────────────────────────────────────────────────────────
@@ -210,6 +229,7 @@ fn apply_plate_appearance_core(
         state.on_1b = None;
         state.on_2b = None;
         state.on_3b = None;
+        state.tie_break_runners.clear();
 
         state.current_batter_id = None;
         state.current_batter_jersey_no = None;
@@ -224,15 +244,21 @@ fn apply_plate_appearance_core(
 
         for step in &pa.pitches_sequence {
             match step {
-                PlateAppearanceStep::Pitch(Pitch::Ball) | PlateAppearanceStep::Walk => {
+                PlateAppearanceStep::Pitch(Pitch::Ball | Pitch::HittedBy)
+                | PlateAppearanceStep::Walk
+                | PlateAppearanceStep::HitByPitch => {
                     stats.balls = stats.balls.saturating_add(1);
                 }
 
+                // No pitch is thrown on an intentional walk.
+                PlateAppearanceStep::IntentionalWalk => {}
+
                 PlateAppearanceStep::Pitch(_) => {
                     stats.strikes = stats.strikes.saturating_add(1);
                 }
 
                 PlateAppearanceStep::Strikeout
+                | PlateAppearanceStep::CatcherInterference
                 | PlateAppearanceStep::Out
                 | PlateAppearanceStep::UnassistedOut { .. }
                 | PlateAppearanceStep::GroundOut { .. }
@@ -240,6 +266,7 @@ fn apply_plate_appearance_core(
                 | PlateAppearanceStep::LineOut { .. }
                 | PlateAppearanceStep::InfieldFly { .. }
                 | PlateAppearanceStep::FieldersChoice { .. }
+                | PlateAppearanceStep::ReachedOnError { .. }
                 | PlateAppearanceStep::Single
                 | PlateAppearanceStep::Double
                 | PlateAppearanceStep::Triple
@@ -260,7 +287,10 @@ fn apply_plate_appearance_core(
 
     // Outcome effects — replay uses automatic advancement only for simple cases.
     match &pa.outcome {
-        crate::models::plate_appearance::PlateAppearanceOutcome::Walk => {
+        crate::models::plate_appearance::PlateAppearanceOutcome::Walk
+        | crate::models::plate_appearance::PlateAppearanceOutcome::HitByPitch
+        | crate::models::plate_appearance::PlateAppearanceOutcome::IntentionalWalk
+        | crate::models::plate_appearance::PlateAppearanceOutcome::CatcherInterference => {
             if apply_walk_base_advancement {
                 apply_walk_advancement(state, pa.batter_order);
             }
@@ -276,6 +306,18 @@ fn apply_plate_appearance_core(
             state.outs = pa.outs;
         }
 
+        // The batter reaches first (or is thrown out) and the runners
+        // move from the PA itself, like a walk plus the scorer's overrides.
+        crate::models::plate_appearance::PlateAppearanceOutcome::DroppedThirdStrike(how) => {
+            let _ = crate::engine::runners::apply_forced_advance(
+                state,
+                pa.batter_order,
+                u8::from(how.batter_reaches()),
+                &pa.runner_overrides,
+            );
+            state.outs = pa.outs;
+        }
+
         crate::models::plate_appearance::PlateAppearanceOutcome::FieldersChoice { .. } => {
             // v0.11.0-alpha2-fix_codex: the batter's BAT → <base> movement
             // is now applied from the `runner_movements` row produced by
@@ -289,27 +331,69 @@ fn apply_plate_appearance_core(
             state.outs = pa.outs;
         }
 
+        // Like a dropped third strike: only the runners the batter forces
+        // move, unless the scorer places them.
+        crate::models::plate_appearance::PlateAppearanceOutcome::ReachedOnError {
+            reached_base,
+            ..
+        } => {
+            let _ = crate::engine::runners::apply_forced_advance(
+                state,
+                pa.batter_order,
+                reached_base.bases(),
+                &pa.runner_overrides,
+            );
+            state.outs = pa.outs;
+        }
+
         crate::models::plate_appearance::PlateAppearanceOutcome::Single { .. } => {
-            let _ = apply_hit_with_overrides(state, pa.batter_order, 1, &pa.runner_overrides);
+            let _ = apply_hit_with_overrides(
+                state,
+                pa.batter_order,
+                1,
+                pa.outcome.hit_modifier(),
+                &pa.runner_overrides,
+            );
             state.outs = pa.outs;
         }
 
         crate::models::plate_appearance::PlateAppearanceOutcome::Double { .. } => {
-            let _ = apply_hit_with_overrides(state, pa.batter_order, 2, &pa.runner_overrides);
+            let _ = apply_hit_with_overrides(
+                state,
+                pa.batter_order,
+                2,
+                pa.outcome.hit_modifier(),
+                &pa.runner_overrides,
+            );
             state.outs = pa.outs;
         }
 
         crate::models::plate_appearance::PlateAppearanceOutcome::Triple { .. } => {
-            let _ = apply_hit_with_overrides(state, pa.batter_order, 3, &pa.runner_overrides);
+            let _ = apply_hit_with_overrides(
+                state,
+                pa.batter_order,
+                3,
+                pa.outcome.hit_modifier(),
+                &pa.runner_overrides,
+            );
             state.outs = pa.outs;
         }
 
         crate::models::plate_appearance::PlateAppearanceOutcome::HomeRun { .. } => {
-            let _ = apply_hit_with_overrides(state, pa.batter_order, 4, &pa.runner_overrides);
+            let _ = apply_hit_with_overrides(
+                state,
+                pa.batter_order,
+                4,
+                pa.outcome.hit_modifier(),
+                &pa.runner_overrides,
+            );
             state.outs = pa.outs;
         }
     }
 
+    // Errors on the batter or on runners (carried by the overrides).
+    crate::engine::runners::add_errors_to_score(state, pa.errors_charged());
+
     // Advance batting order
     match pa.half {
         HalfInning::Top => {
@@ -349,15 +433,18 @@ pub fn apply_live_plate_appearance(
     };
 
     // In live mode we add the terminal synthetic pitch for:
-    // - hits
+    // - hits and reached-on-error
     // - batter outs in play
-    // Walk is handled separately inside core as terminal ball.
+    // - catcher's interference (the batter swung at the pitch)
+    // Walk and HBP record their terminal pitch through PitchRecorded.
     let add_terminal_live_pitch = matches!(
         &pa.outcome,
         PlateAppearanceOutcome::Single { .. }
             | PlateAppearanceOutcome::Double { .. }
             | PlateAppearanceOutcome::Triple { .. }
             | PlateAppearanceOutcome::HomeRun { .. }
+            | PlateAppearanceOutcome::ReachedOnError { .. }
+            | PlateAppearanceOutcome::CatcherInterference
             | PlateAppearanceOutcome::GroundOut { .. }
             | PlateAppearanceOutcome::FlyOut { .. }
             | PlateAppearanceOutcome::LineOut { .. }
@@ -373,7 +460,10 @@ pub fn apply_live_plate_appearance(
                 runner_on_3b,
                 pa.batter_order,
                 1,
-                &pa.runner_overrides,
+                crate::engine::runners::effective_hit_overrides(
+                    pa.outcome.hit_modifier(),
+                    &pa.runner_overrides,
+                ),
                 inning,
                 half_str,
             )
@@ -387,7 +477,10 @@ pub fn apply_live_plate_appearance(
                 runner_on_3b,
                 pa.batter_order,
                 2,
-                &pa.runner_overrides,
+                crate::engine::runners::effective_hit_overrides(
+                    pa.outcome.hit_modifier(),
+                    &pa.runner_overrides,
+                ),
                 inning,
                 half_str,
             )
@@ -401,7 +494,10 @@ pub fn apply_live_plate_appearance(
                 runner_on_3b,
                 pa.batter_order,
                 3,
-                &pa.runner_overrides,
+                crate::engine::runners::effective_hit_overrides(
+                    pa.outcome.hit_modifier(),
+                    &pa.runner_overrides,
+                ),
                 inning,
                 half_str,
             )
@@ -415,7 +511,61 @@ pub fn apply_live_plate_appearance(
                 runner_on_3b,
                 pa.batter_order,
                 4,
+                crate::engine::runners::effective_hit_overrides(
+                    pa.outcome.hit_modifier(),
+                    &pa.runner_overrides,
+                ),
+                inning,
+                half_str,
+            )
+        }
+
+        // The batter and the runners they force move on the error; a
+        // runner the scorer sends on without charging the error moves on
+        // the play (`hit_override`).
+        PlateAppearanceOutcome::ReachedOnError { reached_base, .. } => {
+            let snapshot = crate::engine::runners::BaseSnapshot {
+                on_1b: runner_on_1b,
+                on_2b: runner_on_2b,
+                on_3b: runner_on_3b,
+            };
+            apply_plate_appearance_core(state, pa, false, add_terminal_live_pitch, false);
+            let mut movements = crate::engine::runners::build_forced_movements(
+                &snapshot,
+                pa.batter_order,
+                reached_base.bases(),
                 &pa.runner_overrides,
+                "error",
+                true,
+                inning,
+                half_str,
+            );
+            for m in movements.iter_mut().filter(|m| m.start_base != "BAT") {
+                if pa
+                    .runner_overrides
+                    .iter()
+                    .any(|o| o.order == m.batter_order && o.error.is_none())
+                {
+                    m.advancement_type = "hit_override";
+                }
+            }
+            movements
+        }
+
+        PlateAppearanceOutcome::DroppedThirdStrike(how) => {
+            let snapshot = crate::engine::runners::BaseSnapshot {
+                on_1b: runner_on_1b,
+                on_2b: runner_on_2b,
+                on_3b: runner_on_3b,
+            };
+            apply_plate_appearance_core(state, pa, false, false, false);
+            crate::engine::runners::build_forced_movements(
+                &snapshot,
+                pa.batter_order,
+                u8::from(how.batter_reaches()),
+                &pa.runner_overrides,
+                "dropped_third_strike",
+                !matches!(how, DroppedThirdStrike::PassedBall),
                 inning,
                 half_str,
             )
@@ -424,7 +574,8 @@ pub fn apply_live_plate_appearance(
         PlateAppearanceOutcome::GroundOut { .. }
         | PlateAppearanceOutcome::FlyOut { .. }
         | PlateAppearanceOutcome::LineOut { .. }
-        | PlateAppearanceOutcome::InfieldFly { .. } => {
+        | PlateAppearanceOutcome::InfieldFly { .. }
+        | PlateAppearanceOutcome::CatcherInterference => {
             apply_plate_appearance_core(state, pa, false, add_terminal_live_pitch, false);
             vec![]
         }
@@ -438,6 +589,9 @@ pub fn apply_live_plate_appearance(
 
 /// Build RunnerMovementInsert rows from a pre-mutation base snapshot.
 /// Delegates to crate::engine::runners::build_movements_from_snapshot.
+///
+/// Runners whose override carries a fielding error are tagged `"error"`
+/// instead of `"hit_override"`.
 #[allow(clippy::too_many_arguments)]
 fn build_hit_movements_from_snapshot(
     runner_on_1b: Option<u8>,
@@ -456,24 +610,52 @@ fn build_hit_movements_from_snapshot(
     };
     let override_map: std::collections::HashMap<u8, RunnerDest> =
         overrides.iter().map(|r| (r.order, r.dest)).collect();
-    crate::engine::runners::build_movements_from_snapshot(
+    let mut movements = crate::engine::runners::build_movements_from_snapshot(
         &snapshot,
         batter_order,
         bases,
         &override_map,
         inning,
         half_str,
-    )
+    );
+    for m in movements.iter_mut().filter(|m| m.start_base != "BAT") {
+        if overrides
+            .iter()
+            .any(|o| o.order == m.batter_order && o.error.is_some())
+        {
+            m.advancement_type = "error";
+        }
+    }
+    movements
 }
 
 fn parse_hit_outcome_data(raw: Option<&str>) -> crate::models::plate_appearance::HitOutcomeData {
-    serde_json::from_str(raw.unwrap_or(r#"{"zone":null}"#))
-        .unwrap_or(crate::models::plate_appearance::HitOutcomeData { zone: None })
+    serde_json::from_str(raw.unwrap_or(r#"{"zone":null}"#)).unwrap_or(
+        crate::models::plate_appearance::HitOutcomeData {
+            zone: None,
+            modifier: None,
+        },
+    )
 }
 
 pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRow) {
+    apply_plate_appearance(state, &plate_appearance_from_row(row));
+}
+
+/// Rebuild the plate appearance stored in `row`. Unknown outcome types
+/// read back as a plain out.
+pub fn plate_appearance_from_row(
+    row: &PlateAppearanceRow,
+) -> crate::models::plate_appearance::PlateAppearance {
     let outcome = match row.outcome_type.as_str() {
         "walk" => crate::models::plate_appearance::PlateAppearanceOutcome::Walk,
+        "hit_by_pitch" => crate::models::plate_appearance::PlateAppearanceOutcome::HitByPitch,
+        "intentional_walk" => {
+            crate::models::plate_appearance::PlateAppearanceOutcome::IntentionalWalk
+        }
+        "catcher_interference" => {
+            crate::models::plate_appearance::PlateAppearanceOutcome::CatcherInterference
+        }
 
         "strikeout" => {
             let kind: StrikeoutKind =
@@ -483,26 +665,49 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
             crate::models::plate_appearance::PlateAppearanceOutcome::Strikeout(kind)
         }
 
+        "dropped_third_strike" => {
+            let how: DroppedThirdStrike = serde_json::from_str(
+                row.outcome_data.as_deref().unwrap_or("null"),
+            )
+            .unwrap_or(DroppedThirdStrike::ThrownOut {
+                sequence: "-".to_string(),
+            });
+
+            crate::models::plate_appearance::PlateAppearanceOutcome::DroppedThirdStrike(how)
+        }
+
         "out" => crate::models::plate_appearance::PlateAppearanceOutcome::Out,
 
         "single" => {
             let data = parse_hit_outcome_data(row.outcome_data.as_deref());
-            crate::models::plate_appearance::PlateAppearanceOutcome::Single { zone: data.zone }
+            crate::models::plate_appearance::PlateAppearanceOutcome::Single {
+                zone: data.zone,
+                modifier: data.modifier,
+            }
         }
 
         "double" => {
             let data = parse_hit_outcome_data(row.outcome_data.as_deref());
-            crate::models::plate_appearance::PlateAppearanceOutcome::Double { zone: data.zone }
+            crate::models::plate_appearance::PlateAppearanceOutcome::Double {
+                zone: data.zone,
+                modifier: data.modifier,
+            }
         }
 
         "triple" => {
             let data = parse_hit_outcome_data(row.outcome_data.as_deref());
-            crate::models::plate_appearance::PlateAppearanceOutcome::Triple { zone: data.zone }
+            crate::models::plate_appearance::PlateAppearanceOutcome::Triple {
+                zone: data.zone,
+                modifier: data.modifier,
+            }
         }
 
         "home_run" => {
             let data = parse_hit_outcome_data(row.outcome_data.as_deref());
-            crate::models::plate_appearance::PlateAppearanceOutcome::HomeRun { zone: data.zone }
+            crate::models::plate_appearance::PlateAppearanceOutcome::HomeRun {
+                zone: data.zone,
+                modifier: data.modifier,
+            }
         }
 
         "ground_out" => {
@@ -516,7 +721,15 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
                 .unwrap_or("-")
                 .to_string();
 
-            crate::models::plate_appearance::PlateAppearanceOutcome::GroundOut { sequence }
+            let sacrifice = value
+                .get("sacrifice")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            crate::models::plate_appearance::PlateAppearanceOutcome::GroundOut {
+                sequence,
+                sacrifice,
+            }
         }
 
         "fly_out" => {
@@ -535,9 +748,15 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
+            let sacrifice = value
+                .get("sacrifice")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
             crate::models::plate_appearance::PlateAppearanceOutcome::FlyOut {
                 fielder,
                 in_foul_territory,
+                sacrifice,
             }
         }
 
@@ -580,7 +799,15 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
                 .and_then(|v| u8::try_from(v).ok())
                 .unwrap_or(0);
 
-            crate::models::plate_appearance::PlateAppearanceOutcome::UnassistedOut { fielder }
+            let sacrifice = value
+                .get("sacrifice")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            crate::models::plate_appearance::PlateAppearanceOutcome::UnassistedOut {
+                fielder,
+                sacrifice,
+            }
         }
 
         "fielders_choice" => {
@@ -614,12 +841,41 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
             }
         }
 
+        "reached_on_error" => {
+            let value: serde_json::Value =
+                serde_json::from_str(row.outcome_data.as_deref().unwrap_or("{}"))
+                    .unwrap_or_else(|_| serde_json::json!({}));
+
+            let fielder = value
+                .get("fielder")
+                .and_then(|v| v.as_u64())
+                .and_then(|v| u8::try_from(v).ok())
+                .unwrap_or(0);
+
+            let throwing = value
+                .get("throwing")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let reached_base = value
+                .get("reached_base")
+                .and_then(|v| v.as_str())
+                .and_then(RunnerDest::parse)
+                .unwrap_or(RunnerDest::First);
+
+            crate::models::plate_appearance::PlateAppearanceOutcome::ReachedOnError {
+                fielder,
+                throwing,
+                reached_base,
+            }
+        }
+
         _ => crate::models::plate_appearance::PlateAppearanceOutcome::Out,
     };
 
     let seq: Vec<PlateAppearanceStep> = parse_pa_sequence(&row.pitches_sequence);
 
-    let pa = crate::models::plate_appearance::PlateAppearance {
+    crate::models::plate_appearance::PlateAppearance {
         inning: row.inning as u32,
         half: if row.half_inning == "Bottom" {
             HalfInning::Bottom
@@ -634,7 +890,196 @@ pub fn apply_plate_appearance_row(state: &mut GameState, row: &PlateAppearanceRo
         outcome,
         outs: row.outs as u8,
         runner_overrides: row.runner_overrides(),
-    };
+        double_play: row.double_play(),
+    }
+}
+
+// ─── Runner movement replay ──────────────────────────────────────────────────
+
+/// `advancement_type` values of runner movements that happen between
+/// pitches rather than at the end of a plate appearance: steals, the
+/// advances on a wild pitch, passed ball, defensive indifference or balk,
+/// and runners caught stealing or picked off. The runner the tie-break
+/// rule places on base at the start of an extra half is recorded the same
+/// way. On resume they are replayed one by one, interlaced with the PAs.
+pub const STANDALONE_TYPES: &[&str] = &[
+    "steal",
+    "wild_pitch",
+    "passed_ball",
+    "defensive_indifference",
+    "balk",
+    "caught_stealing",
+    "pickoff",
+    "pickoff_caught_stealing",
+    "tie_break",
+];
+
+/// `advancement_type` values of the per-runner segments of a composite
+/// defensive play (`5 l6, 3 64, 4 43`, `9 64, 1 o6 1b`, the tag-up on
+/// `sf8`). They are re-applied when the matching PA is replayed.
+pub const COMPOSITE_TYPES: &[&str] = &[
+    "ground_out",
+    "fly_out",
+    "line_out",
+    "infield_fly",
+    "unassisted_out",
+    "fielders_choice",
+    "sacrifice_fly",
+    "sacrifice_bunt",
+];
+
+/// Split runner_movements rows into (standalone, composite) for replay.
+///
+/// The remaining rows ("walk", "hit_by_pitch", "intentional_walk",
+/// "catcher_interference", "dropped_third_strike", "hit_auto",
+/// "hit_override", "error") are dropped: `apply_plate_appearance_row`
+/// already applies them and re-applying them would double-count scoring
+/// movements.
+pub fn split_replay_movements(
+    rows: Vec<RunnerMovementRow>,
+) -> (Vec<RunnerMovementRow>, Vec<RunnerMovementRow>) {
+    rows.into_iter()
+        .filter(|r| {
+            STANDALONE_TYPES.contains(&r.advancement_type.as_str())
+                || COMPOSITE_TYPES.contains(&r.advancement_type.as_str())
+        })
+        .partition(|r| STANDALONE_TYPES.contains(&r.advancement_type.as_str()))
+}
+
+/// Replay a persisted PA and the composite-play rows linked to it.
+///
+/// The composite rows (runner outs and FC-safe advances with
+/// `pa_seq == pa.seq`) are the replay mirror of the in-memory mutation
+/// performed by `apply_defensive_play_command` on the live path. Every
+/// runner-out is cleared before any FC-safe advance is placed, so that a
+/// runner advancing to a base just vacated by a runner who was put out
+/// sees an empty slot.
+pub fn replay_plate_appearance(
+    state: &mut GameState,
+    pa: &PlateAppearanceRow,
+    composite_movements: &[RunnerMovementRow],
+) {
+    apply_plate_appearance_row(state, pa);
+
+    for rm in composite_movements
+        .iter()
+        .filter(|r| r.pa_seq == Some(pa.seq) && r.is_out)
+    {
+        apply_composite_movement(state, rm);
+    }
+    for rm in composite_movements
+        .iter()
+        .filter(|r| r.pa_seq == Some(pa.seq) && !r.is_out)
+    {
+        apply_composite_movement(state, rm);
+    }
+    drop_departed_tie_break_runners(state);
+}
+
+/// Apply a standalone movement (steal, caught stealing, ...) to the state.
+/// Rows of any other type are ignored.
+pub fn apply_standalone_movement(state: &mut GameState, rm: &RunnerMovementRow) {
+    if !STANDALONE_TYPES.contains(&rm.advancement_type.as_str()) {
+        return;
+    }
+    // A row recorded before the first PA of a half (the tie-break runner)
+    // starts that half.
+    let half = HalfInning::from_str_loose(&rm.half_inning);
+    let row_key = (rm.inning as u32, half == HalfInning::Bottom);
+    if row_key > (state.inning, state.half == HalfInning::Bottom) {
+        state.inning = row_key.0;
+        state.half = half;
+        state.outs = 0;
+        state.on_1b = None;
+        state.on_2b = None;
+        state.on_3b = None;
+        state.tie_break_runners.clear();
+    }
+    let order = rm.batter_order;
+    if rm.advancement_type == "tie_break" {
+        state.tie_break_runners.push(order);
+    }
+    match rm.start_base.as_str() {
+        "1B" if state.on_1b == Some(order) => {
+            state.on_1b = None;
+        }
+
+        "2B" if state.on_2b == Some(order) => {
+            state.on_2b = None;
+        }
+
+        "3B" if state.on_3b == Some(order) => {
+            state.on_3b = None;
+        }
+
+        _ => {}
+    }
+    match rm.end_base.as_str() {
+        "1B" => state.on_1b = Some(order),
+        "2B" => state.on_2b = Some(order),
+        "3B" => state.on_3b = Some(order),
+        // A steal can be linked to the last PA of the previous half, so
+        // the run goes to the half recorded in the row.
+        "HOME" => add_run_for_row(state, rm),
+        // Caught stealing / pickoff. The next PA row realigns the outs
+        // anyway; counting here keeps the state right when the out is
+        // the last thing recorded before the resume. A third out ends
+        // the half: nobody is left on base.
+        "OUT" => {
+            state.outs = state.outs.saturating_add(1);
+            if state.outs >= 3 {
+                state.on_1b = None;
+                state.on_2b = None;
+                state.on_3b = None;
+            }
+        }
+        _ => {}
+    }
+    drop_departed_tie_break_runners(state);
+}
+
+/// Apply a composite defensive-play movement (runner-out, FC-safe
+/// advance, etc.) to the state.
+///
+/// The row's `start_base` is not used to clear the previous base: the
+/// slot is cleared from every base before the destination is applied,
+/// matching `place_runner_on_base` in apply.rs.
+pub fn apply_composite_movement(state: &mut GameState, rm: &RunnerMovementRow) {
+    let order = rm.batter_order;
+
+    if state.on_1b == Some(order) {
+        state.on_1b = None;
+    }
+    if state.on_2b == Some(order) {
+        state.on_2b = None;
+    }
+    if state.on_3b == Some(order) {
+        state.on_3b = None;
+    }
+
+    // Runner-out row: the slot is cleared and that is all.
+    if rm.is_out {
+        return;
+    }
+
+    match rm.end_base.as_str() {
+        "1B" => state.on_1b = Some(order),
+        "2B" => state.on_2b = Some(order),
+        "3B" => state.on_3b = Some(order),
+        "HOME" => add_run_for_row(state, rm),
+        _ => {}
+    }
+}
 
-    apply_plate_appearance(state, &pa);
+/// Credit a run to the inning/half recorded in the row rather than
+/// `state.inning`/`state.half`: the row is the authoritative source for
+/// when the movement was recorded.
+fn add_run_for_row(state: &mut GameState, rm: &RunnerMovementRow) {
+    let saved_inning = state.inning;
+    let saved_half = state.half;
+    state.inning = rm.inning as u32;
+    state.half = HalfInning::from_str_loose(&rm.half_inning);
+    add_runs_to_score(state, 1);
+    state.inning = saved_inning;
+    state.half = saved_half;
 }
@@ -4,11 +4,15 @@
 //! duplicated across `play_ball_reducer.rs` and `play_ball_apply.rs`.
 //! It provides a single source of truth for:
 //! - Hit advancement (with optional overrides)
+//! - Reached-on-error advancement (same rules as a hit, no hit credited)
 //! - Walk advancement (forced advancement)
+//! - Dropped-third-strike advancement (forced advancement with overrides)
 //! - Movement record generation for DB persistence
 
 use crate::db::runner_movements::RunnerMovementInsert;
+use crate::engine::game_end::{half_run_limit, walk_off_run_limit};
 use crate::models::game_state::{BatterOrder, GameState};
+use crate::models::plate_appearance::HitModifier;
 use crate::models::runner::{RunnerDest, RunnerOverride};
 use crate::models::types::HalfInning;
 use std::collections::HashMap;
@@ -22,7 +26,31 @@ fn ensure_inning(vec: &mut Vec<u16>, inning: u32) {
     }
 }
 
+///
+/// In the bottom of the last inning only the runs needed to win count (see
+/// [`walk_off_run_limit`]), and never more than the run cap of the half
+/// (see [`half_run_limit`]).
 pub fn add_runs_to_score(state: &mut GameState, runs: u32) {
+    count_runs(state, runs, true);
+}
+
+/// Add runs up to the run cap of the half and, with `walk_off`, up to the
+/// walk-off limit; returns how many were counted.
+fn count_runs(state: &mut GameState, runs: u32, walk_off: bool) -> u32 {
+    let walk_off_limit = if walk_off {
+        walk_off_run_limit(state)
+    } else {
+        None
+    };
+    let runs = [walk_off_limit, half_run_limit(state)]
+        .into_iter()
+        .flatten()
+        .fold(runs, u32::min);
+    credit_runs(state, runs);
+    runs
+}
+
+fn credit_runs(state: &mut GameState, runs: u32) {
     if runs == 0 {
         return;
     }
@@ -42,8 +70,64 @@ pub fn add_runs_to_score(state: &mut GameState, runs: u32) {
     }
 }
 
+/// Charge `errors` to the fielding team: the home side fields in the top
+/// half, the away side in the bottom half.
+pub fn add_errors_to_score(state: &mut GameState, errors: u16) {
+    match state.half {
+        HalfInning::Top => state.score.home_errors += errors,
+        HalfInning::Bottom => state.score.away_errors += errors,
+    }
+}
+
+/// Rewrite the rows of a walk-off play so only `counted` runners score,
+/// lead runners first; `counted` is decremented by the rows kept. The others,
+/// and the batter, are credited with as many bases as the winning runner
+/// advanced (Rule 9.06(f)), never past third.
+pub fn hold_uncounted_runners(movements: &mut [RunnerMovementInsert], counted: &mut u32) {
+    let mut winning_advance = 1;
+    for m in movements.iter_mut().filter(|m| m.scored) {
+        if *counted > 0 {
+            *counted -= 1;
+            winning_advance = 4 - base_num(m.start_base);
+            continue;
+        }
+        m.scored = false;
+        m.end_base = base_str((base_num(m.start_base) + winning_advance).min(3));
+    }
+}
+
+/// Mark the runs of the runners placed by the tie-break rule as unearned:
+/// they did not reach base by batting.
+pub fn mark_tie_break_runs(state: &GameState, movements: &mut [RunnerMovementInsert]) {
+    for m in movements.iter_mut().filter(|m| {
+        m.scored && m.start_base != "BAT" && state.tie_break_runners.contains(&m.batter_order)
+    }) {
+        m.is_earned = false;
+    }
+}
+
+/// Forget the tie-break runners who left the bases, by scoring or being
+/// put out. Their batting slot may come around and reach base again in
+/// the same half: that runner reached by batting.
+pub fn drop_departed_tie_break_runners(state: &mut GameState) {
+    let on_base = [state.on_1b, state.on_2b, state.on_3b];
+    state
+        .tie_break_runners
+        .retain(|order| on_base.contains(&Some(*order)));
+}
+
 // ─── Base helpers ─────────────────────────────────────────────────────────────
 
+fn base_num(b: &str) -> u8 {
+    match b {
+        "1B" => 1,
+        "2B" => 2,
+        "3B" => 3,
+        "HOME" => 4,
+        _ => 0,
+    }
+}
+
 fn base_str(b: u8) -> &'static str {
     match b {
         1 => "1B",
@@ -117,11 +201,50 @@ pub struct HitResult {
 /// The `batter_order` is who just hit; they go to `bases` base by default.
 /// Each `RunnerOverride` explicitly places a runner already on base.
 /// Any runner NOT mentioned in overrides advances automatically (`current_base + bases`).
+/// On a ground-rule double the overrides are ignored: see
+/// [`effective_hit_overrides`].
 pub fn apply_hit(
+    state: &mut GameState,
+    batter_order: BatterOrder,
+    bases: u8,
+    modifier: Option<HitModifier>,
+    overrides: &[RunnerOverride],
+) -> HitResult {
+    let overrides = effective_hit_overrides(modifier, overrides);
+    // Every run counts on a home run out of the park, walk-off or not (the
+    // run cap of the half still applies).
+    let all_runs_count = bases == 4 && modifier != Some(HitModifier::InsideThePark);
+    let result = batted_ball_advance(state, batter_order, bases, overrides, all_runs_count);
+
+    // Hits counter
+    match state.half {
+        HalfInning::Top => state.score.away_hits += 1,
+        HalfInning::Bottom => state.score.home_hits += 1,
+    }
+
+    result
+}
+
+/// Overrides that take effect on a hit. A ground-rule double awards the
+/// batter and every runner exactly two bases, so none apply there.
+pub fn effective_hit_overrides(
+    modifier: Option<HitModifier>,
+    overrides: &[RunnerOverride],
+) -> &[RunnerOverride] {
+    match modifier {
+        Some(HitModifier::GroundRule) => &[],
+        _ => overrides,
+    }
+}
+
+/// Advance the batter `bases` bases and every runner by the same amount
+/// unless overridden, without crediting a hit.
+fn batted_ball_advance(
     state: &mut GameState,
     batter_order: BatterOrder,
     bases: u8,
     overrides: &[RunnerOverride],
+    all_runs_count: bool,
 ) -> HitResult {
     let half_str = state.half.as_str();
     let inning = state.inning;
@@ -166,13 +289,7 @@ pub fn apply_hit(
         &mut state.on_3b,
     );
 
-    add_runs_to_score(state, runs_scored);
-
-    // Hits counter
-    match state.half {
-        HalfInning::Top => state.score.away_hits += 1,
-        HalfInning::Bottom => state.score.home_hits += 1,
-    }
+    let runs_scored = count_runs(state, runs_scored, !all_runs_count);
 
     // Build movement rows
     let movements = build_movements_from_snapshot(
@@ -266,21 +383,7 @@ pub fn apply_walk(state: &mut GameState, batter_order: BatterOrder) -> WalkResul
         ));
         state.on_3b = None;
 
-        runs_scored += 1;
-        match state.half {
-            HalfInning::Top => {
-                state.score.away = state.score.away.saturating_add(1);
-                ensure_inning(&mut state.score.away_innings, state.inning);
-                let idx = (state.inning - 1) as usize;
-                state.score.away_innings[idx] = state.score.away_innings[idx].saturating_add(1);
-            }
-            HalfInning::Bottom => {
-                state.score.home = state.score.home.saturating_add(1);
-                ensure_inning(&mut state.score.home_innings, state.inning);
-                let idx = (state.inning - 1) as usize;
-                state.score.home_innings[idx] = state.score.home_innings[idx].saturating_add(1);
-            }
-        }
+        runs_scored += count_runs(state, 1, true);
     }
 
     // Forced advancements
@@ -318,6 +421,136 @@ pub fn apply_walk(state: &mut GameState, batter_order: BatterOrder) -> WalkResul
     }
 }
 
+// ─── Forced advancement ──────────────────────────────────────────────────────
+
+/// Where a runner on `base` ends up when the batter takes `batter_base`
+/// (`0` when they do not reach) and nobody else moves unless forced: a
+/// runner standing on or behind the base taken by the batter, or by the
+/// runner forced before them, is pushed one base further.
+fn forced_dest(snapshot: &BaseSnapshot, base: u8, batter_base: u8) -> u8 {
+    let mut taken = batter_base;
+    for (_, current) in snapshot.runners_descending().into_iter().rev() {
+        let dest = if batter_base > 0 && current <= taken {
+            taken + 1
+        } else {
+            current
+        };
+        if current == base {
+            return dest;
+        }
+        taken = dest;
+    }
+    base
+}
+
+/// Apply a play where the batter takes `batter_base` and the runners move
+/// only if forced: a dropped third strike (`batter_base` 1, or 0 when the
+/// batter is thrown out) or a batter reaching on an error. Runner
+/// overrides win over the automatic placement either way (`kwp, 3 sc`,
+/// `e6, 3 sc`).
+pub fn apply_forced_advance(
+    state: &mut GameState,
+    batter_order: BatterOrder,
+    batter_base: u8,
+    overrides: &[RunnerOverride],
+) -> u32 {
+    let snapshot = BaseSnapshot::from_state(state);
+    state.on_1b = None;
+    state.on_2b = None;
+    state.on_3b = None;
+
+    let override_map: HashMap<BatterOrder, RunnerDest> =
+        overrides.iter().map(|r| (r.order, r.dest)).collect();
+
+    let mut runs_scored: u32 = 0;
+    for (runner, current_base) in snapshot.runners_descending() {
+        let auto_dest = forced_dest(&snapshot, current_base, batter_base);
+        let (dest, _is_override) = resolve_dest(runner, auto_dest, &override_map);
+        place_runner(
+            runner,
+            dest,
+            &mut runs_scored,
+            &mut state.on_1b,
+            &mut state.on_2b,
+            &mut state.on_3b,
+        );
+    }
+    if batter_base > 0 {
+        place_runner(
+            batter_order,
+            batter_base,
+            &mut runs_scored,
+            &mut state.on_1b,
+            &mut state.on_2b,
+            &mut state.on_3b,
+        );
+    }
+
+    count_runs(state, runs_scored, true)
+}
+
+/// Movement rows for [`apply_forced_advance`], built from the
+/// pre-mutation snapshot. Runners who stay put get no row; runners moved
+/// by an override attributed to an error are tagged `"error"`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_forced_movements(
+    snapshot: &BaseSnapshot,
+    batter_order: BatterOrder,
+    batter_base: u8,
+    overrides: &[RunnerOverride],
+    advancement_type: &'static str,
+    batter_earned: bool,
+    inning: u32,
+    half_str: &str,
+) -> Vec<RunnerMovementInsert> {
+    let override_map: HashMap<BatterOrder, RunnerDest> =
+        overrides.iter().map(|r| (r.order, r.dest)).collect();
+
+    let mut movements = Vec::with_capacity(4);
+    for (order, current_base) in snapshot.runners_descending() {
+        let auto_dest = forced_dest(snapshot, current_base, batter_base);
+        let (dest, _is_override) = resolve_dest(order, auto_dest, &override_map);
+        if dest == current_base {
+            continue;
+        }
+        let (end, scored) = effective_end(dest);
+        let adv = if overrides
+            .iter()
+            .any(|o| o.order == order && o.error.is_some())
+        {
+            "error"
+        } else {
+            advancement_type
+        };
+        movements.push(make_movement(
+            order,
+            base_str(current_base),
+            end,
+            scored,
+            adv,
+            inning,
+            half_str,
+        ));
+    }
+
+    if batter_base > 0 {
+        let (end, scored) = effective_end(batter_base);
+        let mut batter = make_movement(
+            batter_order,
+            "BAT",
+            end,
+            scored,
+            advancement_type,
+            inning,
+            half_str,
+        );
+        batter.is_earned = batter_earned;
+        movements.push(batter);
+    }
+
+    movements
+}
+
 // ─── Base snapshot ─────────────────────────────────────────────────────────────
 
 /// Snapshot of base occupancy before a play mutates the state.
@@ -380,6 +613,7 @@ fn make_movement(
         is_out: false,
         scored,
         is_earned: true,
+        fielders: None,
     }
 }
 
@@ -13,8 +13,11 @@
 //!   - `2 64, o6`
 //!   - `l6, 2 64, 3 43`
 //!   - `3 o5 2b`
+//! - sacrifices (`sf8`, `sh13`), which reuse the fly-out / ground-out
+//!   shapes and carry the scoring designation on the play itself
 
 use crate::RunnerDest;
+use crate::models::runner::RunnerOverride;
 use std::error::Error;
 use std::fmt;
 
@@ -152,6 +155,19 @@ pub enum DefensiveOutKind {
     },
 }
 
+impl DefensiveOutKind {
+    /// Fielders who handled the ball on this out, putout last.
+    pub fn fielders(&self) -> Vec<u8> {
+        match self {
+            Self::GroundOut { sequence } => sequence.fielders().to_vec(),
+            Self::UnassistedOut { fielder }
+            | Self::FlyOut { fielder, .. }
+            | Self::LineOut { fielder }
+            | Self::InfieldFly { fielder } => vec![*fielder],
+        }
+    }
+}
+
 /// Represents one defensive out recorded on either the batter or a runner.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DefensiveOutRecord {
@@ -167,12 +183,30 @@ pub struct FielderChoiceAdvance {
     pub reached_base: RunnerDest,
 }
 
+/// Scoring designation for a batter out that advanced the runners on
+/// purpose. Neither counts as an at-bat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sacrifice {
+    /// `sf<n>`: the batter flies out and a runner tags up and scores.
+    Fly,
+    /// `sh<seq>`: the batter bunts and is thrown out while runners advance.
+    Bunt,
+}
+
 /// Represents a full defensive play that may contain multiple outs
 /// and optional safe advances.
+///
+/// On a sacrifice, runners not named in `runner_advances` move by the
+/// default rule: on a fly only the runner on third scores, on a bunt
+/// every runner advances one base.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DefensivePlayCommand {
     pub outs: Vec<DefensiveOutRecord>,
     pub safe_advances: Vec<FielderChoiceAdvance>,
+    /// Set when the batter's out is a sacrifice fly or bunt.
+    pub sacrifice: Option<Sacrifice>,
+    /// Explicit runner destinations on a sacrifice (`sf8, 2 3b`).
+    pub runner_advances: Vec<RunnerOverride>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -307,6 +341,8 @@ pub fn parse_defensive_play_command(
     Ok(DefensivePlayCommand {
         outs,
         safe_advances,
+        sacrifice: None,
+        runner_advances: vec![],
     })
 }
 
@@ -0,0 +1,619 @@
+//! In-game substitutions: pitching changes, pinch hitters and runners,
+//! defensive substitutions and position changes.
+//!
+//! The engine loop loads both teams' active lineups and rosters, and
+//! [`plan_substitutions`] plays a whole substitution line on an in-memory
+//! copy. That is what makes a double switch (`7 sub 34 p, 9 sub 22 lf`)
+//! possible: the lineup only has to be consistent once the line is done.
+//! Rules:
+//!
+//! - the incoming player must be on the team's roster, not already in the
+//!   lineup and not removed earlier in the game (no re-entry);
+//! - pinch hitters and runners take the replaced player's batting slot
+//!   and defensive position, so a pinch hitter for the DH becomes the DH;
+//! - with a DH the pitcher does not bat: a fielder cannot move to the
+//!   mound from the batting order, and the DH cannot take the field;
+//! - after a change on the fielding team, each of the nine defensive
+//!   positions is held by exactly one player.
+
+use crate::db::lineups::{LineupEntry, RosterPlayer};
+use crate::engine::commands::types::EngineCommand;
+use crate::models::events::{DomainEvent, SubstitutionData, SubstitutionKind};
+use crate::models::game_state::GameState;
+use crate::models::types::Position;
+
+/// One team's side of a substitution line.
+#[derive(Debug, Clone)]
+pub struct TeamLineup {
+    pub team_id: i64,
+    /// Active lineup spots.
+    pub entries: Vec<LineupEntry>,
+    /// Every active roster player, with the jersey worn in this game.
+    pub roster: Vec<RosterPlayer>,
+    /// Players already replaced in this game.
+    pub removed: Vec<i64>,
+}
+
+/// A write to `game_lineups`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineupChange {
+    /// `player_in_id` replaces the active player in `batting_order`.
+    Substitute {
+        team_id: i64,
+        batting_order: u8,
+        player_in_id: i64,
+        position: Position,
+    },
+    /// The active player in `batting_order` moves to `position`.
+    Move {
+        team_id: i64,
+        batting_order: u8,
+        position: Position,
+    },
+}
+
+/// Outcome of a valid substitution line.
+#[derive(Debug, Clone, Default)]
+pub struct SubstitutionPlan {
+    pub changes: Vec<LineupChange>,
+    /// One `PlayerSubstituted` event per change, with its log line.
+    pub persisted: Vec<(DomainEvent, String)>,
+    /// Set when the fielding team ends the line with a new pitcher. The
+    /// lineup is the record of who pitches, so this is applied to the
+    /// game state but not persisted.
+    pub pitcher_changed: Option<DomainEvent>,
+}
+
+/// Check a line of substitution commands against both lineups. Errors
+/// are user-facing and leave nothing applied.
+pub fn plan_substitutions(
+    state: &GameState,
+    mut batting: TeamLineup,
+    mut fielding: TeamLineup,
+    cmds: &[EngineCommand],
+) -> Result<SubstitutionPlan, String> {
+    let mut plan = SubstitutionPlan::default();
+    let mut fielding_changed = false;
+
+    for cmd in cmds {
+        match cmd {
+            EngineCommand::PitchingChange { jersey } => {
+                let order = fielding
+                    .entries
+                    .iter()
+                    .find(|e| e.position == Position::Pitcher)
+                    .map(|e| e.batting_order)
+                    .ok_or("The fielding team has no pitcher in its lineup.")?;
+                substitute(
+                    &mut fielding,
+                    order,
+                    *jersey,
+                    Position::Pitcher,
+                    SubstitutionKind::PitchingChange,
+                    &mut plan,
+                )?;
+                fielding_changed = true;
+            }
+            EngineCommand::PinchHitter { jersey } => {
+                let order = state.current_batter_order.ok_or("No batter is up.")?;
+                let position = entry(&batting, order)?.position;
+                substitute(
+                    &mut batting,
+                    order,
+                    *jersey,
+                    position,
+                    SubstitutionKind::PinchHitter,
+                    &mut plan,
+                )?;
+            }
+            EngineCommand::PinchRunner { order, jersey } => {
+                let position = entry(&batting, *order)?.position;
+                substitute(
+                    &mut batting,
+                    *order,
+                    *jersey,
+                    position,
+                    SubstitutionKind::PinchRunner,
+                    &mut plan,
+                )?;
+            }
+            EngineCommand::DefensiveSubstitution {
+                order,
+                jersey,
+                position,
+            } => {
+                check_dh_rules(&fielding, *order, *position)?;
+                substitute(
+                    &mut fielding,
+                    *order,
+                    *jersey,
+                    *position,
+                    SubstitutionKind::Defensive,
+                    &mut plan,
+                )?;
+                fielding_changed = true;
+            }
+            EngineCommand::PositionChange { order, position } => {
+                check_dh_rules(&fielding, *order, *position)?;
+                move_player(&mut fielding, *order, *position, &mut plan)?;
+                fielding_changed = true;
+            }
+            other => return Err(format!("{other:?} is not a substitution.")),
+        }
+    }
+
+    if fielding_changed {
+        check_defense(&fielding)?;
+        if let Some(p) = fielding
+            .entries
+            .iter()
+            .find(|e| e.position == Position::Pitcher)
+            && state.current_pitcher_id != Some(p.player.id)
+        {
+            plan.pitcher_changed = Some(DomainEvent::PitcherChanged {
+                pitcher_id: p.player.id,
+                pitcher_jersey_no: p.player.jersey_no,
+                pitcher_first_name: p.player.first_name.clone(),
+                pitcher_last_name: p.player.last_name.clone(),
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+fn label(p: &RosterPlayer) -> String {
+    format!("#{} {} {}", p.jersey_no, p.first_name, p.last_name)
+}
+
+fn entry(team: &TeamLineup, order: u8) -> Result<&LineupEntry, String> {
+    team.entries
+        .iter()
+        .find(|e| e.batting_order == order)
+        .ok_or_else(|| format!("No player in batting slot {order}."))
+}
+
+fn substitute(
+    team: &mut TeamLineup,
+    order: u8,
+    jersey: u8,
+    position: Position,
+    kind: SubstitutionKind,
+    plan: &mut SubstitutionPlan,
+) -> Result<(), String> {
+    let incoming = team
+        .roster
+        .iter()
+        .find(|p| p.jersey_no == i32::from(jersey))
+        .cloned()
+        .ok_or_else(|| format!("No player #{jersey} on the roster."))?;
+    let name = label(&incoming);
+
+    if team.removed.contains(&incoming.id) {
+        return Err(format!("{name} already left the game and cannot re-enter."));
+    }
+    if let Some(e) = team.entries.iter().find(|e| e.player.id == incoming.id) {
+        return Err(
+            if kind == SubstitutionKind::PitchingChange && e.position == Position::Pitcher {
+                format!("{name} is already pitching.")
+            } else {
+                format!(
+                    "{name} is already in the lineup (slot {}, {}).",
+                    e.batting_order, e.position
+                )
+            },
+        );
+    }
+
+    let idx = team
+        .entries
+        .iter()
+        .position(|e| e.batting_order == order)
+        .ok_or_else(|| format!("No player in batting slot {order}."))?;
+    let outgoing = std::mem::replace(&mut team.entries[idx].player, incoming.clone());
+    let previous_position = std::mem::replace(&mut team.entries[idx].position, position);
+    team.removed.push(outgoing.id);
+
+    let out_name = label(&outgoing);
+    let msg = match kind {
+        SubstitutionKind::PitchingChange => {
+            format!("🔄 Pitching change: {name} replaces {out_name}.")
+        }
+        SubstitutionKind::PinchHitter => format!("🔄 Pinch hitter: {name} bats for {out_name}."),
+        SubstitutionKind::PinchRunner => format!("🔄 Pinch runner: {name} runs for {out_name}."),
+        SubstitutionKind::Defensive | SubstitutionKind::PositionChange => format!(
+            "🔄 Defensive substitution: {name} replaces {out_name} in slot {order} ({position})."
+        ),
+    };
+
+    plan.changes.push(LineupChange::Substitute {
+        team_id: team.team_id,
+        batting_order: order,
+        player_in_id: incoming.id,
+        position,
+    });
+    plan.persisted.push((
+        DomainEvent::PlayerSubstituted(SubstitutionData {
+            team_id: team.team_id,
+            kind,
+            batting_order: order,
+            player_out_id: Some(outgoing.id),
+            player_in_id: incoming.id,
+            player_in_jersey_no: incoming.jersey_no,
+            player_in_first_name: incoming.first_name,
+            player_in_last_name: incoming.last_name,
+            position,
+            previous_position: Some(previous_position),
+        }),
+        msg,
+    ));
+    Ok(())
+}
+
+fn move_player(
+    team: &mut TeamLineup,
+    order: u8,
+    position: Position,
+    plan: &mut SubstitutionPlan,
+) -> Result<(), String> {
+    let idx = team
+        .entries
+        .iter()
+        .position(|e| e.batting_order == order)
+        .ok_or_else(|| format!("No player in batting slot {order}."))?;
+    let from = team.entries[idx].position;
+    if from == position {
+        return Err(format!(
+            "{} is already playing {position}.",
+            label(&team.entries[idx].player)
+        ));
+    }
+    team.entries[idx].position = position;
+
+    let player = &team.entries[idx].player;
+    plan.changes.push(LineupChange::Move {
+        team_id: team.team_id,
+        batting_order: order,
+        position,
+    });
+    plan.persisted.push((
+        DomainEvent::PlayerSubstituted(SubstitutionData {
+            team_id: team.team_id,
+            kind: SubstitutionKind::PositionChange,
+            batting_order: order,
+            player_out_id: None,
+            player_in_id: player.id,
+            player_in_jersey_no: player.jersey_no,
+            player_in_first_name: player.first_name.clone(),
+            player_in_last_name: player.last_name.clone(),
+            position,
+            previous_position: Some(from),
+        }),
+        format!(
+            "🔄 Position change: {} moves from {from} to {position}.",
+            label(player)
+        ),
+    ));
+    Ok(())
+}
+
+/// DH rules for a fielding-team change of batting slot `order`. A team
+/// uses the DH when its pitcher sits in spot 10, outside the batting
+/// order.
+fn check_dh_rules(team: &TeamLineup, order: u8, position: Position) -> Result<(), String> {
+    let current = entry(team, order)?.position;
+    let uses_dh = team.entries.iter().any(|e| e.batting_order == 10);
+
+    if position == Position::DesignatedHitter && current != Position::DesignatedHitter {
+        return Err(if uses_dh {
+            "Only the designated hitter's batting slot can hold the DH.".to_string()
+        } else {
+            "This lineup does not use a DH.".to_string()
+        });
+    }
+    if current == Position::DesignatedHitter && position != Position::DesignatedHitter {
+        return Err(format!(
+            "The DH in slot {order} cannot take the field: the team would lose the DH."
+        ));
+    }
+    if uses_dh && position == Position::Pitcher {
+        return Err(
+            "With a DH the pitcher does not bat: use `pc <jersey>` to change pitchers.".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Every defensive position from pitcher to right field is held by
+/// exactly one player.
+fn check_defense(team: &TeamLineup) -> Result<(), String> {
+    for n in 1..=9 {
+        let position = Position::from_number(n).expect("1..=9 are fielding positions");
+        let holders: Vec<String> = team
+            .entries
+            .iter()
+            .filter(|e| e.position == position)
+            .map(|e| label(&e.player))
+            .collect();
+        match holders.len() {
+            0 => return Err(format!("No one is playing {position} after this change.")),
+            1 => {}
+            _ => {
+                return Err(format!(
+                    "{} would all be playing {position}.",
+                    holders.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: i64, jersey_no: i32) -> RosterPlayer {
+        RosterPlayer {
+            id,
+            jersey_no,
+            first_name: "P".to_string(),
+            last_name: format!("{jersey_no}"),
+        }
+    }
+
+    /// Nine fielders batting in position order (the pitcher bats ninth
+    /// and wears #1x, slot n wears #n0) plus a bench of #51..#54. With
+    /// `dh`, slot 9 is the DH (#90) and the pitcher (#19) sits in spot 10.
+    fn team(team_id: i64, dh: bool) -> TeamLineup {
+        let base = team_id * 100;
+        let mut entries: Vec<LineupEntry> = (1..=9u8)
+            .map(|order| {
+                let position = match order {
+                    9 => Position::Pitcher,
+                    n => Position::from_number(n + 1).unwrap(),
+                };
+                LineupEntry {
+                    player: player(base + i64::from(order), i32::from(order) * 10),
+                    batting_order: order,
+                    position,
+                }
+            })
+            .collect();
+        if dh {
+            entries[8].position = Position::DesignatedHitter;
+            entries.push(LineupEntry {
+                player: player(base + 10, 19),
+                batting_order: 10,
+                position: Position::Pitcher,
+            });
+        }
+        let mut roster: Vec<RosterPlayer> = entries.iter().map(|e| e.player.clone()).collect();
+        roster.extend((51..=54).map(|j| player(base + i64::from(j), j)));
+        TeamLineup {
+            team_id,
+            entries,
+            roster,
+            removed: Vec::new(),
+        }
+    }
+
+    fn state(batter: u8, pitcher_id: i64) -> GameState {
+        let mut st = GameState::new();
+        st.current_batter_order = Some(batter);
+        st.current_pitcher_id = Some(pitcher_id);
+        st
+    }
+
+    fn plan(fielding: TeamLineup, cmds: &[EngineCommand]) -> Result<SubstitutionPlan, String> {
+        plan_substitutions(&state(3, 209), team(1, false), fielding, cmds)
+    }
+
+    #[test]
+    fn pinch_hitter_takes_slot_and_position() {
+        let p = plan(team(2, false), &[EngineCommand::PinchHitter { jersey: 54 }]).unwrap();
+        assert_eq!(
+            p.changes,
+            vec![LineupChange::Substitute {
+                team_id: 1,
+                batting_order: 3,
+                player_in_id: 154,
+                position: Position::SecondBase,
+            }]
+        );
+        assert!(matches!(
+            &p.persisted[0].0,
+            DomainEvent::PlayerSubstituted(d)
+                if d.kind == SubstitutionKind::PinchHitter && d.player_out_id == Some(103)
+        ));
+        assert!(p.pitcher_changed.is_none());
+    }
+
+    #[test]
+    fn pitching_change_brings_in_a_new_pitcher() {
+        let p = plan(
+            team(2, false),
+            &[EngineCommand::PitchingChange { jersey: 51 }],
+        )
+        .unwrap();
+        assert_eq!(
+            p.changes,
+            vec![LineupChange::Substitute {
+                team_id: 2,
+                batting_order: 9,
+                player_in_id: 251,
+                position: Position::Pitcher,
+            }]
+        );
+        assert!(matches!(
+            p.pitcher_changed,
+            Some(DomainEvent::PitcherChanged {
+                pitcher_id: 251,
+                ..
+            })
+        ));
+
+        let err = plan(
+            team(2, false),
+            &[EngineCommand::PitchingChange { jersey: 90 }],
+        )
+        .unwrap_err();
+        assert!(err.contains("already pitching"), "{err}");
+        let err = plan(
+            team(2, false),
+            &[EngineCommand::PitchingChange { jersey: 10 }],
+        )
+        .unwrap_err();
+        assert!(err.contains("already in the lineup"), "{err}");
+        let err = plan(
+            team(2, false),
+            &[EngineCommand::PitchingChange { jersey: 77 }],
+        )
+        .unwrap_err();
+        assert!(err.contains("No player #77"), "{err}");
+    }
+
+    #[test]
+    fn removed_players_cannot_reenter() {
+        let mut fielding = team(2, false);
+        fielding.removed.push(254);
+        let err = plan(fielding, &[EngineCommand::PitchingChange { jersey: 54 }]).unwrap_err();
+        assert!(err.contains("cannot re-enter"), "{err}");
+
+        // Replaced earlier on the same line.
+        let err = plan(
+            team(2, false),
+            &[
+                EngineCommand::DefensiveSubstitution {
+                    order: 6,
+                    jersey: 54,
+                    position: Position::LeftField,
+                },
+                EngineCommand::DefensiveSubstitution {
+                    order: 7,
+                    jersey: 60,
+                    position: Position::CenterField,
+                },
+            ],
+        )
+        .unwrap_err();
+        assert!(err.contains("cannot re-enter"), "{err}");
+    }
+
+    #[test]
+    fn double_switch_on_one_line() {
+        // New pitcher bats sixth, new left fielder bats ninth.
+        let p = plan(
+            team(2, false),
+            &[
+                EngineCommand::DefensiveSubstitution {
+                    order: 6,
+                    jersey: 51,
+                    position: Position::Pitcher,
+                },
+                EngineCommand::DefensiveSubstitution {
+                    order: 9,
+                    jersey: 52,
+                    position: Position::LeftField,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(p.changes.len(), 2);
+        assert_eq!(p.persisted.len(), 2);
+        assert!(matches!(
+            p.pitcher_changed,
+            Some(DomainEvent::PitcherChanged {
+                pitcher_id: 251,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn defense_must_stay_complete() {
+        let swap = |a: u8, pa: Position, b: u8, pb: Position| {
+            vec![
+                EngineCommand::PositionChange {
+                    order: a,
+                    position: pa,
+                },
+                EngineCommand::PositionChange {
+                    order: b,
+                    position: pb,
+                },
+            ]
+        };
+        // Slot 3 plays 2B, slot 5 plays SS.
+        let cmds = swap(3, Position::Shortstop, 5, Position::SecondBase);
+        assert!(plan(team(2, false), &cmds).is_ok());
+
+        let err = plan(team(2, false), &cmds[..1]).unwrap_err();
+        assert!(err.contains("No one is playing 2B"), "{err}");
+    }
+
+    #[test]
+    fn dh_rules() {
+        // Slot 9 is the DH; the pitcher sits in spot 10.
+        let p = plan(
+            team(2, true),
+            &[EngineCommand::PitchingChange { jersey: 51 }],
+        )
+        .unwrap();
+        assert!(matches!(
+            p.changes[0],
+            LineupChange::Substitute {
+                batting_order: 10,
+                ..
+            }
+        ));
+
+        let err = plan(
+            team(2, true),
+            &[EngineCommand::PositionChange {
+                order: 9,
+                position: Position::LeftField,
+            }],
+        )
+        .unwrap_err();
+        assert!(err.contains("lose the DH"), "{err}");
+
+        let err = plan(
+            team(2, true),
+            &[EngineCommand::DefensiveSubstitution {
+                order: 3,
+                jersey: 54,
+                position: Position::Pitcher,
+            }],
+        )
+        .unwrap_err();
+        assert!(err.contains("pitcher does not bat"), "{err}");
+
+        let err = plan(
+            team(2, false),
+            &[EngineCommand::DefensiveSubstitution {
+                order: 3,
+                jersey: 54,
+                position: Position::DesignatedHitter,
+            }],
+        )
+        .unwrap_err();
+        assert!(err.contains("does not use a DH"), "{err}");
+
+        // A pinch hitter for the DH becomes the DH.
+        let p = plan_substitutions(
+            &state(9, 109),
+            team(1, true),
+            team(2, true),
+            &[EngineCommand::PinchHitter { jersey: 54 }],
+        )
+        .unwrap();
+        assert!(matches!(
+            p.changes[0],
+            LineupChange::Substitute {
+                position: Position::DesignatedHitter,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,385 @@
+//! `undo [n]`: take back the last plate appearances.
+//!
+//! Everything recorded from the first undone PA onward goes with it: the
+//! PA rows, their runner movements, the in-pitch movements and the game
+//! events logged after them. Lineup changes and status changes carried by
+//! those events are reverted. The at-bat draft is reset to the first
+//! undone PA's batter and pitcher with the count they had before the
+//! play, so the engine can rebuild `GameState` through the regular resume
+//! replay and the scorer re-enters the play.
+
+use crate::db::at_bat_draft::upsert_at_bat_draft;
+use crate::db::game_events::{GameEventRow, delete_game_events_from, list_game_events};
+use crate::db::game_queries::set_game_status;
+use crate::db::lineups::{revert_substitution, set_defensive_position};
+use crate::db::plate_appearances::{
+    PlateAppearanceRow, delete_plate_appearances_from, list_plate_appearances,
+};
+use crate::db::runner_movements::delete_runner_movements_from;
+use crate::engine::play_ball::parse_pa_sequence;
+use crate::engine::reducer::apply_domain_event;
+use crate::models::events::DomainEvent;
+use crate::models::game_state::GameState;
+use crate::models::plate_appearance::PlateAppearanceStep;
+use crate::models::types::{GameStatus, HalfInning, PitchCount};
+use rusqlite::Connection;
+
+/// What an undo removes, loaded before asking the scorer to confirm.
+#[derive(Debug, Clone)]
+pub struct UndoPlan {
+    /// The undone plate appearances, oldest first.
+    pub plate_appearances: Vec<PlateAppearanceRow>,
+    /// Game events logged after the first undone PA, oldest first.
+    pub events: Vec<GameEventRow>,
+}
+
+impl UndoPlan {
+    fn first(&self) -> &PlateAppearanceRow {
+        &self.plate_appearances[0]
+    }
+}
+
+/// Load the last `count` plate appearances of the game and the events
+/// logged after them.
+pub fn plan_undo(conn: &Connection, game_pk: i64, count: u8) -> Result<UndoPlan, String> {
+    let pas = list_plate_appearances(conn, game_pk)
+        .map_err(|e| format!("Failed to load plate appearances: {e}"))?;
+    let count = usize::from(count);
+    if pas.is_empty() {
+        return Err("Nothing to undo: no plate appearance recorded yet.".to_string());
+    }
+    if count > pas.len() {
+        return Err(format!(
+            "Cannot undo {count} plate appearances: only {} recorded.",
+            pas.len()
+        ));
+    }
+
+    let plate_appearances = pas[pas.len() - count..].to_vec();
+    let from_seq = plate_appearances[0].seq;
+    let events = list_game_events(conn, game_pk)
+        .map_err(|e| format!("Failed to load game events: {e}"))?
+        .into_iter()
+        .filter(|ev| ev.pa_seq.is_some_and(|seq| seq >= from_seq))
+        .collect();
+
+    Ok(UndoPlan {
+        plate_appearances,
+        events,
+    })
+}
+
+/// Delete everything in `plan` in one transaction, revert the lineup and
+/// status changes of the removed events and reset the at-bat draft.
+pub fn apply_undo(
+    conn: &mut Connection,
+    game_pk: i64,
+    game_id: &str,
+    plan: &UndoPlan,
+) -> rusqlite::Result<()> {
+    let first = plan.first();
+    let tx = conn.transaction()?;
+
+    let mut status_reverted = false;
+    for ev in plan.events.iter().rev() {
+        match parse_event(ev) {
+            Some(DomainEvent::PlayerSubstituted(d)) => match d.player_out_id {
+                Some(out_id) => revert_substitution(
+                    &tx,
+                    game_id,
+                    d.team_id,
+                    d.batting_order,
+                    out_id,
+                    d.player_in_id,
+                )?,
+                None => {
+                    if let Some(previous) = d.previous_position {
+                        set_defensive_position(&tx, game_id, d.team_id, d.batting_order, previous)?;
+                    }
+                }
+            },
+            Some(DomainEvent::StatusChanged(_)) => status_reverted = true,
+            _ => {}
+        }
+    }
+
+    delete_game_events_from(&tx, game_pk, first.seq)?;
+    delete_runner_movements_from(&tx, game_pk, first.seq)?;
+    delete_plate_appearances_from(&tx, game_pk, first.seq)?;
+
+    if status_reverted {
+        // Back to the last status still on record; the game was in
+        // progress otherwise (entering Play Ball sets it).
+        let status = list_game_events(&tx, game_pk)?
+            .iter()
+            .rev()
+            .find_map(|ev| match parse_event(ev) {
+                Some(DomainEvent::StatusChanged(d)) => Some(d.to),
+                _ => None,
+            })
+            .unwrap_or(GameStatus::InProgress);
+        set_game_status(&tx, game_id, status)?;
+    }
+
+    upsert_at_bat_draft(
+        &tx,
+        game_pk,
+        first.inning as u32,
+        HalfInning::from_str_loose(&first.half_inning),
+        Some(first.batter_id),
+        Some(first.pitcher_id),
+        &count_before_play(first),
+    )?;
+
+    tx.commit()
+}
+
+fn parse_event(ev: &GameEventRow) -> Option<DomainEvent> {
+    ev.event_data
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+}
+
+/// The count of a PA before its terminal step (the pitch or play that
+/// ended it).
+pub fn count_before_play(pa: &PlateAppearanceRow) -> PitchCount {
+    let mut steps = parse_pa_sequence(&pa.pitches_sequence);
+    steps.pop();
+
+    let mut scratch = GameState::new();
+    for step in steps {
+        if let PlateAppearanceStep::Pitch(pitch) = step {
+            apply_domain_event(
+                &mut scratch,
+                &DomainEvent::PitchRecorded {
+                    pitcher_id: pa.pitcher_id,
+                    batter_id: pa.batter_id,
+                    pitch,
+                },
+            );
+        }
+    }
+    scratch.pitch_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::at_bat_draft::load_at_bat_draft;
+    use crate::db::database::Database;
+    use crate::db::fixtures::{BullpenGame, bullpen_game};
+    use crate::db::game_events::append_game_event;
+    use crate::db::lineups::{active_lineup, substitute_player};
+    use crate::db::plate_appearances::append_plate_appearance;
+    use crate::db::runner_movements::{
+        RunnerMovementInsert, append_runner_movement, list_runner_movements,
+    };
+    use crate::models::events::{StatusChangedData, SubstitutionData, SubstitutionKind};
+    use crate::models::plate_appearance::{PlateAppearance, PlateAppearanceOutcome};
+    use crate::models::types::{Pitch, Position};
+
+    const GAME_ID: &str = "G-UNDO";
+
+    /// Two home pitchers, the starter batting ninth, and two away batters.
+    fn setup(conn: &Connection) -> BullpenGame {
+        bullpen_game(
+            conn,
+            GAME_ID,
+            &[(21, None, "Pat", "Starter"), (34, None, "Pat", "Reliever")],
+            2,
+        )
+    }
+
+    fn pa(batter_id: i64, pitcher_id: i64, pitches: &[Pitch], outs: u8) -> PlateAppearance {
+        let mut seq: Vec<_> = pitches
+            .iter()
+            .cloned()
+            .map(PlateAppearanceStep::Pitch)
+            .collect();
+        seq.push(PlateAppearanceStep::Single);
+        PlateAppearance {
+            inning: 1,
+            half: HalfInning::Top,
+            batter_id,
+            batter_order: 1,
+            pitcher_id,
+            pitches: seq.len() as u32,
+            pitches_sequence: seq,
+            outcome: PlateAppearanceOutcome::Single {
+                zone: None,
+                modifier: None,
+            },
+            outs,
+            runner_overrides: vec![],
+            double_play: None,
+        }
+    }
+
+    fn movement(game_pk: i64, pa_seq: i64, kind: &'static str) -> RunnerMovementInsert {
+        RunnerMovementInsert {
+            game_id: game_pk,
+            pa_seq: Some(pa_seq),
+            game_event_id: None,
+            inning: 1,
+            half_inning: "Top".to_string(),
+            runner_id: None,
+            batter_order: 1,
+            start_base: "BAT",
+            end_base: "1B",
+            advancement_type: kind,
+            is_out: false,
+            scored: false,
+            is_earned: false,
+            fielders: None,
+        }
+    }
+
+    #[test]
+    fn count_before_play_drops_the_terminal_step() {
+        let row = PlateAppearanceRow {
+            id: 1,
+            game_id: 1,
+            seq: 1,
+            inning: 1,
+            half_inning: "Top".to_string(),
+            batter_id: 7,
+            batter_order: 1,
+            pitcher_id: 9,
+            pitches: 5,
+            pitches_sequence: serde_json::to_string(&[
+                PlateAppearanceStep::Pitch(Pitch::Ball),
+                PlateAppearanceStep::Pitch(Pitch::Foul),
+                PlateAppearanceStep::Pitch(Pitch::Foul),
+                PlateAppearanceStep::Pitch(Pitch::Foul),
+                PlateAppearanceStep::Pitch(Pitch::SwingingStrike),
+            ])
+            .unwrap(),
+            outcome_type: "strikeout".to_string(),
+            outcome_data: None,
+            outs: 1,
+            runner_overrides_json: "[]".to_string(),
+            double_play_json: None,
+            rbi: None,
+        };
+
+        let count = count_before_play(&row);
+        assert_eq!((count.balls, count.strikes), (1, 2));
+        assert_eq!(count.sequence.len(), 4);
+    }
+
+    #[test]
+    fn undo_removes_the_play_and_everything_after_it() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection_mut();
+        let BullpenGame {
+            game_pk,
+            home_team_id: team_id,
+            pitchers,
+            batters,
+        } = setup(conn);
+
+        // PA 1, then a steal during the next at-bat, then PA 2.
+        append_plate_appearance(conn, game_pk, &pa(batters[0], pitchers[0], &[], 0)).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 1, "hit_auto")).unwrap();
+        append_runner_movement(conn, &movement(game_pk, 1, "steal")).unwrap();
+        append_plate_appearance(
+            conn,
+            game_pk,
+            &pa(
+                batters[1],
+                pitchers[0],
+                &[Pitch::Ball, Pitch::CalledStrike],
+                0,
+            ),
+        )
+        .unwrap();
+        append_runner_movement(conn, &movement(game_pk, 2, "hit_auto")).unwrap();
+
+        // After PA 2: a pitching change and a status change.
+        substitute_player(
+            conn,
+            GAME_ID,
+            team_id,
+            9,
+            pitchers[1],
+            Position::Pitcher,
+            1,
+            HalfInning::Top,
+        )
+        .unwrap();
+        let sub = DomainEvent::PlayerSubstituted(SubstitutionData {
+            team_id,
+            kind: SubstitutionKind::PitchingChange,
+            batting_order: 9,
+            player_out_id: Some(pitchers[0]),
+            player_in_id: pitchers[1],
+            player_in_jersey_no: 34,
+            player_in_first_name: "Pat".to_string(),
+            player_in_last_name: "Reliever".to_string(),
+            position: Position::Pitcher,
+            previous_position: Some(Position::Pitcher),
+        });
+        append_game_event(conn, game_pk, 1, HalfInning::Top, &sub, "pc").unwrap();
+        let susp = DomainEvent::StatusChanged(StatusChangedData {
+            to: GameStatus::Suspended,
+        });
+        append_game_event(conn, game_pk, 1, HalfInning::Top, &susp, "susp").unwrap();
+        set_game_status(conn, GAME_ID, GameStatus::Suspended).unwrap();
+
+        let plan = plan_undo(conn, game_pk, 1).unwrap();
+        assert_eq!(plan.plate_appearances.len(), 1);
+        assert_eq!(plan.plate_appearances[0].seq, 2);
+        assert_eq!(plan.events.len(), 2);
+
+        apply_undo(conn, game_pk, GAME_ID, &plan).unwrap();
+
+        let pas = list_plate_appearances(conn, game_pk).unwrap();
+        assert_eq!(pas.iter().map(|p| p.seq).collect::<Vec<_>>(), vec![1]);
+        let kinds: Vec<_> = list_runner_movements(conn, game_pk)
+            .unwrap()
+            .into_iter()
+            .map(|rm| rm.advancement_type)
+            .collect();
+        assert_eq!(kinds, vec!["hit_auto", "steal"]);
+        assert!(list_game_events(conn, game_pk).unwrap().is_empty());
+
+        let lineup = active_lineup(conn, GAME_ID, team_id, true).unwrap();
+        assert_eq!(lineup[0].player.id, pitchers[0]);
+        let status: i64 = conn
+            .query_row(
+                "SELECT status FROM games WHERE game_id = ?1",
+                [GAME_ID],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, GameStatus::InProgress.to_i64());
+
+        let draft = load_at_bat_draft(conn, game_pk).unwrap().unwrap();
+        assert_eq!(draft.batter_id, Some(batters[1]));
+        assert_eq!(draft.pitcher_id, Some(pitchers[0]));
+        let count: PitchCount = serde_json::from_str(&draft.pitch_count_json).unwrap();
+        assert_eq!((count.balls, count.strikes), (1, 1));
+    }
+
+    #[test]
+    fn undo_cannot_go_past_the_first_plate_appearance() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let BullpenGame {
+            game_pk,
+            pitchers,
+            batters,
+            ..
+        } = setup(conn);
+
+        assert!(plan_undo(conn, game_pk, 1).is_err());
+        append_plate_appearance(conn, game_pk, &pa(batters[0], pitchers[0], &[], 0)).unwrap();
+        assert!(plan_undo(conn, game_pk, 2).is_err());
+        assert_eq!(
+            plan_undo(conn, game_pk, 1).unwrap().plate_appearances.len(),
+            1
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub mod cli;
 pub mod db;
 pub mod engine;
 pub mod models;
+pub mod retrosheet;
+pub mod stats;
 pub mod ui;
 pub mod utils;
 
@@ -32,7 +34,7 @@ pub use db::team::Team;
 // ─── Menu / CLI ───────────────────────────────────────────────────────────────
 pub use cli::menu::{
     DBMenuChoice, GameMenuChoice, LeagueMenuChoice, MainMenuChoice, Menu, PlayerMenuChoice,
-    TeamMenuChoice, UmpireSupervisorMenuChoice,
+    StatisticsMenuChoice, TeamMenuChoice, UmpireSupervisorMenuChoice,
 };
 pub use engine::notation::CommandParser;
 
@@ -42,7 +44,7 @@ pub use models::types::{GameStatus, HalfInning, Pitch, PitchCount, Position, Sco
 // ─── Live game model ─────────────────────────────────────────────────────────
 pub use models::game_state::{BatterOrder, GameState, PitchStats};
 pub use models::plate_appearance::PlateAppearance;
-pub use models::runner::{RunnerDest, RunnerOverride};
+pub use models::runner::{FieldingError, RunnerDest, RunnerOverride};
 pub use models::session::{LineupSide, PlayBallGameContext, PlayBallGate};
 
 // ─── Full scoring notation (parser / future engine) ──────────────────────────
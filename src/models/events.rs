@@ -40,6 +40,10 @@ pub enum DomainEvent {
         pitcher_last_name: String,
     },
 
+    /// A lineup change written to `game_lineups` (pitching change, pinch
+    /// hitter or runner, defensive substitution, position change).
+    PlayerSubstituted(SubstitutionData),
+
     PitchRecorded {
         pitcher_id: i64,
         batter_id: i64,
@@ -99,6 +103,34 @@ pub struct StatusChangedData {
     pub to: GameStatus,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubstitutionKind {
+    PitchingChange,
+    PinchHitter,
+    PinchRunner,
+    Defensive,
+    /// The player stays in the game and only changes position.
+    PositionChange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubstitutionData {
+    pub team_id: i64,
+    pub kind: SubstitutionKind,
+    pub batting_order: u8,
+    /// The player leaving the game; `None` on a position change.
+    pub player_out_id: Option<i64>,
+    pub player_in_id: i64,
+    pub player_in_jersey_no: i32,
+    pub player_in_first_name: String,
+    pub player_in_last_name: String,
+    pub position: Position,
+    /// Position held in the slot before the change, so an undo can put
+    /// it back. `None` on events written before it was recorded.
+    #[serde(default)]
+    pub previous_position: Option<Position>,
+}
+
 impl DomainEvent {
     pub fn event_type(&self) -> &'static str {
         match self {
@@ -107,6 +139,7 @@ impl DomainEvent {
             DomainEvent::GameStarted => "game_started",
             DomainEvent::AtBatStarted { .. } => "at_bat_started",
             DomainEvent::PitcherChanged { .. } => "pitcher_changed",
+            DomainEvent::PlayerSubstituted(_) => "player_substituted",
             DomainEvent::PitchRecorded { .. } => "pitch_recorded",
             DomainEvent::AtBatPitchesCount { .. } => "at_bat_pitches_count",
             DomainEvent::CountReset => "count_reset",
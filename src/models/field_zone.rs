@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Field zones used to tag hits according to scorer spray chart notation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FieldZone {
     LL,
     LF,
@@ -1,6 +1,7 @@
 //! Live game state — the single source of truth for the in-memory scoreboard
 //! during a Play Ball session.
 
+use crate::models::rules::RuleSet;
 use crate::models::types::{HalfInning, PitchCount, Position, Score};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -52,6 +53,13 @@ pub struct GameState {
     pub on_1b: Option<BatterOrder>,
     pub on_2b: Option<BatterOrder>,
     pub on_3b: Option<BatterOrder>,
+    /// Runners the tie-break rule put on base in the current half, while they
+    /// stay on base. Their runs are unearned.
+    pub tie_break_runners: Vec<BatterOrder>,
+
+    // ── Game rules ───────────────────────────────────────────────────────────
+    /// Rules the game is played under.
+    pub rules: RuleSet,
 }
 
 impl GameState {
@@ -88,6 +96,9 @@ impl GameState {
             on_1b: None,
             on_2b: None,
             on_3b: None,
+            tie_break_runners: Vec::new(),
+
+            rules: RuleSet::default(),
         }
     }
 
@@ -9,6 +9,7 @@ pub mod field_zone;
 pub mod game_state;
 pub mod plate_appearance;
 pub mod player_traits;
+pub mod rules;
 pub mod runner;
 pub mod scoring;
 pub mod session;
@@ -26,6 +26,95 @@ pub struct PlateAppearance {
     /// Empty vec means full automatic advancement.
     #[serde(default)]
     pub runner_overrides: Vec<RunnerOverride>,
+    /// Double or triple play turned on this PA, if any.
+    #[serde(default)]
+    pub double_play: Option<DoublePlay>,
+}
+
+impl PlateAppearance {
+    /// Number of errors charged to the defence on this PA: the batter's
+    /// own reached-on-error plus every runner override attributed to one.
+    pub fn errors_charged(&self) -> u16 {
+        let batter = matches!(self.outcome, PlateAppearanceOutcome::ReachedOnError { .. }) as u16;
+        let runners = self
+            .runner_overrides
+            .iter()
+            .filter(|o| o.error.is_some())
+            .count() as u16;
+        batter + runners
+    }
+}
+
+/// How a double or triple play started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoublePlayKind {
+    /// The batter grounded into it (`2 64, 63`), including a batter safe
+    /// on a fielder's choice while two runners are retired.
+    GroundBall,
+    /// Line drive caught with a runner doubled off (`l6, 2 64`).
+    LineDrive,
+    /// Fly ball or infield fly with a runner doubled off or thrown out.
+    FlyBall,
+    /// Strike three on the batter and the runner caught stealing or picked
+    /// off on the same line (`s, 5 cs 2b 26`).
+    StrikeoutThrowOut,
+}
+
+/// Double (`outs == 2`) or triple (`outs == 3`) play credited on a PA.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoublePlay {
+    pub kind: DoublePlayKind,
+    pub outs: u8,
+    /// Every fielder who took part, in the order they handled the ball.
+    /// Each one is credited with the DP/TP in fielding stats.
+    pub fielders: Vec<u8>,
+}
+
+impl DoublePlay {
+    /// Build the marker, listing each fielder once in order of first touch.
+    pub fn new(kind: DoublePlayKind, outs: u8, fielders: impl IntoIterator<Item = u8>) -> Self {
+        let mut unique: Vec<u8> = Vec::new();
+        for f in fielders {
+            if !unique.contains(&f) {
+                unique.push(f);
+            }
+        }
+        Self {
+            kind,
+            outs,
+            fielders: unique,
+        }
+    }
+
+    /// Returns true for a triple play.
+    pub fn is_triple_play(&self) -> bool {
+        self.outs >= 3
+    }
+
+    /// Whether `fielder` took part in the play and gets a DP/TP credit.
+    pub fn involves(&self, fielder: u8) -> bool {
+        self.fielders.contains(&fielder)
+    }
+
+    /// Short scorebook label ("GDP", "LTP", "KS-CS DP", ...).
+    pub fn label(&self) -> &'static str {
+        match (self.kind, self.is_triple_play()) {
+            (DoublePlayKind::GroundBall, false) => "GDP",
+            (DoublePlayKind::GroundBall, true) => "GTP",
+            (DoublePlayKind::LineDrive, false) => "LDP",
+            (DoublePlayKind::LineDrive, true) => "LTP",
+            (DoublePlayKind::FlyBall, false) => "FDP",
+            (DoublePlayKind::FlyBall, true) => "FTP",
+            (DoublePlayKind::StrikeoutThrowOut, _) => "KS-CS DP",
+        }
+    }
+}
+
+impl fmt::Display for DoublePlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain: Vec<String> = self.fielders.iter().map(|n| n.to_string()).collect();
+        write!(f, "{} ({})", self.label(), chain.join("-"))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +125,9 @@ pub enum PlateAppearanceStep {
     Triple,
     HomeRun,
     Walk,
+    HitByPitch,
+    IntentionalWalk,
+    CatcherInterference,
     Strikeout,
     Out,
     UnassistedOut {
@@ -58,6 +150,10 @@ pub enum PlateAppearanceStep {
         fielder: u8,
         reached_base: RunnerDest,
     },
+    ReachedOnError {
+        fielder: u8,
+        throwing: bool,
+    },
 }
 
 impl fmt::Display for PlateAppearanceStep {
@@ -71,6 +167,9 @@ impl fmt::Display for PlateAppearanceStep {
             PlateAppearanceStep::HomeRun => write!(f, "HR"),
 
             PlateAppearanceStep::Walk => write!(f, "BB"),
+            PlateAppearanceStep::HitByPitch => write!(f, "HBP"),
+            PlateAppearanceStep::IntentionalWalk => write!(f, "IBB"),
+            PlateAppearanceStep::CatcherInterference => write!(f, "CI"),
             PlateAppearanceStep::Strikeout => write!(f, "K"),
             PlateAppearanceStep::Out => write!(f, "OUT"),
 
@@ -87,6 +186,7 @@ impl fmt::Display for PlateAppearanceStep {
             PlateAppearanceStep::LineOut { .. } => write!(f, "LO"),
             PlateAppearanceStep::InfieldFly { .. } => write!(f, "IF"),
             PlateAppearanceStep::FieldersChoice { .. } => write!(f, "FC"),
+            PlateAppearanceStep::ReachedOnError { .. } => write!(f, "E"),
         }
     }
 }
@@ -94,36 +194,118 @@ impl fmt::Display for PlateAppearanceStep {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HitOutcomeData {
     pub zone: Option<FieldZone>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<HitModifier>,
+}
+
+/// Qualifier typed after a hit verb: `2h gr`, `hr itp`, `h bu`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitModifier {
+    /// Ball bounced out of play: the batter and every runner are awarded
+    /// exactly two bases. Only valid on a double.
+    GroundRule,
+    /// Home run without the ball leaving the field. Only valid on a home run.
+    InsideThePark,
+    /// Bunt hit. Not valid on a home run.
+    Bunt,
+}
+
+impl HitModifier {
+    /// Grammar token and scorebook suffix ("GR", "ITP", "BU").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GroundRule => "GR",
+            Self::InsideThePark => "ITP",
+            Self::Bunt => "BU",
+        }
+    }
+
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_uppercase().as_str() {
+            "GR" => Some(Self::GroundRule),
+            "ITP" => Some(Self::InsideThePark),
+            "BU" => Some(Self::Bunt),
+            _ => None,
+        }
+    }
+
+    /// Whether the modifier can qualify a hit of `bases` bases (1-4).
+    pub fn applies_to(&self, bases: u8) -> bool {
+        match self {
+            Self::GroundRule => bases == 2,
+            Self::InsideThePark => bases == 4,
+            Self::Bunt => bases < 4,
+        }
+    }
+}
+
+/// How a dropped third strike ended: the batter reached first on a wild
+/// pitch or a passed ball, or was thrown out (`sequence` like `"2-3"`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DroppedThirdStrike {
+    WildPitch,
+    PassedBall,
+    ThrownOut { sequence: String },
+}
+
+impl DroppedThirdStrike {
+    /// Returns true when the batter reached first base.
+    pub fn batter_reaches(&self) -> bool {
+        !matches!(self, Self::ThrownOut { .. })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum PlateAppearanceOutcome {
     Walk,
+    HitByPitch,
+    IntentionalWalk,
+    CatcherInterference,
     Strikeout(crate::models::events::StrikeoutKind),
+    /// Strike three not caught (`kwp`, `kpb`, `k23`): a strikeout for the
+    /// pitcher whether or not the batter reached.
+    DroppedThirdStrike(DroppedThirdStrike),
     Out,
     Single {
         zone: Option<FieldZone>,
+        #[serde(default)]
+        modifier: Option<HitModifier>,
     },
     Double {
         zone: Option<FieldZone>,
+        #[serde(default)]
+        modifier: Option<HitModifier>,
     },
     Triple {
         zone: Option<FieldZone>,
+        #[serde(default)]
+        modifier: Option<HitModifier>,
     },
     HomeRun {
         zone: Option<FieldZone>,
+        #[serde(default)]
+        modifier: Option<HitModifier>,
     },
 
+    /// `sacrifice` marks a sacrifice bunt fielded by a single player.
     UnassistedOut {
         fielder: u8,
+        #[serde(default)]
+        sacrifice: bool,
     },
+    /// `sacrifice` marks a sacrifice bunt (`sh13`).
     GroundOut {
         sequence: String,
+        #[serde(default)]
+        sacrifice: bool,
     },
+    /// `sacrifice` marks a sacrifice fly (`sf8`).
     FlyOut {
         fielder: u8,
         in_foul_territory: bool,
+        #[serde(default)]
+        sacrifice: bool,
     },
     LineOut {
         fielder: u8,
@@ -135,6 +317,11 @@ pub enum PlateAppearanceOutcome {
         fielder: u8,
         reached_base: RunnerDest,
     },
+    ReachedOnError {
+        fielder: u8,
+        throwing: bool,
+        reached_base: RunnerDest,
+    },
 }
 
 impl PlateAppearanceOutcome {
@@ -154,18 +341,85 @@ impl PlateAppearanceOutcome {
         self.bases() > 0
     }
 
+    /// Returns true for the awards that put the batter on first with
+    /// forced runner advancement (BB, HBP, IBB, CI).
+    pub fn is_base_award(&self) -> bool {
+        matches!(
+            self,
+            Self::Walk | Self::HitByPitch | Self::IntentionalWalk | Self::CatcherInterference
+        )
+    }
+
+    /// Returns true for a sacrifice fly.
+    pub fn is_sacrifice_fly(&self) -> bool {
+        matches!(
+            self,
+            Self::FlyOut {
+                sacrifice: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns true for a sacrifice bunt.
+    pub fn is_sacrifice_bunt(&self) -> bool {
+        matches!(
+            self,
+            Self::GroundOut {
+                sacrifice: true,
+                ..
+            } | Self::UnassistedOut {
+                sacrifice: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns true for a strikeout, including a dropped third strike the
+    /// batter reached first on.
+    pub fn is_strikeout(&self) -> bool {
+        matches!(self, Self::Strikeout(_) | Self::DroppedThirdStrike(_))
+    }
+
+    /// Returns true if this PA counts as an official at-bat. Base awards
+    /// and sacrifices are plate appearances but not at-bats.
+    pub fn is_at_bat(&self) -> bool {
+        !self.is_base_award() && !self.is_sacrifice_fly() && !self.is_sacrifice_bunt()
+    }
+
+    /// Returns true if this PA enters the on-base-percentage denominator
+    /// (AB + BB + HBP + SF). Sacrifice bunts and catcher's interference
+    /// are left out.
+    pub fn counts_for_obp(&self) -> bool {
+        self.is_at_bat()
+            || self.is_sacrifice_fly()
+            || matches!(self, Self::Walk | Self::HitByPitch | Self::IntentionalWalk)
+    }
+
     /// Extract the field zone from hit outcomes.
     pub fn zone(&self) -> Option<FieldZone> {
         match self {
-            Self::Single { zone }
-            | Self::Double { zone }
-            | Self::Triple { zone }
-            | Self::HomeRun { zone } => *zone,
+            Self::Single { zone, .. }
+            | Self::Double { zone, .. }
+            | Self::Triple { zone, .. }
+            | Self::HomeRun { zone, .. } => *zone,
             _ => None,
         }
     }
 
-    /// Short label for display (e.g. "H", "2H", "3H", "HR", "BB", "K", "OUT").
+    /// Extract the ground-rule / inside-the-park / bunt qualifier from hit
+    /// outcomes.
+    pub fn hit_modifier(&self) -> Option<HitModifier> {
+        match self {
+            Self::Single { modifier, .. }
+            | Self::Double { modifier, .. }
+            | Self::Triple { modifier, .. }
+            | Self::HomeRun { modifier, .. } => *modifier,
+            _ => None,
+        }
+    }
+
+    /// Short label for display (e.g. "H", "2H", "3H", "HR", "BB", "K", "OUT", "E").
     pub fn label(&self) -> &'static str {
         match self {
             Self::Single { .. } => "H",
@@ -173,8 +427,23 @@ impl PlateAppearanceOutcome {
             Self::Triple { .. } => "3H",
             Self::HomeRun { .. } => "HR",
             Self::Walk => "BB",
+            Self::HitByPitch => "HBP",
+            Self::IntentionalWalk => "IBB",
+            Self::CatcherInterference => "CI",
             Self::Strikeout(_) => "K",
+            Self::DroppedThirdStrike(DroppedThirdStrike::WildPitch) => "K+WP",
+            Self::DroppedThirdStrike(DroppedThirdStrike::PassedBall) => "K+PB",
+            Self::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { .. }) => "K",
             Self::Out => "OUT",
+            Self::UnassistedOut {
+                sacrifice: true, ..
+            }
+            | Self::GroundOut {
+                sacrifice: true, ..
+            } => "SH",
+            Self::FlyOut {
+                sacrifice: true, ..
+            } => "SF",
             Self::UnassistedOut { .. } => "UO",
             Self::GroundOut { .. } => "GO",
             Self::FlyOut {
@@ -188,19 +457,57 @@ impl PlateAppearanceOutcome {
             Self::LineOut { .. } => "LO",
             Self::InfieldFly { .. } => "IF",
             Self::FieldersChoice { .. } => "FC",
+            Self::ReachedOnError { .. } => "E",
         }
     }
 
     /// Human-readable label for display.
     pub fn display_label(&self) -> &'static str {
         match self {
+            Self::Single {
+                modifier: Some(HitModifier::Bunt),
+                ..
+            } => "Bunt single",
+            Self::Double {
+                modifier: Some(HitModifier::GroundRule),
+                ..
+            } => "Ground-rule double",
+            Self::Double {
+                modifier: Some(HitModifier::Bunt),
+                ..
+            } => "Bunt double",
+            Self::Triple {
+                modifier: Some(HitModifier::Bunt),
+                ..
+            } => "Bunt triple",
+            Self::HomeRun {
+                modifier: Some(HitModifier::InsideThePark),
+                ..
+            } => "Inside-the-park home run",
             Self::Single { .. } => "Single",
             Self::Double { .. } => "Double",
             Self::Triple { .. } => "Triple",
             Self::HomeRun { .. } => "Home run",
             Self::Walk => "BB",
+            Self::HitByPitch => "Hit by pitch",
+            Self::IntentionalWalk => "Intentional walk",
+            Self::CatcherInterference => "Catcher's interference",
             Self::Strikeout(_) => "K",
+            Self::DroppedThirdStrike(DroppedThirdStrike::WildPitch) => "Strikeout, wild pitch",
+            Self::DroppedThirdStrike(DroppedThirdStrike::PassedBall) => "Strikeout, passed ball",
+            Self::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { .. }) => {
+                "Strikeout, batter thrown out"
+            }
             Self::Out => "OUT",
+            Self::UnassistedOut {
+                sacrifice: true, ..
+            }
+            | Self::GroundOut {
+                sacrifice: true, ..
+            } => "Sacrifice bunt",
+            Self::FlyOut {
+                sacrifice: true, ..
+            } => "Sacrifice fly",
             Self::UnassistedOut { .. } => "Unassisted out",
             Self::GroundOut { .. } => "Ground out",
             Self::FlyOut {
@@ -214,6 +521,41 @@ impl PlateAppearanceOutcome {
             Self::LineOut { .. } => "Line out",
             Self::InfieldFly { .. } => "Infield fly",
             Self::FieldersChoice { .. } => "Fielder's choice",
+            Self::ReachedOnError { .. } => "Reached on error",
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A plate appearance in the top of the first with every runner
+    /// advancing automatically.
+    pub(crate) fn pa(
+        batter_id: i64,
+        order: BatterOrder,
+        pitcher_id: i64,
+        outcome: PlateAppearanceOutcome,
+        pitches: &[Pitch],
+        outs: u8,
+    ) -> PlateAppearance {
+        PlateAppearance {
+            inning: 1,
+            half: HalfInning::Top,
+            batter_id,
+            batter_order: order,
+            pitcher_id,
+            pitches: pitches.len() as u32,
+            pitches_sequence: pitches
+                .iter()
+                .cloned()
+                .map(PlateAppearanceStep::Pitch)
+                .collect(),
+            outcome,
+            outs,
+            runner_overrides: vec![],
+            double_play: None,
         }
     }
 }
@@ -0,0 +1,242 @@
+//! Rule profile of a league or a game: what kind of game is played, how
+//! long it lasts and which optional rules apply.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Regulation length of a baseball game.
+pub const DEFAULT_SCHEDULED_INNINGS: u32 = 9;
+
+/// Regulation length of a softball game.
+pub const SOFTBALL_SCHEDULED_INNINGS: u32 = 7;
+
+/// The game being scored.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GameKind {
+    Baseball,
+    FastPitchSoftball,
+    SlowPitchSoftball,
+}
+
+impl GameKind {
+    pub const ALL: [GameKind; 3] = [
+        GameKind::Baseball,
+        GameKind::FastPitchSoftball,
+        GameKind::SlowPitchSoftball,
+    ];
+
+    /// DB value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameKind::Baseball => "baseball",
+            GameKind::FastPitchSoftball => "fastpitch",
+            GameKind::SlowPitchSoftball => "slowpitch",
+        }
+    }
+
+    /// Parse the DB value; anything unknown is baseball.
+    pub fn from_str_loose(s: &str) -> Self {
+        match s {
+            "fastpitch" => GameKind::FastPitchSoftball,
+            "slowpitch" => GameKind::SlowPitchSoftball,
+            _ => GameKind::Baseball,
+        }
+    }
+
+    pub fn default_innings(&self) -> u32 {
+        match self {
+            GameKind::Baseball => DEFAULT_SCHEDULED_INNINGS,
+            GameKind::FastPitchSoftball | GameKind::SlowPitchSoftball => SOFTBALL_SCHEDULED_INNINGS,
+        }
+    }
+
+    /// Runners may lead off and advance while the ball is pitched: steals,
+    /// pickoffs, wild pitches and passed balls.
+    pub fn allows_running_on_pitches(&self) -> bool {
+        !matches!(self, GameKind::SlowPitchSoftball)
+    }
+
+    /// The pitcher can commit a balk.
+    pub fn allows_balk(&self) -> bool {
+        matches!(self, GameKind::Baseball)
+    }
+
+    /// Bunts and the dropped third strike are part of the game.
+    pub fn allows_bunts_and_dropped_third_strike(&self) -> bool {
+        !matches!(self, GameKind::SlowPitchSoftball)
+    }
+}
+
+impl fmt::Display for GameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GameKind::Baseball => "Baseball",
+            GameKind::FastPitchSoftball => "Fast-pitch softball",
+            GameKind::SlowPitchSoftball => "Slow-pitch softball",
+        })
+    }
+}
+
+/// Run rule threshold: the game ends when a team leads by `run_lead` runs
+/// or more once `after_inning` innings are complete.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MercyRule {
+    pub run_lead: u16,
+    pub after_inning: u32,
+}
+
+impl MercyRule {
+    /// Parse a list like `15@5, 10@7`.
+    pub fn parse_list(s: &str) -> Result<Vec<MercyRule>, String> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl FromStr for MercyRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{s}' is not a run rule: use <runs>@<inning>, e.g. 10@7");
+        let (runs, inning) = s.split_once('@').ok_or_else(invalid)?;
+        let run_lead: u16 = runs.trim().parse().map_err(|_| invalid())?;
+        let after_inning: u32 = inning.trim().parse().map_err(|_| invalid())?;
+        if run_lead == 0 || after_inning == 0 {
+            return Err(invalid());
+        }
+        Ok(MercyRule {
+            run_lead,
+            after_inning,
+        })
+    }
+}
+
+impl fmt::Display for MercyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.run_lead, self.after_inning)
+    }
+}
+
+/// Rules a game is played under. A league carries a default profile; each
+/// game keeps its own copy, taken when it is created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    pub kind: GameKind,
+    /// Regulation length in innings.
+    pub innings: u32,
+    /// Teams may use a designated hitter.
+    pub dh_allowed: bool,
+    /// Run rule thresholds, if any.
+    pub mercy_rules: Vec<MercyRule>,
+    /// Most runs a team may score in one half-inning.
+    pub max_runs_per_half: Option<u16>,
+    /// First extra inning started with a runner placed on base.
+    pub tie_break_inning: Option<u32>,
+}
+
+impl RuleSet {
+    /// Standard rules for `kind`: regulation length, DH allowed, no
+    /// optional rule.
+    pub fn standard(kind: GameKind) -> Self {
+        RuleSet {
+            kind,
+            innings: kind.default_innings(),
+            dh_allowed: true,
+            mercy_rules: Vec::new(),
+            max_runs_per_half: None,
+            tie_break_inning: None,
+        }
+    }
+
+    /// Run rules as stored in the DB (a JSON array).
+    pub fn mercy_rules_json(&self) -> String {
+        serde_json::to_string(&self.mercy_rules).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Read run rules stored with [`RuleSet::mercy_rules_json`].
+    pub fn parse_mercy_rules_json(raw: &str) -> Vec<MercyRule> {
+        serde_json::from_str(raw).unwrap_or_default()
+    }
+
+    /// One-line description, e.g. `Baseball, 9 innings, DH`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.kind.to_string(), format!("{} innings", self.innings)];
+        parts.push(if self.dh_allowed { "DH" } else { "no DH" }.to_string());
+        if !self.mercy_rules.is_empty() {
+            let rules: Vec<_> = self.mercy_rules.iter().map(ToString::to_string).collect();
+            parts.push(format!("run rule {}", rules.join(" ")));
+        }
+        if let Some(max) = self.max_runs_per_half {
+            parts.push(format!("max {max} runs per half"));
+        }
+        if let Some(inning) = self.tie_break_inning {
+            parts.push(format!("tie-break from inning {inning}"));
+        }
+        parts.join(", ")
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::standard(GameKind::Baseball)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mercy_rules_parse_and_round_trip() {
+        let rules = MercyRule::parse_list("15@5, 10@7").unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                MercyRule {
+                    run_lead: 15,
+                    after_inning: 5
+                },
+                MercyRule {
+                    run_lead: 10,
+                    after_inning: 7
+                },
+            ]
+        );
+
+        let mut set = RuleSet::standard(GameKind::SlowPitchSoftball);
+        set.mercy_rules = rules.clone();
+        assert_eq!(
+            RuleSet::parse_mercy_rules_json(&set.mercy_rules_json()),
+            rules
+        );
+        assert!(MercyRule::parse_list("10").is_err());
+        assert!(MercyRule::parse_list("0@5").is_err());
+        assert_eq!(MercyRule::parse_list("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn game_kind_round_trips_through_the_db_value() {
+        for kind in GameKind::ALL {
+            assert_eq!(GameKind::from_str_loose(kind.as_str()), kind);
+        }
+        assert_eq!(GameKind::from_str_loose("cricket"), GameKind::Baseball);
+    }
+
+    #[test]
+    fn summary_lists_the_optional_rules() {
+        let mut set = RuleSet::standard(GameKind::FastPitchSoftball);
+        assert_eq!(set.summary(), "Fast-pitch softball, 7 innings, DH");
+        set.dh_allowed = false;
+        set.mercy_rules = MercyRule::parse_list("10@5").unwrap();
+        set.max_runs_per_half = Some(5);
+        set.tie_break_inning = Some(8);
+        assert_eq!(
+            set.summary(),
+            "Fast-pitch softball, 7 innings, no DH, run rule 10@5, max 5 runs per half, tie-break from inning 8"
+        );
+    }
+}
@@ -33,6 +33,16 @@ impl RunnerDest {
         }
     }
 
+    /// Bases counted from home plate: 1 for first … 4 for scoring.
+    pub fn bases(self) -> u8 {
+        match self {
+            Self::First => 1,
+            Self::Second => 2,
+            Self::Third => 3,
+            Self::Score => 4,
+        }
+    }
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::First => "1B",
@@ -49,14 +59,41 @@ impl fmt::Display for RunnerDest {
     }
 }
 
+/// A fielding or throwing error charged to a defensive position (1–9).
+///
+/// Entered by the scorer as `e<n>` (fielding) or `te<n>` (throwing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldingError {
+    /// Defensive position charged with the error.
+    pub fielder: u8,
+    /// `true` for a throwing error (`te<n>`), `false` for a fielding error.
+    pub throwing: bool,
+}
+
+impl fmt::Display for FieldingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.throwing {
+            write!(f, "TE{}", self.fielder)
+        } else {
+            write!(f, "E{}", self.fielder)
+        }
+    }
+}
+
 /// An explicit override for one runner: "batting-order slot N goes to dest D".
 ///
 /// Entered by the scorer as part of a hit command:
 /// `"6 h, 5 2b"` → batter #6 singles; runner #5 stays on 2nd.
+///
+/// When the runner advanced on a misplay (`"h, 2 e7 3b"`) the override
+/// also carries the [`FieldingError`], so replay can recharge it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RunnerOverride {
     /// Batting order of the runner being overridden (1–9).
     pub order: BatterOrder,
     /// Where this runner ends up after the play.
     pub dest: RunnerDest,
+    /// Error that let the runner take this base, if any (0.12.0+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<FieldingError>,
 }
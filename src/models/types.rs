@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Defensive positions with official scoring numbers
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Position {
     Pitcher = 1,
     Catcher = 2,
@@ -52,6 +52,15 @@ impl Position {
     pub fn to_number(self) -> u8 {
         self as u8
     }
+
+    /// Value stored in `game_lineups.defensive_position`: `1`–`9`, or
+    /// `DH`.
+    pub fn to_db_value(self) -> String {
+        match self {
+            Position::DesignatedHitter => "DH".to_string(),
+            other => other.to_number().to_string(),
+        }
+    }
 }
 
 impl fmt::Display for Position {
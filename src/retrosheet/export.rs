@@ -0,0 +1,896 @@
+//! A stored game as a Retrosheet event file.
+//!
+//! The header comes from the `games` row and the umpires, plus the
+//! pitchers' decisions once the game is over; the `start` records from the
+//! lineup cards. Every plate appearance becomes a `play` record. The runner
+//! events between pitches follow as records of their own, with no pitches
+//! since the stored game does not say on which pitch they happened, and
+//! the substitutions as `sub` records in the gap they were made in. The
+//! earned runs of each pitcher close the game as `data,er` records.
+//!
+//! Players get Retrosheet-style ids built from their names (`smitj001`),
+//! unique within the game.
+
+use super::{Appearance, EventGame, PlayRecord, Record, location};
+use crate::db::lineups::batting_slot_player;
+use crate::db::runner_movements::RunnerMovementRow;
+use crate::db::umpire::list_game_umpires;
+use crate::engine::reducer::{STANDALONE_TYPES, plate_appearance_from_row};
+use crate::models::events::{SubstitutionData, SubstitutionKind};
+use crate::models::field_zone::FieldZone;
+use crate::models::plate_appearance::{
+    DoublePlayKind, DroppedThirdStrike, HitModifier, PlateAppearance, PlateAppearanceOutcome,
+    PlateAppearanceStep,
+};
+use crate::models::types::{GameStatus, HalfInning, Pitch};
+use crate::stats::GameRecord;
+use crate::stats::alignment::substitution;
+use crate::stats::box_score::{BoxScore, Decision};
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+use std::collections::{HashMap, HashSet};
+
+/// Build the event file records of game `game_pk` (the `games` row id).
+pub fn export_event_game(conn: &Connection, game_pk: i64) -> Result<EventGame> {
+    let (game_id, venue, date, time, dh, status): (
+        String,
+        Option<String>,
+        String,
+        Option<String>,
+        bool,
+        i64,
+    ) = conn
+        .query_row(
+            "SELECT game_id, venue, game_date, game_time,
+                    COALESCE(at_uses_dh, 0) OR COALESCE(ht_uses_dh, 0), status
+             FROM games WHERE id = ?1",
+            params![game_pk],
+            |r| {
+                Ok((
+                    r.get(0)?,
+                    r.get(1)?,
+                    r.get(2)?,
+                    r.get(3)?,
+                    r.get(4)?,
+                    r.get(5)?,
+                ))
+            },
+        )
+        .with_context(|| format!("Game {game_pk} not found"))?;
+    let game = GameRecord::load(conn, game_pk)?;
+    let mut ids = PlayerIds::new(conn);
+
+    let mut records = vec![Record::Version("2".to_string())];
+    let mut info = |key: &str, value: String| {
+        records.push(Record::Info {
+            key: key.to_string(),
+            value,
+        })
+    };
+    info("visteam", team_code(conn, game.away_team_id)?);
+    info("hometeam", team_code(conn, game.home_team_id)?);
+    if let Some(venue) = venue {
+        info("site", venue);
+    }
+    info("date", date.replace('-', "/"));
+    info("number", "0".to_string());
+    if let Some(time) = time {
+        info("starttime", time);
+    }
+    info("usedh", dh.to_string());
+    for umpire in list_game_umpires(conn, game_pk)? {
+        let key = match umpire.position.as_str() {
+            "HP" => "umphome",
+            "1B" => "ump1b",
+            "2B" => "ump2b",
+            "3B" => "ump3b",
+            "LF" => "umplf",
+            "RF" => "umprf",
+            _ => continue,
+        };
+        info(key, umpire.umpire_name.unwrap_or_default());
+    }
+
+    let finished = GameStatus::try_from(status) == Ok(GameStatus::Regulation);
+    let box_score = finished.then(|| BoxScore::build(&game, &HashMap::new(), Vec::new()));
+    let pitchers: Vec<_> = box_score
+        .iter()
+        .flat_map(|b| b.away.pitchers.iter().chain(&b.home.pitchers))
+        .collect();
+    for (decision, key) in [
+        (Decision::Win, "wp"),
+        (Decision::Loss, "lp"),
+        (Decision::Save, "save"),
+    ] {
+        if let Some(p) = pitchers.iter().find(|p| p.decision == Some(decision)) {
+            let id = ids.get(p.line.player_id)?.0;
+            records.push(Record::Info {
+                key: key.to_string(),
+                value: id,
+            });
+        }
+    }
+
+    let mut starters = game.lineup_cards();
+    starters.sort_by_key(|(team_id, spot)| {
+        (
+            *team_id == game.home_team_id,
+            spot.batting_order > 9,
+            spot.batting_order,
+        )
+    });
+    for (team_id, spot) in &starters {
+        let (player, name) = ids.get(spot.player_id)?;
+        records.push(Record::Start(Appearance {
+            player,
+            name,
+            team: (*team_id == game.home_team_id) as u8,
+            slot: slot(spot.batting_order),
+            position: spot.position.to_number(),
+        }));
+    }
+
+    let mut timeline = Timeline {
+        conn,
+        game: &game,
+        ids: &mut ids,
+        records: &mut records,
+    };
+    timeline.gap(None, None)?;
+    for row in &game.plate_appearances {
+        let pa = plate_appearance_from_row(row);
+        timeline.gap(Some(row.seq), Some(&pa))?;
+    }
+
+    for p in pitchers {
+        let id = ids.get(p.line.player_id)?.0;
+        records.push(Record::Other(vec![
+            "data".to_string(),
+            "er".to_string(),
+            id,
+            p.line.earned_runs.to_string(),
+        ]));
+    }
+
+    Ok(EventGame {
+        id: game_id,
+        records,
+    })
+}
+
+fn team_code(conn: &Connection, team_id: i64) -> Result<String> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(abbreviation, name) FROM teams WHERE id = ?1",
+        params![team_id],
+        |r| r.get(0),
+    )?)
+}
+
+/// Batting slot of a lineup spot; a pitcher who does not bat is `0`.
+fn slot(batting_order: u8) -> u8 {
+    if batting_order > 9 { 0 } else { batting_order }
+}
+
+/// Retrosheet ids of the players, given out in order of appearance.
+struct PlayerIds<'c> {
+    conn: &'c Connection,
+    /// Player id → (Retrosheet id, full name).
+    ids: HashMap<i64, (String, String)>,
+    used: HashSet<String>,
+}
+
+impl<'c> PlayerIds<'c> {
+    fn new(conn: &'c Connection) -> Self {
+        Self {
+            conn,
+            ids: HashMap::new(),
+            used: HashSet::new(),
+        }
+    }
+
+    fn get(&mut self, player_id: i64) -> Result<(String, String)> {
+        if let Some(known) = self.ids.get(&player_id) {
+            return Ok(known.clone());
+        }
+        let (first, last): (String, String) = self.conn.query_row(
+            "SELECT first_name, COALESCE(last_name, '') FROM players WHERE id = ?1",
+            params![player_id],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+        let letters = |name: &str| -> Vec<char> {
+            name.chars()
+                .filter(char::is_ascii_alphabetic)
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        };
+        let mut stem: String = letters(&last)
+            .into_iter()
+            .chain(std::iter::repeat('-'))
+            .take(4)
+            .collect();
+        stem.push(letters(&first).first().copied().unwrap_or('-'));
+        let id = (1..)
+            .map(|n| format!("{stem}{n:03}"))
+            .find(|id| !self.used.contains(id))
+            .unwrap_or_default();
+        self.used.insert(id.clone());
+        let known = (id, format!("{first} {last}").trim().to_string());
+        self.ids.insert(player_id, known.clone());
+        Ok(known)
+    }
+}
+
+/// Writes the plays in the order they happened.
+struct Timeline<'a, 'c> {
+    conn: &'c Connection,
+    game: &'a GameRecord,
+    ids: &'a mut PlayerIds<'c>,
+    records: &'a mut Vec<Record>,
+}
+
+impl Timeline<'_, '_> {
+    /// The plate appearance `seq`, if any, then what happened before the
+    /// next one: the runner events and the substitutions. A runner event
+    /// goes before the substitutions when its runner is replaced in them.
+    fn gap(&mut self, seq: Option<i64>, pa: Option<&PlateAppearance>) -> Result<()> {
+        let game = self.game;
+        let rows: Vec<&RunnerMovementRow> = game
+            .movements
+            .iter()
+            .filter(|rm| {
+                rm.pa_seq == seq && STANDALONE_TYPES.contains(&rm.advancement_type.as_str())
+            })
+            .collect();
+        let mut groups = between_groups(&rows);
+
+        if let (Some(seq), Some(pa)) = (seq, pa) {
+            // A strikeout-throw-out double play is one record: `K+CS2(26)/DP`.
+            let throw_out = pa
+                .double_play
+                .as_ref()
+                .is_some_and(|dp| dp.kind == DoublePlayKind::StrikeoutThrowOut);
+            let merged = throw_out
+                .then(|| {
+                    groups.iter().position(|g| {
+                        matches!(
+                            g[0].advancement_type.as_str(),
+                            "caught_stealing" | "pickoff" | "pickoff_caught_stealing"
+                        )
+                    })
+                })
+                .flatten()
+                .map(|i| groups.remove(i));
+            let own: Vec<&RunnerMovementRow> = game
+                .movements
+                .iter()
+                .filter(|rm| {
+                    rm.pa_seq == Some(seq)
+                        && !STANDALONE_TYPES.contains(&rm.advancement_type.as_str())
+                })
+                .collect();
+            let row = game
+                .plate_appearances
+                .iter()
+                .find(|r| r.seq == seq)
+                .expect("the plate appearance is in the game");
+            let (pitches, count) = pitch_string(pa);
+            let batter = self.ids.get(pa.batter_id)?.0;
+            self.records.push(Record::Play(PlayRecord {
+                inning: pa.inning,
+                team: (row.half_inning == "Bottom") as u8,
+                batter,
+                count,
+                pitches,
+                event: pa_event(pa, &own, merged.as_deref()),
+            }));
+        }
+
+        let subs: Vec<SubstitutionData> = game
+            .events
+            .iter()
+            .filter(|ev| ev.pa_seq == seq)
+            .filter_map(substitution)
+            .collect();
+        let replaced: Vec<i64> = subs.iter().filter_map(|d| d.player_out_id).collect();
+        let (before, after): (Vec<_>, Vec<_>) = groups.into_iter().partition(|g| {
+            g.iter()
+                .any(|rm| rm.runner_id.is_some_and(|id| replaced.contains(&id)))
+        });
+        for group in before {
+            self.between(seq, &group)?;
+        }
+        for d in &subs {
+            self.sub(d)?;
+        }
+        for group in after {
+            self.between(seq, &group)?;
+        }
+        Ok(())
+    }
+
+    fn sub(&mut self, d: &SubstitutionData) -> Result<()> {
+        let (player, name) = self.ids.get(d.player_in_id)?;
+        let position = match d.kind {
+            SubstitutionKind::PinchHitter => 11,
+            SubstitutionKind::PinchRunner => 12,
+            _ => d.position.to_number(),
+        };
+        self.records.push(Record::Sub(Appearance {
+            player,
+            name,
+            team: (d.team_id == self.game.home_team_id) as u8,
+            slot: slot(d.batting_order),
+            position,
+        }));
+        Ok(())
+    }
+
+    /// A record for runner events between pitches: `radj` for the
+    /// tie-break runner, a `play` record at the current batter otherwise.
+    fn between(&mut self, seq: Option<i64>, group: &[&RunnerMovementRow]) -> Result<()> {
+        let first = group[0];
+        let half = HalfInning::from_str_loose(&first.half_inning);
+        if first.advancement_type == "tie_break" {
+            for rm in group {
+                let runner = match rm.runner_id {
+                    Some(id) => Some(id),
+                    None => {
+                        batting_slot_player(self.conn, self.game.game_pk, half, rm.batter_order)?
+                    }
+                };
+                if let Some(runner) = runner {
+                    self.records.push(Record::RunnerAdjustment {
+                        player: self.ids.get(runner)?.0,
+                        base: base_char(&rm.end_base).to_digit(10).unwrap_or(2) as u8,
+                    });
+                }
+            }
+            return Ok(());
+        }
+        let Some(batter) = self.batter_after(seq, first)? else {
+            return Ok(());
+        };
+        let batter = self.ids.get(batter)?.0;
+        self.records.push(Record::Play(PlayRecord {
+            inning: first.inning as u32,
+            team: (half == HalfInning::Bottom) as u8,
+            batter,
+            count: "??".to_string(),
+            pitches: String::new(),
+            event: between_event(group),
+        }));
+        Ok(())
+    }
+
+    /// The batter at the plate during a runner event: the next one of the
+    /// batting team, who may lead off its next inning after a third out.
+    fn batter_after(&self, seq: Option<i64>, rm: &RunnerMovementRow) -> Result<Option<i64>> {
+        let after = |r: &&crate::db::plate_appearances::PlateAppearanceRow| {
+            seq.is_none_or(|s| r.seq > s) && r.half_inning == rm.half_inning
+        };
+        let pas = &self.game.plate_appearances;
+        if let Some(next) = pas.iter().find(|r| after(r) && r.inning == rm.inning) {
+            return Ok(Some(next.batter_id));
+        }
+        if let Some(next) = pas.iter().find(after) {
+            return Ok(Some(next.batter_id));
+        }
+        let last = pas
+            .iter()
+            .rev()
+            .find(|r| r.half_inning == rm.half_inning)
+            .map_or(0, |r| r.batter_order);
+        let half = HalfInning::from_str_loose(&rm.half_inning);
+        Ok(batting_slot_player(
+            self.conn,
+            self.game.game_pk,
+            half,
+            last % 9 + 1,
+        )?)
+    }
+}
+
+/// Split the runner events of a gap into records: the runners moving on
+/// the same steal, wild pitch, passed ball, indifference or balk share
+/// one; a runner caught or picked off has a record of their own.
+fn between_groups<'a>(rows: &[&'a RunnerMovementRow]) -> Vec<Vec<&'a RunnerMovementRow>> {
+    let mut groups: Vec<Vec<&RunnerMovementRow>> = Vec::new();
+    for rm in rows {
+        let joins = groups.last().is_some_and(|g: &Vec<&RunnerMovementRow>| {
+            g[0].advancement_type == rm.advancement_type
+                && matches!(
+                    rm.advancement_type.as_str(),
+                    "steal" | "wild_pitch" | "passed_ball" | "defensive_indifference" | "balk"
+                )
+                && g.iter().all(|o| o.batter_order != rm.batter_order)
+        });
+        match groups.last_mut() {
+            Some(g) if joins => g.push(rm),
+            _ => groups.push(vec![rm]),
+        }
+    }
+    groups
+}
+
+/// `1`, `2`, `3` or `H` of a `runner_movements` base; `B` for the batter.
+fn base_char(base: &str) -> char {
+    match base {
+        "1B" => '1',
+        "2B" => '2',
+        "3B" => '3',
+        "HOME" => 'H',
+        _ => 'B',
+    }
+}
+
+/// The base after `start`: where a runner caught stealing was going.
+fn next_base(start: &str) -> char {
+    match start {
+        "1B" => '2',
+        "2B" => '3',
+        _ => 'H',
+    }
+}
+
+fn digits(fielders: Option<&str>) -> String {
+    fielders
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect()
+}
+
+fn advance_list(rows: &[&RunnerMovementRow]) -> String {
+    let mut rows = rows.to_vec();
+    rows.sort_by_key(|rm| std::cmp::Reverse(base_char(&rm.start_base)));
+    rows.iter()
+        .map(|rm| format!("{}-{}", base_char(&rm.start_base), base_char(&rm.end_base)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn between_event(group: &[&RunnerMovementRow]) -> String {
+    let first = group[0];
+    let caught = |verb: &str, base: char| {
+        let chain = digits(first.fielders.as_deref());
+        if chain.is_empty() {
+            format!("{verb}{base}")
+        } else {
+            format!("{verb}{base}({chain})")
+        }
+    };
+    match first.advancement_type.as_str() {
+        "steal" => group
+            .iter()
+            .map(|rm| format!("SB{}", base_char(&rm.end_base)))
+            .collect::<Vec<_>>()
+            .join(";"),
+        "caught_stealing" => caught("CS", next_base(&first.start_base)),
+        "pickoff" => caught("PO", base_char(&first.start_base)),
+        "pickoff_caught_stealing" => caught("POCS", next_base(&first.start_base)),
+        kind => {
+            let verb = match kind {
+                "wild_pitch" => "WP",
+                "passed_ball" => "PB",
+                "defensive_indifference" => "DI",
+                _ => "BK",
+            };
+            format!("{verb}.{}", advance_list(group))
+        }
+    }
+}
+
+/// Retrosheet pitch string of the plate appearance, with the count before
+/// the last pitch (`??` when no pitch was recorded).
+fn pitch_string(pa: &PlateAppearance) -> (String, String) {
+    let mut pitches: String = pa
+        .pitches_sequence
+        .iter()
+        .filter_map(|step| match step {
+            PlateAppearanceStep::Pitch(p) => Some(match p {
+                Pitch::Ball => 'B',
+                Pitch::CalledStrike => 'C',
+                Pitch::SwingingStrike => 'S',
+                Pitch::Foul => 'F',
+                Pitch::FoulBunt => 'L',
+                Pitch::InPlay => 'X',
+                Pitch::HittedBy => 'H',
+            }),
+            _ => None,
+        })
+        .collect();
+    let batted = !matches!(
+        pa.outcome,
+        PlateAppearanceOutcome::Walk
+            | PlateAppearanceOutcome::IntentionalWalk
+            | PlateAppearanceOutcome::HitByPitch
+            | PlateAppearanceOutcome::CatcherInterference
+            | PlateAppearanceOutcome::Strikeout(_)
+            | PlateAppearanceOutcome::DroppedThirdStrike(_)
+    );
+    if batted && !pitches.ends_with('X') {
+        pitches.push('X');
+    }
+    if matches!(pa.outcome, PlateAppearanceOutcome::HitByPitch) && !pitches.ends_with('H') {
+        pitches.push('H');
+    }
+    if pitches.is_empty() {
+        return (pitches, "??".to_string());
+    }
+    let (mut balls, mut strikes) = (0u8, 0u8);
+    for p in pitches.chars().take(pitches.len() - 1) {
+        match p {
+            'B' => balls += 1,
+            'F' if strikes >= 2 => {}
+            'C' | 'S' | 'F' | 'L' => strikes += 1,
+            _ => {}
+        }
+    }
+    (pitches, format!("{}{}", balls.min(3), strikes.min(2)))
+}
+
+fn hit_event(letter: &str, zone: Option<FieldZone>, modifier: Option<HitModifier>) -> String {
+    let loc = zone.map(location).unwrap_or_default();
+    let fielder: String = loc.chars().take(1).collect();
+    match modifier {
+        Some(HitModifier::GroundRule) if loc.is_empty() => "DGR".to_string(),
+        Some(HitModifier::GroundRule) => format!("DGR/{loc}"),
+        Some(HitModifier::Bunt) => format!("{letter}{fielder}/BG{loc}"),
+        Some(HitModifier::InsideThePark) if loc.is_empty() => format!("{letter}/IPHR"),
+        Some(HitModifier::InsideThePark) => format!("{letter}{fielder}/{loc}"),
+        None if loc.is_empty() => letter.to_string(),
+        // A fielder after `HR` would make it inside the park.
+        None if letter == "HR" => format!("HR/{loc}"),
+        None => format!("{letter}{fielder}/{loc}"),
+    }
+}
+
+/// Event field of a plate appearance. `rows` are its own runner
+/// movements; `merged` the runner event of a strikeout-throw-out double
+/// play.
+fn pa_event(
+    pa: &PlateAppearance,
+    rows: &[&RunnerMovementRow],
+    merged: Option<&[&RunnerMovementRow]>,
+) -> String {
+    use PlateAppearanceOutcome as O;
+
+    let runner_outs: Vec<&RunnerMovementRow> = rows
+        .iter()
+        .copied()
+        .filter(|rm| rm.start_base != "BAT" && rm.is_out)
+        .collect();
+    let mut advances: Vec<String> = Vec::new();
+    let mut moved: Vec<&RunnerMovementRow> = rows
+        .iter()
+        .copied()
+        .filter(|rm| rm.start_base != "BAT" && !rm.is_out && rm.start_base != rm.end_base)
+        .collect();
+    moved.sort_by_key(|rm| std::cmp::Reverse(base_char(&rm.start_base)));
+    let overrides = &pa.runner_overrides;
+    for rm in moved {
+        let mut advance = format!("{}-{}", base_char(&rm.start_base), base_char(&rm.end_base));
+        if let Some(error) = overrides
+            .iter()
+            .find(|o| o.order == rm.batter_order)
+            .and_then(|o| o.error)
+        {
+            let throwing = if error.throwing { "/TH" } else { "" };
+            advance.push_str(&format!("(E{}{throwing})", error.fielder));
+        }
+        advances.push(advance);
+    }
+
+    // The batter's own advance, when it differs from what the play gives.
+    let batter_end = rows
+        .iter()
+        .find(|rm| rm.start_base == "BAT" && !rm.is_out)
+        .map(|rm| base_char(&rm.end_base));
+    let default_end = match &pa.outcome {
+        O::Single { .. } => Some('1'),
+        O::Double { .. } => Some('2'),
+        O::Triple { .. } => Some('3'),
+        O::HomeRun { .. } => Some('H'),
+        O::DroppedThirdStrike(how) if how.batter_reaches() => None,
+        O::DroppedThirdStrike(_) => Some('B'),
+        _ => Some('1'),
+    };
+    if let Some(end) = batter_end
+        && Some(end) != default_end
+    {
+        advances.push(format!("B-{end}"));
+    }
+
+    let description = match &pa.outcome {
+        O::Walk => "W".to_string(),
+        O::IntentionalWalk => "IW".to_string(),
+        O::HitByPitch => "HP".to_string(),
+        O::CatcherInterference => "C/E2".to_string(),
+        O::Strikeout(_) => match merged {
+            Some(group) => format!("K+{}/DP", between_event(group)),
+            None => "K".to_string(),
+        },
+        O::DroppedThirdStrike(DroppedThirdStrike::WildPitch) => "K+WP".to_string(),
+        O::DroppedThirdStrike(DroppedThirdStrike::PassedBall) => "K+PB".to_string(),
+        O::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { sequence }) => {
+            format!("K{}", digits(Some(sequence)))
+        }
+        O::Single { zone, modifier } => hit_event("S", *zone, *modifier),
+        O::Double { zone, modifier } => hit_event("D", *zone, *modifier),
+        O::Triple { zone, modifier } => hit_event("T", *zone, *modifier),
+        O::HomeRun { zone, modifier } => hit_event("HR", *zone, *modifier),
+        O::ReachedOnError {
+            fielder, throwing, ..
+        } => format!("E{fielder}{}", if *throwing { "/TH" } else { "" }),
+        O::FieldersChoice { fielder, .. } => {
+            let mut outs: Vec<&RunnerMovementRow> = runner_outs.clone();
+            outs.sort_by_key(|rm| std::cmp::Reverse(base_char(&rm.start_base)));
+            for rm in outs {
+                advances.push(format!(
+                    "{}X{}({})",
+                    base_char(&rm.start_base),
+                    next_base(&rm.start_base),
+                    digits(rm.fielders.as_deref())
+                ));
+            }
+            match &pa.double_play {
+                Some(dp) => format!("FC{fielder}/{}", dp.label()),
+                None => format!("FC{fielder}"),
+            }
+        }
+        O::Out => "99".to_string(),
+        _ => fielded_out(pa, &runner_outs),
+    };
+
+    if advances.is_empty() {
+        description
+    } else {
+        format!("{description}.{}", advances.join(";"))
+    }
+}
+
+/// `63/G`, `8/SF`, `64(1)3/GDP`, `6(B)64(2)/LDP`: the batter's out, and
+/// the runners put out on the same play. On a ground ball the runners are
+/// retired first and a chain starting with the fielder who made the
+/// previous putout leaves that fielder out.
+fn fielded_out(pa: &PlateAppearance, runner_outs: &[&RunnerMovementRow]) -> String {
+    use PlateAppearanceOutcome as O;
+
+    let (chain, air, mut modifiers): (String, bool, Vec<&str>) = match &pa.outcome {
+        O::UnassistedOut { fielder, sacrifice } => (
+            fielder.to_string(),
+            false,
+            vec![if *sacrifice { "SH" } else { "G" }],
+        ),
+        O::GroundOut {
+            sequence,
+            sacrifice,
+        } => (
+            digits(Some(sequence)),
+            false,
+            vec![if *sacrifice { "SH" } else { "G" }],
+        ),
+        O::FlyOut {
+            fielder,
+            in_foul_territory,
+            sacrifice,
+        } => {
+            let mut modifiers = vec![if *sacrifice { "SF" } else { "F" }];
+            if *in_foul_territory {
+                modifiers.push("FL");
+            }
+            (fielder.to_string(), true, modifiers)
+        }
+        O::LineOut { fielder } => (fielder.to_string(), true, vec!["L"]),
+        O::InfieldFly { fielder } => (fielder.to_string(), true, vec!["IF"]),
+        _ => ("99".to_string(), false, Vec::new()),
+    };
+    if let Some(dp) = &pa.double_play
+        && dp.kind != DoublePlayKind::StrikeoutThrowOut
+    {
+        match modifiers.first() {
+            Some(&("G" | "F" | "L")) => modifiers[0] = dp.label(),
+            _ => modifiers.push(dp.label()),
+        }
+    }
+
+    let mut description = String::new();
+    if air {
+        description.push_str(&chain);
+        if !runner_outs.is_empty() {
+            description.push_str("(B)");
+        }
+        for rm in runner_outs {
+            description.push_str(&format!(
+                "{}({})",
+                digits(rm.fielders.as_deref()),
+                base_char(&rm.start_base)
+            ));
+        }
+    } else {
+        let mut last: Option<char> = None;
+        let mut segment = |chain: String| -> String {
+            let trimmed = match (last, chain.chars().next()) {
+                (Some(l), Some(f)) if l == f && chain.len() > 1 => chain[1..].to_string(),
+                _ => chain.clone(),
+            };
+            last = chain.chars().last();
+            trimmed
+        };
+        for rm in runner_outs {
+            let chain = segment(digits(rm.fielders.as_deref()));
+            description.push_str(&format!("{chain}({})", base_char(&rm.start_base)));
+        }
+        description.push_str(&segment(chain));
+    }
+    for modifier in modifiers {
+        description.push('/');
+        description.push_str(modifier);
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use crate::models::plate_appearance::{self, DoublePlay};
+    use crate::models::runner::{FieldingError, RunnerOverride};
+
+    /// The slot 3 batter's plate appearance.
+    fn pa(outcome: PlateAppearanceOutcome, pitches: &[Pitch]) -> PlateAppearance {
+        plate_appearance::tests::pa(1, 3, 9, outcome, pitches, 0)
+    }
+
+    fn row(
+        order: u8,
+        start: &str,
+        end: &str,
+        kind: &str,
+        fielders: Option<&str>,
+    ) -> RunnerMovementRow {
+        RunnerMovementRow {
+            fielders: fielders.map(str::to_string),
+            ..fixtures::rm(0, Some(1), "Top", order, start, end, kind)
+        }
+    }
+
+    #[test]
+    fn pitches_and_count() {
+        let single = pa(
+            PlateAppearanceOutcome::Single {
+                zone: None,
+                modifier: None,
+            },
+            &[Pitch::Ball, Pitch::CalledStrike, Pitch::Foul, Pitch::Foul],
+        );
+        assert_eq!(
+            pitch_string(&single),
+            ("BCFFX".to_string(), "12".to_string())
+        );
+        let walk = pa(PlateAppearanceOutcome::IntentionalWalk, &[]);
+        assert_eq!(pitch_string(&walk), (String::new(), "??".to_string()));
+    }
+
+    #[test]
+    fn hits_with_their_advances() {
+        let mut double = pa(
+            PlateAppearanceOutcome::Double {
+                zone: Some(FieldZone::LC),
+                modifier: None,
+            },
+            &[],
+        );
+        double.runner_overrides = vec![RunnerOverride {
+            order: 1,
+            dest: crate::models::runner::RunnerDest::Score,
+            error: Some(FieldingError {
+                fielder: 7,
+                throwing: true,
+            }),
+        }];
+        let rows = [
+            row(1, "1B", "HOME", "hit_override", None),
+            row(2, "2B", "HOME", "hit_override", None),
+            row(3, "BAT", "2B", "hit_override", None),
+        ];
+        let rows: Vec<_> = rows.iter().collect();
+        assert_eq!(pa_event(&double, &rows, None), "D7/78.2-H;1-H(E7/TH)");
+
+        let bunt = pa(
+            PlateAppearanceOutcome::Single {
+                zone: None,
+                modifier: Some(HitModifier::Bunt),
+            },
+            &[],
+        );
+        assert_eq!(pa_event(&bunt, &[], None), "S/BG");
+        let gr = pa(
+            PlateAppearanceOutcome::Double {
+                zone: Some(FieldZone::RF),
+                modifier: Some(HitModifier::GroundRule),
+            },
+            &[],
+        );
+        assert_eq!(pa_event(&gr, &[], None), "DGR/9");
+    }
+
+    #[test]
+    fn outs_and_double_plays() {
+        let mut gdp = pa(
+            PlateAppearanceOutcome::GroundOut {
+                sequence: "4-3".to_string(),
+                sacrifice: false,
+            },
+            &[],
+        );
+        gdp.double_play = Some(DoublePlay::new(DoublePlayKind::GroundBall, 2, [6, 4, 3]));
+        let rows = [
+            row(5, "1B", "OUT", "ground_out", Some("6-4")),
+            row(4, "2B", "3B", "ground_out", None),
+        ];
+        let rows: Vec<_> = rows.iter().collect();
+        assert_eq!(pa_event(&gdp, &rows, None), "64(1)3/GDP.2-3");
+
+        let mut ldp = pa(PlateAppearanceOutcome::LineOut { fielder: 6 }, &[]);
+        ldp.double_play = Some(DoublePlay::new(DoublePlayKind::LineDrive, 2, [6, 4]));
+        let rows = [row(5, "1B", "OUT", "line_out", Some("6-4"))];
+        let rows: Vec<_> = rows.iter().collect();
+        assert_eq!(pa_event(&ldp, &rows, None), "6(B)64(1)/LDP");
+
+        let fc = pa(
+            PlateAppearanceOutcome::FieldersChoice {
+                fielder: 6,
+                reached_base: crate::models::runner::RunnerDest::First,
+            },
+            &[],
+        );
+        let rows = [
+            row(5, "1B", "OUT", "fielders_choice", Some("6-4")),
+            row(6, "BAT", "1B", "fielders_choice", None),
+        ];
+        let rows: Vec<_> = rows.iter().collect();
+        assert_eq!(pa_event(&fc, &rows, None), "FC6.1X2(64)");
+
+        let sf = pa(
+            PlateAppearanceOutcome::FlyOut {
+                fielder: 8,
+                in_foul_territory: false,
+                sacrifice: true,
+            },
+            &[],
+        );
+        let rows = [row(2, "3B", "HOME", "sacrifice_fly", None)];
+        let rows: Vec<_> = rows.iter().collect();
+        assert_eq!(pa_event(&sf, &rows, None), "8/SF.3-H");
+    }
+
+    #[test]
+    fn runner_events_between_pitches() {
+        let steals = [
+            row(5, "2B", "3B", "steal", None),
+            row(6, "1B", "2B", "steal", None),
+            row(5, "3B", "HOME", "wild_pitch", None),
+            row(6, "2B", "OUT", "caught_stealing", Some("2-5")),
+        ];
+        let rows: Vec<_> = steals.iter().collect();
+        let events: Vec<String> = between_groups(&rows)
+            .iter()
+            .map(|g| between_event(g))
+            .collect();
+        assert_eq!(events, vec!["SB3;SB2", "WP.3-H", "CS3(25)"]);
+
+        let mut k = pa(
+            PlateAppearanceOutcome::Strikeout(crate::models::events::StrikeoutKind::Swinging),
+            &[],
+        );
+        k.double_play = Some(DoublePlay::new(
+            DoublePlayKind::StrikeoutThrowOut,
+            2,
+            [2, 6],
+        ));
+        let cs = [row(6, "1B", "OUT", "caught_stealing", Some("2-6"))];
+        let cs: Vec<_> = cs.iter().collect();
+        assert_eq!(pa_event(&k, &[], Some(&cs)), "K+CS2(26)/DP");
+    }
+}
@@ -0,0 +1,661 @@
+//! A Retrosheet event file game into the database.
+//!
+//! The game is created from the `info` header and the `start` records,
+//! then its `play` and `sub` records are typed into the Play Ball engine,
+//! one command line at a time, the way a scorer would have entered them.
+//! The engine keeps every check it makes live: a record it refuses stops
+//! the import and nothing of the game is kept.
+
+use super::translate::play_lines;
+use super::{Appearance, EventGame, Record};
+use crate::cli::screens::game::save_lineup;
+use crate::db::game_queries::shared_league_rules;
+use crate::db::umpire::{UmpirePosition, assign_umpire};
+use crate::engine::play_ball::run_play_ball_engine;
+use crate::models::game_state::GameState;
+use crate::models::rules::RuleSet;
+use crate::models::types::{GameStatus, HalfInning, Position};
+use crate::ui::Ui;
+use crate::ui::events::UiEvent;
+use anyhow::{Result, anyhow, bail};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::collections::{HashMap, VecDeque};
+
+/// Create the game of `game` and replay its plays. Returns the new `games`
+/// row id. Nothing is written when a team or a player cannot be found,
+/// when the game already exists or when a record cannot be scored.
+pub fn import_event_game(conn: &mut Connection, game: &EventGame) -> Result<i64> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM games WHERE game_id = ?1)",
+        params![game.id],
+        |r| r.get(0),
+    )?;
+    if exists {
+        bail!("Game {} already exists", game.id);
+    }
+
+    let team = |key: &str| -> Result<i64> {
+        let code = game
+            .info(key)
+            .ok_or_else(|| anyhow!("Game {}: no {key} record", game.id))?;
+        conn.query_row(
+            "SELECT id FROM teams WHERE UPPER(TRIM(abbreviation)) = UPPER(?1) ORDER BY id",
+            params![code.trim()],
+            |r| r.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| anyhow!("Team {code} not found"))
+    };
+    let team_ids = [team("visteam")?, team("hometeam")?];
+
+    let players = find_players(conn, game, team_ids)?;
+
+    let starters: Vec<&Appearance> = game
+        .records
+        .iter()
+        .filter_map(|r| match r {
+            Record::Start(a) => Some(a),
+            _ => None,
+        })
+        .collect();
+    let uses_dh = [0, 1].map(|team| starters.iter().any(|a| a.team == team && a.slot == 0));
+
+    let (league_id, mut rules) = match shared_league_rules(conn, team_ids[0], team_ids[1])? {
+        Some((id, rules)) => (Some(id), rules),
+        None => (None, RuleSet::default()),
+    };
+    rules.dh_allowed |= uses_dh.contains(&true);
+    if rules.tie_break_inning.is_none() {
+        rules.tie_break_inning = game
+            .records
+            .iter()
+            .skip_while(|r| !matches!(r, Record::RunnerAdjustment { .. }))
+            .find_map(|r| match r {
+                Record::Play(p) => Some(p.inning),
+                _ => None,
+            });
+    }
+
+    // The engine saves every play as it goes: a savepoint around the game
+    // takes all of it back when a record is refused.
+    conn.execute_batch("SAVEPOINT retrosheet_import")?;
+    let result =
+        insert_game(conn, game, team_ids, uses_dh, league_id, &rules).and_then(|game_pk| {
+            replay(conn, game, game_pk, team_ids, &players, &starters).map(|()| game_pk)
+        });
+    match result {
+        Ok(game_pk) => {
+            conn.execute_batch("RELEASE retrosheet_import")?;
+            Ok(game_pk)
+        }
+        Err(e) => {
+            conn.execute_batch("ROLLBACK TO retrosheet_import; RELEASE retrosheet_import")?;
+            Err(e)
+        }
+    }
+}
+
+/// Insert the `games` row of `game`; returns its id.
+fn insert_game(
+    conn: &Connection,
+    game: &EventGame,
+    team_ids: [i64; 2],
+    uses_dh: [bool; 2],
+    league_id: Option<i64>,
+    rules: &RuleSet,
+) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO games (game_id, home_team_id, away_team_id, venue, game_date, game_time,
+                            at_uses_dh, ht_uses_dh, status, league_id, game_kind,
+                            scheduled_innings, dh_allowed, mercy_rules, max_runs_per_half,
+                            tie_break_inning)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        params![
+            game.id,
+            team_ids[1],
+            team_ids[0],
+            game.info("site").unwrap_or_default(),
+            game.info("date").unwrap_or_default().replace('/', "-"),
+            game.info("starttime"),
+            uses_dh[0],
+            uses_dh[1],
+            i64::from(GameStatus::InProgress),
+            league_id,
+            rules.kind.as_str(),
+            rules.innings,
+            rules.dh_allowed,
+            rules.mercy_rules_json(),
+            rules.max_runs_per_half,
+            rules.tie_break_inning
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// A player of the event file in this database.
+#[derive(Debug, Clone, Copy)]
+struct Player {
+    id: i64,
+    /// Number worn in this game.
+    jersey: i32,
+}
+
+/// The players of every `start` and `sub` record, by team and Retrosheet
+/// id, matched by full name on the team's roster.
+fn find_players(
+    conn: &Connection,
+    game: &EventGame,
+    team_ids: [i64; 2],
+) -> Result<HashMap<(u8, String), Player>> {
+    let mut players = HashMap::new();
+    let mut problems = Vec::new();
+    for record in &game.records {
+        let (Record::Start(a) | Record::Sub(a)) = record else {
+            continue;
+        };
+        let key = (a.team, a.player.clone());
+        if players.contains_key(&key) {
+            continue;
+        }
+        let mut stmt = conn.prepare(
+            "SELECT id, CASE WHEN ?3 THEN number ELSE COALESCE(away_number, number) END
+             FROM players
+             WHERE team_id = ?1
+               AND LOWER(TRIM(first_name || ' ' || COALESCE(last_name, ''))) = LOWER(TRIM(?2))",
+        )?;
+        let found: Vec<Player> = stmt
+            .query_map(
+                params![team_ids[a.team as usize], a.name, a.team == 1],
+                |r| {
+                    Ok(Player {
+                        id: r.get(0)?,
+                        jersey: r.get(1)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        match found.as_slice() {
+            [player] => {
+                players.insert(key, *player);
+            }
+            [] => problems.push(format!("{} ({}) not found", a.name, a.player)),
+            _ => problems.push(format!("{} ({}) is ambiguous", a.name, a.player)),
+        }
+    }
+    if !problems.is_empty() {
+        bail!("Players: {}", problems.join(", "));
+    }
+    Ok(players)
+}
+
+fn replay(
+    conn: &mut Connection,
+    game: &EventGame,
+    game_pk: i64,
+    team_ids: [i64; 2],
+    players: &HashMap<(u8, String), Player>,
+    starters: &[&Appearance],
+) -> Result<()> {
+    for team in [0u8, 1] {
+        let lineup: Vec<(i64, i32, String)> = starters
+            .iter()
+            .filter(|a| a.team == team)
+            .map(|a| {
+                let position = Position::from_number(a.position)
+                    .ok_or_else(|| anyhow!("{} starts at position {}", a.name, a.position))?;
+                let order = if a.slot == 0 { 10 } else { a.slot as i32 };
+                Ok((
+                    players[&(team, a.player.clone())].id,
+                    order,
+                    position.to_db_value(),
+                ))
+            })
+            .collect::<Result<_>>()?;
+        save_lineup(conn, &game.id, team_ids[team as usize], &lineup)?;
+    }
+    assign_umpires(conn, game, game_pk)?;
+
+    let mut ui = ReplayUi::new(&game.records, players);
+    run_play_ball_engine(conn, &mut ui, game_pk, &game.id, team_ids[0], team_ids[1]);
+    if let Some(error) = ui.error {
+        bail!("Game {}: {error}", game.id);
+    }
+    if let Some(left) = game.records[ui.next..].iter().find(|r| match r {
+        Record::Play(p) => !matches!(p.event.as_str(), "" | "NP"),
+        _ => false,
+    }) {
+        bail!("Game {}: {left}: the game is already over", game.id);
+    }
+    Ok(())
+}
+
+/// The umpires of the header who are already in the database.
+fn assign_umpires(conn: &Connection, game: &EventGame, game_pk: i64) -> Result<()> {
+    for (key, position) in [
+        ("umphome", UmpirePosition::HomePlate),
+        ("ump1b", UmpirePosition::FirstBase),
+        ("ump2b", UmpirePosition::SecondBase),
+        ("ump3b", UmpirePosition::ThirdBase),
+        ("umplf", UmpirePosition::LeftField),
+        ("umprf", UmpirePosition::RightField),
+    ] {
+        let Some(name) = game.info(key).filter(|n| !n.trim().is_empty()) else {
+            continue;
+        };
+        let umpire: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM umpires WHERE LOWER(first_name || ' ' || last_name) = LOWER(?1)
+                 ORDER BY id",
+                params![name.trim()],
+                |r| r.get(0),
+            )
+            .optional()?;
+        if let Some(umpire) = umpire {
+            assign_umpire(conn, game_pk, umpire, position)?;
+        }
+    }
+    Ok(())
+}
+
+/// Grammar token of a fielding position.
+fn position_token(position: u8) -> Option<&'static str> {
+    let token = match position {
+        1 => "p",
+        2 => "c",
+        3 => "1b",
+        4 => "2b",
+        5 => "3b",
+        6 => "ss",
+        7 => "lf",
+        8 => "cf",
+        9 => "rf",
+        10 => "dh",
+        _ => return None,
+    };
+    Some(token)
+}
+
+/// Types the records of a game into the engine. Each record is turned
+/// into command lines against the state the engine shows when they are
+/// due. The first error ends the replay.
+struct ReplayUi<'a> {
+    records: &'a [Record],
+    /// Index of the next record to enter.
+    next: usize,
+    players: &'a HashMap<(u8, String), Player>,
+    /// Who is in each batting slot (`0` for a pitcher who does not bat)
+    /// and at which position, by team.
+    lineups: [HashMap<u8, (String, u8)>; 2],
+    started: bool,
+    pending: VecDeque<String>,
+    /// The record the pending lines come from.
+    current: String,
+    state: GameState,
+    error: Option<String>,
+}
+
+impl<'a> ReplayUi<'a> {
+    fn new(records: &'a [Record], players: &'a HashMap<(u8, String), Player>) -> Self {
+        let mut lineups: [HashMap<u8, (String, u8)>; 2] = Default::default();
+        for record in records {
+            if let Record::Start(a) = record {
+                lineups[a.team as usize].insert(a.slot, (a.player.clone(), a.position));
+            }
+        }
+        Self {
+            records,
+            next: 0,
+            players,
+            lineups,
+            started: false,
+            pending: VecDeque::new(),
+            current: String::new(),
+            state: GameState::new(),
+            error: None,
+        }
+    }
+
+    fn batting_team(&self) -> u8 {
+        (self.state.half == HalfInning::Bottom) as u8
+    }
+
+    /// Lines of the next record that has anything to score.
+    fn next_lines(&mut self) -> Result<Option<Vec<String>>, String> {
+        while let Some(record) = self.records.get(self.next) {
+            self.next += 1;
+            self.current = record.to_string();
+            let lines = match record {
+                Record::Play(play) => {
+                    if play.team != self.batting_team() {
+                        return Err(format!(
+                            "the {} team is not at bat",
+                            if play.team == 0 { "visiting" } else { "home" }
+                        ));
+                    }
+                    let batter = self.players.get(&(play.team, play.batter.clone()));
+                    if batter.map(|p| p.id) != self.state.current_batter_id {
+                        return Err(format!("{} is not the batter up", play.batter));
+                    }
+                    play_lines(play, &self.state)?
+                }
+                Record::Sub(_) => {
+                    let start = self.next - 1;
+                    while matches!(self.records.get(self.next), Some(Record::Sub(_))) {
+                        self.next += 1;
+                    }
+                    let subs: Vec<&Appearance> = self.records[start..self.next]
+                        .iter()
+                        .filter_map(|r| match r {
+                            Record::Sub(a) => Some(a),
+                            _ => None,
+                        })
+                        .collect();
+                    self.current = subs
+                        .iter()
+                        .map(|a| format!("sub,{a}"))
+                        .collect::<Vec<_>>()
+                        .join(" / ");
+                    self.sub_lines(&subs)?
+                }
+                _ => continue,
+            };
+            if !lines.is_empty() {
+                return Ok(Some(lines));
+            }
+        }
+        Ok(None)
+    }
+
+    /// One line per team for consecutive `sub` records, so a double
+    /// switch is checked as a whole.
+    fn sub_lines(&mut self, subs: &[&Appearance]) -> Result<Vec<String>, String> {
+        let mut lines: Vec<(u8, Vec<String>)> = Vec::new();
+        for a in subs {
+            let Some(segment) = self.sub_segment(a)? else {
+                continue;
+            };
+            match lines.iter_mut().find(|(team, _)| *team == a.team) {
+                Some((_, segments)) => segments.push(segment),
+                None => lines.push((a.team, vec![segment])),
+            }
+        }
+        Ok(lines
+            .into_iter()
+            .map(|(_, segments)| segments.join(", "))
+            .collect())
+    }
+
+    /// The command for one `sub` record, `None` when it changes nothing.
+    fn sub_segment(&mut self, a: &Appearance) -> Result<Option<String>, String> {
+        let jersey = self.players[&(a.team, a.player.clone())].jersey;
+        let batting = a.team == self.batting_team();
+        let lineup = &mut self.lineups[a.team as usize];
+        let (held_by, held_at) = lineup
+            .get(&a.slot)
+            .cloned()
+            .ok_or_else(|| format!("no player in batting slot {}", a.slot))?;
+        let segment = match a.position {
+            11 | 12 if !batting => {
+                return Err("a pinch hitter or runner for the team in the field".to_string());
+            }
+            11 => {
+                if self.state.current_batter_order != Some(a.slot) {
+                    return Err(format!("batting slot {} is not up", a.slot));
+                }
+                format!("ph {jersey}")
+            }
+            12 => format!("{} pr {jersey}", a.slot),
+            _ if batting => {
+                return Err("a defensive change for the team at bat".to_string());
+            }
+            position if held_by == a.player => {
+                if position == held_at {
+                    return Ok(None);
+                }
+                let token = position_token(position)
+                    .ok_or_else(|| format!("invalid position {position}"))?;
+                format!("{} pos {token}", a.slot)
+            }
+            1 if a.slot == 0 || held_at == 1 => format!("pc {jersey}"),
+            position => {
+                let token = position_token(position)
+                    .ok_or_else(|| format!("invalid position {position}"))?;
+                format!("{} sub {jersey} {token}", a.slot)
+            }
+        };
+        // Pinch hitters and runners take the replaced player's position.
+        let position = if matches!(a.position, 11 | 12) {
+            held_at
+        } else {
+            a.position
+        };
+        lineup.insert(a.slot, (a.player.clone(), position));
+        Ok(Some(segment))
+    }
+}
+
+impl Ui for ReplayUi<'_> {
+    fn emit(&mut self, event: UiEvent) {
+        if let UiEvent::Error(message) = event
+            && self.error.is_none()
+        {
+            self.error = Some(format!("{}: {message}", self.current));
+        }
+    }
+
+    fn read_command_line(&mut self, _prompt: &str) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some("playball".to_string());
+        }
+        if let Some(line) = self.pending.pop_front() {
+            return Some(line);
+        }
+        match self.next_lines() {
+            Ok(Some(lines)) => {
+                self.pending = lines.into();
+                self.pending.pop_front()
+            }
+            Ok(None) => None,
+            Err(message) => {
+                self.error = Some(format!("{}: {message}", self.current));
+                None
+            }
+        }
+    }
+
+    fn confirm(&mut self, _question: &str) -> bool {
+        false
+    }
+
+    fn set_state(&mut self, state: &GameState) {
+        self.state = state.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Database;
+    use crate::retrosheet::export::export_event_game;
+    use crate::retrosheet::parse_event_file;
+
+    /// Teams AWY (jerseys 1-9) and HOM (jerseys 11-21), named `P N<jersey>`.
+    fn seed(conn: &Connection) {
+        for (name, abbreviation, numbers) in [("Away", "AWY", 1..=9), ("Home", "HOM", 11..=21)] {
+            conn.execute(
+                "INSERT INTO teams (name, abbreviation) VALUES (?1, ?2)",
+                params![name, abbreviation],
+            )
+            .unwrap();
+            let team_id = conn.last_insert_rowid();
+            for number in numbers {
+                conn.execute(
+                    "INSERT INTO players (team_id, number, first_name, last_name, position)
+                     VALUES (?1, ?2, 'P', ?3, 'CF')",
+                    params![team_id, number, format!("N{number}")],
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    const GAME: &str = "\
+id,HOM202604180
+version,2
+info,visteam,AWY
+info,hometeam,HOM
+info,site,North Park
+info,date,2026/04/18
+info,number,0
+info,usedh,false
+start,a1,\"P N1\",0,1,8
+start,a2,\"P N2\",0,2,6
+start,a3,\"P N3\",0,3,9
+start,a4,\"P N4\",0,4,3
+start,a5,\"P N5\",0,5,7
+start,a6,\"P N6\",0,6,5
+start,a7,\"P N7\",0,7,4
+start,a8,\"P N8\",0,8,2
+start,a9,\"P N9\",0,9,1
+start,h1,\"P N11\",1,1,8
+start,h2,\"P N12\",1,2,6
+start,h3,\"P N13\",1,3,9
+start,h4,\"P N14\",1,4,3
+start,h5,\"P N15\",1,5,7
+start,h6,\"P N16\",1,6,5
+start,h7,\"P N17\",1,7,4
+start,h8,\"P N18\",1,8,2
+start,h9,\"P N19\",1,9,1
+play,1,0,a1,12,BCFX,S8/8
+play,1,0,a2,??,,SB2
+play,1,0,a2,30,BBBB,W
+play,1,0,a3,??,,WP.2-3;1-2
+play,1,0,a3,02,CSS,K
+play,1,0,a4,00,X,8/SF.3-H
+play,1,0,a5,11,BFX,6/L
+play,1,1,h1,00,X,D9/9
+play,1,1,h2,10,BX,S7/7.2-H
+play,1,1,h3,??,,CS2(26)
+play,1,1,h3,01,CX,9/F
+sub,ph,\"P N21\",1,4,11
+play,1,1,ph,00,X,HR/89
+play,1,1,h5,00,X,43/G
+sub,ph,\"P N21\",1,4,7
+sub,h5,\"P N15\",1,5,3
+sub,rp,\"P N20\",1,9,1
+play,2,0,a6,00,X,E6
+play,2,0,a7,00,X,FC6.1X2(64)
+play,2,0,a8,??,,PB.1-2
+play,2,0,a8,00,H,HP
+play,2,0,a9,11,BCX,64(1)3/GDP
+";
+
+    /// The play and sub records, with players by name.
+    fn plays(game: &EventGame) -> Vec<String> {
+        let mut names = HashMap::new();
+        let mut out = Vec::new();
+        for record in &game.records {
+            match record {
+                Record::Start(a) => {
+                    names.insert(a.player.clone(), a.name.clone());
+                }
+                Record::Sub(a) => {
+                    names.insert(a.player.clone(), a.name.clone());
+                    out.push(format!(
+                        "sub {} {} {} {}",
+                        a.name, a.team, a.slot, a.position
+                    ));
+                }
+                Record::Play(p) => out.push(format!(
+                    "play {} {} {} {} {} {}",
+                    p.inning, p.team, names[&p.batter], p.count, p.pitches, p.event
+                )),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn imported_game_exports_the_same_plays() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        seed(db.get_connection());
+        let game = parse_event_file(GAME).unwrap().remove(0);
+
+        let game_pk = import_event_game(db.get_connection_mut(), &game).unwrap();
+        let conn = db.get_connection();
+        let (away, home): (i64, i64) = conn
+            .query_row(
+                "SELECT COALESCE(SUM(CASE WHEN half_inning = 'Top' THEN runs END), 0),
+                        COALESCE(SUM(CASE WHEN half_inning = 'Bottom' THEN runs END), 0)
+                 FROM (SELECT half_inning, scored AS runs FROM runner_movements WHERE game_id = ?1)",
+                params![game_pk],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((away, home), (1, 2));
+
+        let exported = export_event_game(conn, game_pk).unwrap();
+        assert_eq!(exported.info("site"), Some("North Park"));
+        assert_eq!(exported.info("date"), Some("2026/04/18"));
+        assert_eq!(plays(&exported), plays(&game));
+    }
+
+    #[test]
+    fn a_refused_record_leaves_nothing_behind() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        seed(db.get_connection());
+        // Refused in the first inning, and after the substitutions of the
+        // second one.
+        for (play, refused) in [
+            ("play,1,0,a4,00,X,8/SF.3-H", "play,1,0,a4,00,X,S9.3XH(92)"),
+            (
+                "play,2,0,a7,00,X,FC6.1X2(64)",
+                "play,2,0,a7,00,X,S9.1XH(92)",
+            ),
+        ] {
+            let game = parse_event_file(&GAME.replace(play, refused))
+                .unwrap()
+                .remove(0);
+
+            let err = import_event_game(db.get_connection_mut(), &game).unwrap_err();
+            assert!(err.to_string().contains("XH(92)"), "{err}");
+            let conn = db.get_connection();
+            for table in [
+                "games",
+                "game_lineups",
+                "plate_appearances",
+                "runner_movements",
+                "game_events",
+                "game_umpires",
+                "at_bat_draft",
+            ] {
+                let rows: i64 = conn
+                    .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |r| r.get(0))
+                    .unwrap();
+                assert_eq!(rows, 0, "{table}");
+            }
+        }
+    }
+
+    #[test]
+    fn players_must_be_on_the_roster() {
+        let mut db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        seed(db.get_connection());
+        let text = GAME.replace("\"P N21\"", "\"Joe Nobody\"");
+        let game = parse_event_file(&text).unwrap().remove(0);
+
+        let err = import_event_game(db.get_connection_mut(), &game).unwrap_err();
+        assert!(
+            err.to_string().contains("Joe Nobody (ph) not found"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Retrosheet event files (`.EVA`, `.EVN`, `.EVX`).
+//!
+//! An event file is a CSV text with one record per line: `id` opens a
+//! game, followed by its `version`, `info` header, the `start` lineups and
+//! the `play`, `sub`, `com`, `radj` and `data` records in the order they
+//! happened.
+//!
+//! - `export` — writes a stored game from its plate appearances, runner
+//!   movements and substitutions.
+//! - `import` — loads a game by replaying its records through the Play
+//!   Ball engine.
+//! - `translate` — turns a `play` record into scoring command lines.
+
+pub mod export;
+pub mod import;
+pub mod translate;
+
+use crate::models::field_zone::FieldZone;
+use anyhow::{Result, bail};
+use std::fmt;
+
+/// Retrosheet hit locations of the field zones.
+const LOCATIONS: [(FieldZone, &str); 12] = [
+    (FieldZone::LL, "7L"),
+    (FieldZone::LF, "7"),
+    (FieldZone::LC, "78"),
+    (FieldZone::CF, "8"),
+    (FieldZone::RC, "89"),
+    (FieldZone::RF, "9"),
+    (FieldZone::RL, "9L"),
+    (FieldZone::GLL, "5L"),
+    (FieldZone::LS, "56"),
+    (FieldZone::MI, "6M"),
+    (FieldZone::RS, "34"),
+    (FieldZone::GRL, "3L"),
+];
+
+/// Hit location of `zone`, e.g. `78` for left-center.
+fn location(zone: FieldZone) -> &'static str {
+    LOCATIONS
+        .iter()
+        .find(|(z, _)| *z == zone)
+        .map(|(_, loc)| *loc)
+        .expect("every zone has a location")
+}
+
+/// Zone of the longest location `text` starts with: `78XD` is left-center.
+fn zone_at(text: &str) -> Option<FieldZone> {
+    LOCATIONS
+        .iter()
+        .filter(|(_, loc)| text.starts_with(loc))
+        .max_by_key(|(_, loc)| loc.len())
+        .map(|(zone, _)| *zone)
+}
+
+/// A player in a `start` or `sub` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Appearance {
+    /// Retrosheet player id, e.g. `smitj001`.
+    pub player: String,
+    pub name: String,
+    /// `0` for the visiting team, `1` for the home team.
+    pub team: u8,
+    /// Batting slot 1-9, `0` for a pitcher who does not bat.
+    pub slot: u8,
+    /// Fielding position 1-9, `10` for the DH, `11` for a pinch hitter and
+    /// `12` for a pinch runner.
+    pub position: u8,
+}
+
+/// A `play` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayRecord {
+    pub inning: u32,
+    /// `0` when the visiting team bats.
+    pub team: u8,
+    pub batter: String,
+    /// Balls and strikes before the last pitch, `??` when unknown.
+    pub count: String,
+    pub pitches: String,
+    pub event: String,
+}
+
+/// One line of an event file, `id` excepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    Version(String),
+    Info {
+        key: String,
+        value: String,
+    },
+    Start(Appearance),
+    Sub(Appearance),
+    Play(PlayRecord),
+    /// `radj`: the runner placed on `base` by the tie-break rule.
+    RunnerAdjustment {
+        player: String,
+        base: u8,
+    },
+    Comment(String),
+    /// `data` and any record this program does not use, field by field.
+    Other(Vec<String>),
+}
+
+/// A game of an event file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventGame {
+    pub id: String,
+    pub records: Vec<Record>,
+}
+
+impl EventGame {
+    /// Value of the first `info` record with `key`.
+    pub fn info(&self, key: &str) -> Option<&str> {
+        self.records.iter().find_map(|r| match r {
+            Record::Info { key: k, value } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Read every game of an event file.
+pub fn parse_event_file(text: &str) -> Result<Vec<EventGame>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let mut games: Vec<EventGame> = Vec::new();
+    for row in reader.records() {
+        let row = row?;
+        let line = row.position().map(|p| p.line()).unwrap_or_default();
+        let fields: Vec<String> = row.iter().map(str::to_string).collect();
+        let Some(kind) = fields.first() else {
+            continue;
+        };
+        if kind.is_empty() {
+            continue;
+        }
+        if kind == "id" {
+            let Some(id) = fields.get(1).filter(|id| !id.is_empty()) else {
+                bail!("Line {line}: id record without a game id");
+            };
+            games.push(EventGame {
+                id: id.clone(),
+                records: Vec::new(),
+            });
+            continue;
+        }
+        let Some(game) = games.last_mut() else {
+            bail!("Line {line}: {kind} record before the first id record");
+        };
+        let record = parse_record(&fields).map_err(|e| anyhow::anyhow!("Line {line}: {e}"))?;
+        game.records.push(record);
+    }
+    if games.is_empty() {
+        bail!("No game found: an event file starts with an id record");
+    }
+    Ok(games)
+}
+
+fn parse_record(fields: &[String]) -> Result<Record, String> {
+    let field = |i: usize| fields.get(i).map(String::as_str).unwrap_or_default();
+    let number = |i: usize| -> Result<u8, String> {
+        field(i)
+            .parse()
+            .map_err(|_| format!("{} record: invalid number {:?}", field(0), field(i)))
+    };
+    let record = match field(0) {
+        "version" => Record::Version(field(1).to_string()),
+        "info" => Record::Info {
+            key: field(1).to_string(),
+            value: field(2).to_string(),
+        },
+        kind @ ("start" | "sub") => {
+            if fields.len() < 6 {
+                return Err(format!("{kind} record needs 6 fields"));
+            }
+            let appearance = Appearance {
+                player: field(1).to_string(),
+                name: field(2).to_string(),
+                team: number(3)?,
+                slot: number(4)?,
+                position: number(5)?,
+            };
+            if appearance.team > 1
+                || appearance.slot > 9
+                || !(1..=12).contains(&appearance.position)
+            {
+                return Err(format!("{kind} record out of range"));
+            }
+            if kind == "start" {
+                Record::Start(appearance)
+            } else {
+                Record::Sub(appearance)
+            }
+        }
+        "play" => {
+            if fields.len() < 7 {
+                return Err("play record needs 7 fields".to_string());
+            }
+            Record::Play(PlayRecord {
+                inning: field(1)
+                    .parse()
+                    .map_err(|_| format!("play record: invalid inning {:?}", field(1)))?,
+                team: number(2)?,
+                batter: field(3).to_string(),
+                count: field(4).to_string(),
+                pitches: field(5).to_string(),
+                event: field(6).to_string(),
+            })
+        }
+        "radj" => Record::RunnerAdjustment {
+            player: field(1).to_string(),
+            base: number(2)?,
+        },
+        "com" => Record::Comment(field(1).to_string()),
+        _ => Record::Other(fields.to_vec()),
+    };
+    Ok(record)
+}
+
+/// Quoted text field; Retrosheet has no escape for a double quote.
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "'"))
+}
+
+impl fmt::Display for Appearance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{}",
+            self.player,
+            quoted(&self.name),
+            self.team,
+            self.slot,
+            self.position
+        )
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Record::Version(v) => write!(f, "version,{v}"),
+            Record::Info { key, value } if value.contains(',') => {
+                write!(f, "info,{key},{}", quoted(value))
+            }
+            Record::Info { key, value } => write!(f, "info,{key},{value}"),
+            Record::Start(a) => write!(f, "start,{a}"),
+            Record::Sub(a) => write!(f, "sub,{a}"),
+            Record::Play(p) => write!(
+                f,
+                "play,{},{},{},{},{},{}",
+                p.inning, p.team, p.batter, p.count, p.pitches, p.event
+            ),
+            Record::RunnerAdjustment { player, base } => write!(f, "radj,{player},{base}"),
+            Record::Comment(text) => write!(f, "com,{}", quoted(text)),
+            Record::Other(fields) => write!(f, "{}", fields.join(",")),
+        }
+    }
+}
+
+impl fmt::Display for EventGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "id,{}", self.id)?;
+        for record in &self.records {
+            writeln!(f, "{record}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_read_back_as_written() {
+        let text = "id,HOM202604010\n\
+                    version,2\n\
+                    info,visteam,AWY\n\
+                    info,site,\"Park, North\"\n\
+                    start,smitj001,\"John Smith\",0,1,8\n\
+                    play,1,0,smitj001,12,BCFX,S8/78.1-3\n\
+                    sub,jonep001,\"Pat Jones\",1,0,1\n\
+                    radj,smitj001,2\n\
+                    com,\"rain delay\"\n\
+                    data,er,jonep001,2\n";
+        let games = parse_event_file(text).unwrap();
+        assert_eq!(games.len(), 1);
+        let game = &games[0];
+        assert_eq!(game.id, "HOM202604010");
+        assert_eq!(game.info("site"), Some("Park, North"));
+        assert_eq!(
+            game.records[4],
+            Record::Play(PlayRecord {
+                inning: 1,
+                team: 0,
+                batter: "smitj001".to_string(),
+                count: "12".to_string(),
+                pitches: "BCFX".to_string(),
+                event: "S8/78.1-3".to_string(),
+            })
+        );
+        assert_eq!(game.to_string(), text);
+    }
+
+    #[test]
+    fn locations_map_to_zones() {
+        for (zone, _) in LOCATIONS {
+            assert_eq!(zone_at(location(zone)), Some(zone));
+        }
+        assert_eq!(zone_at("78XD"), Some(FieldZone::LC));
+        assert_eq!(zone_at("9LS"), Some(FieldZone::RL));
+        assert_eq!(zone_at("6"), None);
+    }
+
+    #[test]
+    fn records_outside_a_game_are_rejected() {
+        let err = parse_event_file("version,2\n").unwrap_err();
+        assert!(err.to_string().contains("before the first id"), "{err}");
+        let err = parse_event_file("id,G1\nstart,x,\"X\",3,1,1\n").unwrap_err();
+        assert!(err.to_string().contains("Line 2"), "{err}");
+    }
+}
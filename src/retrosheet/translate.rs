@@ -0,0 +1,1029 @@
+//! `play` records to scoring command lines.
+//!
+//! A record is entered the way a scorer would type it against the current
+//! game state: the pitches not yet on the count, then the play. Every
+//! runner the play moves is named, so nothing is left to the automatic
+//! advancement. Plays the grammar has no notation for (a runner out on a
+//! hit, an extra base on the throw, `OA`, ...) are refused with the part
+//! that cannot be scored.
+
+use super::{PlayRecord, zone_at};
+use crate::models::field_zone::FieldZone;
+use crate::models::game_state::GameState;
+
+/// Command lines for `play`, in the order they are entered. Empty for a
+/// record with nothing to score (`NP`, an error on a foul fly).
+pub fn play_lines(play: &PlayRecord, state: &GameState) -> Result<Vec<String>, String> {
+    let text: String = play
+        .event
+        .chars()
+        .filter(|c| !matches!(c, '#' | '!' | '?'))
+        .collect();
+    let event = Event::parse(&text)?;
+    for a in &event.advances {
+        if a.from != 'B' && occupant(state, a.from).is_none() {
+            return Err(format!("no runner on {} for {a:?}", base_name(a.from)));
+        }
+    }
+
+    let p = event.play;
+    match p {
+        "" | "NP" => Ok(Vec::new()),
+        _ if p.starts_with("FLE") => Ok(Vec::new()),
+        "W" => walk(play, &event, state),
+        "IW" | "I" | "HP" | "C" => {
+            if let Some(extra) = event.extra {
+                return Err(unsupported(extra));
+            }
+            check_forced_advances(&event.advances, state)?;
+            let award = match p {
+                "HP" => "hbp",
+                "C" => "ci",
+                _ => return Ok(vec!["ibb".to_string()]),
+            };
+            let mut lines = one_line(pitches_before_play(play, state)?);
+            lines.push(award.to_string());
+            Ok(lines)
+        }
+        _ if p.starts_with('K') => strikeout(play, &event, state),
+        "DGR" => {
+            if event
+                .advances
+                .iter()
+                .any(|a| a.is_out() || bases_between(a.from, a.to) != 2)
+            {
+                return Err("a ground-rule double moves everyone two bases".to_string());
+            }
+            let zone = event.zone(None);
+            let mut segment = "2h".to_string();
+            if let Some(zone) = zone {
+                segment.push(' ');
+                segment.push_str(&zone.as_str().to_ascii_lowercase());
+            }
+            segment.push_str(" gr");
+            batted_ball(play, state, vec![segment])
+        }
+        _ if is_home_run(p) => {
+            if event.advances.iter().any(|a| a.is_out() || a.to != 'H') {
+                return Err("every runner scores on a home run".to_string());
+            }
+            let fielder = p.trim_start_matches("HR").trim_start_matches('H');
+            let inside = !fielder.is_empty() || event.modifiers.contains(&"IPHR");
+            let mut segment = "hr".to_string();
+            if let Some(zone) = event.zone(fielder.chars().next()) {
+                segment.push(' ');
+                segment.push_str(&zone.as_str().to_ascii_lowercase());
+            }
+            if inside {
+                segment.push_str(" itp");
+            }
+            batted_ball(play, state, vec![segment])
+        }
+        _ if is_hit(p) => {
+            let bases = match &p[..1] {
+                "S" => 1,
+                "D" => 2,
+                _ => 3,
+            };
+            if let Some(b) = event.batter_advance()
+                && (b.is_out() || bases_between('B', b.to) != bases)
+            {
+                return Err(unsupported(&format!("{b:?}")));
+            }
+            let mut segment = ["h", "2h", "3h"][bases as usize - 1].to_string();
+            if let Some(zone) = event.zone(p[1..].chars().next()) {
+                segment.push(' ');
+                segment.push_str(&zone.as_str().to_ascii_lowercase());
+            }
+            if event.is_bunt() {
+                segment.push_str(" bu");
+            }
+            let mut segments = vec![segment];
+            segments.extend(placed_runners(&event.advances, state)?);
+            batted_ball(play, state, segments)
+        }
+        _ if p.starts_with('E') && fielder_of(&p[1..]).is_some() => {
+            let fielder = fielder_of(&p[1..]).unwrap_or_default();
+            let throwing = event.modifiers.iter().any(|m| m.starts_with("TH"));
+            let mut segment = format!("{}e{fielder}", if throwing { "t" } else { "" });
+            match event.batter_advance() {
+                Some(b) if b.is_out() => return Err(unsupported(&format!("{b:?}"))),
+                Some(b) if b.to != '1' => {
+                    segment.push(' ');
+                    segment.push_str(dest(b.to));
+                }
+                _ => {}
+            }
+            let mut segments = vec![segment];
+            segments.extend(placed_runners(&event.advances, state)?);
+            batted_ball(play, state, segments)
+        }
+        _ if p.starts_with("FC") => {
+            let Some(fielder) = fielder_of(&p[2..]) else {
+                return Err("a fielder's choice needs the fielder".to_string());
+            };
+            let mut segments = vec![fielders_choice(fielder, &event)?];
+            segments.extend(runner_results(&event.advances, &[], state, false)?);
+            batted_ball(play, state, segments)
+        }
+        _ if p.starts_with(|c: char| c.is_ascii_digit()) => {
+            let segments = fielded_out(&event, state)?;
+            batted_ball(play, state, segments)
+        }
+        _ => {
+            let mut segments: Vec<String> = pitches_before_play(play, state)?
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            segments.extend(runner_events(p, &event.advances, state)?);
+            Ok(one_line(segments))
+        }
+    }
+}
+
+// ─── Event text ──────────────────────────────────────────────────────────────
+
+/// The parts of an event: `64(1)3/GDP/G6.2-3` is the play `64(1)3`, the
+/// modifiers `GDP` and `G6` and the advance `2-3`.
+struct Event<'a> {
+    play: &'a str,
+    /// Runner event on the same pitch as a strikeout or a walk: `SB2` in
+    /// `K+SB2`.
+    extra: Option<&'a str>,
+    modifiers: Vec<&'a str>,
+    advances: Vec<Advance>,
+}
+
+impl<'a> Event<'a> {
+    fn parse(text: &'a str) -> Result<Self, String> {
+        let (description, advances) = match find_outside(text, '.') {
+            Some(i) => (&text[..i], &text[i + 1..]),
+            None => (text, ""),
+        };
+        let mut parts = split_outside(description, '/').into_iter();
+        let main = parts.next().unwrap_or_default();
+        let (play, extra) = match find_outside(main, '+') {
+            Some(i) => (&main[..i], Some(&main[i + 1..])),
+            None => (main, None),
+        };
+        let advances = advances
+            .split(';')
+            .filter(|a| !a.is_empty())
+            .map(Advance::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            play,
+            extra,
+            modifiers: parts.collect(),
+            advances,
+        })
+    }
+
+    fn batter_advance(&self) -> Option<&Advance> {
+        self.advances.iter().find(|a| a.from == 'B')
+    }
+
+    /// Zone of the hit location in the modifiers, else of the outfielder
+    /// who fielded the ball.
+    fn zone(&self, fielder: Option<char>) -> Option<FieldZone> {
+        self.modifiers
+            .iter()
+            .find_map(|m| zone_at(m.trim_start_matches(|c: char| c.is_ascii_alphabetic())))
+            .or(match fielder {
+                Some('7') => Some(FieldZone::LF),
+                Some('8') => Some(FieldZone::CF),
+                Some('9') => Some(FieldZone::RF),
+                _ => None,
+            })
+    }
+
+    fn trajectory(&self) -> Option<Trajectory> {
+        self.modifiers.iter().find_map(|m| {
+            let prefix = m.split(|c: char| c.is_ascii_digit()).next()?;
+            match prefix {
+                "G" | "GDP" | "GTP" | "BG" | "BGDP" => Some(Trajectory::Ground),
+                "F" | "P" | "FDP" | "FTP" | "BP" | "BPDP" => Some(Trajectory::Fly),
+                "L" | "LDP" | "LTP" | "BL" => Some(Trajectory::Line),
+                _ => None,
+            }
+        })
+    }
+
+    fn is_bunt(&self) -> bool {
+        self.modifiers
+            .iter()
+            .any(|m| m.starts_with("BG") || m.starts_with("BP") || m.starts_with("BL"))
+    }
+
+    fn has(&self, modifier: &str) -> bool {
+        self.modifiers.contains(&modifier)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trajectory {
+    Ground,
+    Fly,
+    Line,
+}
+
+/// A runner advance: `1-3`, `2XH(92)`, `B-2(E6/TH)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Advance {
+    /// `B`, `1`, `2` or `3`.
+    from: char,
+    /// `1`, `2`, `3` or `H`.
+    to: char,
+    /// Written with `X`: out, unless an error let the runner stay safe.
+    thrown_at: bool,
+    /// Parenthesised notes: the fielders, an error, `NR`, `RBI`, ...
+    notes: Vec<String>,
+}
+
+impl Advance {
+    fn parse(text: &str) -> Result<Self, String> {
+        let unreadable = || format!("unreadable advance {text:?}");
+        let mut chars = text.chars();
+        let (Some(from), Some(sep), Some(to)) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(unreadable());
+        };
+        if !"B123".contains(from) || !"-X".contains(sep) || !"123H".contains(to) {
+            return Err(unreadable());
+        }
+        let mut rest = chars.as_str();
+        let mut notes = Vec::new();
+        while !rest.is_empty() {
+            let close = rest.find(')').filter(|_| rest.starts_with('('));
+            let Some(close) = close else {
+                return Err(unreadable());
+            };
+            notes.push(rest[1..close].to_string());
+            rest = &rest[close + 1..];
+        }
+        Ok(Self {
+            from,
+            to,
+            thrown_at: sep == 'X',
+            notes,
+        })
+    }
+
+    /// Grammar token of the error the runner moved up on: `e7`, `te7`.
+    fn error(&self) -> Option<String> {
+        let throwing = self.notes.iter().any(|n| n.contains("TH"));
+        self.notes.iter().find_map(|n| {
+            let after = &n[n.find('E')? + 1..];
+            let fielder = fielder_of(after.split('/').next()?)?;
+            Some(format!("{}e{fielder}", if throwing { "t" } else { "" }))
+        })
+    }
+
+    fn is_out(&self) -> bool {
+        self.thrown_at && self.error().is_none()
+    }
+
+    /// Fielding chain of the out, e.g. `92`.
+    fn fielders(&self) -> Option<&str> {
+        self.notes
+            .iter()
+            .map(String::as_str)
+            .find(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    }
+}
+
+/// Byte index of the first `sep` outside parentheses.
+fn find_outside(text: &str, sep: char) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if c == sep && depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn split_outside(mut text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    while let Some(i) = find_outside(text, sep) {
+        parts.push(&text[..i]);
+        text = &text[i + 1..];
+    }
+    parts.push(text);
+    parts
+}
+
+/// The fielder when `text` is exactly one position digit.
+fn fielder_of(text: &str) -> Option<u8> {
+    let mut chars = text.chars();
+    let fielder = chars.next()?.to_digit(10).filter(|d| *d >= 1)?;
+    chars.next().is_none().then_some(fielder as u8)
+}
+
+fn is_home_run(play: &str) -> bool {
+    let fielder = play.strip_prefix("HR").or_else(|| play.strip_prefix('H'));
+    fielder.is_some_and(|f| f.is_empty() || fielder_of(f).is_some())
+}
+
+/// `S`, `D` or `T`, with or without the fielder.
+fn is_hit(play: &str) -> bool {
+    let mut chars = play.chars();
+    matches!(chars.next(), Some('S' | 'D' | 'T')) && chars.all(|c| c.is_ascii_digit())
+}
+
+fn unsupported(part: &str) -> String {
+    format!("{part} has no scoring notation")
+}
+
+// ─── Runners ─────────────────────────────────────────────────────────────────
+
+fn occupant(state: &GameState, base: char) -> Option<u8> {
+    match base {
+        '1' => state.on_1b,
+        '2' => state.on_2b,
+        '3' => state.on_3b,
+        _ => None,
+    }
+}
+
+fn runner(state: &GameState, base: char) -> Result<u8, String> {
+    occupant(state, base).ok_or_else(|| format!("no runner on {}", base_name(base)))
+}
+
+fn base_name(base: char) -> &'static str {
+    match base {
+        '1' => "first",
+        '2' => "second",
+        '3' => "third",
+        _ => "home",
+    }
+}
+
+/// Grammar token of a destination base.
+fn dest(base: char) -> &'static str {
+    match base {
+        '1' => "1b",
+        '2' => "2b",
+        '3' => "3b",
+        _ => "sc",
+    }
+}
+
+fn base_number(base: char) -> u8 {
+    match base {
+        'B' => 0,
+        'H' => 4,
+        b => b.to_digit(10).unwrap_or_default() as u8,
+    }
+}
+
+fn bases_between(from: char, to: char) -> u8 {
+    base_number(to).saturating_sub(base_number(from))
+}
+
+/// `5 3b`, `5 e7 3b`.
+fn override_segment(order: u8, advance: &Advance) -> String {
+    match advance.error() {
+        Some(error) => format!("{order} {error} {}", dest(advance.to)),
+        None => format!("{order} {}", dest(advance.to)),
+    }
+}
+
+/// An override for every runner on a hit or an error: where the advances
+/// send them, the base they hold otherwise.
+fn placed_runners(advances: &[Advance], state: &GameState) -> Result<Vec<String>, String> {
+    let mut segments = Vec::new();
+    for base in ['1', '2', '3'] {
+        let Some(order) = occupant(state, base) else {
+            continue;
+        };
+        match advances.iter().find(|a| a.from == base) {
+            Some(a) if a.is_out() => return Err(unsupported(&format!("runner out {a:?}"))),
+            Some(a) => segments.push(override_segment(order, a)),
+            None => segments.push(format!("{order} {}", dest(base))),
+        }
+    }
+    Ok(segments)
+}
+
+/// The runners' part of an out or a fielder's choice: the outs in the
+/// advances, and overrides for the runners who moved or, on a
+/// sacrifice, for every runner still on base.
+fn runner_results(
+    advances: &[Advance],
+    already_out: &[char],
+    state: &GameState,
+    sacrifice: bool,
+) -> Result<Vec<String>, String> {
+    let mut segments = Vec::new();
+    for base in ['1', '2', '3'] {
+        let Some(order) = occupant(state, base) else {
+            continue;
+        };
+        if already_out.contains(&base) {
+            continue;
+        }
+        match advances.iter().find(|a| a.from == base) {
+            Some(a) if a.is_out() => {
+                let Some(fielders) = a.fielders() else {
+                    return Err(format!("runner out {a:?} without the fielders"));
+                };
+                segments.push(format!("{order} {fielders}"));
+            }
+            Some(a) => segments.push(override_segment(order, a)),
+            None if sacrifice => segments.push(format!("{order} {}", dest(base))),
+            None => {}
+        }
+    }
+    Ok(segments)
+}
+
+/// Runner moves a base award forces, as `(from, to)`.
+fn forced_moves(state: &GameState) -> Vec<(char, char)> {
+    let mut moves = vec![('B', '1')];
+    if state.on_1b.is_some() {
+        moves.push(('1', '2'));
+        if state.on_2b.is_some() {
+            moves.push(('2', '3'));
+            if state.on_3b.is_some() {
+                moves.push(('3', 'H'));
+            }
+        }
+    }
+    moves
+}
+
+fn check_forced_advances(advances: &[Advance], state: &GameState) -> Result<(), String> {
+    let forced = forced_moves(state);
+    match advances
+        .iter()
+        .find(|a| a.thrown_at || !forced.contains(&(a.from, a.to)))
+    {
+        Some(a) => Err(unsupported(&format!("{a:?} on a base award"))),
+        None => Ok(()),
+    }
+}
+
+/// Segments of the runner events between pitches: `SB2;SB3`, `CS2(26)`,
+/// `PO1(13)`, `POCS2(136)`, `WP`, `PB`, `DI`, `BK`. The advances must be
+/// the ones the events make.
+fn runner_events(
+    text: &str,
+    advances: &[Advance],
+    state: &GameState,
+) -> Result<Vec<String>, String> {
+    let mut segments = Vec::new();
+    let mut made: Vec<(char, char)> = Vec::new();
+    for part in text.split(';') {
+        let (verb, notes) = match part.find('(') {
+            Some(i) => (&part[..i], part[i..].trim_matches(|c| c == '(' || c == ')')),
+            None => (part, ""),
+        };
+        let target = |prefix: &str| verb.strip_prefix(prefix).and_then(|b| b.chars().next());
+        let chain = || {
+            if notes.is_empty() || !notes.chars().all(|c| c.is_ascii_digit()) {
+                Err(unsupported(part))
+            } else {
+                Ok(notes)
+            }
+        };
+        match verb {
+            "WP" | "PB" | "DI" => {
+                for a in advances {
+                    if a.from == 'B' || a.thrown_at {
+                        return Err(unsupported(&format!("{a:?} on {verb}")));
+                    }
+                    let order = runner(state, a.from)?;
+                    segments.push(format!(
+                        "{order} {} {}",
+                        verb.to_ascii_lowercase(),
+                        dest(a.to)
+                    ));
+                    made.push((a.from, a.to));
+                }
+            }
+            "BK" => {
+                for base in ['1', '2', '3'] {
+                    if occupant(state, base).is_some() {
+                        let to = ['2', '3', 'H'][base_number(base) as usize - 1];
+                        made.push((base, to));
+                    }
+                }
+                segments.push("bk".to_string());
+            }
+            _ if verb.starts_with("POCS") => {
+                let to = target("POCS").ok_or_else(|| unsupported(part))?;
+                let from = previous_base(to).ok_or_else(|| unsupported(part))?;
+                let order = runner(state, from)?;
+                segments.push(format!("{order} pocs {} {}", dest(to), chain()?));
+                made.push((from, to));
+            }
+            _ if verb.starts_with("PO") => {
+                let from = target("PO").ok_or_else(|| unsupported(part))?;
+                let order = runner(state, from)?;
+                segments.push(format!("{order} po {}", chain()?));
+                made.push((from, from));
+            }
+            _ if verb.starts_with("CS") => {
+                let to = target("CS").ok_or_else(|| unsupported(part))?;
+                let from = previous_base(to).ok_or_else(|| unsupported(part))?;
+                let order = runner(state, from)?;
+                segments.push(format!("{order} cs {} {}", dest(to), chain()?));
+                made.push((from, to));
+            }
+            _ if verb.starts_with("SB") => {
+                let to = target("SB").ok_or_else(|| unsupported(part))?;
+                let from = previous_base(to).ok_or_else(|| unsupported(part))?;
+                let order = runner(state, from)?;
+                segments.push(format!("{order} st {}", dest(to)));
+                made.push((from, to));
+            }
+            _ => return Err(unsupported(part)),
+        }
+    }
+    for a in advances {
+        let same = made
+            .iter()
+            .any(|(from, to)| *from == a.from && (*to == a.to || a.is_out()));
+        if !same {
+            return Err(unsupported(&format!("{a:?} on {text}")));
+        }
+    }
+    Ok(segments)
+}
+
+fn previous_base(to: char) -> Option<char> {
+    match to {
+        '2' => Some('1'),
+        '3' => Some('2'),
+        'H' => Some('3'),
+        _ => None,
+    }
+}
+
+// ─── Pitches ─────────────────────────────────────────────────────────────────
+
+/// Grammar tokens of a pitch string up to the ball in play or the hit
+/// batter. Pickoff throws, markers and unknown pitches are left out.
+fn pitch_tokens(pitches: &str) -> Vec<&'static str> {
+    let mut tokens = Vec::new();
+    for c in pitches.chars() {
+        let token = match c.to_ascii_uppercase() {
+            'B' | 'I' | 'P' | 'V' => "b",
+            'C' | 'K' | 'A' => "k",
+            'S' | 'M' | 'Q' | 'T' => "s",
+            'F' | 'R' => "f",
+            'L' | 'O' => "fl",
+            'X' | 'Y' | 'H' => break,
+            _ => continue,
+        };
+        tokens.push(token);
+    }
+    tokens
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Count {
+    balls: u8,
+    strikes: u8,
+}
+
+impl Count {
+    fn of(state: &GameState) -> Self {
+        Self {
+            balls: state.pitch_count.balls,
+            strikes: state.pitch_count.strikes,
+        }
+    }
+
+    /// Add a pitch; true when it ends the plate appearance.
+    fn pitch(&mut self, token: &str) -> bool {
+        match token {
+            "b" => self.balls += 1,
+            "f" if self.strikes >= 2 => {}
+            _ => self.strikes += 1,
+        }
+        self.balls >= 4 || self.strikes >= 3
+    }
+}
+
+/// The pitches of the record the engine has not seen yet.
+fn new_pitches(play: &PlayRecord, state: &GameState) -> Vec<&'static str> {
+    pitch_tokens(&play.pitches)
+        .into_iter()
+        .skip(state.pitch_count.sequence.len())
+        .collect()
+}
+
+fn pitches_before_play(play: &PlayRecord, state: &GameState) -> Result<Vec<&'static str>, String> {
+    let pitches = new_pitches(play, state);
+    let mut count = Count::of(state);
+    if pitches.iter().any(|p| count.pitch(p)) {
+        return Err(format!(
+            "the pitches {} end the plate appearance before the play",
+            play.pitches
+        ));
+    }
+    Ok(pitches)
+}
+
+/// The pitches of a walk or a strikeout, completed with the balls or
+/// strikes the record leaves out.
+fn pitches_ending(
+    play: &PlayRecord,
+    state: &GameState,
+    walk: bool,
+) -> Result<Vec<&'static str>, String> {
+    let mut pitches = new_pitches(play, state);
+    let mut count = Count::of(state);
+    for (i, p) in pitches.iter().enumerate() {
+        if count.pitch(p) {
+            if (count.balls >= 4) != walk || i + 1 != pitches.len() {
+                return Err(format!(
+                    "the pitches {} do not match the play",
+                    play.pitches
+                ));
+            }
+            return Ok(pitches);
+        }
+    }
+    let pad = if walk { "b" } else { "s" };
+    loop {
+        pitches.push(pad);
+        if count.pitch(pad) {
+            return Ok(pitches);
+        }
+    }
+}
+
+/// The pitches before a third strike that was not caught, completed to
+/// two strikes.
+fn pitches_before_third_strike(
+    play: &PlayRecord,
+    state: &GameState,
+) -> Result<Vec<&'static str>, String> {
+    let mut count = Count::of(state);
+    let mut pitches = Vec::new();
+    for p in new_pitches(play, state) {
+        let mut next = count;
+        if next.pitch(p) {
+            if next.strikes < 3 {
+                return Err(format!(
+                    "the pitches {} do not match the play",
+                    play.pitches
+                ));
+            }
+            break;
+        }
+        count = next;
+        pitches.push(p);
+    }
+    while count.strikes < 2 {
+        count.pitch("s");
+        pitches.push("s");
+    }
+    Ok(pitches)
+}
+
+fn one_line<S: ToString>(segments: Vec<S>) -> Vec<String> {
+    if segments.is_empty() {
+        return Vec::new();
+    }
+    let segments: Vec<String> = segments.iter().map(S::to_string).collect();
+    vec![segments.join(", ")]
+}
+
+// ─── Plays ───────────────────────────────────────────────────────────────────
+
+/// The pitches before a ball in play, then the play itself.
+fn batted_ball(
+    play: &PlayRecord,
+    state: &GameState,
+    segments: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let mut lines = one_line(pitches_before_play(play, state)?);
+    lines.extend(one_line(segments));
+    Ok(lines)
+}
+
+fn walk(play: &PlayRecord, event: &Event, state: &GameState) -> Result<Vec<String>, String> {
+    let mut segments: Vec<String> = pitches_ending(play, state, true)?
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    match event.extra {
+        None => check_forced_advances(&event.advances, state)?,
+        Some(extra) => {
+            let forced = forced_moves(state);
+            let advances: Vec<Advance> = event
+                .advances
+                .iter()
+                .filter(|a| a.thrown_at || !forced.contains(&(a.from, a.to)))
+                .cloned()
+                .collect();
+            segments.extend(runner_events(extra, &advances, state)?);
+        }
+    }
+    Ok(one_line(segments))
+}
+
+fn strikeout(play: &PlayRecord, event: &Event, state: &GameState) -> Result<Vec<String>, String> {
+    let runner_advances: Vec<Advance> = event
+        .advances
+        .iter()
+        .filter(|a| a.from != 'B')
+        .cloned()
+        .collect();
+    let batter_reaches = event.batter_advance().is_some_and(|b| !b.is_out());
+
+    // Strike three not caught: `K23`, `K+WP.B-1`.
+    let throw = &event.play[1..];
+    let dropped = match event.extra {
+        _ if !throw.is_empty() => {
+            if !throw.chars().all(|c| c.is_ascii_digit()) || batter_reaches {
+                return Err(unsupported(event.play));
+            }
+            Some(format!("k{throw}"))
+        }
+        Some("WP") if batter_reaches => Some("kwp".to_string()),
+        Some("PB") if batter_reaches => Some("kpb".to_string()),
+        _ if batter_reaches => return Err(unsupported("the batter reaching on a strikeout")),
+        _ => None,
+    };
+    if let Some(verb) = dropped {
+        if let Some(a) = runner_advances.iter().find(|a| a.is_out()) {
+            return Err(unsupported(&format!(
+                "runner out {a:?} on a dropped third strike"
+            )));
+        }
+        let mut segments = vec![verb];
+        for a in &runner_advances {
+            segments.push(override_segment(runner(state, a.from)?, a));
+        }
+        let mut lines = one_line(pitches_before_third_strike(play, state)?);
+        lines.extend(one_line(segments));
+        return Ok(lines);
+    }
+
+    let mut segments: Vec<String> = pitches_ending(play, state, false)?
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    match event.extra {
+        Some(extra) => segments.extend(runner_events(extra, &runner_advances, state)?),
+        None if !runner_advances.is_empty() => {
+            return Err(unsupported("a runner advance on a strikeout"));
+        }
+        None => {}
+    }
+    Ok(one_line(segments))
+}
+
+/// `o6 1b`: the batter safe on a fielder's choice.
+fn fielders_choice(fielder: u8, event: &Event) -> Result<String, String> {
+    match event.batter_advance() {
+        Some(b) if b.is_out() => Err(unsupported(&format!("{b:?} on a fielder's choice"))),
+        Some(b) => Ok(format!("o{fielder} {}", dest(b.to))),
+        None => Ok(format!("o{fielder} 1b")),
+    }
+}
+
+/// An out made by the fielders: `63`, `8/F`, `64(1)3/GDP`, `8/SF.3-H`,
+/// or `64(1)` with the batter safe at first.
+fn fielded_out(event: &Event, state: &GameState) -> Result<Vec<String>, String> {
+    // Each out is a chain of fielders followed by the runner put out; the
+    // last chain without a runner is the batter.
+    let mut outs: Vec<(String, char)> = Vec::new();
+    let mut rest = event.play;
+    let mut previous_last: Option<char> = None;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(unsupported(event.play));
+        }
+        let mut chain = rest[..digits].to_string();
+        rest = &rest[digits..];
+        let who = if let Some(tagged) = rest.strip_prefix('(') {
+            let Some(close) = tagged.find(')') else {
+                return Err(unsupported(event.play));
+            };
+            let who = &tagged[..close];
+            rest = &tagged[close + 1..];
+            match who {
+                "B" | "1" | "2" | "3" => who.chars().next().unwrap_or('B'),
+                _ => return Err(unsupported(event.play)),
+            }
+        } else if rest.is_empty() {
+            'B'
+        } else {
+            return Err(unsupported(event.play));
+        };
+        if chain.contains('0') {
+            return Err(unsupported(event.play));
+        }
+        if let Some(p) = previous_last
+            && !chain.starts_with(p)
+        {
+            chain.insert(0, p);
+        }
+        previous_last = chain.chars().last();
+        outs.push((chain, who));
+    }
+    if outs.iter().filter(|(_, who)| *who == 'B').count() > 1 || outs.iter().any(|(c, _)| c == "99")
+    {
+        return Err(unsupported(event.play));
+    }
+
+    let sacrifice_fly = event.has("SF");
+    let sacrifice_bunt = event.has("SH");
+    let mut segments = Vec::new();
+    match outs.iter().find(|(_, who)| *who == 'B') {
+        Some((chain, _)) => {
+            if event.batter_advance().is_some_and(|b| !b.is_out()) {
+                return Err(unsupported("the batter safe on an out"));
+            }
+            let single = fielder_of(chain);
+            let segment = match (single, event.trajectory()) {
+                (Some(f), _) if sacrifice_fly => format!("sf{f}"),
+                (None, _) if sacrifice_fly => return Err(unsupported(event.play)),
+                _ if sacrifice_bunt => format!("sh{chain}"),
+                (Some(f), _) if event.has("IF") => format!("if{f}"),
+                (Some(f), Some(Trajectory::Line)) => format!("l{f}"),
+                (Some(f), Some(Trajectory::Ground)) => {
+                    let batter = state
+                        .current_batter_order
+                        .ok_or_else(|| "no batter at the plate".to_string())?;
+                    format!("{batter} {f}")
+                }
+                (Some(f), _) if event.has("FL") => format!("ff{f}"),
+                (Some(f), _) => format!("f{f}"),
+                (None, _) => chain.clone(),
+            };
+            segments.push(segment);
+        }
+        None => {
+            let first = outs[0].0.chars().next().and_then(|c| c.to_digit(10));
+            segments.push(fielders_choice(first.unwrap_or_default() as u8, event)?);
+        }
+    }
+    let mut already_out = Vec::new();
+    for (chain, who) in &outs {
+        if *who != 'B' {
+            segments.push(format!("{} {chain}", runner(state, *who)?));
+            already_out.push(*who);
+        }
+    }
+    segments.extend(runner_results(
+        &event.advances,
+        &already_out,
+        state,
+        sacrifice_fly || sacrifice_bunt,
+    )?);
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser::parse_engine_commands;
+    use crate::models::types::HalfInning;
+
+    fn state(batter: u8, bases: [Option<u8>; 3]) -> GameState {
+        let mut s = GameState::new();
+        s.half = HalfInning::Top;
+        s.current_batter_order = Some(batter);
+        s.current_batter_id = Some(batter as i64);
+        s.current_pitcher_id = Some(99);
+        [s.on_1b, s.on_2b, s.on_3b] = bases;
+        s
+    }
+
+    fn record(pitches: &str, event: &str) -> PlayRecord {
+        PlayRecord {
+            inning: 1,
+            team: 0,
+            batter: "batta001".to_string(),
+            count: "??".to_string(),
+            pitches: pitches.to_string(),
+            event: event.to_string(),
+        }
+    }
+
+    /// The lines for `event`, each checked against the grammar. The
+    /// pitches of a multi-line result come first, so the play is checked
+    /// against the count they leave.
+    fn lines(s: &GameState, pitches: &str, event: &str) -> Vec<String> {
+        let lines =
+            play_lines(&record(pitches, event), s).unwrap_or_else(|e| panic!("{event}: {e}"));
+        let mut count = s.clone();
+        for line in &lines {
+            parse_engine_commands(line, &count)
+                .unwrap_or_else(|e| panic!("{event}: {line:?} rejected: {e:?}"));
+            for token in line.split(", ") {
+                let mut c = Count::of(&count);
+                if matches!(token, "b" | "k" | "s" | "f" | "fl") {
+                    c.pitch(token);
+                    count.pitch_count.balls = c.balls;
+                    count.pitch_count.strikes = c.strikes;
+                }
+            }
+        }
+        lines
+    }
+
+    fn refused(s: &GameState, event: &str) -> String {
+        play_lines(&record("", event), s).expect_err(event)
+    }
+
+    #[test]
+    fn hits_place_every_runner() {
+        let s = state(3, [Some(1), Some(2), None]);
+        assert_eq!(
+            lines(&s, "BCX", "S7/L7.2-H;1-3(E7/TH)"),
+            vec!["b, k", "h lf, 1 te7 3b, 2 sc"]
+        );
+        assert_eq!(lines(&s, "X", "D8/78.2-H;1-3"), vec!["2h lc, 1 3b, 2 sc"]);
+        assert_eq!(lines(&s, "", "S5/BG.2-3;1-2"), vec!["h bu, 1 2b, 2 3b"]);
+        assert_eq!(lines(&s, "", "DGR/9.2-H;1-3"), vec!["2h rf gr"]);
+        assert_eq!(lines(&s, "", "HR/89.2-H;1-H"), vec!["hr rc"]);
+        assert_eq!(lines(&s, "", "HR8/IPHR.2-H;1-H"), vec!["hr cf itp"]);
+        assert!(refused(&s, "S9.2XH(92);1-2").contains("runner out"));
+        assert!(refused(&s, "S9.B-2;2-H;1-3").contains("no scoring notation"));
+    }
+
+    #[test]
+    fn awards_and_errors() {
+        let s = state(4, [Some(3), None, None]);
+        assert_eq!(lines(&s, "BBCBB", "W.1-2"), vec!["b, b, k, b, b"]);
+        assert_eq!(lines(&s, "", "W"), vec!["b, b, b, b"]);
+        assert_eq!(lines(&s, "III", "IW.1-2"), vec!["ibb"]);
+        assert_eq!(lines(&s, "CH", "HP.1-2"), vec!["k", "hbp"]);
+        assert_eq!(lines(&s, "", "C/E2.1-2"), vec!["ci"]);
+        assert_eq!(lines(&s, "", "E6/TH.B-2;1-3"), vec!["te6 2b, 3 3b"]);
+        assert!(refused(&s, "W.1-3").contains("base award"));
+    }
+
+    #[test]
+    fn strikeouts_and_dropped_third_strikes() {
+        let s = state(5, [Some(4), None, None]);
+        assert_eq!(lines(&s, "CSS", "K"), vec!["k, s, s"]);
+        assert_eq!(lines(&s, "CSBS", "K+SB2"), vec!["k, s, b, s, 4 st 2b"]);
+        assert_eq!(
+            lines(&s, "CSS", "K+CS2(26)/DP"),
+            vec!["k, s, s, 4 cs 2b 26"]
+        );
+        let empty = state(5, [None, None, None]);
+        assert_eq!(lines(&empty, "CSS", "K+WP.B-1"), vec!["k, s", "kwp"]);
+        assert_eq!(lines(&empty, "", "K23"), vec!["s, s", "k23"]);
+        assert!(refused(&empty, "K+E2.B-1").contains("no scoring notation"));
+    }
+
+    #[test]
+    fn outs_and_double_plays() {
+        let s = state(6, [Some(5), Some(4), None]);
+        assert_eq!(lines(&s, "", "63/G.2-3"), vec!["63, 4 3b"]);
+        assert_eq!(lines(&s, "", "3/G"), vec!["6 3"]);
+        assert_eq!(lines(&s, "", "8/F"), vec!["f8"]);
+        assert_eq!(lines(&s, "", "2/FL"), vec!["ff2"]);
+        assert_eq!(lines(&s, "", "6/L"), vec!["l6"]);
+        assert_eq!(lines(&s, "", "4/IF"), vec!["if4"]);
+        assert_eq!(lines(&s, "", "64(1)3/GDP.2-3"), vec!["43, 5 64, 4 3b"]);
+        assert_eq!(lines(&s, "", "6(1)3/GDP"), vec!["63, 5 6"]);
+        assert_eq!(lines(&s, "", "13/SH.2-3;1-2"), vec!["sh13, 5 2b, 4 3b"]);
+        assert_eq!(lines(&s, "", "FC6.1X2(64);2-3"), vec!["o6 1b, 5 64, 4 3b"]);
+        assert_eq!(lines(&s, "", "64(1)/FO.B-1"), vec!["o6 1b, 5 64"]);
+        let third = state(6, [None, None, Some(2)]);
+        assert_eq!(lines(&third, "", "8/SF.3-H"), vec!["sf8, 2 sc"]);
+        assert_eq!(lines(&third, "", "6/L.3X3(65)"), vec!["l6, 2 65"]);
+        let corners = state(6, [Some(5), None, Some(3)]);
+        assert_eq!(lines(&corners, "", "1/SH.1-2"), vec!["sh1, 5 2b, 3 3b"]);
+        assert!(refused(&s, "99").contains("no scoring notation"));
+    }
+
+    #[test]
+    fn runner_events_share_the_pitch_line() {
+        let s = state(7, [Some(6), Some(5), None]);
+        let mut counted = s.clone();
+        counted.pitch_count.balls = 1;
+        counted.pitch_count.sequence = vec![crate::models::types::Pitch::Ball];
+        assert_eq!(
+            lines(&counted, "BC", "SB3;SB2"),
+            vec!["k, 5 st 3b, 6 st 2b"]
+        );
+        assert_eq!(lines(&s, "", "WP.2-3;1-2"), vec!["5 wp 3b, 6 wp 2b"]);
+        assert_eq!(lines(&s, "", "BK.2-3;1-2"), vec!["bk"]);
+        assert_eq!(lines(&s, "1", "PO1(13)"), vec!["6 po 13"]);
+        assert_eq!(lines(&s, "", "NP"), Vec::<String>::new());
+        assert!(refused(&s, "SB3.1-2").contains("no scoring notation"));
+        assert!(refused(&s, "OA.2-3").contains("no scoring notation"));
+        assert!(
+            play_lines(&record("BBBB", "SB3"), &s)
+                .unwrap_err()
+                .contains("end the plate appearance")
+        );
+    }
+}
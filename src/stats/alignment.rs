@@ -0,0 +1,97 @@
+//! Defensive alignment replay: who held each position when a play was
+//! made, so the fielder numbers of a sequence (`6-4-3`) can be credited
+//! to players.
+//!
+//! The alignment starts from the lineup cards and replays the
+//! `player_substituted` events in the order they were recorded.
+
+use crate::db::game_events::GameEventRow;
+use crate::models::events::{DomainEvent, SubstitutionData};
+use crate::models::types::Position;
+use crate::stats::GameRecord;
+
+struct Spot {
+    team_id: i64,
+    batting_order: u8,
+    player_id: i64,
+    position: Position,
+}
+
+/// Both defensive alignments of a game at some point of it.
+pub struct Alignment<'a> {
+    spots: Vec<Spot>,
+    events: &'a [GameEventRow],
+    /// Index of the first event not applied yet.
+    next: usize,
+}
+
+impl<'a> Alignment<'a> {
+    /// The alignments on the lineup cards, before the first pitch.
+    pub fn new(game: &'a GameRecord) -> Self {
+        let spots = game
+            .lineup_cards()
+            .into_iter()
+            .map(|(team_id, s)| Spot {
+                team_id,
+                batting_order: s.batting_order,
+                player_id: s.player_id,
+                position: s.position,
+            })
+            .collect();
+        Self {
+            spots,
+            events: &game.events,
+            next: 0,
+        }
+    }
+
+    /// Apply the lineup changes recorded after plate appearance `pa_seq`
+    /// or earlier (`None`: before the first one).
+    pub fn advance_to(&mut self, pa_seq: Option<i64>) {
+        while let Some(ev) = self.events.get(self.next) {
+            if ev.pa_seq > pa_seq {
+                break;
+            }
+            self.next += 1;
+            let Some(d) = substitution(ev) else {
+                continue;
+            };
+            match self
+                .spots
+                .iter_mut()
+                .find(|s| s.team_id == d.team_id && s.batting_order == d.batting_order)
+            {
+                Some(spot) => {
+                    spot.player_id = d.player_in_id;
+                    spot.position = d.position;
+                }
+                None => self.spots.push(Spot {
+                    team_id: d.team_id,
+                    batting_order: d.batting_order,
+                    player_id: d.player_in_id,
+                    position: d.position,
+                }),
+            }
+        }
+    }
+
+    /// The player of `team_id` at fielder number `fielder` (1-9).
+    pub fn player_at(&self, team_id: i64, fielder: u8) -> Option<i64> {
+        let position = Position::from_number(fielder)?;
+        self.spots
+            .iter()
+            .find(|s| s.team_id == team_id && s.position == position)
+            .map(|s| s.player_id)
+    }
+}
+
+/// The lineup change recorded by `ev`, if it is one.
+pub(crate) fn substitution(ev: &GameEventRow) -> Option<SubstitutionData> {
+    if ev.event_type != "player_substituted" {
+        return None;
+    }
+    match serde_json::from_str(ev.event_data.as_deref()?).ok()? {
+        DomainEvent::PlayerSubstituted(d) => Some(d),
+        _ => None,
+    }
+}
@@ -0,0 +1,195 @@
+//! Batting lines.
+//!
+//! Every stored plate appearance is a PA; base awards and sacrifices are
+//! not at-bats. BB includes intentional walks. A run scored is credited
+//! to the runner on the scoring movement, which needs `runner_id` (rows
+//! written before 0.12 without it are not counted).
+
+use crate::engine::rbi::row_rbi;
+use crate::engine::reducer::plate_appearance_from_row;
+use crate::models::plate_appearance::PlateAppearanceOutcome;
+use crate::stats::{GameRecord, ratio};
+use std::collections::BTreeMap;
+
+/// A player's batting for one team.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BattingLine {
+    pub player_id: i64,
+    pub team_id: i64,
+    pub plate_appearances: u32,
+    pub at_bats: u32,
+    pub runs: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub home_runs: u32,
+    pub rbi: u32,
+    pub walks: u32,
+    pub hit_by_pitch: u32,
+    pub sacrifice_flies: u32,
+    pub sacrifice_bunts: u32,
+    pub strikeouts: u32,
+}
+
+impl BattingLine {
+    /// Bases on hits: 1 per single up to 4 per home run.
+    pub fn total_bases(&self) -> u32 {
+        let singles = self.hits - self.doubles - self.triples - self.home_runs;
+        singles + 2 * self.doubles + 3 * self.triples + 4 * self.home_runs
+    }
+
+    /// Batting average: H / AB.
+    pub fn avg(&self) -> Option<f64> {
+        ratio(self.hits, self.at_bats)
+    }
+
+    /// On-base percentage: (H + BB + HBP) / (AB + BB + HBP + SF).
+    pub fn obp(&self) -> Option<f64> {
+        ratio(
+            self.hits + self.walks + self.hit_by_pitch,
+            self.at_bats + self.walks + self.hit_by_pitch + self.sacrifice_flies,
+        )
+    }
+
+    /// Slugging percentage: total bases / AB.
+    pub fn slg(&self) -> Option<f64> {
+        ratio(self.total_bases(), self.at_bats)
+    }
+
+    /// On-base plus slugging.
+    pub fn ops(&self) -> Option<f64> {
+        match (self.obp(), self.slg()) {
+            (None, None) => None,
+            (obp, slg) => Some(obp.unwrap_or(0.0) + slg.unwrap_or(0.0)),
+        }
+    }
+}
+
+/// Batting lines over `games`, by team then player. With `team_id` only
+/// that team's batters are kept.
+pub fn batting_lines(games: &[GameRecord], team_id: Option<i64>) -> Vec<BattingLine> {
+    let mut lines: BTreeMap<(i64, i64), BattingLine> = BTreeMap::new();
+
+    for game in games {
+        for row in &game.plate_appearances {
+            let team = game.batting_team(&row.half_inning);
+            if team_id.is_some_and(|t| t != team) {
+                continue;
+            }
+            let pa = plate_appearance_from_row(row);
+            let movements: Vec<_> = game
+                .movements
+                .iter()
+                .filter(|m| m.pa_seq == Some(row.seq))
+                .collect();
+            let line = line_of(&mut lines, team, row.batter_id);
+            line.plate_appearances += 1;
+            line.at_bats += pa.outcome.is_at_bat() as u32;
+            line.hits += pa.outcome.is_hit() as u32;
+            match pa.outcome.bases() {
+                2 => line.doubles += 1,
+                3 => line.triples += 1,
+                4 => line.home_runs += 1,
+                _ => {}
+            }
+            line.rbi += u32::from(row_rbi(row, &movements));
+            line.walks += matches!(
+                pa.outcome,
+                PlateAppearanceOutcome::Walk | PlateAppearanceOutcome::IntentionalWalk
+            ) as u32;
+            line.hit_by_pitch += matches!(pa.outcome, PlateAppearanceOutcome::HitByPitch) as u32;
+            line.sacrifice_flies += pa.outcome.is_sacrifice_fly() as u32;
+            line.sacrifice_bunts += pa.outcome.is_sacrifice_bunt() as u32;
+            line.strikeouts += pa.outcome.is_strikeout() as u32;
+        }
+
+        for rm in game.movements.iter().filter(|rm| rm.scored) {
+            let (Some(runner_id), team) = (rm.runner_id, game.batting_team(&rm.half_inning)) else {
+                continue;
+            };
+            if team_id.is_some_and(|t| t != team) {
+                continue;
+            }
+            line_of(&mut lines, team, runner_id).runs += 1;
+        }
+    }
+    lines.into_values().collect()
+}
+
+fn line_of(
+    lines: &mut BTreeMap<(i64, i64), BattingLine>,
+    team_id: i64,
+    player_id: i64,
+) -> &mut BattingLine {
+    lines.entry((team_id, player_id)).or_insert(BattingLine {
+        player_id,
+        team_id,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{AWAY, HOME, game_record, pa, rm};
+    use crate::stats::format_rate;
+
+    #[test]
+    fn line_counts_at_bats_hits_and_rates() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "double", None, 0),
+            pa(2, "Top", 2, 21, "walk", None, 0),
+            pa(3, "Top", 1, 21, "home_run", None, 0),
+            pa(4, "Top", 1, 21, "strikeout", Some(r#""Swinging""#), 1),
+            pa(
+                5,
+                "Top",
+                1,
+                21,
+                "fly_out",
+                Some(r#"{"fielder":8,"sacrifice":true}"#),
+                2,
+            ),
+        ];
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "2B", "hit_auto"),
+            rm(2, Some(2), "Top", 2, "BAT", "1B", "walk"),
+            rm(3, Some(3), "Top", 1, "BAT", "HOME", "hit_auto"),
+            rm(4, Some(3), "Top", 2, "1B", "HOME", "hit_auto"),
+        ];
+        let lines = batting_lines(&[game_record(pas, rows)], None);
+        let first = lines.iter().find(|l| l.player_id == 11).unwrap();
+        assert_eq!(
+            (first.plate_appearances, first.at_bats, first.hits),
+            (4, 3, 2)
+        );
+        assert_eq!(
+            (first.doubles, first.home_runs, first.strikeouts),
+            (1, 1, 1)
+        );
+        assert_eq!((first.runs, first.rbi, first.sacrifice_flies), (1, 2, 1));
+        assert_eq!(first.total_bases(), 6);
+        assert_eq!(format_rate(first.avg()), ".667");
+        assert_eq!(format_rate(first.obp()), ".500");
+        assert_eq!(format_rate(first.slg()), "2.000");
+        assert_eq!(format_rate(first.ops()), "2.500");
+
+        let second = lines.iter().find(|l| l.player_id == 12).unwrap();
+        assert_eq!((second.walks, second.at_bats, second.runs), (1, 0, 1));
+        assert_eq!(format_rate(second.avg()), "-");
+        assert_eq!(format_rate(second.obp()), "1.000");
+    }
+
+    #[test]
+    fn team_filter_keeps_only_its_batters() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "single", None, 0),
+            pa(2, "Bottom", 1, 11, "single", None, 0),
+        ];
+        let games = [game_record(pas, Vec::new())];
+        let lines = batting_lines(&games, Some(HOME));
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].player_id, lines[0].team_id), (21, HOME));
+        assert_eq!(batting_lines(&games, Some(AWAY))[0].player_id, 11);
+    }
+}
@@ -0,0 +1,779 @@
+//! Traditional box score of one game.
+//!
+//! Built from the same rows as the season stats: the line score from the
+//! scoring movements, a batting line per player in lineup-slot order
+//! (substitutes under the player they replaced), the pitching lines in
+//! order of appearance with the decisions, and the notes under each
+//! table.
+//!
+//! Decisions (Rule 9.17, 9.19):
+//!
+//! - the winning pitcher is the winning team's pitcher of record when it
+//!   took the lead for good; a starter who did not pitch five innings of
+//!   a nine-inning game (in proportion for shorter games) gives way to the
+//!   reliever with the most outs;
+//! - the losing pitcher is the one charged with the go-ahead run;
+//! - the save goes to the winning team's last pitcher, when they are not
+//!   the winner, got at least one out and either came in leading by three
+//!   runs or fewer and pitched an inning, came in with the tying run on
+//!   base, at bat or on deck, or pitched three innings.
+
+use crate::db::umpire::list_game_umpires;
+use crate::engine::earned_runs::{Play, charge_runs, half_innings, pitcher_on_the_mound};
+use crate::engine::reducer::plate_appearance_from_row;
+use crate::models::events::SubstitutionKind;
+use crate::models::game_state::BatterOrder;
+use crate::models::plate_appearance::{DroppedThirdStrike, PlateAppearanceOutcome};
+use crate::stats::alignment::substitution;
+use crate::stats::batting::{BattingLine, batting_lines};
+use crate::stats::fielding::fielding_lines;
+use crate::stats::pitching::{PitchingLine, pitching_lines};
+use crate::stats::{GameRecord, player_names};
+use rusqlite::{Connection, Result, params};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Pitcher's decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Win,
+    Loss,
+    Save,
+}
+
+impl Decision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Win => "W",
+            Self::Loss => "L",
+            Self::Save => "S",
+        }
+    }
+}
+
+/// A batting line of the box score.
+#[derive(Debug, Clone)]
+pub struct BoxBatter {
+    pub batting_order: u8,
+    /// Entered the game as a substitute.
+    pub substitute: bool,
+    pub name: String,
+    /// Positions played in order, `PH` / `PR` for a pinch hitter or
+    /// runner: `"SS"`, `"PH-2B"`.
+    pub positions: String,
+    pub line: BattingLine,
+}
+
+/// A pitching line of the box score.
+#[derive(Debug, Clone)]
+pub struct BoxPitcher {
+    pub name: String,
+    pub decision: Option<Decision>,
+    pub line: PitchingLine,
+}
+
+/// One team's half of the box score.
+#[derive(Debug, Clone, Default)]
+pub struct TeamBox {
+    pub team_id: i64,
+    pub name: String,
+    pub abbreviation: String,
+    pub runs: u32,
+    pub hits: u32,
+    pub errors: u32,
+    pub left_on_base: u32,
+    pub batters: Vec<BoxBatter>,
+    pub pitchers: Vec<BoxPitcher>,
+    /// Notes under the batting table: 2B, 3B, HR, SB, CS, E, DP, LOB.
+    pub notes: Vec<(String, String)>,
+}
+
+/// Box score of a game.
+#[derive(Debug, Clone, Default)]
+pub struct BoxScore {
+    pub game_id: String,
+    pub date: String,
+    pub venue: String,
+    pub away: TeamBox,
+    pub home: TeamBox,
+    /// Runs of each inning, away then home; `None` for a bottom half not
+    /// played.
+    pub line_score: Vec<(u32, Option<u32>)>,
+    /// Game notes: HBP, WP, umpires.
+    pub notes: Vec<(String, String)>,
+}
+
+/// What a pass over the plays in order finds out.
+#[derive(Default)]
+struct Replay {
+    /// Runs by (inning, bottom half).
+    runs: BTreeMap<(i64, bool), u32>,
+    left_on_base: [u32; 2],
+    /// Go-ahead run for good: its movement and the winning team's pitcher
+    /// of record, with the index of the play.
+    go_ahead: Option<(i64, Option<i64>, usize)>,
+    /// For every pitcher: index of their first play, the winning team's
+    /// lead and the runners on base when they came in.
+    entries: HashMap<i64, (usize, i32, usize)>,
+    /// Pitcher charged with each wild pitch.
+    wild_pitches: Vec<i64>,
+}
+
+impl BoxScore {
+    /// Build the box score of game `game_pk` (the `games` row id).
+    pub fn load(conn: &Connection, game_pk: i64) -> Result<Self> {
+        let game = GameRecord::load(conn, game_pk)?;
+        let (game_id, date, venue): (String, String, Option<String>) = conn.query_row(
+            "SELECT game_id, game_date, venue FROM games WHERE id = ?1",
+            params![game_pk],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        let team = |team_id: i64| -> Result<(String, String)> {
+            conn.query_row(
+                "SELECT name, COALESCE(abbreviation, name) FROM teams WHERE id = ?1",
+                params![team_id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+        };
+        let names = player_names(conn)?;
+        let umpires = list_game_umpires(conn, game_pk)?
+            .into_iter()
+            .map(|u| (u.position, u.umpire_name.unwrap_or_default()))
+            .collect();
+
+        let mut box_score = Self::build(&game, &names, umpires);
+        box_score.game_id = game_id;
+        box_score.date = date;
+        box_score.venue = venue.unwrap_or_default();
+        (box_score.away.name, box_score.away.abbreviation) = team(game.away_team_id)?;
+        (box_score.home.name, box_score.home.abbreviation) = team(game.home_team_id)?;
+        Ok(box_score)
+    }
+
+    /// Build the tables of `game`; `names` maps player ids to display
+    /// names and `umpires` lists (position, name).
+    pub fn build(
+        game: &GameRecord,
+        names: &HashMap<i64, String>,
+        umpires: Vec<(String, String)>,
+    ) -> Self {
+        let name = |id: i64| names.get(&id).cloned().unwrap_or_else(|| format!("#{id}"));
+        let games = std::slice::from_ref(game);
+        let batting = batting_lines(games, None);
+        let pitching = pitching_lines(games, None);
+        let fielding = fielding_lines(games, None);
+
+        let mut final_score = [0i32; 2];
+        for rm in game.movements.iter().filter(|rm| rm.scored) {
+            final_score[(rm.half_inning == "Bottom") as usize] += 1;
+        }
+        let winner = match final_score[0].cmp(&final_score[1]) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        };
+        let replay = replay(game, winner);
+
+        let mut teams = [game.away_team_id, game.home_team_id].map(|team_id| TeamBox {
+            team_id,
+            ..Default::default()
+        });
+        for (side, team) in teams.iter_mut().enumerate() {
+            team.runs = final_score[side] as u32;
+            team.left_on_base = replay.left_on_base[side];
+            team.batters = slots(game, team.team_id)
+                .into_iter()
+                .map(
+                    |(batting_order, substitute, player_id, positions)| BoxBatter {
+                        batting_order,
+                        substitute,
+                        name: name(player_id),
+                        positions,
+                        line: batting
+                            .iter()
+                            .find(|l| l.team_id == team.team_id && l.player_id == player_id)
+                            .cloned()
+                            .unwrap_or(BattingLine {
+                                player_id,
+                                team_id: team.team_id,
+                                ..Default::default()
+                            }),
+                    },
+                )
+                .collect();
+            team.hits = team.batters.iter().map(|b| b.line.hits).sum();
+            team.pitchers = pitchers_in_order(game, team.team_id)
+                .into_iter()
+                .filter_map(|id| {
+                    pitching
+                        .iter()
+                        .find(|l| l.team_id == team.team_id && l.player_id == id)
+                })
+                .map(|line| BoxPitcher {
+                    name: name(line.player_id),
+                    decision: None,
+                    line: line.clone(),
+                })
+                .collect();
+        }
+        for row in &game.plate_appearances {
+            let fielding_side = (row.half_inning != "Bottom") as usize;
+            teams[fielding_side].errors +=
+                u32::from(plate_appearance_from_row(row).errors_charged());
+        }
+        if let Some(winner) = winner {
+            decide(&mut teams, winner, &replay, game);
+        }
+
+        for (side, team) in teams.iter_mut().enumerate() {
+            let batters: Vec<(String, &BattingLine)> = team
+                .batters
+                .iter()
+                .map(|b| (b.name.clone(), &b.line))
+                .collect();
+            let mut notes = Vec::new();
+            let mut note = |label: &str, text: String| {
+                if !text.is_empty() {
+                    notes.push((label.to_string(), text));
+                }
+            };
+            note("2B", counted(batters.iter().map(|(n, l)| (n, l.doubles))));
+            note("3B", counted(batters.iter().map(|(n, l)| (n, l.triples))));
+            note("HR", counted(batters.iter().map(|(n, l)| (n, l.home_runs))));
+            let runner_notes = |types: &[&str]| {
+                let mut counts: Vec<(String, u32)> = Vec::new();
+                for rm in game.movements.iter().filter(|rm| {
+                    types.contains(&rm.advancement_type.as_str())
+                        && (rm.half_inning == "Bottom") == (side == 1)
+                }) {
+                    let runner = rm.runner_id.map(name).unwrap_or_else(|| "?".to_string());
+                    match counts.iter_mut().find(|(n, _)| *n == runner) {
+                        Some((_, c)) => *c += 1,
+                        None => counts.push((runner, 1)),
+                    }
+                }
+                counted(counts.iter().map(|(n, c)| (n, *c)))
+            };
+            note("SB", runner_notes(&["steal"]));
+            note(
+                "CS",
+                runner_notes(&["caught_stealing", "pickoff_caught_stealing"]),
+            );
+            let errors: Vec<(String, u32)> = fielding
+                .iter()
+                .filter(|l| l.team_id == team.team_id)
+                .map(|l| (name(l.player_id), l.errors))
+                .collect();
+            note("E", counted(errors.iter().map(|(n, c)| (n, *c))));
+            let double_plays = game
+                .plate_appearances
+                .iter()
+                .filter(|row| {
+                    (row.half_inning != "Bottom") == (side == 1) && row.double_play_json.is_some()
+                })
+                .count();
+            if double_plays > 0 {
+                note("DP", double_plays.to_string());
+            }
+            note("LOB", team.left_on_base.to_string());
+            team.notes = notes;
+        }
+
+        let mut notes = Vec::new();
+        let hit_by_pitch: Vec<String> = game
+            .plate_appearances
+            .iter()
+            .filter(|row| row.outcome_type == "hit_by_pitch")
+            .map(|row| format!("{} (by {})", name(row.batter_id), name(row.pitcher_id)))
+            .collect();
+        if !hit_by_pitch.is_empty() {
+            notes.push(("HBP".to_string(), hit_by_pitch.join(", ")));
+        }
+        let mut wild_pitches: Vec<(String, u32)> = Vec::new();
+        for pitcher in &replay.wild_pitches {
+            let pitcher = name(*pitcher);
+            match wild_pitches.iter_mut().find(|(n, _)| *n == pitcher) {
+                Some((_, c)) => *c += 1,
+                None => wild_pitches.push((pitcher, 1)),
+            }
+        }
+        let wild_pitches = counted(wild_pitches.iter().map(|(n, c)| (n, *c)));
+        if !wild_pitches.is_empty() {
+            notes.push(("WP".to_string(), wild_pitches));
+        }
+        if !umpires.is_empty() {
+            let crew: Vec<String> = umpires
+                .iter()
+                .map(|(position, name)| format!("{position}: {name}"))
+                .collect();
+            notes.push(("Umpires".to_string(), crew.join(", ")));
+        }
+
+        let last_inning = replay.runs.keys().map(|(i, _)| *i).max().unwrap_or(0);
+        let line_score = (1..=last_inning)
+            .map(|inning| {
+                (
+                    replay.runs.get(&(inning, false)).copied().unwrap_or(0),
+                    replay.runs.get(&(inning, true)).copied(),
+                )
+            })
+            .collect();
+
+        let [away, home] = teams;
+        Self {
+            away,
+            home,
+            line_score,
+            notes,
+            ..Default::default()
+        }
+    }
+}
+
+/// "Name", or "Name 2" when it happened more than once, for every
+/// non-zero count.
+fn counted<'a>(counts: impl Iterator<Item = (&'a String, u32)>) -> String {
+    counts
+        .filter(|(_, c)| *c > 0)
+        .map(|(n, c)| if c > 1 { format!("{n} {c}") } else { n.clone() })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The players of `team_id` by lineup slot, in the order they entered:
+/// (slot, substitute, player id, positions played).
+fn slots(game: &GameRecord, team_id: i64) -> Vec<(u8, bool, i64, String)> {
+    let mut slots: BTreeMap<u8, Vec<(bool, i64, Vec<String>)>> = BTreeMap::new();
+    for (team, spot) in game.lineup_cards() {
+        if team == team_id {
+            slots.entry(spot.batting_order).or_default().push((
+                false,
+                spot.player_id,
+                vec![spot.position.to_string()],
+            ));
+        }
+    }
+    for d in game.events.iter().filter_map(substitution) {
+        if d.team_id != team_id {
+            continue;
+        }
+        let players = slots.entry(d.batting_order).or_default();
+        match (d.player_out_id, players.last_mut()) {
+            (None, Some((_, _, positions))) => {
+                if positions.last() != Some(&d.position.to_string()) {
+                    positions.push(d.position.to_string());
+                }
+            }
+            _ => {
+                let position = match d.kind {
+                    SubstitutionKind::PinchHitter => "PH".to_string(),
+                    SubstitutionKind::PinchRunner => "PR".to_string(),
+                    _ => d.position.to_string(),
+                };
+                players.push((true, d.player_in_id, vec![position]));
+            }
+        }
+    }
+    slots
+        .into_iter()
+        // The pitcher of a DH lineup does not bat.
+        .filter(|(order, _)| *order <= 9)
+        .flat_map(|(order, players)| {
+            players
+                .into_iter()
+                .map(move |(sub, id, positions)| (order, sub, id, positions.join("-")))
+        })
+        .collect()
+}
+
+/// Pitchers of `team_id` in the order they pitched.
+fn pitchers_in_order(game: &GameRecord, team_id: i64) -> Vec<i64> {
+    let mut pitchers = Vec::new();
+    for row in &game.plate_appearances {
+        if game.fielding_team(&row.half_inning) == team_id && !pitchers.contains(&row.pitcher_id) {
+            pitchers.push(row.pitcher_id);
+        }
+    }
+    pitchers
+}
+
+fn replay(game: &GameRecord, winner: Option<usize>) -> Replay {
+    let mut replay = Replay::default();
+    let mut score = [0i32; 2];
+    let mut pitcher_of_record: [Option<i64>; 2] = [None, None];
+    let mut index = 0;
+
+    for plays in half_innings(&game.plate_appearances, &game.movements) {
+        let mut on_base: Vec<BatterOrder> = Vec::new();
+        let mut bottom = false;
+        for (i, play) in plays.iter().enumerate() {
+            let (inning, half, rows): (i64, &str, Vec<_>) = match play {
+                Play::PlateAppearance(row, rows) => (
+                    row.inning,
+                    &row.half_inning,
+                    game.movements
+                        .iter()
+                        .filter(|rm| rows.iter().any(|r| r.id == rm.id))
+                        .collect(),
+                ),
+                Play::Between(rm) => (rm.inning, &rm.half_inning, vec![*rm]),
+            };
+            bottom = half == "Bottom";
+            let batting = bottom as usize;
+            let fielding = 1 - batting;
+            replay.runs.entry((inning, bottom)).or_insert(0);
+
+            let pitcher_id = pitcher_on_the_mound(&plays, i);
+            pitcher_of_record[fielding] = Some(pitcher_id);
+            replay.entries.entry(pitcher_id).or_insert_with(|| {
+                let lead = winner.map_or(0, |w| score[w] - score[1 - w]);
+                (index, lead, on_base.len())
+            });
+
+            match play {
+                Play::PlateAppearance(row, _) => {
+                    if matches!(
+                        plate_appearance_from_row(row).outcome,
+                        PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::WildPitch)
+                    ) {
+                        replay.wild_pitches.push(pitcher_id);
+                    }
+                }
+                Play::Between(rm) if rm.advancement_type == "wild_pitch" => {
+                    // One pitch can move several runners: a new wild pitch
+                    // starts with the first runner of a run of rows, or when
+                    // a runner moves again.
+                    let previous = plays[..i].last();
+                    let same_pitch = matches!(previous, Some(Play::Between(p))
+                        if p.advancement_type == "wild_pitch"
+                            && p.pa_seq == rm.pa_seq
+                            && p.batter_order != rm.batter_order);
+                    if !same_pitch {
+                        replay.wild_pitches.push(pitcher_id);
+                    }
+                }
+                Play::Between(_) => {}
+            }
+
+            for rm in rows {
+                on_base.retain(|o| *o != rm.batter_order);
+                if matches!(rm.end_base.as_str(), "1B" | "2B" | "3B") {
+                    on_base.push(rm.batter_order);
+                }
+                if !rm.scored {
+                    continue;
+                }
+                *replay.runs.entry((inning, bottom)).or_insert(0) += 1;
+                let lead_before = score[batting] - score[fielding];
+                score[batting] += 1;
+                if winner == Some(batting) && lead_before == 0 {
+                    replay.go_ahead = Some((rm.id, pitcher_of_record[batting], index));
+                }
+            }
+            index += 1;
+        }
+        replay.left_on_base[bottom as usize] += on_base.len() as u32;
+    }
+    replay
+}
+
+/// Set the W, L and S of the pitching lines.
+fn decide(teams: &mut [TeamBox; 2], winner: usize, replay: &Replay, game: &GameRecord) {
+    let Some((run_id, of_record, go_ahead_index)) = replay.go_ahead else {
+        return;
+    };
+    let loser = 1 - winner;
+    let charges = charge_runs(&game.plate_appearances, &game.movements);
+    let losing = charges
+        .iter()
+        .find(|c| c.movement_id == run_id)
+        .map(|c| c.pitcher_id);
+
+    let pitchers = &teams[winner].pitchers;
+    let Some(starter) = pitchers.first().map(|p| p.line.player_id) else {
+        return;
+    };
+    // Five innings of nine, in proportion for shorter games.
+    let required_outs = (game.innings * 5).div_ceil(9) * 3;
+    let mut winning = of_record.unwrap_or(starter);
+    if winning == starter
+        && pitchers[0].line.outs < required_outs
+        && let Some(reliever) = pitchers[1..].iter().max_by_key(|p| {
+            (
+                p.line.outs,
+                std::cmp::Reverse(replay.entries.get(&p.line.player_id)),
+            )
+        })
+    {
+        winning = reliever.line.player_id;
+    }
+
+    let saving = pitchers.last().filter(|p| {
+        let Some(&(entry, lead, runners)) = replay.entries.get(&p.line.player_id) else {
+            return false;
+        };
+        p.line.player_id != winning
+            && p.line.outs >= 1
+            && entry > go_ahead_index
+            && ((lead <= 3 && p.line.outs >= 3) || lead <= runners as i32 + 2 || p.line.outs >= 9)
+    });
+    let saving = saving.map(|p| p.line.player_id);
+
+    for p in teams[winner].pitchers.iter_mut() {
+        if p.line.player_id == winning {
+            p.decision = Some(Decision::Win);
+        } else if Some(p.line.player_id) == saving {
+            p.decision = Some(Decision::Save);
+        }
+    }
+    for p in teams[loser].pitchers.iter_mut() {
+        if Some(p.line.player_id) == losing {
+            p.decision = Some(Decision::Loss);
+        }
+    }
+}
+
+impl fmt::Display for BoxScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {}, {} {}",
+            self.away.name, self.away.runs, self.home.name, self.home.runs
+        )?;
+        writeln!(f, "{} - {} ({})", self.date, self.venue, self.game_id)?;
+        writeln!(f)?;
+
+        // Line score
+        write!(f, "{:<12}", "")?;
+        for inning in 1..=self.line_score.len() {
+            write!(f, "{inning:>3}")?;
+        }
+        writeln!(f, "   {:>3}{:>3}{:>3}", "R", "H", "E")?;
+        for (side, team) in [&self.away, &self.home].into_iter().enumerate() {
+            write!(f, "{:<12}", team.abbreviation)?;
+            for (top, bottom) in &self.line_score {
+                match (side, bottom) {
+                    (0, _) => write!(f, "{top:>3}")?,
+                    (_, Some(runs)) => write!(f, "{runs:>3}")?,
+                    (_, None) => write!(f, "{:>3}", "X")?,
+                }
+            }
+            writeln!(f, "   {:>3}{:>3}{:>3}", team.runs, team.hits, team.errors)?;
+        }
+
+        for team in [&self.away, &self.home] {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{:<28}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}",
+                team.name, "AB", "R", "H", "RBI", "BB", "K"
+            )?;
+            let mut totals = BattingLine::default();
+            for b in &team.batters {
+                let label = if b.substitute {
+                    format!("  {}, {}", b.name, b.positions)
+                } else {
+                    format!("{} {}, {}", b.batting_order, b.name, b.positions)
+                };
+                let l = &b.line;
+                writeln!(
+                    f,
+                    "{:<28}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}",
+                    label, l.at_bats, l.runs, l.hits, l.rbi, l.walks, l.strikeouts
+                )?;
+                totals.at_bats += l.at_bats;
+                totals.runs += l.runs;
+                totals.hits += l.hits;
+                totals.rbi += l.rbi;
+                totals.walks += l.walks;
+                totals.strikeouts += l.strikeouts;
+            }
+            writeln!(
+                f,
+                "{:<28}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}",
+                "Totals",
+                totals.at_bats,
+                totals.runs,
+                totals.hits,
+                totals.rbi,
+                totals.walks,
+                totals.strikeouts
+            )?;
+            for (label, text) in &team.notes {
+                writeln!(f, "{label}: {text}.")?;
+            }
+        }
+
+        for team in [&self.away, &self.home] {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{:<28}{:>5}{:>4}{:>4}{:>4}{:>4}{:>4}{:>5}",
+                team.name, "IP", "H", "R", "ER", "BB", "K", "PC"
+            )?;
+            for p in &team.pitchers {
+                let label = match p.decision {
+                    Some(d) => format!("{} ({})", p.name, d.as_str()),
+                    None => p.name.clone(),
+                };
+                let l = &p.line;
+                writeln!(
+                    f,
+                    "{:<28}{:>5}{:>4}{:>4}{:>4}{:>4}{:>4}{:>5}",
+                    label,
+                    l.innings_pitched(),
+                    l.hits,
+                    l.runs,
+                    l.earned_runs,
+                    l.walks,
+                    l.strikeouts,
+                    l.pitches
+                )?;
+            }
+        }
+
+        if !self.notes.is_empty() {
+            writeln!(f)?;
+            for (label, text) in &self.notes {
+                writeln!(f, "{label}: {text}.")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{game_record, pa, rm};
+
+    fn names() -> HashMap<i64, String> {
+        (11..=39).map(|id| (id, format!("P{id}"))).collect()
+    }
+
+    fn decision(team: &TeamBox, player: &str) -> Option<Decision> {
+        team.pitchers
+            .iter()
+            .find(|p| p.name == player)
+            .and_then(|p| p.decision)
+    }
+
+    #[test]
+    fn line_score_totals_and_notes() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "double", None, 0),
+            pa(2, "Top", 2, 21, "home_run", None, 0),
+            pa(
+                3,
+                "Top",
+                3,
+                21,
+                "reached_on_error",
+                Some(r#"{"fielder":6}"#),
+                0,
+            ),
+            pa(4, "Top", 4, 21, "strikeout", None, 1),
+            pa(5, "Top", 5, 21, "strikeout", None, 2),
+            pa(6, "Top", 6, 21, "strikeout", None, 3),
+            pa(7, "Bottom", 1, 11, "hit_by_pitch", None, 0),
+            pa(8, "Bottom", 2, 11, "strikeout", None, 1),
+            pa(9, "Bottom", 3, 11, "strikeout", None, 2),
+            pa(10, "Bottom", 4, 11, "strikeout", None, 3),
+        ];
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "2B", "hit_auto"),
+            rm(2, Some(2), "Top", 1, "2B", "HOME", "hit_auto"),
+            rm(3, Some(2), "Top", 2, "BAT", "HOME", "hit_auto"),
+            rm(4, Some(3), "Top", 3, "BAT", "1B", "error"),
+            rm(5, Some(3), "Top", 3, "1B", "2B", "steal"),
+            rm(6, Some(7), "Bottom", 1, "BAT", "1B", "hit_by_pitch"),
+            rm(7, Some(7), "Bottom", 1, "1B", "2B", "wild_pitch"),
+        ];
+        let box_score = BoxScore::build(&game_record(pas, rows), &names(), Vec::new());
+
+        assert_eq!(box_score.line_score, vec![(2, Some(0))]);
+        assert_eq!(
+            (
+                box_score.away.runs,
+                box_score.away.hits,
+                box_score.away.errors
+            ),
+            (2, 2, 0)
+        );
+        assert_eq!(box_score.home.errors, 1);
+        assert_eq!(box_score.away.left_on_base, 1);
+        assert_eq!(box_score.home.left_on_base, 1);
+        assert_eq!(box_score.away.batters.len(), 9);
+
+        let note = |team: &TeamBox, label: &str| {
+            team.notes
+                .iter()
+                .find(|(l, _)| l == label)
+                .map(|(_, t)| t.clone())
+        };
+        assert_eq!(note(&box_score.away, "2B").as_deref(), Some("P11"));
+        assert_eq!(note(&box_score.away, "HR").as_deref(), Some("P12"));
+        assert_eq!(note(&box_score.away, "SB").as_deref(), Some("P13"));
+        assert_eq!(note(&box_score.home, "E").as_deref(), Some("P26"));
+        assert_eq!(
+            box_score.notes,
+            vec![
+                ("HBP".to_string(), "P21 (by P11)".to_string()),
+                ("WP".to_string(), "P11".to_string()),
+            ]
+        );
+
+        let text = box_score.to_string();
+        assert!(text.contains("2B: P11."));
+        assert!(text.contains("LOB: 1."));
+    }
+
+    #[test]
+    fn decisions_go_to_the_pitchers_of_record_and_the_closer() {
+        // Three-inning game won 1-0 on a first-inning home run off the
+        // home starter (21). The away starter (11) pitches two innings,
+        // the reliever (31) the third with a one-run lead.
+        let mut pas = vec![pa(1, "Top", 1, 21, "home_run", None, 0)];
+        for inning in 1..=3 {
+            for (half, pitcher) in [("Top", 21), ("Bottom", if inning < 3 { 11 } else { 31 })] {
+                for outs in 1..=3 {
+                    let seq = pas.len() as i64 + 1;
+                    let mut row = pa(seq, half, outs as u8 + 1, pitcher, "strikeout", None, outs);
+                    row.inning = inning;
+                    pas.push(row);
+                }
+            }
+        }
+        let rows = vec![rm(1, Some(1), "Top", 1, "BAT", "HOME", "hit_auto")];
+        let mut record = game_record(pas, rows);
+        record.innings = 3;
+        let box_score = BoxScore::build(&record, &names(), Vec::new());
+
+        assert_eq!(decision(&box_score.home, "P21"), Some(Decision::Loss));
+        assert_eq!(decision(&box_score.away, "P11"), Some(Decision::Win));
+        assert_eq!(decision(&box_score.away, "P31"), Some(Decision::Save));
+        assert_eq!(
+            box_score.line_score,
+            vec![(1, Some(0)), (0, Some(0)), (0, Some(0))]
+        );
+    }
+
+    #[test]
+    fn short_starter_gives_the_win_to_the_reliever() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "home_run", None, 0),
+            pa(2, "Top", 2, 21, "strikeout", None, 1),
+            pa(3, "Top", 3, 21, "strikeout", None, 2),
+            pa(4, "Top", 4, 21, "strikeout", None, 3),
+            pa(5, "Bottom", 1, 11, "strikeout", None, 1),
+            pa(6, "Bottom", 2, 31, "strikeout", None, 2),
+            pa(7, "Bottom", 3, 31, "strikeout", None, 3),
+        ];
+        let rows = vec![rm(1, Some(1), "Top", 1, "BAT", "HOME", "hit_auto")];
+        // Nine-inning game: one out is far from the five innings needed.
+        let box_score = BoxScore::build(&game_record(pas, rows), &names(), Vec::new());
+
+        assert_eq!(decision(&box_score.away, "P11"), None);
+        assert_eq!(decision(&box_score.away, "P31"), Some(Decision::Win));
+    }
+}
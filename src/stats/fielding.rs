@@ -0,0 +1,274 @@
+//! Fielding lines.
+//!
+//! Every out is credited from its fielding sequence: the last fielder
+//! gets the putout, each one before an assist (once per play, even when
+//! they handle the ball twice in a rundown). A strikeout is the
+//! catcher's putout. Errors come from the batter reaching on one and from
+//! the runners the scorer moved up on one. Outs recorded before 0.12
+//! kept no sequence for the runners retired on a batted ball, so only
+//! the batter's out of those plays is credited.
+
+use crate::engine::earned_runs::{Play, half_innings, pitcher_on_the_mound};
+use crate::engine::reducer::plate_appearance_from_row;
+use crate::models::plate_appearance::{DroppedThirdStrike, PlateAppearanceOutcome};
+use crate::stats::alignment::Alignment;
+use crate::stats::{GameRecord, ratio};
+use std::collections::BTreeMap;
+
+/// A fielder's line for one team.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldingLine {
+    pub player_id: i64,
+    pub team_id: i64,
+    pub putouts: u32,
+    pub assists: u32,
+    pub errors: u32,
+    /// Double and triple plays taken part in.
+    pub double_plays: u32,
+}
+
+impl FieldingLine {
+    /// Total chances: PO + A + E.
+    pub fn chances(&self) -> u32 {
+        self.putouts + self.assists + self.errors
+    }
+
+    /// Fielding percentage: (PO + A) / (PO + A + E).
+    pub fn fielding_pct(&self) -> Option<f64> {
+        ratio(self.putouts + self.assists, self.chances())
+    }
+}
+
+/// Fielder numbers credited on one play.
+#[derive(Default)]
+struct Credits {
+    putouts: Vec<u8>,
+    assists: Vec<u8>,
+    errors: Vec<u8>,
+    double_plays: Vec<u8>,
+}
+
+impl Credits {
+    /// An out on `chain` (`"6-4-3"`, `"63"`), putout last.
+    fn chain(&mut self, chain: &str) {
+        let fielders: Vec<u8> = chain
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .filter_map(|d| u8::try_from(d).ok())
+            .filter(|d| (1..=9).contains(d))
+            .collect();
+        let Some((putout, assists)) = fielders.split_last() else {
+            return;
+        };
+        self.putouts.push(*putout);
+        for a in assists {
+            if !self.assists.contains(a) {
+                self.assists.push(*a);
+            }
+        }
+    }
+
+    fn batter(&mut self, outcome: &PlateAppearanceOutcome) {
+        match outcome {
+            PlateAppearanceOutcome::Strikeout(_) => self.putouts.push(2),
+            PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::ThrownOut {
+                sequence,
+            })
+            | PlateAppearanceOutcome::GroundOut { sequence, .. } => self.chain(sequence),
+            PlateAppearanceOutcome::UnassistedOut { fielder, .. }
+            | PlateAppearanceOutcome::FlyOut { fielder, .. }
+            | PlateAppearanceOutcome::LineOut { fielder }
+            | PlateAppearanceOutcome::InfieldFly { fielder } => self.chain(&fielder.to_string()),
+            PlateAppearanceOutcome::ReachedOnError { fielder, .. } => self.errors.push(*fielder),
+            _ => {}
+        }
+    }
+}
+
+/// Fielding lines over `games`, by team then player. With `team_id` only
+/// that team's fielders are kept.
+pub fn fielding_lines(games: &[GameRecord], team_id: Option<i64>) -> Vec<FieldingLine> {
+    let mut lines: BTreeMap<(i64, i64), FieldingLine> = BTreeMap::new();
+
+    for game in games {
+        let mut alignment = Alignment::new(game);
+        let mut last_pa = None;
+        for plays in half_innings(&game.plate_appearances, &game.movements) {
+            for (i, play) in plays.iter().enumerate() {
+                let mut credits = Credits::default();
+                let half_inning = match play {
+                    Play::PlateAppearance(row, rows) => {
+                        alignment.advance_to(last_pa);
+                        last_pa = Some(row.seq);
+                        let pa = plate_appearance_from_row(row);
+                        credits.batter(&pa.outcome);
+                        for rm in rows.iter().filter(|rm| rm.is_out && rm.start_base != "BAT") {
+                            credits.chain(rm.fielders.as_deref().unwrap_or_default());
+                        }
+                        credits.errors.extend(
+                            pa.runner_overrides
+                                .iter()
+                                .filter_map(|o| o.error.map(|e| e.fielder)),
+                        );
+                        if let Some(dp) = pa.double_play {
+                            credits.double_plays = dp.fielders;
+                        }
+                        &row.half_inning
+                    }
+                    Play::Between(rm) => {
+                        alignment.advance_to(rm.pa_seq);
+                        if rm.is_out {
+                            credits.chain(rm.fielders.as_deref().unwrap_or_default());
+                        }
+                        &rm.half_inning
+                    }
+                };
+
+                let team = game.fielding_team(half_inning);
+                if team_id.is_some_and(|t| t != team) {
+                    continue;
+                }
+                // The pitcher of the play, even when the lineup has none
+                // (DH) or it changed between pitches.
+                let pitcher_id = pitcher_on_the_mound(&plays, i);
+                let player = |fielder: u8| match fielder {
+                    1 => Some(pitcher_id),
+                    n => alignment.player_at(team, n),
+                };
+                let mut credit = |fielders: &[u8], stat: fn(&mut FieldingLine)| {
+                    for player_id in fielders.iter().filter_map(|f| player(*f)) {
+                        stat(lines.entry((team, player_id)).or_insert(FieldingLine {
+                            player_id,
+                            team_id: team,
+                            ..Default::default()
+                        }));
+                    }
+                };
+                credit(&credits.putouts, |l| l.putouts += 1);
+                credit(&credits.assists, |l| l.assists += 1);
+                credit(&credits.errors, |l| l.errors += 1);
+                credit(&credits.double_plays, |l| l.double_plays += 1);
+            }
+        }
+    }
+    lines.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{HOME, game_record, pa, rm};
+    use crate::db::game_events::GameEventRow;
+    use crate::models::events::{DomainEvent, SubstitutionData, SubstitutionKind};
+    use crate::models::plate_appearance::{DoublePlay, DoublePlayKind};
+    use crate::models::types::Position;
+    use crate::stats::format_rate;
+
+    fn line(lines: &[FieldingLine], player_id: i64) -> (u32, u32, u32, u32) {
+        lines
+            .iter()
+            .find(|l| l.player_id == player_id)
+            .map(|l| (l.putouts, l.assists, l.errors, l.double_plays))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn sequences_credit_putouts_assists_errors_and_double_plays() {
+        let mut dp = pa(
+            2,
+            "Top",
+            2,
+            21,
+            "ground_out",
+            Some(r#"{"sequence":"4-3"}"#),
+            2,
+        );
+        dp.double_play_json = Some(
+            serde_json::to_string(&DoublePlay::new(DoublePlayKind::GroundBall, 2, [6, 4, 3]))
+                .unwrap(),
+        );
+        let pas = vec![
+            pa(1, "Top", 1, 21, "single", None, 0),
+            dp,
+            pa(
+                3,
+                "Top",
+                3,
+                21,
+                "reached_on_error",
+                Some(r#"{"fielder":5}"#),
+                2,
+            ),
+            pa(4, "Top", 4, 21, "strikeout", Some(r#""Swinging""#), 3),
+        ];
+        let mut forced = rm(1, Some(2), "Top", 1, "1B", "OUT", "ground_out");
+        forced.fielders = Some("6-4".to_string());
+        let lines = fielding_lines(&[game_record(pas, vec![forced])], None);
+
+        assert_eq!(line(&lines, 26), (0, 1, 0, 1));
+        assert_eq!(line(&lines, 24), (1, 1, 0, 1));
+        assert_eq!(line(&lines, 23), (1, 0, 0, 1));
+        assert_eq!(line(&lines, 25), (0, 0, 1, 0));
+        assert_eq!(line(&lines, 22), (1, 0, 0, 0));
+        assert!(lines.iter().all(|l| l.team_id == HOME));
+
+        let third = lines.iter().find(|l| l.player_id == 25).unwrap();
+        assert_eq!(format_rate(third.fielding_pct()), ".000");
+    }
+
+    #[test]
+    fn defensive_substitution_moves_the_credits_to_the_new_fielder() {
+        let pas = vec![
+            pa(
+                1,
+                "Top",
+                1,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                1,
+            ),
+            pa(
+                2,
+                "Top",
+                2,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                2,
+            ),
+        ];
+        // Catcher throws out the runner stealing between the two PAs.
+        let mut cs = rm(1, Some(1), "Top", 1, "1B", "OUT", "caught_stealing");
+        cs.fielders = Some("2-6".to_string());
+        let mut record = game_record(pas, vec![cs]);
+        let sub = DomainEvent::PlayerSubstituted(SubstitutionData {
+            team_id: HOME,
+            kind: SubstitutionKind::Defensive,
+            batting_order: 6,
+            player_out_id: Some(26),
+            player_in_id: 36,
+            player_in_jersey_no: 36,
+            player_in_first_name: "Sub".to_string(),
+            player_in_last_name: "Short".to_string(),
+            position: Position::Shortstop,
+            previous_position: Some(Position::Shortstop),
+        });
+        record.events.push(GameEventRow {
+            id: 1,
+            inning: 1,
+            half_inning: "Top".to_string(),
+            event_type: sub.event_type().to_string(),
+            event_data: Some(serde_json::to_string(&sub).unwrap()),
+            description: None,
+            created_at: None,
+            pa_seq: Some(1),
+        });
+
+        let lines = fielding_lines(&[record], None);
+        assert_eq!(line(&lines, 26), (0, 1, 0, 0));
+        assert_eq!(line(&lines, 36), (1, 1, 0, 0));
+        assert_eq!(line(&lines, 22), (0, 1, 0, 0));
+        assert_eq!(line(&lines, 23), (2, 0, 0, 0));
+    }
+}
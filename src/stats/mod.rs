@@ -0,0 +1,302 @@
+//! Player statistics rebuilt from the stored games.
+//!
+//! Nothing is accumulated while scoring: every line is computed from
+//! `plate_appearances` and `runner_movements`, so an undo or a correction
+//! shows up the next time the stats are read.
+//!
+//! - `batting` — PA, AB, R, H, 2B, 3B, HR, RBI, BB, K and the rates
+//!   (AVG, OBP, SLG, OPS).
+//! - `pitching` — innings by outs recorded, pitches, H, R, ER, BB, K, ERA
+//!   and WHIP.
+//! - `fielding` — putouts, assists and errors from the fielding
+//!   sequences, and the fielding percentage.
+//! - `alignment` — who held each position when a play was made.
+//! - `box_score` — the traditional box score of one game.
+//! - `scorecard` — the paper scorecard of each team, as SVG or PDF.
+//! - `spray` — where a batter, a team or a pitcher's opponents hit the
+//!   ball, by field zone.
+
+pub mod alignment;
+pub mod batting;
+pub mod box_score;
+pub mod fielding;
+pub mod pitching;
+pub mod scorecard;
+pub mod spray;
+
+use crate::db::game_events::{GameEventRow, list_game_events};
+use crate::db::game_queries::game_rules;
+use crate::db::lineups::{StartingSpot, starting_lineup};
+use crate::db::plate_appearances::{PlateAppearanceRow, list_plate_appearances};
+use crate::db::runner_movements::{RunnerMovementRow, list_runner_movements};
+use batting::{BattingLine, batting_lines};
+use fielding::{FieldingLine, fielding_lines};
+use pitching::{PitchingLine, pitching_lines};
+use rusqlite::{Connection, Result, params};
+use std::collections::HashMap;
+
+/// Which games the stats are read from, and for which team. Every field
+/// left `None` matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsFilter {
+    /// A single game (`games.id`).
+    pub game_pk: Option<i64>,
+    /// Games the team played; only its own players are listed.
+    pub team_id: Option<i64>,
+    pub league_id: Option<i64>,
+    /// The league season, or the year of the game date for games
+    /// outside a league.
+    pub season: Option<String>,
+}
+
+/// Everything recorded for one game.
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    pub game_pk: i64,
+    pub away_team_id: i64,
+    pub home_team_id: i64,
+    /// Regulation length in innings.
+    pub innings: u32,
+    pub plate_appearances: Vec<PlateAppearanceRow>,
+    pub movements: Vec<RunnerMovementRow>,
+    pub events: Vec<GameEventRow>,
+    /// Starters of both teams, with their team id.
+    pub starters: Vec<(i64, StartingSpot)>,
+}
+
+impl GameRecord {
+    /// Read game `game_pk` (the `games` row id) back from the database.
+    pub fn load(conn: &Connection, game_pk: i64) -> Result<Self> {
+        let (game_id, away_team_id, home_team_id): (String, i64, i64) = conn.query_row(
+            "SELECT game_id, away_team_id, home_team_id FROM games WHERE id = ?1",
+            params![game_pk],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        let mut starters = Vec::new();
+        for team_id in [away_team_id, home_team_id] {
+            for spot in starting_lineup(conn, &game_id, team_id)? {
+                starters.push((team_id, spot));
+            }
+        }
+        Ok(Self {
+            game_pk,
+            away_team_id,
+            home_team_id,
+            innings: game_rules(conn, game_pk)?.innings,
+            plate_appearances: list_plate_appearances(conn, game_pk)?,
+            movements: list_runner_movements(conn, game_pk)?,
+            events: list_game_events(conn, game_pk)?,
+            starters,
+        })
+    }
+
+    /// The starters with the position on the lineup card.
+    /// `game_lineups` only keeps the position each player holds now; the
+    /// first change of a spot remembers what was there before it.
+    pub fn lineup_cards(&self) -> Vec<(i64, StartingSpot)> {
+        let substitutions: Vec<_> = self
+            .events
+            .iter()
+            .filter_map(alignment::substitution)
+            .collect();
+        self.starters
+            .iter()
+            .map(|(team_id, spot)| {
+                let position = substitutions
+                    .iter()
+                    .find(|d| d.team_id == *team_id && d.batting_order == spot.batting_order)
+                    .and_then(|d| d.previous_position)
+                    .unwrap_or(spot.position);
+                (
+                    *team_id,
+                    StartingSpot {
+                        position,
+                        ..spot.clone()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The team at bat in `half_inning` (`"Top"` or `"Bottom"`).
+    pub fn batting_team(&self, half_inning: &str) -> i64 {
+        if half_inning == "Bottom" {
+            self.home_team_id
+        } else {
+            self.away_team_id
+        }
+    }
+
+    /// The team in the field in `half_inning`.
+    pub fn fielding_team(&self, half_inning: &str) -> i64 {
+        if half_inning == "Bottom" {
+            self.away_team_id
+        } else {
+            self.home_team_id
+        }
+    }
+}
+
+/// Batting, pitching and fielding lines over a set of games.
+#[derive(Debug, Clone, Default)]
+pub struct StatsReport {
+    pub games: usize,
+    pub batting: Vec<BattingLine>,
+    pub pitching: Vec<PitchingLine>,
+    pub fielding: Vec<FieldingLine>,
+    /// Innings ERA is scaled to: the regulation length shared by the
+    /// games, 9 when they differ.
+    pub era_innings: u32,
+}
+
+/// Compute every line over `games`, keeping only the players of
+/// `team_id` when set.
+pub fn compute(games: &[GameRecord], team_id: Option<i64>) -> StatsReport {
+    let era_innings = match games.first() {
+        Some(first) if games.iter().all(|g| g.innings == first.innings) => first.innings,
+        _ => 9,
+    };
+    StatsReport {
+        games: games.len(),
+        batting: batting_lines(games, team_id),
+        pitching: pitching_lines(games, team_id),
+        fielding: fielding_lines(games, team_id),
+        era_innings,
+    }
+}
+
+/// Ids of the games matching `filter`, oldest first.
+pub fn filtered_games(conn: &Connection, filter: &StatsFilter) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT g.id
+        FROM games g
+        LEFT JOIN leagues l ON g.league_id = l.id
+        WHERE (?1 IS NULL OR g.id = ?1)
+          AND (?2 IS NULL OR g.home_team_id = ?2 OR g.away_team_id = ?2)
+          AND (?3 IS NULL OR g.league_id = ?3)
+          AND (?4 IS NULL OR COALESCE(NULLIF(l.season, ''), substr(g.game_date, 1, 4)) = ?4)
+        ORDER BY g.game_date, g.id
+        "#,
+    )?;
+    stmt.query_map(
+        params![
+            filter.game_pk,
+            filter.team_id,
+            filter.league_id,
+            filter.season
+        ],
+        |r| r.get(0),
+    )?
+    .collect()
+}
+
+/// Load the games matching `filter` and compute their stats.
+pub fn load_stats(conn: &Connection, filter: &StatsFilter) -> Result<StatsReport> {
+    let games = filtered_games(conn, filter)?
+        .into_iter()
+        .map(|game_pk| GameRecord::load(conn, game_pk))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(compute(&games, filter.team_id))
+}
+
+/// Scorebook rendering of a rate: `.333`, `1.000`, or `-` with no
+/// opportunity.
+pub fn format_rate(rate: Option<f64>) -> String {
+    match rate {
+        None => "-".to_string(),
+        Some(r) if r >= 1.0 => format!("{r:.3}"),
+        Some(r) => format!("{r:.3}").trim_start_matches('0').to_string(),
+    }
+}
+
+/// "F. Last" of every player, by id.
+pub fn player_names(conn: &Connection) -> Result<HashMap<i64, String>> {
+    let mut stmt = conn.prepare("SELECT id, first_name, last_name FROM players")?;
+    let rows = stmt.query_map([], |r| {
+        let first: String = r.get(1)?;
+        let last: String = r.get(2)?;
+        let initial = first
+            .chars()
+            .next()
+            .map(|c| format!("{c}. "))
+            .unwrap_or_default();
+        Ok((r.get(0)?, format!("{initial}{last}")))
+    })?;
+    rows.collect()
+}
+
+/// `numerator / denominator`, `None` when nothing can be divided.
+pub(crate) fn ratio(numerator: u32, denominator: u32) -> Option<f64> {
+    (denominator > 0).then(|| f64::from(numerator) / f64::from(denominator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+
+    #[test]
+    fn rates_print_like_a_scorebook() {
+        assert_eq!(format_rate(ratio(1, 3)), ".333");
+        assert_eq!(format_rate(ratio(4, 4)), "1.000");
+        assert_eq!(format_rate(ratio(0, 2)), ".000");
+        assert_eq!(format_rate(ratio(0, 0)), "-");
+    }
+
+    #[test]
+    fn games_are_filtered_by_team_league_and_season() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        for (name, abbr) in [("Lions", "LIO"), ("Tigers", "TIG"), ("Bears", "BEA")] {
+            conn.execute(
+                "INSERT INTO teams (name, abbreviation) VALUES (?1, ?2)",
+                params![name, abbr],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO leagues (name, season) VALUES ('Spring', '2025')",
+            [],
+        )
+        .unwrap();
+        let league = conn.last_insert_rowid();
+        for (game_id, home, away, date, league_id) in [
+            ("G1", 1, 2, "2025-04-01", Some(league)),
+            ("G2", 2, 3, "2025-05-01", Some(league)),
+            ("G3", 1, 3, "2026-04-01", None),
+        ] {
+            conn.execute(
+                "INSERT INTO games (game_id, home_team_id, away_team_id, venue, game_date, league_id)
+                 VALUES (?1, ?2, ?3, 'Park', ?4, ?5)",
+                params![game_id, home, away, date, league_id],
+            )
+            .unwrap();
+        }
+
+        let games = |filter: StatsFilter| filtered_games(conn, &filter).unwrap();
+        assert_eq!(games(StatsFilter::default()), vec![1, 2, 3]);
+        let lions = StatsFilter {
+            team_id: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(games(lions), vec![1, 3]);
+        let league_games = StatsFilter {
+            league_id: Some(league),
+            ..Default::default()
+        };
+        assert_eq!(games(league_games), vec![1, 2]);
+        // Outside a league the season is the year of the game.
+        let season = StatsFilter {
+            season: Some("2026".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(games(season), vec![3]);
+
+        let report = load_stats(conn, &StatsFilter::default()).unwrap();
+        assert_eq!(report.games, 3);
+        assert!(report.batting.is_empty());
+        assert_eq!(report.era_innings, 9);
+    }
+}
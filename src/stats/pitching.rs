@@ -0,0 +1,189 @@
+//! Pitching lines.
+//!
+//! Innings pitched are counted in outs: the outs made on each plate
+//! appearance, plus the runners caught stealing or picked off during it.
+//! Runs and earned runs are charged by `engine::earned_runs`, so a
+//! reliever answers for the runners they put on base, not the inherited
+//! ones.
+
+use crate::engine::earned_runs::{Play, charge_runs, half_innings, pitcher_on_the_mound};
+use crate::engine::reducer::plate_appearance_from_row;
+use crate::models::plate_appearance::PlateAppearanceOutcome;
+use crate::stats::GameRecord;
+use std::collections::BTreeMap;
+
+/// A pitcher's line for one team.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PitchingLine {
+    pub player_id: i64,
+    pub team_id: i64,
+    pub outs: u32,
+    pub batters_faced: u32,
+    pub pitches: u32,
+    pub hits: u32,
+    pub runs: u32,
+    pub earned_runs: u32,
+    pub walks: u32,
+    pub hit_by_pitch: u32,
+    pub strikeouts: u32,
+    pub home_runs: u32,
+}
+
+impl PitchingLine {
+    /// Innings pitched the scorebook way: `6.2` is six innings and two
+    /// outs.
+    pub fn innings_pitched(&self) -> String {
+        format!("{}.{}", self.outs / 3, self.outs % 3)
+    }
+
+    /// Earned runs per `innings_per_game` innings.
+    pub fn era(&self, innings_per_game: u32) -> Option<f64> {
+        (self.outs > 0).then(|| {
+            f64::from(self.earned_runs) * f64::from(innings_per_game) * 3.0 / f64::from(self.outs)
+        })
+    }
+
+    /// Walks plus hits per inning pitched.
+    pub fn whip(&self) -> Option<f64> {
+        (self.outs > 0).then(|| f64::from(self.walks + self.hits) * 3.0 / f64::from(self.outs))
+    }
+}
+
+/// Pitching lines over `games`, by team then pitcher. With `team_id`
+/// only that team's pitchers are kept.
+pub fn pitching_lines(games: &[GameRecord], team_id: Option<i64>) -> Vec<PitchingLine> {
+    let mut lines: BTreeMap<(i64, i64), PitchingLine> = BTreeMap::new();
+
+    for game in games {
+        for plays in half_innings(&game.plate_appearances, &game.movements) {
+            let mut outs: u32 = 0;
+            for (i, play) in plays.iter().enumerate() {
+                match play {
+                    Play::PlateAppearance(row, _) => {
+                        let after = row.outs.clamp(0, 3) as u32;
+                        let made = after.saturating_sub(outs);
+                        outs = after;
+                        let Some(line) =
+                            line_of(&mut lines, game, team_id, &row.half_inning, row.pitcher_id)
+                        else {
+                            continue;
+                        };
+                        let pa = plate_appearance_from_row(row);
+                        line.outs += made;
+                        line.batters_faced += 1;
+                        line.pitches += pa.pitches;
+                        line.hits += pa.outcome.is_hit() as u32;
+                        line.home_runs += (pa.outcome.bases() == 4) as u32;
+                        line.walks += matches!(
+                            pa.outcome,
+                            PlateAppearanceOutcome::Walk | PlateAppearanceOutcome::IntentionalWalk
+                        ) as u32;
+                        line.hit_by_pitch +=
+                            matches!(pa.outcome, PlateAppearanceOutcome::HitByPitch) as u32;
+                        line.strikeouts += pa.outcome.is_strikeout() as u32;
+                    }
+                    Play::Between(rm) if rm.is_out && outs < 3 => {
+                        outs += 1;
+                        let pitcher_id = pitcher_on_the_mound(&plays, i);
+                        if let Some(line) =
+                            line_of(&mut lines, game, team_id, &rm.half_inning, pitcher_id)
+                        {
+                            line.outs += 1;
+                        }
+                    }
+                    Play::Between(_) => {}
+                }
+            }
+        }
+
+        let halves: BTreeMap<i64, &str> = game
+            .movements
+            .iter()
+            .map(|rm| (rm.id, rm.half_inning.as_str()))
+            .collect();
+        for charge in charge_runs(&game.plate_appearances, &game.movements) {
+            let half = halves.get(&charge.movement_id).copied().unwrap_or("Top");
+            if let Some(line) = line_of(&mut lines, game, team_id, half, charge.pitcher_id) {
+                line.runs += 1;
+                line.earned_runs += charge.earned as u32;
+            }
+        }
+    }
+    lines.into_values().collect()
+}
+
+/// The line of `pitcher_id` for the team in the field in `half_inning`,
+/// `None` when the team filter leaves it out.
+fn line_of<'a>(
+    lines: &'a mut BTreeMap<(i64, i64), PitchingLine>,
+    game: &GameRecord,
+    team_filter: Option<i64>,
+    half_inning: &str,
+    pitcher_id: i64,
+) -> Option<&'a mut PitchingLine> {
+    let team_id = game.fielding_team(half_inning);
+    if team_filter.is_some_and(|t| t != team_id) {
+        return None;
+    }
+    Some(lines.entry((team_id, pitcher_id)).or_insert(PitchingLine {
+        player_id: pitcher_id,
+        team_id,
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{HOME, game_record, pa, rm};
+    use crate::stats::format_rate;
+
+    const STARTER: i64 = 21;
+    const RELIEVER: i64 = 31;
+
+    #[test]
+    fn outs_runs_and_rates_go_to_the_pitcher_on_the_mound() {
+        let pas = vec![
+            pa(1, "Top", 1, STARTER, "walk", None, 0),
+            pa(2, "Top", 2, STARTER, "strikeout", Some(r#""Called""#), 1),
+            pa(3, "Top", 3, RELIEVER, "home_run", None, 2),
+            pa(
+                4,
+                "Top",
+                4,
+                RELIEVER,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                3,
+            ),
+        ];
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "walk"),
+            // Caught stealing while the reliever faces batter 3.
+            rm(2, Some(2), "Top", 1, "1B", "OUT", "caught_stealing"),
+            rm(3, Some(3), "Top", 3, "BAT", "HOME", "hit_auto"),
+        ];
+        let lines = pitching_lines(&[game_record(pas, rows)], None);
+        assert_eq!(lines.len(), 2);
+
+        let starter = lines.iter().find(|l| l.player_id == STARTER).unwrap();
+        assert_eq!((starter.outs, starter.batters_faced), (1, 2));
+        assert_eq!(
+            (starter.walks, starter.strikeouts, starter.pitches),
+            (1, 1, 6)
+        );
+        assert_eq!(starter.innings_pitched(), "0.1");
+        assert_eq!(format_rate(starter.whip()), "3.000");
+
+        let reliever = lines.iter().find(|l| l.player_id == RELIEVER).unwrap();
+        assert_eq!(reliever.team_id, HOME);
+        assert_eq!(
+            (reliever.outs, reliever.hits, reliever.home_runs),
+            (2, 1, 1)
+        );
+        assert_eq!((reliever.runs, reliever.earned_runs), (1, 1));
+        assert_eq!(reliever.innings_pitched(), "0.2");
+        assert_eq!(reliever.era(9), Some(13.5));
+        assert_eq!(reliever.era(7), Some(10.5));
+    }
+}
@@ -0,0 +1,407 @@
+//! Drawing of a scorecard on a landscape letter page.
+//!
+//! Coordinates are in points from the top-left corner; text is placed on
+//! its baseline. Grays go from `0.0` (black) to `1.0` (white).
+
+use crate::stats::scorecard::{Cell, InningTotals, Scorecard};
+
+/// Landscape letter, in points.
+pub const PAGE_WIDTH: f32 = 792.0;
+pub const PAGE_HEIGHT: f32 = 612.0;
+
+const MARGIN: f32 = 24.0;
+const NAME_WIDTH: f32 = 150.0;
+const STAT_WIDTH: f32 = 20.0;
+const MAX_CELL_WIDTH: f32 = 60.0;
+const GRID_TOP: f32 = 66.0;
+const HEADER_HEIGHT: f32 = 14.0;
+const ROW_HEIGHT: f32 = 40.0;
+const TOTALS_HEIGHT: f32 = 11.0;
+const GRID_GRAY: f32 = 0.4;
+const BATTING_STATS: [&str; 4] = ["AB", "R", "H", "RBI"];
+const PITCHING_STATS: [&str; 7] = ["IP", "H", "R", "ER", "BB", "K", "PC"];
+
+/// Label and value of a row of inning totals.
+type TotalsRow = (&'static str, fn(&InningTotals) -> u32);
+
+/// Horizontal alignment of a text on its point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// A drawing primitive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+        gray: f32,
+    },
+    /// A filled rectangle.
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        gray: f32,
+    },
+    /// A filled polygon.
+    Polygon { points: Vec<(f32, f32)>, gray: f32 },
+    /// Black Helvetica text.
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        size: f32,
+        anchor: Anchor,
+        bold: bool,
+    },
+}
+
+/// A page of shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub width: f32,
+    pub height: f32,
+    pub shapes: Vec<Shape>,
+}
+
+impl Page {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, gray: f32) {
+        self.shapes.push(Shape::Line {
+            from,
+            to,
+            width,
+            gray,
+        });
+    }
+
+    fn text(&mut self, x: f32, y: f32, text: impl Into<String>, size: f32, anchor: Anchor) {
+        self.shapes.push(Shape::Text {
+            x,
+            y,
+            text: text.into(),
+            size,
+            anchor,
+            bold: false,
+        });
+    }
+
+    fn bold(&mut self, x: f32, y: f32, text: impl Into<String>, size: f32, anchor: Anchor) {
+        self.shapes.push(Shape::Text {
+            x,
+            y,
+            text: text.into(),
+            size,
+            anchor,
+            bold: true,
+        });
+    }
+}
+
+/// Lay `card` out on one page.
+pub fn page(card: &Scorecard) -> Page {
+    let mut page = Page {
+        width: PAGE_WIDTH,
+        height: PAGE_HEIGHT,
+        shapes: Vec::new(),
+    };
+
+    // Title
+    page.bold(MARGIN, 38.0, card.team.as_str(), 14.0, Anchor::Start);
+    let mut subtitle = format!("{} {}", if card.home { "vs" } else { "at" }, card.opponent);
+    for part in [&card.date, &card.venue, &card.game_id] {
+        if !part.is_empty() {
+            subtitle.push_str(" - ");
+            subtitle.push_str(part);
+        }
+    }
+    page.text(MARGIN, 52.0, subtitle, 8.0, Anchor::Start);
+
+    let columns = card.columns.len().max(1);
+    let grid_left = MARGIN + NAME_WIDTH;
+    let stats_left = PAGE_WIDTH - MARGIN - STAT_WIDTH * BATTING_STATS.len() as f32;
+    let cell_width = ((stats_left - grid_left) / columns as f32).min(MAX_CELL_WIDTH);
+    let stats_left = grid_left + cell_width * columns as f32;
+    let right = stats_left + STAT_WIDTH * BATTING_STATS.len() as f32;
+    let column_x = |column: usize| grid_left + cell_width * column as f32;
+    let stat_x = |stat: usize| stats_left + STAT_WIDTH * (stat + 1) as f32 - 3.0;
+
+    // Header row
+    let header = GRID_TOP + HEADER_HEIGHT - 4.0;
+    page.bold(MARGIN + 2.0, header, "Player", 7.0, Anchor::Start);
+    page.bold(grid_left - 3.0, header, "Pos", 7.0, Anchor::End);
+    for (column, inning) in card.columns.iter().enumerate() {
+        page.bold(
+            column_x(column) + cell_width / 2.0,
+            header,
+            inning.to_string(),
+            7.0,
+            Anchor::Middle,
+        );
+    }
+    for (stat, label) in BATTING_STATS.iter().enumerate() {
+        page.bold(stat_x(stat), header, *label, 7.0, Anchor::End);
+    }
+
+    // A row per lineup slot
+    let slots = card
+        .batters
+        .iter()
+        .map(|b| b.batting_order)
+        .max()
+        .unwrap_or(0)
+        .max(9);
+    let rows_top = GRID_TOP + HEADER_HEIGHT;
+    for slot in 1..=slots {
+        let y = rows_top + ROW_HEIGHT * f32::from(slot - 1);
+        let players: Vec<_> = card.slot(slot).collect();
+        let spacing = ((ROW_HEIGHT - 4.0) / players.len().max(1) as f32).min(10.0);
+        for (i, batter) in players.iter().enumerate() {
+            let baseline = y + 10.0 + spacing * i as f32;
+            if batter.substitute {
+                page.text(
+                    MARGIN + 16.0,
+                    baseline,
+                    batter.name.as_str(),
+                    7.0,
+                    Anchor::Start,
+                );
+            } else {
+                page.bold(MARGIN + 2.0, baseline, slot.to_string(), 7.0, Anchor::Start);
+                page.bold(
+                    MARGIN + 12.0,
+                    baseline,
+                    batter.name.as_str(),
+                    7.0,
+                    Anchor::Start,
+                );
+            }
+            page.text(
+                grid_left - 3.0,
+                baseline,
+                batter.positions.as_str(),
+                6.0,
+                Anchor::End,
+            );
+            let line = &batter.line;
+            for (stat, value) in [line.at_bats, line.runs, line.hits, line.rbi]
+                .into_iter()
+                .enumerate()
+            {
+                page.text(stat_x(stat), baseline, value.to_string(), 7.0, Anchor::End);
+            }
+        }
+        for column in 0..card.columns.len() {
+            match card.cell(slot, column) {
+                Some(cell) => draw_cell(&mut page, column_x(column), y, cell_width, cell),
+                None => draw_diamond(&mut page, column_x(column), y, cell_width),
+            }
+        }
+    }
+
+    // Inning totals
+    let totals_top = rows_top + ROW_HEIGHT * f32::from(slots);
+    let totals: [TotalsRow; 4] = [
+        ("R", |t| t.runs),
+        ("H", |t| t.hits),
+        ("E", |t| t.errors),
+        ("LOB", |t| t.left_on_base),
+    ];
+    for (row, (label, value)) in totals.iter().enumerate() {
+        let baseline = totals_top + TOTALS_HEIGHT * (row + 1) as f32 - 3.0;
+        page.bold(grid_left - 3.0, baseline, *label, 7.0, Anchor::End);
+        for inning in &card.innings {
+            let columns: Vec<usize> = (0..card.columns.len())
+                .filter(|&c| card.columns[c] == inning.inning)
+                .collect();
+            let (Some(first), Some(last)) = (columns.first(), columns.last()) else {
+                continue;
+            };
+            let center = (column_x(*first) + column_x(*last + 1)) / 2.0;
+            page.text(
+                center,
+                baseline,
+                value(inning).to_string(),
+                7.0,
+                Anchor::Middle,
+            );
+        }
+        let total: u32 = card.innings.iter().map(value).sum();
+        page.bold(
+            (stats_left + right) / 2.0,
+            baseline,
+            total.to_string(),
+            7.0,
+            Anchor::Middle,
+        );
+    }
+
+    // Grid lines
+    let bottom = totals_top + TOTALS_HEIGHT * totals.len() as f32;
+    for y in [GRID_TOP, rows_top, totals_top, bottom] {
+        page.line((MARGIN, y), (right, y), 0.8, GRID_GRAY);
+    }
+    for slot in 1..slots {
+        let y = rows_top + ROW_HEIGHT * f32::from(slot);
+        page.line((MARGIN, y), (right, y), 0.5, GRID_GRAY);
+    }
+    for row in 1..totals.len() {
+        let y = totals_top + TOTALS_HEIGHT * row as f32;
+        page.line((grid_left, y), (right, y), 0.3, GRID_GRAY);
+    }
+    for x in [MARGIN, grid_left, stats_left, right] {
+        page.line((x, GRID_TOP), (x, bottom), 0.8, GRID_GRAY);
+    }
+    for column in 1..card.columns.len() {
+        let x = column_x(column);
+        page.line((x, GRID_TOP), (x, bottom), 0.5, GRID_GRAY);
+    }
+    for stat in 1..BATTING_STATS.len() {
+        let x = stats_left + STAT_WIDTH * stat as f32;
+        page.line((x, GRID_TOP), (x, totals_top), 0.3, GRID_GRAY);
+    }
+
+    // Pitchers
+    let mut y = bottom + 16.0;
+    let pitching_x = |stat: usize| MARGIN + NAME_WIDTH + 30.0 * stat as f32;
+    page.bold(MARGIN + 2.0, y, "Pitcher", 7.0, Anchor::Start);
+    for (stat, label) in PITCHING_STATS.iter().enumerate() {
+        page.bold(pitching_x(stat), y, *label, 7.0, Anchor::End);
+    }
+    page.line(
+        (MARGIN, y + 3.0),
+        (pitching_x(PITCHING_STATS.len() - 1) + 3.0, y + 3.0),
+        0.5,
+        GRID_GRAY,
+    );
+    for pitcher in &card.pitchers {
+        y += 10.0;
+        let name = match pitcher.decision {
+            Some(d) => format!("{} ({})", pitcher.name, d.as_str()),
+            None => pitcher.name.clone(),
+        };
+        page.text(MARGIN + 2.0, y, name, 7.0, Anchor::Start);
+        let l = &pitcher.line;
+        let values = [
+            l.innings_pitched(),
+            l.hits.to_string(),
+            l.runs.to_string(),
+            l.earned_runs.to_string(),
+            l.walks.to_string(),
+            l.strikeouts.to_string(),
+            l.pitches.to_string(),
+        ];
+        for (stat, value) in values.into_iter().enumerate() {
+            page.text(pitching_x(stat), y, value, 7.0, Anchor::End);
+        }
+    }
+
+    page
+}
+
+/// Center and corners of the diamond of a cell: home, first, second,
+/// third and home again.
+fn bases(x: f32, y: f32, width: f32) -> [(f32, f32); 5] {
+    let (cx, cy) = (x + width / 2.0, y + ROW_HEIGHT / 2.0 + 1.0);
+    let r = (ROW_HEIGHT * 0.25).min(width * 0.3);
+    [
+        (cx, cy + r),
+        (cx + r, cy),
+        (cx, cy - r),
+        (cx - r, cy),
+        (cx, cy + r),
+    ]
+}
+
+fn draw_diamond(page: &mut Page, x: f32, y: f32, width: f32) {
+    let bases = bases(x, y, width);
+    for leg in bases.windows(2) {
+        page.line(leg[0], leg[1], 0.4, 0.75);
+    }
+}
+
+fn draw_cell(page: &mut Page, x: f32, y: f32, width: f32, cell: &Cell) {
+    let bases = bases(x, y, width);
+    let center = (x + width / 2.0, y + ROW_HEIGHT / 2.0 + 1.0);
+    if cell.scored {
+        page.shapes.push(Shape::Polygon {
+            points: bases[..4].to_vec(),
+            gray: 0.8,
+        });
+    }
+    draw_diamond(page, x, y, width);
+
+    for segment in &cell.path {
+        let (from, to) = (usize::from(segment.from), usize::from(segment.to));
+        let last_leg = if segment.out {
+            // Half-way to the next base, with a bar where the out was made.
+            let next = (from + 1).min(4);
+            let target = if to == from { bases[from] } else { bases[next] };
+            let start = bases[from];
+            let end = if to == from {
+                start
+            } else {
+                ((start.0 + target.0) / 2.0, (start.1 + target.1) / 2.0)
+            };
+            if to != from {
+                page.line(start, end, 1.2, 0.0);
+            }
+            let (dx, dy) = (bases[next].0 - start.0, bases[next].1 - start.1);
+            let length = (dx * dx + dy * dy).sqrt().max(1.0);
+            let (nx, ny) = (-dy / length * 3.0, dx / length * 3.0);
+            page.line((end.0 - nx, end.1 - ny), (end.0 + nx, end.1 + ny), 1.2, 0.0);
+            (start, end)
+        } else {
+            for leg in from..to {
+                page.line(bases[leg], bases[leg + 1], 1.2, 0.0);
+            }
+            match to.checked_sub(1) {
+                Some(previous) if to > from => (bases[previous], bases[to]),
+                _ => continue,
+            }
+        };
+        if let Some(label) = &segment.label {
+            let mid = (
+                (last_leg.0.0 + last_leg.1.0) / 2.0,
+                (last_leg.0.1 + last_leg.1.1) / 2.0,
+            );
+            let outward = (mid.0 - center.0, mid.1 - center.1);
+            page.text(
+                mid.0 + outward.0 * 0.7,
+                mid.1 + outward.1 * 0.7 + 2.0,
+                label.as_str(),
+                5.0,
+                Anchor::Middle,
+            );
+        }
+    }
+
+    if !cell.pitches.is_empty() {
+        page.text(x + 2.0, y + 7.0, cell.pitches.as_str(), 5.0, Anchor::Start);
+    }
+    if let Some(out) = cell.out {
+        page.bold(x + width - 3.0, y + 9.0, out.to_string(), 8.0, Anchor::End);
+    }
+    let result = format!("{} {}", cell.symbol, cell.detail);
+    page.text(
+        x + width / 2.0,
+        y + ROW_HEIGHT - 3.0,
+        result.trim(),
+        6.0,
+        Anchor::Middle,
+    );
+    for rbi in 0..cell.rbi {
+        page.shapes.push(Shape::Rect {
+            x: x + 2.0,
+            y: y + ROW_HEIGHT / 2.0 - 4.0 + 4.0 * rbi as f32,
+            width: 2.0,
+            height: 2.0,
+            gray: 0.0,
+        });
+    }
+}
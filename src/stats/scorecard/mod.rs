@@ -0,0 +1,613 @@
+//! Paper-style scorecard of one team's turn at bat.
+//!
+//! The card is laid out like a scorebook page: a row per lineup slot with
+//! every player who held it, a column per inning (two when the lineup
+//! batted around) and a diamond in each cell. A cell shows the pitches,
+//! the outcome symbol of `PlateAppearanceStep`, the bases the batter ran
+//! as a runner, the number of the out they made and whether they scored.
+//!
+//! A runner's movements are drawn in the cell of their last plate
+//! appearance, labelled with the slot of the batter who moved them or
+//! with the play between pitches (`SB`, `WP`, `PB`, `DI`, `BK`, `CS`,
+//! `PO`). A tie-break runner gets a `TB` cell of their own.
+//!
+//! - `layout` — the drawing of a card on a landscape letter page.
+//! - `svg` — a page as an SVG document.
+//! - `pdf` — pages as a PDF document, with the standard Helvetica fonts.
+
+pub mod layout;
+pub mod pdf;
+pub mod svg;
+
+use crate::db::plate_appearances::PlateAppearanceRow;
+use crate::db::runner_movements::RunnerMovementRow;
+use crate::engine::earned_runs::{Play, half_innings};
+use crate::engine::reducer::{STANDALONE_TYPES, plate_appearance_from_row};
+use crate::models::game_state::BatterOrder;
+use crate::models::plate_appearance::{
+    DroppedThirdStrike, PlateAppearanceOutcome, PlateAppearanceStep,
+};
+use crate::stats::GameRecord;
+use crate::stats::box_score::{BoxBatter, BoxPitcher, BoxScore};
+use rusqlite::{Connection, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A leg of the base path, between bases numbered 0 (home plate) to 4
+/// (home after scoring).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub from: u8,
+    /// Base reached, or where the runner was put out.
+    pub to: u8,
+    pub out: bool,
+    /// Slot of the batter who moved the runner, or the play between
+    /// pitches (`SB`, `WP`, ...). `None` on the batter's own play.
+    pub label: Option<String>,
+}
+
+/// One diamond of the card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cell {
+    pub batting_order: BatterOrder,
+    /// Index in `Scorecard::columns`.
+    pub column: usize,
+    /// `PlateAppearanceStep` symbol: `"2H"`, `"GO"`, `"BB"`, `"TB"`.
+    pub symbol: String,
+    /// Fielders, zone and qualifiers: `"6-3"`, `"LC GR"`, `"8 SF"`.
+    pub detail: String,
+    /// Pitches before the ball was put in play: `"BKF"`.
+    pub pitches: String,
+    pub path: Vec<Segment>,
+    pub scored: bool,
+    /// Out of the half-inning (1-3) the player made.
+    pub out: Option<u8>,
+    pub rbi: u32,
+}
+
+/// Totals of one half-inning.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InningTotals {
+    pub inning: u32,
+    pub runs: u32,
+    pub hits: u32,
+    /// Errors of the team in the field.
+    pub errors: u32,
+    pub left_on_base: u32,
+}
+
+/// Scorecard of one team.
+#[derive(Debug, Clone, Default)]
+pub struct Scorecard {
+    pub game_id: String,
+    pub date: String,
+    pub venue: String,
+    pub team: String,
+    pub opponent: String,
+    pub home: bool,
+    /// Batting lines in slot order, substitutes under the player they
+    /// replaced.
+    pub batters: Vec<BoxBatter>,
+    /// Inning of each column.
+    pub columns: Vec<u32>,
+    pub cells: Vec<Cell>,
+    pub innings: Vec<InningTotals>,
+    /// The team's own pitchers, in order of appearance.
+    pub pitchers: Vec<BoxPitcher>,
+}
+
+impl Scorecard {
+    /// Scorecards of game `game_pk` (the `games` row id), away team first.
+    pub fn load(conn: &Connection, game_pk: i64) -> Result<[Self; 2]> {
+        let game = GameRecord::load(conn, game_pk)?;
+        let box_score = BoxScore::load(conn, game_pk)?;
+        Ok(Self::build(&game, &box_score))
+    }
+
+    /// Draw the cards of `game`; `box_score` gives the names and lines.
+    pub fn build(game: &GameRecord, box_score: &BoxScore) -> [Self; 2] {
+        [0, 1].map(|side| {
+            let (team, opponent) = if side == 0 {
+                (&box_score.away, &box_score.home)
+            } else {
+                (&box_score.home, &box_score.away)
+            };
+            let mut card = Self {
+                game_id: box_score.game_id.clone(),
+                date: box_score.date.clone(),
+                venue: box_score.venue.clone(),
+                team: team.name.clone(),
+                opponent: opponent.name.clone(),
+                home: side == 1,
+                batters: team.batters.clone(),
+                pitchers: team.pitchers.clone(),
+                ..Default::default()
+            };
+            card.draw(game, if side == 0 { "Top" } else { "Bottom" });
+            card
+        })
+    }
+
+    /// Players who held `slot`, starter first.
+    pub fn slot(&self, slot: BatterOrder) -> impl Iterator<Item = &BoxBatter> {
+        self.batters.iter().filter(move |b| b.batting_order == slot)
+    }
+
+    /// Cell of `slot` in `column`.
+    pub fn cell(&self, slot: BatterOrder, column: usize) -> Option<&Cell> {
+        self.cells
+            .iter()
+            .find(|c| c.batting_order == slot && c.column == column)
+    }
+
+    fn draw(&mut self, game: &GameRecord, half: &str) {
+        let pas: Vec<&PlateAppearanceRow> = game
+            .plate_appearances
+            .iter()
+            .filter(|row| row.half_inning == half)
+            .collect();
+        let movements: Vec<&RunnerMovementRow> = game
+            .movements
+            .iter()
+            .filter(|rm| rm.half_inning == half)
+            .collect();
+
+        // Columns, and a cell per plate appearance or tie-break runner,
+        // with the seq it starts at.
+        let last_inning = pas
+            .iter()
+            .map(|row| row.inning as u32)
+            .max()
+            .unwrap_or(0)
+            .max(game.innings);
+        let mut starts: Vec<(u32, i64)> = Vec::new();
+        for inning in 1..=last_inning {
+            self.columns.push(inning);
+            let mut used: HashSet<BatterOrder> = HashSet::new();
+            for rm in movements
+                .iter()
+                .filter(|rm| rm.inning as u32 == inning && rm.advancement_type == "tie_break")
+            {
+                used.insert(rm.batter_order);
+                self.cells.push(Cell {
+                    batting_order: rm.batter_order,
+                    column: self.columns.len() - 1,
+                    symbol: "TB".to_string(),
+                    ..Default::default()
+                });
+                starts.push((inning, i64::MIN));
+            }
+            for row in pas.iter().filter(|row| row.inning as u32 == inning) {
+                if !used.insert(row.batter_order) {
+                    self.columns.push(inning);
+                    used = HashSet::from([row.batter_order]);
+                }
+                self.cells
+                    .push(plate_appearance_cell(row, self.columns.len() - 1));
+                starts.push((inning, row.seq));
+            }
+        }
+
+        let slot_of: HashMap<i64, BatterOrder> =
+            pas.iter().map(|row| (row.seq, row.batter_order)).collect();
+        let mut cell_of_movement: HashMap<i64, usize> = HashMap::new();
+        for rm in &movements {
+            let at = rm.pa_seq.unwrap_or(i64::MIN);
+            let Some(index) = (0..self.cells.len())
+                .filter(|&i| {
+                    self.cells[i].batting_order == rm.batter_order
+                        && starts[i].0 == rm.inning as u32
+                        && starts[i].1 <= at
+                })
+                .max_by_key(|&i| starts[i].1)
+            else {
+                continue;
+            };
+            cell_of_movement.insert(rm.id, index);
+            if rm.is_out && rm.start_base == "BAT" {
+                // The batter's own out shows as its symbol and number.
+                continue;
+            }
+            let cell = &mut self.cells[index];
+            let from = base_number(&rm.start_base);
+            let to = if !rm.is_out {
+                base_number(&rm.end_base)
+            } else if rm.advancement_type == "pickoff" {
+                from
+            } else {
+                (from + 1).min(4)
+            };
+            let label = if STANDALONE_TYPES.contains(&rm.advancement_type.as_str()) {
+                Some(play_label(&rm.advancement_type).to_string())
+            } else if rm.pa_seq == Some(starts[index].1) {
+                None
+            } else {
+                rm.pa_seq
+                    .and_then(|seq| slot_of.get(&seq))
+                    .map(|slot| slot.to_string())
+            };
+            cell.path.push(Segment {
+                from,
+                to,
+                out: rm.is_out,
+                label,
+            });
+            cell.scored |= rm.scored;
+        }
+
+        // Outs in the order they were made, and the runners left on base.
+        let cell_of_pa: HashMap<i64, usize> = starts
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, seq))| *seq != i64::MIN)
+            .map(|(i, (_, seq))| (*seq, i))
+            .collect();
+        let mut left_on_base: BTreeMap<u32, u32> = BTreeMap::new();
+        for plays in half_innings(&game.plate_appearances, &game.movements) {
+            let inning = match plays.first() {
+                Some(Play::PlateAppearance(row, _)) if row.half_inning == half => row.inning,
+                Some(Play::Between(rm)) if rm.half_inning == half => rm.inning,
+                _ => continue,
+            };
+            let mut outs: u8 = 0;
+            let mut on_base: Vec<BatterOrder> = Vec::new();
+            for play in &plays {
+                let (rows, outs_after): (Vec<&RunnerMovementRow>, u8) = match play {
+                    Play::PlateAppearance(row, rows) => (
+                        rows.iter()
+                            .filter_map(|r| game.movements.iter().find(|rm| rm.id == r.id))
+                            .collect(),
+                        row.outs.clamp(0, 3) as u8,
+                    ),
+                    Play::Between(rm) => (vec![*rm], outs + u8::from(rm.is_out)),
+                };
+                let runner_outs: Vec<i64> = rows
+                    .iter()
+                    .filter(|rm| rm.is_out && rm.start_base != "BAT")
+                    .map(|rm| rm.id)
+                    .collect();
+                let mut numbered: Vec<usize> = runner_outs
+                    .iter()
+                    .filter_map(|id| cell_of_movement.get(id).copied())
+                    .collect();
+                if let Play::PlateAppearance(row, _) = play {
+                    let made = outs_after.saturating_sub(outs) as usize;
+                    if made > runner_outs.len()
+                        && let Some(&batter) = cell_of_pa.get(&row.seq)
+                    {
+                        if batter_out_first(&plate_appearance_from_row(row).outcome) {
+                            numbered.insert(0, batter);
+                        } else {
+                            numbered.push(batter);
+                        }
+                    }
+                }
+                for index in numbered {
+                    if outs < 3 {
+                        outs += 1;
+                        self.cells[index].out = Some(outs);
+                    }
+                }
+                outs = outs.max(outs_after);
+                for rm in rows {
+                    on_base.retain(|o| *o != rm.batter_order);
+                    if matches!(rm.end_base.as_str(), "1B" | "2B" | "3B") {
+                        on_base.push(rm.batter_order);
+                    }
+                }
+            }
+            *left_on_base.entry(inning as u32).or_insert(0) += on_base.len() as u32;
+        }
+
+        self.innings = (1..=last_inning)
+            .filter(|inning| self.columns.contains(inning))
+            .map(|inning| {
+                let pas = pas.iter().filter(|row| row.inning as u32 == inning);
+                InningTotals {
+                    inning,
+                    runs: movements
+                        .iter()
+                        .filter(|rm| rm.inning as u32 == inning && rm.scored)
+                        .count() as u32,
+                    hits: pas
+                        .clone()
+                        .filter(|row| plate_appearance_from_row(row).outcome.is_hit())
+                        .count() as u32,
+                    errors: pas
+                        .map(|row| u32::from(plate_appearance_from_row(row).errors_charged()))
+                        .sum(),
+                    left_on_base: left_on_base.get(&inning).copied().unwrap_or(0),
+                }
+            })
+            .collect();
+    }
+}
+
+/// The cell of a plate appearance, before its base path.
+fn plate_appearance_cell(row: &PlateAppearanceRow, column: usize) -> Cell {
+    let pa = plate_appearance_from_row(row);
+    let outcome = &pa.outcome;
+    let mut detail: Vec<String> = Vec::new();
+    match outcome {
+        PlateAppearanceOutcome::UnassistedOut { fielder, .. } => detail.push(format!("{fielder}U")),
+        PlateAppearanceOutcome::GroundOut { sequence, .. }
+        | PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { sequence }) => {
+            detail.push(sequence.clone())
+        }
+        PlateAppearanceOutcome::FlyOut { fielder, .. }
+        | PlateAppearanceOutcome::LineOut { fielder }
+        | PlateAppearanceOutcome::InfieldFly { fielder }
+        | PlateAppearanceOutcome::FieldersChoice { fielder, .. }
+        | PlateAppearanceOutcome::ReachedOnError { fielder, .. } => {
+            detail.push(fielder.to_string())
+        }
+        PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::WildPitch) => {
+            detail.push("WP".to_string())
+        }
+        PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::PassedBall) => {
+            detail.push("PB".to_string())
+        }
+        _ => {}
+    }
+    detail.extend(outcome.zone().map(|z| z.as_str().to_string()));
+    detail.extend(outcome.hit_modifier().map(|m| m.as_str().to_string()));
+    if outcome.is_sacrifice_bunt() || outcome.is_sacrifice_fly() {
+        detail.push(outcome.label().to_string());
+    }
+    detail.extend(pa.double_play.as_ref().map(|dp| dp.label().to_string()));
+
+    Cell {
+        batting_order: row.batter_order,
+        column,
+        symbol: outcome_step(outcome).to_string(),
+        detail: detail.join(" "),
+        pitches: pa
+            .pitches_sequence
+            .iter()
+            .filter(|step| matches!(step, PlateAppearanceStep::Pitch(_)))
+            .map(ToString::to_string)
+            .collect(),
+        rbi: u32::from(row.rbi.unwrap_or(0)),
+        ..Default::default()
+    }
+}
+
+/// The step that closes a plate appearance with `outcome`.
+fn outcome_step(outcome: &PlateAppearanceOutcome) -> PlateAppearanceStep {
+    match outcome.clone() {
+        PlateAppearanceOutcome::Single { .. } => PlateAppearanceStep::Single,
+        PlateAppearanceOutcome::Double { .. } => PlateAppearanceStep::Double,
+        PlateAppearanceOutcome::Triple { .. } => PlateAppearanceStep::Triple,
+        PlateAppearanceOutcome::HomeRun { .. } => PlateAppearanceStep::HomeRun,
+        PlateAppearanceOutcome::Walk => PlateAppearanceStep::Walk,
+        PlateAppearanceOutcome::HitByPitch => PlateAppearanceStep::HitByPitch,
+        PlateAppearanceOutcome::IntentionalWalk => PlateAppearanceStep::IntentionalWalk,
+        PlateAppearanceOutcome::CatcherInterference => PlateAppearanceStep::CatcherInterference,
+        PlateAppearanceOutcome::Strikeout(_) | PlateAppearanceOutcome::DroppedThirdStrike(_) => {
+            PlateAppearanceStep::Strikeout
+        }
+        PlateAppearanceOutcome::Out => PlateAppearanceStep::Out,
+        PlateAppearanceOutcome::UnassistedOut { fielder, .. } => {
+            PlateAppearanceStep::UnassistedOut { fielder }
+        }
+        PlateAppearanceOutcome::GroundOut { sequence, .. } => {
+            PlateAppearanceStep::GroundOut { sequence }
+        }
+        PlateAppearanceOutcome::FlyOut {
+            fielder,
+            in_foul_territory,
+            ..
+        } => PlateAppearanceStep::FlyOut {
+            fielder,
+            in_foul_territory,
+        },
+        PlateAppearanceOutcome::LineOut { fielder } => PlateAppearanceStep::LineOut { fielder },
+        PlateAppearanceOutcome::InfieldFly { fielder } => {
+            PlateAppearanceStep::InfieldFly { fielder }
+        }
+        PlateAppearanceOutcome::FieldersChoice {
+            fielder,
+            reached_base,
+        } => PlateAppearanceStep::FieldersChoice {
+            fielder,
+            reached_base,
+        },
+        PlateAppearanceOutcome::ReachedOnError {
+            fielder, throwing, ..
+        } => PlateAppearanceStep::ReachedOnError { fielder, throwing },
+    }
+}
+
+/// Whether the batter's out comes before the runners' on the play: a
+/// ball caught in the air or strike three, but not a ground ball turned
+/// into a force.
+fn batter_out_first(outcome: &PlateAppearanceOutcome) -> bool {
+    !matches!(
+        outcome,
+        PlateAppearanceOutcome::GroundOut { .. }
+            | PlateAppearanceOutcome::UnassistedOut { .. }
+            | PlateAppearanceOutcome::DroppedThirdStrike(DroppedThirdStrike::ThrownOut { .. })
+    )
+}
+
+/// `0` for the batter, `1`-`3` for the bases, `4` for home.
+fn base_number(base: &str) -> u8 {
+    match base {
+        "1B" => 1,
+        "2B" => 2,
+        "3B" => 3,
+        "HOME" => 4,
+        _ => 0,
+    }
+}
+
+/// Scorebook abbreviation of a play between pitches.
+fn play_label(advancement_type: &str) -> &'static str {
+    match advancement_type {
+        "steal" => "SB",
+        "wild_pitch" => "WP",
+        "passed_ball" => "PB",
+        "defensive_indifference" => "DI",
+        "balk" => "BK",
+        "caught_stealing" => "CS",
+        "pickoff" => "PO",
+        "pickoff_caught_stealing" => "POCS",
+        "tie_break" => "TB",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures::{game_record, pa, rm};
+
+    fn cards(pas: Vec<PlateAppearanceRow>, rows: Vec<RunnerMovementRow>) -> [Scorecard; 2] {
+        let names = (11..=29).map(|id| (id, format!("P{id}"))).collect();
+        let game = game_record(pas, rows);
+        let box_score = BoxScore::build(&game, &names, Vec::new());
+        Scorecard::build(&game, &box_score)
+    }
+
+    fn segment(from: u8, to: u8, label: Option<&str>) -> Segment {
+        Segment {
+            from,
+            to,
+            out: false,
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn cells_show_the_play_and_the_bases_run() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "double", Some(r#"{"zone":"LC"}"#), 0),
+            pa(2, "Top", 2, 21, "walk", None, 0),
+            pa(
+                3,
+                "Top",
+                3,
+                21,
+                "fly_out",
+                Some(r#"{"fielder":8,"in_foul_territory":false,"sacrifice":true}"#),
+                1,
+            ),
+            pa(
+                4,
+                "Top",
+                4,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                2,
+            ),
+            pa(5, "Top", 5, 21, "strikeout", None, 3),
+        ];
+        let rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "2B", "hit_auto"),
+            rm(2, Some(2), "Top", 2, "BAT", "1B", "walk"),
+            rm(3, Some(2), "Top", 1, "2B", "3B", "wild_pitch"),
+            rm(4, Some(3), "Top", 1, "3B", "HOME", "sac_fly"),
+            rm(5, Some(4), "Top", 2, "1B", "2B", "forced"),
+        ];
+        let [away, home] = cards(pas, rows);
+        assert!(home.cells.is_empty());
+        assert_eq!(away.columns, (1..=9).collect::<Vec<_>>());
+
+        let leadoff = away.cell(1, 0).unwrap();
+        assert_eq!(
+            (leadoff.symbol.as_str(), leadoff.detail.as_str()),
+            ("2H", "LC")
+        );
+        assert_eq!(
+            leadoff.path,
+            vec![
+                segment(0, 2, None),
+                segment(2, 3, Some("WP")),
+                segment(3, 4, Some("3")),
+            ]
+        );
+        assert!(leadoff.scored);
+        assert_eq!(leadoff.out, None);
+
+        let fly = away.cell(3, 0).unwrap();
+        assert_eq!((fly.symbol.as_str(), fly.detail.as_str()), ("FO", "8 SF"));
+        assert_eq!(fly.out, Some(1));
+        assert_eq!(away.cell(4, 0).unwrap().detail, "6-3");
+        assert_eq!(away.cell(4, 0).unwrap().out, Some(2));
+        assert_eq!(away.cell(5, 0).unwrap().out, Some(3));
+        assert_eq!(
+            away.innings[0],
+            InningTotals {
+                inning: 1,
+                runs: 1,
+                hits: 1,
+                errors: 0,
+                left_on_base: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn a_lineup_batting_around_opens_a_new_column() {
+        let mut pas: Vec<PlateAppearanceRow> = (1..=9)
+            .map(|n| pa(n, "Bottom", n as u8, 11, "single", None, 0))
+            .collect();
+        pas.push(pa(10, "Bottom", 1, 11, "strikeout", None, 1));
+        pas.push(pa(11, "Bottom", 2, 11, "strikeout", None, 2));
+        pas.push(pa(12, "Bottom", 3, 11, "strikeout", None, 3));
+        let [_, home] = cards(pas, Vec::new());
+        assert_eq!(home.columns[..3], [1, 1, 2]);
+        assert_eq!(home.cell(1, 0).unwrap().symbol, "H");
+        assert_eq!(home.cell(1, 1).unwrap().symbol, "K");
+        assert_eq!(home.cell(3, 1).unwrap().out, Some(3));
+        assert!(home.cell(4, 1).is_none());
+    }
+
+    #[test]
+    fn runners_put_out_on_the_bases_make_their_out() {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "single", None, 0),
+            pa(
+                2,
+                "Top",
+                2,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-4-3"}"#),
+                2,
+            ),
+            pa(3, "Top", 3, 21, "walk", None, 2),
+        ];
+        let mut rows = vec![
+            rm(1, Some(1), "Top", 1, "BAT", "1B", "hit_auto"),
+            rm(2, Some(2), "Top", 1, "1B", "OUT", "forced"),
+            rm(3, Some(3), "Top", 3, "BAT", "1B", "walk"),
+            rm(4, Some(3), "Top", 3, "1B", "OUT", "pickoff"),
+        ];
+        rows[1].fielders = Some("6-4".to_string());
+        let [away, _] = cards(pas, rows);
+        let runner = away.cell(1, 0).unwrap();
+        assert_eq!(runner.out, Some(1));
+        assert_eq!(
+            runner.path[1],
+            Segment {
+                from: 1,
+                to: 2,
+                out: true,
+                label: Some("2".to_string()),
+            }
+        );
+        assert_eq!(away.cell(2, 0).unwrap().out, Some(2));
+        let picked_off = away.cell(3, 0).unwrap();
+        assert_eq!(picked_off.out, Some(3));
+        assert_eq!(
+            picked_off.path[1],
+            Segment {
+                from: 1,
+                to: 1,
+                out: true,
+                label: Some("PO".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Scorecard pages as a PDF document.
+//!
+//! A plain PDF 1.4 file with uncompressed content streams and the
+//! standard Helvetica and Helvetica-Bold fonts, so no font is embedded.
+//! Text is WinAnsi encoded: characters outside Latin-1 print as `?`.
+
+use crate::stats::scorecard::layout::{Anchor, Page, Shape};
+use std::fmt::Write;
+
+/// Helvetica advance widths of the characters 32 to 126, in 1/1000 em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' - '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584,
+    556, // '0' - '?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722,
+    778, // '@' - 'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469,
+    556, // 'P' - '_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556,
+    556, // '`' - 'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p' - '~'
+];
+
+/// Write `pages` as a PDF document.
+pub fn render(pages: &[Page]) -> Vec<u8> {
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        Vec::new(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+    let mut kids = Vec::new();
+    for page in pages {
+        let page_id = objects.len() + 1;
+        kids.push(format!("{page_id} 0 R"));
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page.width,
+                page.height,
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        let content = content(page);
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend(b"\nendstream");
+        objects.push(stream);
+    }
+    objects[1] = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        pages.len()
+    )
+    .into_bytes();
+
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+    let xref = pdf.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf.extend(table.into_bytes());
+    pdf
+}
+
+/// Content stream of `page`; PDF puts the origin at the bottom left.
+fn content(page: &Page) -> Vec<u8> {
+    let flip = |y: f32| page.height - y;
+    let mut out = Vec::new();
+    for shape in &page.shapes {
+        match shape {
+            Shape::Line {
+                from,
+                to,
+                width,
+                gray,
+            } => out.extend(
+                format!(
+                    "{gray:.2} G {width:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+                    from.0,
+                    flip(from.1),
+                    to.0,
+                    flip(to.1)
+                )
+                .into_bytes(),
+            ),
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                gray,
+            } => out.extend(
+                format!(
+                    "{gray:.2} g {x:.2} {:.2} {width:.2} {height:.2} re f\n",
+                    flip(y + height)
+                )
+                .into_bytes(),
+            ),
+            Shape::Polygon { points, gray } => {
+                let mut path = format!("{gray:.2} g ");
+                for (i, (x, y)) in points.iter().enumerate() {
+                    let op = if i == 0 { "m" } else { "l" };
+                    let _ = write!(path, "{x:.2} {:.2} {op} ", flip(*y));
+                }
+                path.push_str("h f\n");
+                out.extend(path.into_bytes());
+            }
+            Shape::Text {
+                x,
+                y,
+                text,
+                size,
+                anchor,
+                bold,
+            } => {
+                let encoded = encode(text);
+                let width = text_width(&encoded, *size);
+                let x = match anchor {
+                    Anchor::Start => *x,
+                    Anchor::Middle => x - width / 2.0,
+                    Anchor::End => x - width,
+                };
+                let font = if *bold { "F2" } else { "F1" };
+                out.extend(
+                    format!("0 g BT /{font} {size:.1} Tf {x:.2} {:.2} Td (", flip(*y)).into_bytes(),
+                );
+                for byte in encoded {
+                    if matches!(byte, b'(' | b')' | b'\\') {
+                        out.push(b'\\');
+                    }
+                    out.push(byte);
+                }
+                out.extend(b") Tj ET\n");
+            }
+        }
+    }
+    out
+}
+
+/// WinAnsi bytes of `text`: printable ASCII and Latin-1 as is, `?` for
+/// the rest.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match u32::from(c) {
+            code @ (32..=126 | 0xa0..=0xff) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Width of WinAnsi `text` in points. Bold text is a little wider; this is
+/// close enough to center a label.
+fn text_width(text: &[u8], size: f32) -> f32 {
+    let units: u32 = text
+        .iter()
+        .map(|&b| match b {
+            32..=126 => u32::from(HELVETICA_WIDTHS[usize::from(b - 32)]),
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_make_a_well_formed_document() {
+        let page = Page {
+            width: 792.0,
+            height: 612.0,
+            shapes: vec![Shape::Text {
+                x: 100.0,
+                y: 12.0,
+                text: "Peña (W)".to_string(),
+                size: 10.0,
+                anchor: Anchor::Start,
+                bold: false,
+            }],
+        };
+        let pdf = render(&[page.clone(), page]);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 2"), "{text}");
+        assert!(text.contains("100.00 600.00 Td (Pe"), "{text}");
+        assert!(text.contains(" \\(W\\)) Tj"), "{text}");
+        // `startxref` points at the cross-reference table.
+        let start: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(pdf[start..].starts_with(b"xref\n0 9\n"));
+        // Every object offset points at its object.
+        for (i, line) in text[text.find("xref\n").unwrap()..]
+            .lines()
+            .skip(3)
+            .take(8)
+            .enumerate()
+        {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn labels_are_centered_on_their_width() {
+        assert!((text_width(b"AB", 10.0) - 13.34).abs() < 1e-4);
+        assert_eq!(encode("Núñez\u{2013}"), b"N\xfa\xf1ez?".to_vec());
+    }
+}
@@ -0,0 +1,133 @@
+//! A scorecard page as an SVG document.
+
+use crate::stats::scorecard::layout::{Anchor, Page, Shape};
+use std::fmt::Write;
+
+/// Write `page` as a standalone SVG document, one unit per point.
+pub fn render(page: &Page) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}pt" height="{h}pt" viewBox="0 0 {w} {h}" font-family="Helvetica, Arial, sans-serif">"#,
+        w = page.width,
+        h = page.height
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{}" height="{}" fill="white"/>"#,
+        page.width, page.height
+    );
+    for shape in &page.shapes {
+        let _ = match shape {
+            Shape::Line {
+                from,
+                to,
+                width,
+                gray,
+            } => writeln!(
+                svg,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{width}"/>"#,
+                from.0,
+                from.1,
+                to.0,
+                to.1,
+                color(*gray)
+            ),
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                gray,
+            } => writeln!(
+                svg,
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{width:.1}" height="{height:.1}" fill="{}"/>"#,
+                color(*gray)
+            ),
+            Shape::Polygon { points, gray } => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|(x, y)| format!("{x:.1},{y:.1}"))
+                    .collect();
+                writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}"/>"#,
+                    points.join(" "),
+                    color(*gray)
+                )
+            }
+            Shape::Text {
+                x,
+                y,
+                text,
+                size,
+                anchor,
+                bold,
+            } => {
+                let anchor = match anchor {
+                    Anchor::Start => "start",
+                    Anchor::Middle => "middle",
+                    Anchor::End => "end",
+                };
+                let weight = if *bold { r#" font-weight="bold""# } else { "" };
+                writeln!(
+                    svg,
+                    r#"<text x="{x:.1}" y="{y:.1}" font-size="{size}" text-anchor="{anchor}"{weight}>{}</text>"#,
+                    escape(text)
+                )
+            }
+        };
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// `#rrggbb` of a gray level.
+fn color(gray: f32) -> String {
+    let level = (gray.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{level:02x}{level:02x}{level:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_become_svg_elements() {
+        let page = Page {
+            width: 100.0,
+            height: 50.0,
+            shapes: vec![
+                Shape::Line {
+                    from: (0.0, 0.0),
+                    to: (10.0, 5.0),
+                    width: 0.5,
+                    gray: 0.4,
+                },
+                Shape::Text {
+                    x: 50.0,
+                    y: 20.0,
+                    text: "Smith & Jones".to_string(),
+                    size: 7.0,
+                    anchor: Anchor::Middle,
+                    bold: true,
+                },
+            ],
+        };
+        let svg = render(&page);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(r##"stroke="#666666""##), "{svg}");
+        assert!(
+            svg.contains(r#"text-anchor="middle" font-weight="bold">Smith &amp; Jones</text>"#),
+            "{svg}"
+        );
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
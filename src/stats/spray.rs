@@ -0,0 +1,679 @@
+//! Spray charts: where the balls in play went, by field zone.
+//!
+//! A hit is charted in the zone the scorer gave it. An out, a fielder's
+//! choice or an error is charted where the first fielder to handle the
+//! ball plays: the outfielders in left, center and right field, the third
+//! baseman on the left side, the first baseman on the right side, and the
+//! pitcher, catcher, second baseman and shortstop up the middle. The foul
+//! line zones only ever hold hits. Balls in play with no zone or fielder
+//! are counted apart.
+//!
+//! A switch hitter is charted on the side they bat from against the
+//! pitcher: left-handed against a right-hander and the other way round.
+//! When the pitcher's hand is not on record they count on both sides.
+
+use crate::engine::reducer::plate_appearance_from_row;
+use crate::models::field_zone::FieldZone;
+use crate::models::plate_appearance::PlateAppearanceOutcome;
+use crate::models::player_traits::{BatSide, ThrowHand};
+use crate::stats::scorecard::layout::{Anchor, Page, Shape};
+use crate::stats::{GameRecord, StatsFilter, filtered_games};
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// Whose balls in play are charted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpraySubject {
+    Batter(i64),
+    /// The batters of a team.
+    Team(i64),
+    /// The batters a pitcher faced.
+    Pitcher(i64),
+}
+
+/// Balls in play of one zone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZoneCount {
+    pub hits: u32,
+    /// Outs, fielder's choices and errors.
+    pub outs: u32,
+}
+
+impl ZoneCount {
+    pub fn balls_in_play(&self) -> u32 {
+        self.hits + self.outs
+    }
+
+    fn add(&mut self, other: ZoneCount) {
+        self.hits += other.hits;
+        self.outs += other.outs;
+    }
+}
+
+/// Balls in play by zone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SprayChart {
+    pub zones: HashMap<FieldZone, ZoneCount>,
+    /// Balls in play without a zone or a fielder.
+    pub unlocated: ZoneCount,
+}
+
+/// The `column` hand (`bat` or `throw`) of every player who has one.
+fn hands<T>(
+    conn: &Connection,
+    column: &str,
+    parse: fn(&str) -> Option<T>,
+) -> Result<HashMap<i64, T>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, \"{column}\" FROM players WHERE \"{column}\" IS NOT NULL"
+    ))?;
+    stmt.query_map([], |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)))?
+        .filter_map(|row| {
+            row.map(|(id, hand)| parse(&hand).map(|hand| (id, hand)))
+                .transpose()
+        })
+        .collect()
+}
+
+/// Whether a batter who bats `bat` faced a pitcher throwing `throw` from
+/// `side`. A switch hitter takes the side opposite the pitcher's hand, or
+/// either side when that hand is unknown.
+fn bats_from(bat: Option<BatSide>, throw: Option<ThrowHand>, side: BatSide) -> bool {
+    match bat {
+        Some(bat) if bat == side => true,
+        Some(BatSide::S) => match throw {
+            Some(ThrowHand::R) => side == BatSide::L,
+            Some(ThrowHand::L) => side == BatSide::R,
+            Some(ThrowHand::S) | None => true,
+        },
+        _ => false,
+    }
+}
+
+/// Zone of a ball first handled by `fielder` (1-9).
+pub fn fielder_zone(fielder: u8) -> Option<FieldZone> {
+    match fielder {
+        1 | 2 | 4 | 6 => Some(FieldZone::MI),
+        3 => Some(FieldZone::RS),
+        5 => Some(FieldZone::LS),
+        7 => Some(FieldZone::LF),
+        8 => Some(FieldZone::CF),
+        9 => Some(FieldZone::RF),
+        _ => None,
+    }
+}
+
+impl SprayChart {
+    /// Chart of `subject` over the games matching `filter`; `side` keeps
+    /// the batters who bat from that side only.
+    pub fn load(
+        conn: &Connection,
+        filter: &StatsFilter,
+        subject: SpraySubject,
+        side: Option<BatSide>,
+    ) -> Result<Self> {
+        let games = filtered_games(conn, filter)?
+            .into_iter()
+            .map(|game_pk| GameRecord::load(conn, game_pk))
+            .collect::<Result<Vec<_>>>()?;
+        let bats = hands(conn, "bat", BatSide::parse)?;
+        let throws = hands(conn, "throw", ThrowHand::parse)?;
+        Ok(Self::build(&games, subject, &bats, &throws, side))
+    }
+
+    /// Chart of `subject` over `games`; `bats` and `throws` give the
+    /// batting side and throwing hand of the players.
+    pub fn build(
+        games: &[GameRecord],
+        subject: SpraySubject,
+        bats: &HashMap<i64, BatSide>,
+        throws: &HashMap<i64, ThrowHand>,
+        side: Option<BatSide>,
+    ) -> Self {
+        let mut chart = Self::default();
+        for game in games {
+            for row in &game.plate_appearances {
+                let charted = match subject {
+                    SpraySubject::Batter(id) => row.batter_id == id,
+                    SpraySubject::Team(id) => game.batting_team(&row.half_inning) == id,
+                    SpraySubject::Pitcher(id) => row.pitcher_id == id,
+                };
+                let bat = bats.get(&row.batter_id).copied();
+                let throw = throws.get(&row.pitcher_id).copied();
+                if !charted || side.is_some_and(|side| !bats_from(bat, throw, side)) {
+                    continue;
+                }
+                let outcome = plate_appearance_from_row(row).outcome;
+                let (zone, count) = if outcome.is_hit() {
+                    (outcome.zone(), ZoneCount { hits: 1, outs: 0 })
+                } else {
+                    let fielder = match &outcome {
+                        PlateAppearanceOutcome::GroundOut { sequence, .. } => sequence
+                            .chars()
+                            .find_map(|c| c.to_digit(10))
+                            .map(|d| d as u8),
+                        PlateAppearanceOutcome::UnassistedOut { fielder, .. }
+                        | PlateAppearanceOutcome::FlyOut { fielder, .. }
+                        | PlateAppearanceOutcome::LineOut { fielder }
+                        | PlateAppearanceOutcome::InfieldFly { fielder }
+                        | PlateAppearanceOutcome::FieldersChoice { fielder, .. }
+                        | PlateAppearanceOutcome::ReachedOnError { fielder, .. } => Some(*fielder),
+                        PlateAppearanceOutcome::Out => None,
+                        // Not a ball in play.
+                        _ => continue,
+                    };
+                    (
+                        fielder.and_then(fielder_zone),
+                        ZoneCount { hits: 0, outs: 1 },
+                    )
+                };
+                match zone {
+                    Some(zone) => chart.zones.entry(zone).or_default().add(count),
+                    None => chart.unlocated.add(count),
+                }
+            }
+        }
+        chart
+    }
+
+    pub fn zone(&self, zone: FieldZone) -> ZoneCount {
+        self.zones.get(&zone).copied().unwrap_or_default()
+    }
+
+    /// Every ball in play, located or not.
+    pub fn total(&self) -> ZoneCount {
+        let mut total = self.unlocated;
+        for count in self.zones.values() {
+            total.add(*count);
+        }
+        total
+    }
+
+    /// The field drawn in text, with `hits-outs` under each zone.
+    pub fn diagram(&self) -> String {
+        const WIDTH: usize = 73;
+        const HEIGHT: usize = 20;
+        const HOME: (i32, i32) = (36, 19);
+        let mut grid = vec![vec![' '; WIDTH]; HEIGHT];
+        let mut put = |col: i32, row: i32, c: char, over: bool| {
+            if let (Ok(col), Ok(row)) = (usize::try_from(col), usize::try_from(row))
+                && col < WIDTH
+                && row < HEIGHT
+                && (over || grid[row][col] == ' ')
+            {
+                grid[row][col] = c;
+            }
+        };
+
+        // Fence, foul lines, then the infield: two columns per row.
+        for step in 0..=360 {
+            let angle = (45.0 + step as f32 * 0.25) * PI / 180.0;
+            let col = HOME.0 as f32 + 36.0 * angle.cos();
+            let row = HOME.1 as f32 - 18.0 * angle.sin();
+            put(col.round() as i32, row.round() as i32, '.', false);
+        }
+        for k in 1..19 {
+            put(HOME.0 - 2 * k, HOME.1 - k, '\\', true);
+            put(HOME.0 + 2 * k, HOME.1 - k, '/', true);
+        }
+        for k in 1..6 {
+            put(HOME.0 + 12 - 2 * k, HOME.1 - 6 - k, '\\', true);
+            put(HOME.0 - 12 + 2 * k, HOME.1 - 6 - k, '/', true);
+        }
+        for (col, row) in [
+            (HOME.0 + 12, HOME.1 - 6),
+            (HOME.0, HOME.1 - 12),
+            (HOME.0 - 12, HOME.1 - 6),
+        ] {
+            put(col, row, '+', true);
+        }
+        put(HOME.0, HOME.1, '^', true);
+
+        let positions = [
+            (FieldZone::LL, 21, 8),
+            (FieldZone::LF, 17, 5),
+            (FieldZone::LC, 26, 3),
+            (FieldZone::CF, 36, 2),
+            (FieldZone::RC, 46, 3),
+            (FieldZone::RF, 55, 5),
+            (FieldZone::RL, 52, 8),
+            (FieldZone::GLL, 32, 15),
+            (FieldZone::LS, 30, 11),
+            (FieldZone::MI, 36, 9),
+            (FieldZone::RS, 42, 11),
+            (FieldZone::GRL, 40, 15),
+        ];
+        for (zone, col, row) in positions {
+            let count = self.zone(zone);
+            for (text, row) in [
+                (zone.as_str().to_string(), row),
+                (format!("{}-{}", count.hits, count.outs), row + 1),
+            ] {
+                let start = col - text.len() as i32 / 2;
+                for (i, c) in text.chars().enumerate() {
+                    put(start + i as i32, row, c, true);
+                }
+            }
+        }
+
+        let mut text: String = grid
+            .iter()
+            .map(|row| {
+                let line: String = row.iter().collect();
+                format!("{}\n", line.trim_end())
+            })
+            .collect();
+        let total = self.total();
+        text.push_str(&format!(
+            "\nhits-outs by zone. Balls in play: {} ({} hits, {} outs)",
+            total.balls_in_play(),
+            total.hits,
+            total.outs
+        ));
+        if self.unlocated.balls_in_play() > 0 {
+            text.push_str(&format!(
+                "; {} without a zone",
+                self.unlocated.balls_in_play()
+            ));
+        }
+        text.push_str(".\n");
+        text
+    }
+
+    /// The chart on a page, each zone shaded by its share of the balls in
+    /// play.
+    pub fn page(&self, title: &str, subtitle: &str) -> Page {
+        const HOME: (f32, f32) = (216.0, 380.0);
+        const FENCE: f32 = 300.0;
+        const INFIELD: f32 = 125.0;
+        // Zones by angle from the right-field line, in degrees.
+        const WEDGES: [(FieldZone, f32, f32, bool); 12] = [
+            (FieldZone::RL, 45.0, 52.0, true),
+            (FieldZone::RF, 52.0, 72.0, true),
+            (FieldZone::RC, 72.0, 84.0, true),
+            (FieldZone::CF, 84.0, 96.0, true),
+            (FieldZone::LC, 96.0, 108.0, true),
+            (FieldZone::LF, 108.0, 128.0, true),
+            (FieldZone::LL, 128.0, 135.0, true),
+            (FieldZone::GRL, 45.0, 52.0, false),
+            (FieldZone::RS, 52.0, 75.0, false),
+            (FieldZone::MI, 75.0, 105.0, false),
+            (FieldZone::LS, 105.0, 128.0, false),
+            (FieldZone::GLL, 128.0, 135.0, false),
+        ];
+        let at = |degrees: f32, radius: f32| {
+            let angle = degrees * PI / 180.0;
+            (HOME.0 + radius * angle.cos(), HOME.1 - radius * angle.sin())
+        };
+        let arc = |from: f32, to: f32, radius: f32| -> Vec<(f32, f32)> {
+            let steps = ((to - from) / 2.0).ceil().max(1.0) as usize;
+            (0..=steps)
+                .map(|i| at(from + (to - from) * i as f32 / steps as f32, radius))
+                .collect()
+        };
+
+        let mut page = Page {
+            width: 432.0,
+            height: 400.0,
+            shapes: Vec::new(),
+        };
+        let most = self
+            .zones
+            .values()
+            .map(ZoneCount::balls_in_play)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (zone, from, to, outfield) in WEDGES {
+            let (inner, outer) = if outfield {
+                (INFIELD, FENCE)
+            } else {
+                (20.0, INFIELD)
+            };
+            let count = self.zone(zone);
+            if count.balls_in_play() > 0 {
+                let mut points = arc(from, to, outer);
+                points.extend(arc(from, to, inner).into_iter().rev());
+                let share = count.balls_in_play() as f32 / most as f32;
+                page.shapes.push(Shape::Polygon {
+                    points,
+                    gray: 1.0 - 0.45 * share,
+                });
+            }
+            for (radius, from, to) in [(inner, from, to), (outer, from, to)] {
+                for leg in arc(from, to, radius).windows(2) {
+                    page.shapes.push(Shape::Line {
+                        from: leg[0],
+                        to: leg[1],
+                        width: 0.5,
+                        gray: 0.4,
+                    });
+                }
+            }
+            for angle in [from, to] {
+                page.shapes.push(Shape::Line {
+                    from: at(angle, inner),
+                    to: at(angle, outer),
+                    width: if angle == 45.0 || angle == 135.0 {
+                        1.0
+                    } else {
+                        0.5
+                    },
+                    gray: 0.4,
+                });
+            }
+            let middle = (from + to) / 2.0;
+            let (x, y) = at(middle, (inner + outer) / 2.0);
+            let size = if to - from < 10.0 { 6.0 } else { 8.0 };
+            for (text, dy, bold) in [
+                (zone.as_str().to_string(), -1.0, true),
+                (format!("{}-{}", count.hits, count.outs), size, false),
+            ] {
+                page.shapes.push(Shape::Text {
+                    x,
+                    y: y + dy,
+                    text,
+                    size,
+                    anchor: Anchor::Middle,
+                    bold,
+                });
+            }
+        }
+
+        let total = self.total();
+        let mut legend = format!(
+            "hits-outs by zone. Balls in play: {} ({} hits, {} outs)",
+            total.balls_in_play(),
+            total.hits,
+            total.outs
+        );
+        if self.unlocated.balls_in_play() > 0 {
+            legend.push_str(&format!(
+                "; {} without a zone",
+                self.unlocated.balls_in_play()
+            ));
+        }
+        for (y, text, size, bold) in [
+            (24.0, title, 13.0, true),
+            (38.0, subtitle, 8.0, false),
+            (392.0, legend.as_str(), 7.0, false),
+        ] {
+            page.shapes.push(Shape::Text {
+                x: 16.0,
+                y,
+                text: text.to_string(),
+                size,
+                anchor: Anchor::Start,
+                bold,
+            });
+        }
+        page
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::Database;
+    use crate::db::fixtures::{self, AWAY, HOME, game_record, pa};
+    use crate::db::plate_appearances::append_plate_appearance;
+    use crate::models::plate_appearance;
+    use crate::stats::scorecard::svg;
+    use rusqlite::params;
+
+    fn chart(subject: SpraySubject, side: Option<BatSide>) -> SprayChart {
+        let pas = vec![
+            pa(1, "Top", 1, 21, "double", Some(r#"{"zone":"LC"}"#), 0),
+            pa(
+                2,
+                "Top",
+                2,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                1,
+            ),
+            pa(
+                3,
+                "Top",
+                3,
+                21,
+                "fly_out",
+                Some(r#"{"fielder":9,"in_foul_territory":false}"#),
+                2,
+            ),
+            pa(4, "Top", 4, 21, "single", None, 2),
+            pa(5, "Top", 5, 21, "strikeout", None, 3),
+            pa(6, "Bottom", 1, 11, "home_run", Some(r#"{"zone":"LL"}"#), 0),
+        ];
+        let bats = HashMap::from([(11, BatSide::L), (12, BatSide::R), (13, BatSide::L)]);
+        SprayChart::build(
+            &[game_record(pas, Vec::new())],
+            subject,
+            &bats,
+            &HashMap::new(),
+            side,
+        )
+    }
+
+    #[test]
+    fn outs_are_charted_where_the_fielder_plays() {
+        assert_eq!(fielder_zone(6), Some(FieldZone::MI));
+        assert_eq!(fielder_zone(5), Some(FieldZone::LS));
+        assert_eq!(fielder_zone(3), Some(FieldZone::RS));
+        assert_eq!(fielder_zone(9), Some(FieldZone::RF));
+        assert_eq!(fielder_zone(0), None);
+
+        let away = chart(SpraySubject::Team(AWAY), None);
+        assert_eq!(away.zone(FieldZone::LC), ZoneCount { hits: 1, outs: 0 });
+        assert_eq!(away.zone(FieldZone::MI), ZoneCount { hits: 0, outs: 1 });
+        assert_eq!(away.zone(FieldZone::RF), ZoneCount { hits: 0, outs: 1 });
+        // The strikeout is not a ball in play; the single has no zone.
+        assert_eq!(away.unlocated, ZoneCount { hits: 1, outs: 0 });
+        assert_eq!(away.total().balls_in_play(), 4);
+
+        let home = chart(SpraySubject::Pitcher(11), None);
+        assert_eq!(home.zone(FieldZone::LL), ZoneCount { hits: 1, outs: 0 });
+        assert_eq!(home.total().balls_in_play(), 1);
+        let home = chart(SpraySubject::Team(HOME), None);
+        assert_eq!(home.total().balls_in_play(), 1);
+    }
+
+    #[test]
+    fn charts_filter_by_batter_and_side() {
+        let batter = chart(SpraySubject::Batter(12), None);
+        assert_eq!(batter.zone(FieldZone::MI).outs, 1);
+        assert_eq!(batter.total().balls_in_play(), 1);
+
+        let lefties = chart(SpraySubject::Pitcher(21), Some(BatSide::L));
+        assert_eq!(lefties.zone(FieldZone::LC).hits, 1);
+        assert_eq!(lefties.zone(FieldZone::RF).outs, 1);
+        assert_eq!(lefties.total().balls_in_play(), 2);
+        let switch = chart(SpraySubject::Pitcher(21), Some(BatSide::S));
+        assert_eq!(switch.total(), ZoneCount::default());
+    }
+
+    #[test]
+    fn the_chart_draws_in_text_and_svg() {
+        let chart = chart(SpraySubject::Team(AWAY), None);
+        let diagram = chart.diagram();
+        let lines: Vec<&str> = diagram.lines().collect();
+        assert!(
+            lines[3].contains("LC") && lines[4].contains("1-0"),
+            "{diagram}"
+        );
+        assert!(
+            lines[9].contains("MI") && lines[10].contains("0-1"),
+            "{diagram}"
+        );
+        assert!(
+            diagram.ends_with("Balls in play: 4 (2 hits, 2 outs); 1 without a zone.\n"),
+            "{diagram}"
+        );
+
+        let svg = svg::render(&chart.page("Away", "All games"));
+        assert!(svg.contains(">LC</text>"), "{svg}");
+        assert!(svg.contains(">1-0</text>"), "{svg}");
+        assert!(svg.contains("<polygon"), "{svg}");
+    }
+
+    #[test]
+    fn every_out_is_charted_where_its_first_fielder_plays() {
+        let outs = [
+            ("ground_out", r#"{"sequence":"5-3"}"#),
+            ("ground_out", r#"{"sequence":"1-3"}"#),
+            ("unassisted_out", r#"{"fielder":3}"#),
+            ("line_out", r#"{"fielder":4}"#),
+            ("infield_fly", r#"{"fielder":2}"#),
+            ("fly_out", r#"{"fielder":7,"in_foul_territory":true}"#),
+            ("fly_out", r#"{"fielder":8,"in_foul_territory":false}"#),
+            ("fielders_choice", r#"{"fielder":6,"reached_base":"1B"}"#),
+            ("reached_on_error", r#"{"fielder":5,"throwing":true}"#),
+            ("out", "{}"),
+            ("walk", "{}"),
+        ];
+        let pas = outs
+            .iter()
+            .enumerate()
+            .map(|(i, (outcome, data))| {
+                let order = i as u8 % 9 + 1;
+                pa(i as i64 + 1, "Top", order, 21, outcome, Some(data), 0)
+            })
+            .collect();
+        let chart = SprayChart::build(
+            &[game_record(pas, Vec::new())],
+            SpraySubject::Team(AWAY),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
+        );
+
+        let outs = |zone| chart.zone(zone).outs;
+        // The ball goes where the first fielder of `5-3` plays, not the
+        // fielder who made the putout.
+        assert_eq!(outs(FieldZone::LS), 2);
+        assert_eq!(outs(FieldZone::MI), 4);
+        assert_eq!(outs(FieldZone::RS), 1);
+        assert_eq!(outs(FieldZone::LF), 1);
+        assert_eq!(outs(FieldZone::CF), 1);
+        assert_eq!(outs(FieldZone::RF), 0);
+        // A bare out has no fielder; the walk is not a ball in play.
+        assert_eq!(chart.unlocated, ZoneCount { hits: 0, outs: 1 });
+        assert_eq!(chart.total(), ZoneCount { hits: 0, outs: 10 });
+    }
+
+    #[test]
+    fn the_side_filter_keeps_batters_known_to_bat_from_that_side() {
+        // 11 is a switch hitter, 12 bats right, 14 (the single) and 15 have
+        // no side on record.
+        let pas = vec![
+            pa(1, "Top", 1, 21, "double", Some(r#"{"zone":"LC"}"#), 0),
+            pa(
+                2,
+                "Top",
+                2,
+                21,
+                "ground_out",
+                Some(r#"{"sequence":"6-3"}"#),
+                1,
+            ),
+            pa(3, "Top", 4, 21, "single", None, 1),
+            pa(
+                4,
+                "Top",
+                5,
+                21,
+                "fly_out",
+                Some(r#"{"fielder":9,"in_foul_territory":false}"#),
+                2,
+            ),
+        ];
+        let games = [game_record(pas, Vec::new())];
+        let bats = HashMap::from([(11, BatSide::S), (12, BatSide::R)]);
+        let chart = |throws: &[(i64, ThrowHand)], side| {
+            let throws = HashMap::from_iter(throws.iter().copied());
+            SprayChart::build(&games, SpraySubject::Pitcher(21), &bats, &throws, side)
+        };
+
+        let switch = chart(&[], Some(BatSide::S));
+        assert_eq!(switch.zone(FieldZone::LC).hits, 1);
+        assert_eq!(switch.total().balls_in_play(), 1);
+        // With the pitcher's hand unknown the switch hitter counts on both
+        // sides.
+        let right = chart(&[], Some(BatSide::R));
+        assert_eq!(right.zone(FieldZone::LC).hits, 1);
+        assert_eq!(right.zone(FieldZone::MI).outs, 1);
+        assert_eq!(right.total().balls_in_play(), 2);
+        let left = chart(&[], Some(BatSide::L));
+        assert_eq!(left.zone(FieldZone::LC).hits, 1);
+        assert_eq!(left.total().balls_in_play(), 1);
+        assert_eq!(chart(&[], None).total().balls_in_play(), 4);
+
+        // Against a right-hander they bat left, against a lefty right.
+        let righty = [(21, ThrowHand::R)];
+        assert_eq!(chart(&righty, Some(BatSide::L)).total().balls_in_play(), 1);
+        assert_eq!(chart(&righty, Some(BatSide::R)).zone(FieldZone::LC).hits, 0);
+        let lefty = [(21, ThrowHand::L)];
+        assert_eq!(
+            chart(&lefty, Some(BatSide::L)).total(),
+            ZoneCount::default()
+        );
+        assert_eq!(chart(&lefty, Some(BatSide::R)).total().balls_in_play(), 2);
+    }
+
+    #[test]
+    fn the_side_comes_from_the_players_bat_and_the_pitchers_throw() {
+        let db = Database::new(":memory:").unwrap();
+        db.init_schema().unwrap();
+        let conn = db.get_connection();
+        let away = fixtures::team(conn, "Away", "AWY");
+        let home = fixtures::team(conn, "Home", "HOM");
+        let game_pk = fixtures::game(conn, "G-SPRAY", away, home);
+        let pitcher = fixtures::player(conn, home, 1, None, "Bo", "Arm", "P");
+        conn.execute(
+            "UPDATE players SET throw = 'R' WHERE id = ?1",
+            params![pitcher],
+        )
+        .unwrap();
+        for (number, bat, zone) in [
+            (1, Some("L"), FieldZone::LF),
+            (2, None, FieldZone::RF),
+            (3, Some("S"), FieldZone::CF),
+        ] {
+            let batter = fixtures::player(conn, away, number, None, "Ada", "Bat", "CF");
+            conn.execute(
+                "UPDATE players SET bat = ?1 WHERE id = ?2",
+                params![bat, batter],
+            )
+            .unwrap();
+            let single = PlateAppearanceOutcome::Single {
+                zone: Some(zone),
+                modifier: None,
+            };
+            append_plate_appearance(
+                conn,
+                game_pk,
+                &plate_appearance::tests::pa(batter, number as u8, pitcher, single, &[], 0),
+            )
+            .unwrap();
+        }
+
+        let chart = |side| {
+            SprayChart::load(
+                conn,
+                &StatsFilter::default(),
+                SpraySubject::Team(away),
+                side,
+            )
+            .unwrap()
+        };
+        // The switch hitter bats left against the right-hander.
+        let lefties = chart(Some(BatSide::L));
+        assert_eq!(lefties.zone(FieldZone::LF).hits, 1);
+        assert_eq!(lefties.zone(FieldZone::CF).hits, 1);
+        assert_eq!(lefties.total().balls_in_play(), 2);
+        assert_eq!(chart(Some(BatSide::R)).total(), ZoneCount::default());
+        assert_eq!(chart(None).total().balls_in_play(), 3);
+    }
+}
@@ -32,6 +32,13 @@ impl Ui for CliUi {
         io::stdin().read_line(&mut line).ok()?;
         Some(line)
     }
+
+    fn confirm(&mut self, question: &str) -> bool {
+        self.read_command_line(&format!("{question} [y/N] "))
+            .is_some_and(|answer| {
+                matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+            })
+    }
 }
 
 impl Default for CliUi {
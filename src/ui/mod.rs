@@ -21,6 +21,10 @@ pub trait Ui {
     ///
     /// `prompt` is a fully formatted prompt string (dynamic, derived from game state).
     fn read_command_line(&mut self, prompt: &str) -> Option<String>;
+
+    /// Ask a yes/no question before a destructive action. Only an explicit
+    /// yes returns `true`.
+    fn confirm(&mut self, question: &str) -> bool;
     fn set_state(&mut self, _state: &GameState) {}
     fn set_context(&mut self, _ctx: &PlayBallUiContext) {}
 }
@@ -614,8 +614,8 @@ impl TuiUi {
                 home_score: s.score.home,
                 away_hits: s.score.away_hits,
                 home_hits: s.score.home_hits,
-                away_errors: 0,
-                home_errors: 0,
+                away_errors: s.score.away_errors,
+                home_errors: s.score.home_errors,
                 away_innings: s.score.away_innings.clone(),
                 home_innings: s.score.home_innings.clone(),
 
@@ -797,12 +797,22 @@ impl TuiUi {
             Line::from("  s          Swinging strike"),
             Line::from("  f          Foul"),
             Line::from("  fl         Foul bunt"),
+            Line::from("  kwp / kpb  Strike three, batter to 1B (WP / PB)"),
+            Line::from("  k23        Strike three, batter thrown out"),
             Line::from(""),
             Line::from("Hit commands"),
             Line::from("  h  [zone]  Single"),
             Line::from("  2h [zone]  Double"),
             Line::from("  3h [zone]  Triple"),
             Line::from("  hr [zone]  Home run"),
+            Line::from("  2h gr      Ground-rule double"),
+            Line::from("  hr itp     Inside-the-park home run"),
+            Line::from("  h bu       Bunt hit"),
+            Line::from(""),
+            Line::from("Base awards"),
+            Line::from("  hbp        Hit by pitch"),
+            Line::from("  ibb        Intentional walk"),
+            Line::from("  ci         Catcher's interference"),
             Line::from(""),
             Line::from("Hit zones"),
             Line::from("  LL LF LC CF RC RF RL"),
@@ -810,6 +820,13 @@ impl TuiUi {
             Line::from(""),
             Line::from("Steal commands"),
             Line::from("  <n> st <base>      Steal (1 st 2b, 3 st sc)"),
+            Line::from("  <n> wp <base>      Wild pitch (3 wp sc)"),
+            Line::from("  <n> pb <base>      Passed ball (2 pb 3b)"),
+            Line::from("  <n> di <base>      Defensive indifference (1 di 2b)"),
+            Line::from("  bk                 Balk (every runner moves up)"),
+            Line::from("  <n> cs <base> <f>  Caught stealing (5 cs 2b 26)"),
+            Line::from("  <n> po <f>         Pickoff (3 po 13)"),
+            Line::from("  <n> pocs <base> <f> Pickoff, caught stealing"),
             Line::from(""),
             Line::from("Out commands"),
             Line::from("  <n> 63             Ground out"),
@@ -818,6 +835,8 @@ impl TuiUi {
             Line::from("  <n> ff2            Foul fly out"),
             Line::from("  <n> l6             Line out"),
             Line::from("  <n> if4            Infield fly"),
+            Line::from("  sf8                Sacrifice fly (runner on 3B scores)"),
+            Line::from("  sh13               Sacrifice bunt (runners move up)"),
             Line::from(""),
             Line::from("Defensive play commands"),
             Line::from("  63                 Batter implicit ground out"),
@@ -828,6 +847,24 @@ impl TuiUi {
             Line::from("  <n> o6 1b          Fielder's choice"),
             Line::from("  9 64, 1 o6 1b      Multi-command defensive play"),
             Line::from(""),
+            Line::from("Error commands"),
+            Line::from("  e6 [base]          Batter reaches on fielding error"),
+            Line::from("  te5 [base]         Batter reaches on throwing error"),
+            Line::from("  h, <n> e7 3b       Runner advances on error"),
+            Line::from(""),
+            Line::from("Scorer's credit"),
+            Line::from("  <play>, rbi <n>    Set the RBIs of the play (h, rbi 0)"),
+            Line::from(""),
+            Line::from("Substitutions"),
+            Line::from("  pc <jersey>        Pitching change (pc 34)"),
+            Line::from("  ph <jersey>        Pinch hitter (ph 12)"),
+            Line::from("  <n> pr <jersey>    Pinch runner (3 pr 27)"),
+            Line::from("  <n> sub <j> <pos>  Defensive sub (7 sub 22 lf)"),
+            Line::from("  <n> pos <pos>      Position change (4 pos ss)"),
+            Line::from(""),
+            Line::from("Corrections"),
+            Line::from("  undo [n]           Undo the last n plate appearances"),
+            Line::from(""),
             Line::from("Notes"),
             Line::from("  - Commands are case-insensitive."),
             Line::from("  - Fielder's choice requires an explicit base."),
@@ -1101,6 +1138,42 @@ impl Ui for TuiUi {
         }
     }
 
+    fn confirm(&mut self, question: &str) -> bool {
+        self.input.clear();
+        self.push_line(question.to_string());
+        let prompt = "Confirm? [y/N] ";
+
+        loop {
+            if self.render(prompt).is_err() {
+                return false;
+            }
+
+            let ev = match event::read() {
+                Ok(ev) => ev,
+                Err(_) => return false,
+            };
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = ev
+            else {
+                continue;
+            };
+
+            if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
+                return false;
+            }
+
+            match code {
+                KeyCode::Char('y' | 'Y') => return true,
+                KeyCode::Char('n' | 'N') | KeyCode::Enter | KeyCode::Esc => return false,
+                _ => {}
+            }
+        }
+    }
+
     fn set_state(&mut self, state: &GameState) {
         self.state = Some(state.clone());
     }